@@ -0,0 +1,31 @@
+use bitcoin::{proof::build_transaction_proof, types::H256Le};
+
+// Builds the inclusion proof of a transaction from the raw block, e.g. as returned by
+// bitcoin-cli getblock <block hash> 0
+// Usage: cargo run --example build-proof --features parser -- <raw block hex> <txid>
+// Without arguments, the coinbase of the testnet genesis block is proven.
+
+const GENESIS_BLOCK_HEX: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4adae5494dffff001d1aa4ae180101000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000";
+const GENESIS_COINBASE_TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (block_hex, tx_id) = match args.as_slice() {
+        [block_hex, tx_id] => (block_hex.as_str(), tx_id.as_str()),
+        [] => (GENESIS_BLOCK_HEX, GENESIS_COINBASE_TXID),
+        _ => panic!("expected the raw block hex and the txid as arguments"),
+    };
+
+    let raw_block = hex::decode(block_hex.trim()).unwrap();
+    let result = build_transaction_proof(&raw_block, H256Le::from_hex_be(tx_id.trim())).unwrap();
+    let user_tx_proof = &result.proof.user_tx_proof;
+    println!(
+        "block hash = {}, transactions count = {}, hash count = {}, tx encoded len = {}\nscale ({} bytes) = {}",
+        user_tx_proof.merkle_proof.block_header.hash,
+        user_tx_proof.merkle_proof.transactions_count,
+        user_tx_proof.merkle_proof.hashes.len(),
+        user_tx_proof.tx_encoded_len,
+        result.scale.len(),
+        result.hex
+    );
+}
@@ -1,6 +1,7 @@
-use crate::{types::*, Error, Script};
+use crate::{base58, bech32, types::*, Error, Network, Script};
 use bitcoin_hashes::{hash160::Hash as Hash160, Hash};
 use codec::{Decode, Encode, MaxEncodedLen};
+use core::fmt;
 use primitive_types::{H160, H256};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
@@ -11,6 +12,9 @@ use alloc::vec::Vec;
 
 use secp256k1::{constants::PUBLIC_KEY_SIZE, Error as Secp256k1Error, PublicKey as Secp256k1PublicKey};
 
+const HASH160_SIZE: usize = HASH160_SIZE_HEX as usize;
+const HASH256_SIZE: usize = HASH256_SIZE_HEX as usize;
+
 /// A Bitcoin address is a serialized identifier that represents the destination for a payment.
 /// Address prefixes are used to indicate the network as well as the format. Since the Parachain
 /// follows SPV assumptions we do not need to know which network a payment is included in.
@@ -35,6 +39,10 @@ pub enum Address {
     // output: OP_0 {sha256(redeem_script)}
     // witness: [redeem_script_sig ...] {redeem_script}
     P2WSHv0(H256),
+    // input: <>
+    // output: OP_1 {x-only tweaked pubkey}
+    // witness: {signature} or [script_inputs ...] {leaf_script} {control_block}
+    P2TRv1(H256),
 }
 
 impl Address {
@@ -44,8 +52,7 @@ impl Address {
         const OP_EQUAL_VERIFY: u8 = OpCode::OpEqualVerify as u8;
         const OP_CHECK_SIG: u8 = OpCode::OpCheckSig as u8;
         const OP_EQUAL: u8 = OpCode::OpEqual as u8;
-        const OP_0: u8 = OpCode::Op0 as u8;
-        const MAX_ADDRESS_BYTES: usize = HASH256_SIZE_HEX as usize + 2; // max length is for P2WSHv0; see the match below
+        const MAX_ADDRESS_BYTES: usize = HASH256_SIZE_HEX as usize + 2; // max length is for P2WSHv0 and P2TRv1; see below
 
         let bytes = script.as_bytes();
 
@@ -64,13 +71,21 @@ impl Address {
             &[OP_HASH_160, HASH160_SIZE_HEX, ref addr @ .., OP_EQUAL] if addr.len() == HASH160_SIZE_HEX as usize => {
                 Ok(Self::P2SH(H160::from_slice(addr)))
             }
-            &[OP_0, HASH256_SIZE_HEX, ref addr @ ..] if addr.len() == HASH256_SIZE_HEX as usize => {
-                Ok(Self::P2WSHv0(H256::from_slice(addr)))
-            }
-            &[OP_0, HASH160_SIZE_HEX, ref addr @ ..] if addr.len() == HASH160_SIZE_HEX as usize => {
-                Ok(Self::P2WPKHv0(H160::from_slice(addr)))
-            }
-            _ => Err(Error::InvalidBtcAddress),
+            _ => match script.witness_program() {
+                Some((0, program)) if program.len() == HASH160_SIZE_HEX as usize => {
+                    Ok(Self::P2WPKHv0(H160::from_slice(program)))
+                }
+                Some((0, program)) if program.len() == HASH256_SIZE_HEX as usize => {
+                    Ok(Self::P2WSHv0(H256::from_slice(program)))
+                }
+                Some((1, program)) if program.len() == HASH256_SIZE_HEX as usize => {
+                    Ok(Self::P2TRv1(H256::from_slice(program)))
+                }
+                // witness versions 2 to 16 (and v1 programs that are not 32 bytes) are
+                // currently unencumbered, anyone could spend from them so we do not accept
+                // them as addresses
+                _ => Err(Error::InvalidBtcAddress),
+            },
         }
     }
 
@@ -108,6 +123,94 @@ impl Address {
                 script.append(script_hash);
                 script
             }
+            Self::P2TRv1(output_key) => {
+                let mut script = Script::new();
+                script.append(OpCode::Op1);
+                script.append(HASH256_SIZE_HEX);
+                script.append(output_key);
+                script
+            }
+        }
+    }
+
+    /// Returns the segwit version of the address, or `None` for legacy addresses.
+    pub fn witness_version(&self) -> Option<u8> {
+        match self {
+            Self::P2PKH(_) | Self::P2SH(_) => None,
+            Self::P2WPKHv0(_) | Self::P2WSHv0(_) => Some(0),
+            Self::P2TRv1(_) => Some(1),
+        }
+    }
+
+    /// Parses a Base58Check (P2PKH, P2SH) or Bech32/Bech32m (segwit) encoded address, checking
+    /// that it belongs to the given `network`. Witness versions without a corresponding variant
+    /// are rejected, see [`Address::from_script_pub_key`].
+    pub fn from_str_with_network(s: &str, network: Network) -> Result<Self, Error> {
+        let encoded = s.as_bytes();
+        if encoded.len() > bech32::MAX_LENGTH {
+            // base58 decoding is quadratic in the input length
+            return Err(Error::InvalidAddressEncoding);
+        }
+
+        const NETWORKS: [Network; 4] = [Network::Mainnet, Network::Testnet, Network::Signet, Network::Regtest];
+        if NETWORKS
+            .iter()
+            .any(|network| bech32::has_hrp(network.bech32_hrp(), encoded))
+        {
+            let (version, program) = bech32::decode_segwit(network.bech32_hrp(), encoded)?;
+            return match (version, program.len()) {
+                (0, HASH160_SIZE) => Ok(Self::P2WPKHv0(H160::from_slice(&program))),
+                (0, HASH256_SIZE) => Ok(Self::P2WSHv0(H256::from_slice(&program))),
+                (1, HASH256_SIZE) => Ok(Self::P2TRv1(H256::from_slice(&program))),
+                _ => Err(Error::InvalidBtcAddress),
+            };
+        }
+
+        let payload = base58::decode_check(encoded)?;
+        let (&prefix, hash) = payload.split_first().ok_or(Error::InvalidAddressEncoding)?;
+        if hash.len() != HASH160_SIZE {
+            return Err(Error::InvalidAddressEncoding);
+        }
+        if prefix == network.p2pkh_prefix() {
+            Ok(Self::P2PKH(H160::from_slice(hash)))
+        } else if prefix == network.p2sh_prefix() {
+            Ok(Self::P2SH(H160::from_slice(hash)))
+        } else if NETWORKS
+            .iter()
+            .any(|network| prefix == network.p2pkh_prefix() || prefix == network.p2sh_prefix())
+        {
+            Err(Error::AddressNetworkMismatch)
+        } else {
+            Err(Error::InvalidBtcAddress)
+        }
+    }
+
+    /// Returns the human-readable encoding of the address on the given `network`.
+    pub fn encode_with_network(&self, network: Network) -> Vec<u8> {
+        let base58_check = |prefix: u8, hash: &H160| {
+            let mut payload = Vec::with_capacity(1 + HASH160_SIZE);
+            payload.push(prefix);
+            payload.extend_from_slice(hash.as_bytes());
+            base58::encode_check(&payload)
+        };
+        let segwit = |version: u8, program: &[u8]| {
+            bech32::encode_segwit(network.bech32_hrp(), version, program)
+                .expect("program is converted from 8-bit groups; qed")
+        };
+        match self {
+            Self::P2PKH(hash) => base58_check(network.p2pkh_prefix(), hash),
+            Self::P2SH(hash) => base58_check(network.p2sh_prefix(), hash),
+            Self::P2WPKHv0(hash) => segwit(0, hash.as_bytes()),
+            Self::P2WSHv0(hash) => segwit(0, hash.as_bytes()),
+            Self::P2TRv1(output_key) => segwit(1, output_key.as_bytes()),
+        }
+    }
+
+    /// Returns an object that implements [`Display`](core::fmt::Display) for the given `network`.
+    pub fn display(&self, network: Network) -> DisplayAddress {
+        DisplayAddress {
+            address: *self,
+            network,
         }
     }
 
@@ -126,7 +229,7 @@ impl Address {
     pub fn is_zero(&self) -> bool {
         match self {
             Self::P2PKH(hash) | Self::P2SH(hash) | Self::P2WPKHv0(hash) => hash.is_zero(),
-            Self::P2WSHv0(hash) => hash.is_zero(),
+            Self::P2WSHv0(hash) | Self::P2TRv1(hash) => hash.is_zero(),
         }
     }
 }
@@ -137,6 +240,21 @@ impl Default for Address {
     }
 }
 
+/// Helper for displaying an [`Address`] on a specific [`Network`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DisplayAddress {
+    address: Address,
+    network: Network,
+}
+
+impl fmt::Display for DisplayAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = self.address.encode_with_network(self.network);
+        // both encodings only use ASCII characters
+        f.write_str(core::str::from_utf8(&encoded).map_err(|_| fmt::Error)?)
+    }
+}
+
 /// Compressed ECDSA (secp256k1 curve) Public Key
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
 pub struct PublicKey(pub [u8; PUBLIC_KEY_SIZE]);
@@ -397,4 +515,192 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test_address_script_pub_key_round_trip() {
+        for address in [
+            Address::P2PKH(H160([1; 20])),
+            Address::P2SH(H160([2; 20])),
+            Address::P2WPKHv0(H160([3; 20])),
+            Address::P2WSHv0(H256([4; 32])),
+            Address::P2TRv1(H256([5; 32])),
+        ] {
+            assert_eq!(Address::from_script_pub_key(&address.to_script_pub_key()), Ok(address));
+        }
+    }
+
+    #[test]
+    fn test_p2tr_from_script_pub_key() {
+        // second output of the transaction in `parser::tests::test_parse_transaction_containing_taproot`
+        let script =
+            Script::from(hex::decode("512038234fa3e3ca718dfadfb540c320180e68798e67e0a9d4f10d98ea33d37caf04").unwrap());
+        let address = Address::from_script_pub_key(&script).unwrap();
+        assert_eq!(
+            address,
+            Address::P2TRv1(H256::from_slice(
+                &hex::decode("38234fa3e3ca718dfadfb540c320180e68798e67e0a9d4f10d98ea33d37caf04").unwrap()
+            ))
+        );
+        assert_eq!(address.witness_version(), Some(1));
+        assert_eq!(address.to_script_pub_key(), script);
+    }
+
+    #[test]
+    fn test_unknown_witness_version_is_rejected() {
+        // OP_2 <32 bytes>
+        let mut bytes = vec![OpCode::Op2 as u8, HASH256_SIZE_HEX];
+        bytes.extend_from_slice(&[7; 32]);
+        assert_err!(
+            Address::from_script_pub_key(&Script::from(bytes)),
+            Error::InvalidBtcAddress
+        );
+
+        // OP_1 <20 bytes>
+        let mut bytes = vec![OpCode::Op1 as u8, HASH160_SIZE_HEX];
+        bytes.extend_from_slice(&[7; 20]);
+        assert_err!(
+            Address::from_script_pub_key(&Script::from(bytes)),
+            Error::InvalidBtcAddress
+        );
+    }
+
+    #[test]
+    fn test_address_codec() {
+        // appending the taproot variant must not change the encoding of the existing ones
+        assert_eq!(Address::P2WSHv0(H256([4; 32])).encode()[0], 3);
+
+        let address = Address::P2TRv1(H256([5; 32]));
+        let encoded = address.encode();
+        assert_eq!(encoded[0], 4);
+        assert_eq!(encoded.len(), Address::max_encoded_len());
+        assert_eq!(Address::decode(&mut &encoded[..]), Ok(address));
+    }
+
+    fn h160(hex: &str) -> H160 {
+        H160::from_slice(&hex::decode(hex).unwrap())
+    }
+
+    fn h256(hex: &str) -> H256 {
+        H256::from_slice(&hex::decode(hex).unwrap())
+    }
+
+    #[test]
+    fn test_address_from_str_with_network() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-v0-v16-native-segregated-witness-addresses
+        for (encoded, network, address) in [
+            (
+                "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+                Network::Mainnet,
+                Address::P2PKH(h160("77bff20c60e522dfaa3350c39b030a5d004e839a")),
+            ),
+            (
+                "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+                Network::Mainnet,
+                Address::P2SH(h160("b472a266d0bd89c13706a4132ccfb16f7c3b9fcb")),
+            ),
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                Network::Mainnet,
+                Address::P2WPKHv0(h160("751e76e8199196d454941c45d1b3a323f1433bd6")),
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                Network::Testnet,
+                Address::P2WSHv0(h256("1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262")),
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                Network::Mainnet,
+                Address::P2TRv1(h256("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")),
+            ),
+            (
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                Network::Signet,
+                Address::P2TRv1(h256("000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433")),
+            ),
+        ] {
+            assert_eq!(Address::from_str_with_network(encoded, network), Ok(address));
+            assert_eq!(address.display(network).to_string(), encoded);
+        }
+
+        // segwit addresses may also be uppercase
+        assert_eq!(
+            Address::from_str_with_network("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", Network::Mainnet),
+            Ok(Address::P2WPKHv0(h160("751e76e8199196d454941c45d1b3a323f1433bd6")))
+        );
+    }
+
+    #[test]
+    fn test_address_display_round_trip() {
+        let addresses = [
+            Address::P2PKH(H160([1; 20])),
+            Address::P2SH(H160([2; 20])),
+            Address::P2WPKHv0(H160([3; 20])),
+            Address::P2WSHv0(H256([4; 32])),
+            Address::P2TRv1(H256([5; 32])),
+        ];
+        for network in [Network::Mainnet, Network::Testnet, Network::Signet, Network::Regtest] {
+            for address in addresses {
+                let encoded = address.display(network).to_string();
+                assert_eq!(Address::from_str_with_network(&encoded, network), Ok(address));
+            }
+        }
+        assert!(Address::P2WPKHv0(H160([3; 20]))
+            .display(Network::Regtest)
+            .to_string()
+            .starts_with("bcrt1q"));
+    }
+
+    #[test]
+    fn test_address_from_str_with_network_fails() {
+        // mainnet addresses on other networks
+        assert_err!(
+            Address::from_str_with_network("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2", Network::Testnet),
+            Error::AddressNetworkMismatch
+        );
+        assert_err!(
+            Address::from_str_with_network("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", Network::Regtest),
+            Error::AddressNetworkMismatch
+        );
+        // invalid checksum
+        assert_err!(
+            Address::from_str_with_network("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3", Network::Mainnet),
+            Error::InvalidAddressChecksum
+        );
+        assert_err!(
+            Address::from_str_with_network("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5", Network::Mainnet),
+            Error::InvalidAddressChecksum
+        );
+        // v1 program encoded with bech32 instead of bech32m
+        assert_err!(
+            Address::from_str_with_network(
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+                Network::Mainnet
+            ),
+            Error::InvalidAddressEncoding
+        );
+        // v0 program encoded with bech32m instead of bech32
+        assert_err!(
+            Address::from_str_with_network("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh", Network::Mainnet),
+            Error::InvalidAddressEncoding
+        );
+        // mixed case
+        assert_err!(
+            Address::from_str_with_network(
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7",
+                Network::Testnet
+            ),
+            Error::InvalidAddressEncoding
+        );
+        // valid v2 program without a corresponding variant
+        assert_err!(
+            Address::from_str_with_network("bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs", Network::Mainnet),
+            Error::InvalidBtcAddress
+        );
+        // too long
+        assert_err!(
+            Address::from_str_with_network(&"1".repeat(91), Network::Mainnet),
+            Error::InvalidAddressEncoding
+        );
+    }
 }
@@ -0,0 +1,118 @@
+//! Base58Check encoding as used by legacy (P2PKH and P2SH) addresses.
+//! <https://en.bitcoin.it/wiki/Base58Check_encoding>
+
+use crate::{utils::sha256d, Error};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const CHECKSUM_SIZE: usize = 4;
+
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let mut ret = [0; CHECKSUM_SIZE];
+    ret.copy_from_slice(&sha256d(payload)[..CHECKSUM_SIZE]);
+    ret
+}
+
+/// Encodes the payload followed by its checksum, returning the ASCII characters.
+///
+/// `O(N^2)` where `N` is the length of the payload, callers should bound the input.
+pub(crate) fn encode_check(payload: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(payload.len() + CHECKSUM_SIZE);
+    data.extend_from_slice(payload);
+    data.extend_from_slice(&checksum(payload));
+
+    // little-endian base58 digits
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in data.iter() {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // every leading zero byte is encoded as a leading '1'
+    let leading_zeros = data.iter().take_while(|&&byte| byte == 0).count();
+    let mut result = Vec::with_capacity(leading_zeros + digits.len());
+    result.resize(leading_zeros, ALPHABET[0]);
+    result.extend(digits.iter().rev().map(|&digit| ALPHABET[digit as usize]));
+    result
+}
+
+/// Decodes the ASCII characters and verifies the trailing checksum, returning the payload.
+///
+/// `O(N^2)` where `N` is the length of the input, callers should bound the input.
+pub(crate) fn decode_check(encoded: &[u8]) -> Result<Vec<u8>, Error> {
+    // little-endian bytes
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len() * 733 / 1000 + 1);
+    for &c in encoded.iter() {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|&x| x == c)
+            .ok_or(Error::InvalidAddressEncoding)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let leading_zeros = encoded.iter().take_while(|&&c| c == ALPHABET[0]).count();
+    let mut data = Vec::with_capacity(leading_zeros + bytes.len());
+    data.resize(leading_zeros, 0);
+    data.extend(bytes.iter().rev());
+
+    let payload_len = data
+        .len()
+        .checked_sub(CHECKSUM_SIZE)
+        .ok_or(Error::InvalidAddressEncoding)?;
+    let (payload, expected_checksum) = data.split_at(payload_len);
+    if checksum(payload) != expected_checksum {
+        return Err(Error::InvalidAddressChecksum);
+    }
+    Ok(payload.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58_round_trip() {
+        for payload in [&[][..], &[0], &[0, 0, 1], &[0xff; 21], &[1, 2, 3, 4, 5]] {
+            assert_eq!(decode_check(&encode_check(payload)).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_base58_leading_zeros() {
+        // 21 zero bytes, i.e. a P2PKH address with a zero hash
+        let encoded = encode_check(&[0; 21]);
+        assert_eq!(encoded, b"1111111111111111111114oLvT2");
+    }
+
+    #[test]
+    fn test_base58_invalid() {
+        // '0', 'O', 'I' and 'l' are not part of the alphabet
+        assert_eq!(
+            decode_check(b"1111111111111111111114oLvT0"),
+            Err(Error::InvalidAddressEncoding)
+        );
+        assert_eq!(
+            decode_check(b"1111111111111111111114oLvT3"),
+            Err(Error::InvalidAddressChecksum)
+        );
+        assert_eq!(decode_check(b"1"), Err(Error::InvalidAddressEncoding));
+    }
+}
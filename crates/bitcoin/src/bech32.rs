@@ -0,0 +1,246 @@
+//! Bech32 ([BIP-173](https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki)) and Bech32m
+//! ([BIP-350](https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki)) encoding as used by
+//! segwit addresses.
+
+use crate::Error;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const SEPARATOR: u8 = b'1';
+const CHECKSUM_SIZE: usize = 6;
+/// Maximum length of a segwit address, see BIP-173
+pub(crate) const MAX_LENGTH: usize = 90;
+
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Witness v0 programs use Bech32, all later versions use Bech32m.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Self::Bech32 => BECH32_CONST,
+            Self::Bech32m => BECH32M_CONST,
+        }
+    }
+
+    fn for_witness_version(version: u8) -> Self {
+        if version == 0 {
+            Self::Bech32
+        } else {
+            Self::Bech32m
+        }
+    }
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = (chk & 0x01ff_ffff) << 5 ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    hrp.iter()
+        .map(|c| c >> 5)
+        .chain(core::iter::once(0))
+        .chain(hrp.iter().map(|c| c & 0x1f))
+}
+
+fn create_checksum(hrp: &[u8], data: &[u8], variant: Variant) -> [u8; CHECKSUM_SIZE] {
+    let values = hrp_expand(hrp).chain(data.iter().copied()).chain([0; CHECKSUM_SIZE]);
+    let modulus = polymod(values) ^ variant.constant();
+    let mut ret = [0; CHECKSUM_SIZE];
+    for (i, value) in ret.iter_mut().enumerate() {
+        *value = ((modulus >> (5 * (5 - i))) & 0x1f) as u8;
+    }
+    ret
+}
+
+/// Regroups the bits of `data` from `from`-bit to `to`-bit groups.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Error> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value: u32 = (1 << to) - 1;
+    let mut ret = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    for &value in data {
+        if (value as u32) >> from != 0 {
+            return Err(Error::InvalidAddressEncoding);
+        }
+        acc = (acc << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            ret.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_value) != 0 {
+        return Err(Error::InvalidAddressEncoding);
+    }
+    Ok(ret)
+}
+
+/// Encodes 5-bit `data` under the lowercase `hrp`, returning the ASCII characters.
+fn encode(hrp: &[u8], data: &[u8], variant: Variant) -> Vec<u8> {
+    let checksum = create_checksum(hrp, data, variant);
+    let mut ret = Vec::with_capacity(hrp.len() + 1 + data.len() + CHECKSUM_SIZE);
+    ret.extend_from_slice(hrp);
+    ret.push(SEPARATOR);
+    ret.extend(data.iter().chain(checksum.iter()).map(|&value| CHARSET[value as usize]));
+    ret
+}
+
+/// Decodes the ASCII characters, returning the lowercase hrp, the 5-bit data without the
+/// checksum and the checksum variant.
+fn decode(encoded: &[u8]) -> Result<(Vec<u8>, Vec<u8>, Variant), Error> {
+    if encoded.len() > MAX_LENGTH {
+        return Err(Error::InvalidAddressEncoding);
+    }
+    // mixed case is not allowed
+    if encoded.iter().any(u8::is_ascii_lowercase) && encoded.iter().any(u8::is_ascii_uppercase) {
+        return Err(Error::InvalidAddressEncoding);
+    }
+    let encoded: Vec<u8> = encoded.iter().map(u8::to_ascii_lowercase).collect();
+
+    let separator = encoded
+        .iter()
+        .rposition(|&c| c == SEPARATOR)
+        .ok_or(Error::InvalidAddressEncoding)?;
+    let (hrp, data) = (&encoded[..separator], &encoded[separator + 1..]);
+    if hrp.is_empty() || data.len() < CHECKSUM_SIZE || hrp.iter().any(|c| !(33..=126).contains(c)) {
+        return Err(Error::InvalidAddressEncoding);
+    }
+
+    let data = data
+        .iter()
+        .map(|c| CHARSET.iter().position(|x| x == c).map(|value| value as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(Error::InvalidAddressEncoding)?;
+
+    let variant = match polymod(hrp_expand(hrp).chain(data.iter().copied())) {
+        BECH32_CONST => Variant::Bech32,
+        BECH32M_CONST => Variant::Bech32m,
+        _ => return Err(Error::InvalidAddressChecksum),
+    };
+
+    Ok((hrp.to_vec(), data[..data.len() - CHECKSUM_SIZE].to_vec(), variant))
+}
+
+/// Encodes a witness program as a segwit address, returning the ASCII characters.
+pub(crate) fn encode_segwit(hrp: &str, version: u8, program: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut data = Vec::with_capacity(1 + (program.len() * 8 + 4) / 5);
+    data.push(version);
+    data.extend(convert_bits(program, 8, 5, true)?);
+    Ok(encode(hrp.as_bytes(), &data, Variant::for_witness_version(version)))
+}
+
+/// Decodes a segwit address with the expected `hrp`, returning the witness version and program.
+pub(crate) fn decode_segwit(hrp: &str, encoded: &[u8]) -> Result<(u8, Vec<u8>), Error> {
+    let (decoded_hrp, data, variant) = decode(encoded)?;
+    if decoded_hrp != hrp.as_bytes() {
+        return Err(Error::AddressNetworkMismatch);
+    }
+
+    let (&version, data) = data.split_first().ok_or(Error::InvalidAddressEncoding)?;
+    let program = convert_bits(data, 5, 8, false)?;
+    if version > 16
+        || program.len() < 2
+        || program.len() > 40
+        || (version == 0 && program.len() != 20 && program.len() != 32)
+        || variant != Variant::for_witness_version(version)
+    {
+        return Err(Error::InvalidAddressEncoding);
+    }
+    Ok((version, program))
+}
+
+/// Returns true if the encoded string looks like a segwit address with the given `hrp`.
+pub(crate) fn has_hrp(hrp: &str, encoded: &[u8]) -> bool {
+    encoded.len() > hrp.len()
+        && encoded[..hrp.len()].eq_ignore_ascii_case(hrp.as_bytes())
+        && encoded[hrp.len()] == SEPARATOR
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-bech32m
+    #[test]
+    fn test_valid_checksums() {
+        for (encoded, variant) in [
+            ("A12UEL5L", Variant::Bech32),
+            ("a12uel5l", Variant::Bech32),
+            ("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", Variant::Bech32),
+            (
+                "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+                Variant::Bech32,
+            ),
+            ("?1ezyfcl", Variant::Bech32),
+            ("A1LQFN3A", Variant::Bech32m),
+            ("a1lqfn3a", Variant::Bech32m),
+            ("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", Variant::Bech32m),
+            (
+                "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+                Variant::Bech32m,
+            ),
+            ("?1v759aa", Variant::Bech32m),
+        ] {
+            let (hrp, data, decoded_variant) = decode(encoded.as_bytes()).unwrap();
+            assert_eq!(decoded_variant, variant);
+            assert_eq!(encode(&hrp, &data, variant), encoded.to_ascii_lowercase().as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_invalid_checksums() {
+        for encoded in [
+            // HRP character out of range
+            "\x201xj0phk",
+            // overall max length exceeded
+            "an84characterslonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11d6pts4",
+            // no separator character
+            "qyrz8wqd2c9m",
+            // empty HRP
+            "1qyrz8wqd2c9m",
+            // invalid data character
+            "y1b0jsk6g",
+            // too short checksum
+            "in1muywd",
+            // mixed case
+            "A1g7sgD",
+        ] {
+            assert!(decode(encoded.as_bytes()).is_err(), "{}", encoded);
+        }
+        // checksum calculated with uppercase form of HRP
+        assert_eq!(decode(b"M1VUXWEZ"), Err(Error::InvalidAddressChecksum));
+    }
+
+    #[test]
+    fn test_has_hrp() {
+        assert!(has_hrp("bc", b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+        assert!(has_hrp("bc", b"BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"));
+        assert!(!has_hrp("bc", b"bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+        assert!(has_hrp("bcrt", b"bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+        assert!(!has_hrp("tb", b"1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"));
+    }
+}
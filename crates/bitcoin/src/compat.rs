@@ -116,4 +116,16 @@ mod tests {
         let rust_bitcoin_address: rust_bitcoin::address::Payload = interbtc_address.clone().try_into().unwrap();
         assert_eq!(interbtc_address, rust_bitcoin_address.try_into().unwrap());
     }
+
+    #[test]
+    fn test_taproot_address_compat() {
+        let interbtc_address = crate::Address::P2TRv1(primitive_types::H256([1; 32]));
+        let rust_bitcoin_address: rust_bitcoin::address::Payload = interbtc_address.clone().try_into().unwrap();
+        assert!(matches!(
+            rust_bitcoin_address,
+            rust_bitcoin::address::Payload::WitnessProgram(ref program)
+                if program.version() == rust_bitcoin::address::WitnessVersion::V1
+        ));
+        assert_eq!(interbtc_address, rust_bitcoin_address.try_into().unwrap());
+    }
 }
@@ -21,4 +21,23 @@ pub enum Error {
     InvalidCompact,
     BoundExceeded,
     InvalidTxid,
+    InvalidAddressEncoding,
+    InvalidAddressChecksum,
+    AddressNetworkMismatch,
+    InvalidSighashType,
+    InvalidInputIndex,
+    InvalidPrevouts,
+    UnsupportedOpcode,
+    InvalidStackOperation,
+    UnbalancedConditional,
+    UnsatisfiedLockTime,
+    ScriptVerificationFailed,
+    InvalidPsbt,
+    DuplicatePsbtKey,
+    UnsupportedPsbtVersion,
+    PsbtInputNotFinalized,
+    InvalidWtxid,
+    InvalidWitnessCommitment,
+    InsufficientFeeRate,
+    InvalidSignetSolution,
 }
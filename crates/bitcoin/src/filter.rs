@@ -0,0 +1,425 @@
+//! Compact block filters as defined by [BIP-158](https://github.com/bitcoin/bips/blob/master/bip-0158.mediawiki).
+//!
+//! Only the basic filter type is supported. It commits to every output script of a block, except
+//! for empty and `OP_RETURN` scripts, and to the scripts of all outputs spent by the block. The
+//! scripts are hashed into a Golomb-coded set (GCS) keyed with the block hash, so clients can test
+//! whether a block is relevant to their addresses without downloading it. Matches are
+//! probabilistic with a false positive rate of `1 / M`, there are no false negatives.
+
+use crate::{formatter::TryFormat, parser::parse_compact_uint, types::*, utils::sha256d_le, Error, Script};
+use bitcoin_hashes::siphash24;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeSet, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+/// Golomb-Rice coding parameter of the basic filter
+const BASIC_FILTER_P: u8 = 19;
+/// Inverse false positive rate of the basic filter
+const BASIC_FILTER_M: u64 = 784_931;
+
+/// The basic filter of a block: the number of elements followed by the Golomb-Rice coded set
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BlockFilter {
+    pub content: Vec<u8>,
+}
+
+impl BlockFilter {
+    /// Builds the basic filter of the block. The script of each output spent by the block is
+    /// looked up with `prevout_script`, given the hash and index of the spent output.
+    pub fn new_basic_filter<F>(block: &Block, mut prevout_script: F) -> Result<Self, Error>
+    where
+        F: FnMut(H256Le, u32) -> Result<Script, Error>,
+    {
+        let mut elements = BTreeSet::new();
+        for transaction in block.transactions.iter() {
+            for output in transaction.outputs.iter() {
+                let script = output.script.as_bytes();
+                if !script.is_empty() && script[0] != OpCode::OpReturn as u8 {
+                    elements.insert(script.to_vec());
+                }
+            }
+            for input in transaction.inputs.iter() {
+                if let TransactionInputSource::FromOutput(hash, index) = input.source {
+                    let script = prevout_script(hash, index)?;
+                    if !script.is_empty() {
+                        elements.insert(script.as_bytes().to_vec());
+                    }
+                }
+            }
+        }
+        let content = GcsFilter::new(&block.header.hash).encode(elements.iter().map(Vec::as_slice))?;
+        Ok(Self { content })
+    }
+
+    /// Returns the hash of the serialized filter
+    pub fn filter_hash(&self) -> H256Le {
+        sha256d_le(&self.content)
+    }
+
+    /// Returns the filter header, which commits to this filter and the header of the filter of
+    /// the previous block (zero for the genesis block)
+    pub fn filter_header(&self, previous_filter_header: &H256Le) -> H256Le {
+        let mut bytes = self.filter_hash().to_bytes_le().to_vec();
+        bytes.extend_from_slice(&previous_filter_header.to_bytes_le());
+        sha256d_le(&bytes)
+    }
+
+    /// Returns true if any of the `query` scripts may be in the filter of the block with `block_hash`
+    pub fn match_any<'a>(&self, block_hash: &H256Le, query: impl Iterator<Item = &'a [u8]>) -> Result<bool, Error> {
+        GcsFilter::new(block_hash).match_any(&self.content, query)
+    }
+
+    /// Returns true if all of the `query` scripts may be in the filter of the block with `block_hash`
+    pub fn match_all<'a>(&self, block_hash: &H256Le, query: impl Iterator<Item = &'a [u8]>) -> Result<bool, Error> {
+        GcsFilter::new(block_hash).match_all(&self.content, query)
+    }
+}
+
+/// Golomb-coded set of the basic filter type
+struct GcsFilter {
+    k0: u64,
+    k1: u64,
+}
+
+impl GcsFilter {
+    /// The SipHash key is the first 16 bytes of the block hash in internal byte order
+    fn new(block_hash: &H256Le) -> Self {
+        let bytes = block_hash.to_bytes_le();
+        let mut k0 = [0u8; 8];
+        let mut k1 = [0u8; 8];
+        k0.copy_from_slice(&bytes[0..8]);
+        k1.copy_from_slice(&bytes[8..16]);
+        Self {
+            k0: u64::from_le_bytes(k0),
+            k1: u64::from_le_bytes(k1),
+        }
+    }
+
+    /// Maps the element uniformly onto `[0, n * M)`
+    fn hash_to_range(&self, element: &[u8], range: u64) -> u64 {
+        let hash = siphash24::Hash::hash_to_u64_with_keys(self.k0, self.k1, element);
+        ((hash as u128 * range as u128) >> 64) as u64
+    }
+
+    fn range(count: u64) -> Result<u64, Error> {
+        count.checked_mul(BASIC_FILTER_M).ok_or(Error::ArithmeticOverflow)
+    }
+
+    /// Hashes the elements and returns the sorted, deduplicated values
+    fn hashed_set<'a>(&self, elements: impl Iterator<Item = &'a [u8]>, range: u64) -> Vec<u64> {
+        let mut hashes: Vec<u64> = elements.map(|element| self.hash_to_range(element, range)).collect();
+        hashes.sort_unstable();
+        hashes.dedup();
+        hashes
+    }
+
+    /// Serializes the set of distinct `elements`
+    fn encode<'a>(&self, elements: impl ExactSizeIterator<Item = &'a [u8]>) -> Result<Vec<u8>, Error> {
+        let count = elements.len() as u64;
+        let mut hashes: Vec<u64> = {
+            let range = Self::range(count)?;
+            elements.map(|element| self.hash_to_range(element, range)).collect()
+        };
+        hashes.sort_unstable();
+
+        let mut content = Vec::new();
+        CompactUint { value: count }.try_format(&mut content)?;
+        let mut writer = BitWriter::new(content);
+        let mut last = 0;
+        for hash in hashes {
+            writer.write_golomb_rice(hash - last);
+            last = hash;
+        }
+        Ok(writer.finish())
+    }
+
+    fn match_any<'a>(&self, content: &[u8], query: impl Iterator<Item = &'a [u8]>) -> Result<bool, Error> {
+        let mut reader = FilterReader::new(content)?;
+        let query = self.hashed_set(query, Self::range(reader.count)?);
+        let mut query = query.iter().peekable();
+        while let (Some(&&target), Some(value)) = (query.peek(), reader.peek()?) {
+            if target == value {
+                return Ok(true);
+            } else if target < value {
+                query.next();
+            } else {
+                reader.next()?;
+            }
+        }
+        Ok(false)
+    }
+
+    fn match_all<'a>(&self, content: &[u8], query: impl Iterator<Item = &'a [u8]>) -> Result<bool, Error> {
+        let mut reader = FilterReader::new(content)?;
+        let query = self.hashed_set(query, Self::range(reader.count)?);
+        for target in query {
+            loop {
+                match reader.peek()? {
+                    Some(value) if value < target => {
+                        reader.next()?;
+                    }
+                    Some(value) if value == target => break,
+                    _ => return Ok(false),
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Iterates over the values of a serialized filter in ascending order
+struct FilterReader<'a> {
+    count: u64,
+    read: u64,
+    current: Option<u64>,
+    last: u64,
+    bits: BitReader<'a>,
+}
+
+impl<'a> FilterReader<'a> {
+    fn new(content: &'a [u8]) -> Result<Self, Error> {
+        let (count, consumed) = parse_compact_uint(content)?;
+        Ok(Self {
+            count,
+            read: 0,
+            current: None,
+            last: 0,
+            bits: BitReader::new(content.get(consumed..).ok_or(Error::EndOfFile)?),
+        })
+    }
+
+    /// Returns the current value without advancing, `None` once all values were read
+    fn peek(&mut self) -> Result<Option<u64>, Error> {
+        if self.current.is_none() && self.read < self.count {
+            let delta = self.bits.read_golomb_rice()?;
+            self.last = self.last.checked_add(delta).ok_or(Error::ArithmeticOverflow)?;
+            self.current = Some(self.last);
+            self.read += 1;
+        }
+        Ok(self.current)
+    }
+
+    fn next(&mut self) -> Result<Option<u64>, Error> {
+        let value = self.peek()?;
+        self.current = None;
+        Ok(value)
+    }
+}
+
+/// Writes bits from the most significant bit of each byte
+struct BitWriter {
+    bytes: Vec<u8>,
+    offset: u8,
+}
+
+impl BitWriter {
+    fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes, offset: 8 }
+    }
+
+    fn write_bit(&mut self, bit: bool) {
+        if self.offset == 8 {
+            self.bytes.push(0);
+            self.offset = 0;
+        }
+        if bit {
+            if let Some(byte) = self.bytes.last_mut() {
+                *byte |= 0x80 >> self.offset;
+            }
+        }
+        self.offset += 1;
+    }
+
+    /// Writes the quotient in unary followed by the `P` lowest bits of the remainder
+    fn write_golomb_rice(&mut self, value: u64) {
+        for _ in 0..(value >> BASIC_FILTER_P) {
+            self.write_bit(true);
+        }
+        self.write_bit(false);
+        for shift in (0..BASIC_FILTER_P).rev() {
+            self.write_bit((value >> shift) & 1 == 1);
+        }
+    }
+
+    /// Returns the bytes, the last byte is padded with zero bits
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read_bit(&mut self) -> Result<bool, Error> {
+        let byte = self.bytes.get(self.position / 8).ok_or(Error::EndOfFile)?;
+        let bit = byte & (0x80 >> (self.position % 8)) != 0;
+        self.position += 1;
+        Ok(bit)
+    }
+
+    fn read_golomb_rice(&mut self) -> Result<u64, Error> {
+        let mut quotient: u64 = 0;
+        while self.read_bit()? {
+            quotient = quotient.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+        }
+        let mut remainder = 0;
+        for _ in 0..BASIC_FILTER_P {
+            remainder = (remainder << 1) | self.read_bit()? as u64;
+        }
+        quotient
+            .checked_mul(1 << BASIC_FILTER_P)
+            .and_then(|value| value.checked_add(remainder))
+            .ok_or(Error::ArithmeticOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::{parse_block_header, parse_transaction},
+        Address,
+    };
+    use primitive_types::{H160, H256, U256};
+
+    fn testnet_genesis_block() -> Block {
+        let header = hex::decode("0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4adae5494dffff001d1aa4ae18").unwrap();
+        let coinbase = hex::decode("01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000").unwrap();
+        Block {
+            header: parse_block_header(&header).unwrap(),
+            transactions: vec![parse_transaction(&coinbase).unwrap()],
+        }
+    }
+
+    fn no_prevouts(_: H256Le, _: u32) -> Result<Script, Error> {
+        Err(Error::MalformedTransaction)
+    }
+
+    #[test]
+    fn test_basic_filter_of_testnet_genesis_block() {
+        // test vector from https://github.com/bitcoin/bips/blob/master/bip-0158/testnet-19.json
+        let block = testnet_genesis_block();
+        assert_eq!(
+            block.header.hash,
+            H256Le::from_hex_be("000000000933ea01ad0ee984209779baaec3ced90fa3f408719526f8d77f4943")
+        );
+
+        let filter = BlockFilter::new_basic_filter(&block, no_prevouts).unwrap();
+        assert_eq!(hex::encode(&filter.content), "019dfca8");
+        assert_eq!(
+            filter.filter_header(&H256Le::zero()),
+            H256Le::from_hex_be("21584579b7eb08997773e5aeff3a7f932700042d0ed2a6129012b7d7ae81b750")
+        );
+
+        let script = block.transactions[0].outputs[0].script.clone();
+        assert!(filter
+            .match_any(&block.header.hash, [script.as_bytes()].into_iter())
+            .unwrap());
+        assert!(filter
+            .match_all(&block.header.hash, [script.as_bytes()].into_iter())
+            .unwrap());
+        assert!(!filter
+            .match_any(&block.header.hash, [&b"not in block"[..]].into_iter())
+            .unwrap());
+    }
+
+    #[test]
+    fn test_encode_large_set() {
+        // cross-checked against the BIP-158 reference implementation
+        let block_hash = H256Le::from_hex_be("000000000933ea01ad0ee984209779baaec3ced90fa3f408719526f8d77f4943");
+        let elements: Vec<Vec<u8>> = (0..300usize).map(|i| vec![(i % 256) as u8; i % 7 + 1]).collect();
+        let content = GcsFilter::new(&block_hash)
+            .encode(elements.iter().map(Vec::as_slice))
+            .unwrap();
+
+        assert_eq!(content.len(), 792);
+        assert_eq!(hex::encode(&content[..8]), "fd2c01990719f6e7");
+        assert_eq!(
+            sha256d_le(&content),
+            H256Le::from_hex_be("bce4a72090365fb5580e8f2ebd7ce0cdb2789458f681a7ac7881f356d181a1aa")
+        );
+
+        let filter = BlockFilter { content };
+        assert!(filter
+            .match_all(&block_hash, elements.iter().map(Vec::as_slice))
+            .unwrap());
+        for element in elements.iter() {
+            assert!(filter.match_any(&block_hash, [element.as_slice()].into_iter()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_basic_filter_includes_spent_scripts() {
+        let spent_address = Address::P2WPKHv0(H160::from_low_u64_be(1));
+        let paid_address = Address::P2PKH(H160::from_low_u64_be(2));
+        let unrelated_address = Address::P2SH(H160::from_low_u64_be(3));
+        let spent_tx_id = H256Le::from_bytes_le(&[7; 32]);
+
+        let transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_input(
+                TransactionInputBuilder::new()
+                    .with_source(TransactionInputSource::FromOutput(spent_tx_id, 1))
+                    .build(),
+            )
+            .add_output(TransactionOutput::payment(100, &paid_address))
+            .add_output(TransactionOutput::op_return(0, &H256::zero().0))
+            .build();
+        let block = BlockBuilder::new()
+            .with_coinbase(&Address::default(), 50, 1)
+            .add_transaction(transaction)
+            .mine(U256::from(2).pow(254.into()))
+            .unwrap();
+
+        let spent_script = spent_address.to_script_pub_key();
+        let filter = BlockFilter::new_basic_filter(&block, |tx_id, index| {
+            assert_eq!((tx_id, index), (spent_tx_id, 1));
+            Ok(spent_script.clone())
+        })
+        .unwrap();
+
+        let spent = spent_address.to_script_pub_key();
+        let paid = paid_address.to_script_pub_key();
+        let unrelated = unrelated_address.to_script_pub_key();
+        let hash = &block.header.hash;
+        assert!(filter
+            .match_all(hash, [spent.as_bytes(), paid.as_bytes()].into_iter())
+            .unwrap());
+        assert!(filter
+            .match_any(hash, [unrelated.as_bytes(), paid.as_bytes()].into_iter())
+            .unwrap());
+        assert!(!filter
+            .match_all(hash, [unrelated.as_bytes(), paid.as_bytes()].into_iter())
+            .unwrap());
+        assert!(!filter.match_any(hash, [unrelated.as_bytes()].into_iter()).unwrap());
+        // the filter is keyed with the block hash
+        assert!(!filter
+            .match_any(&H256Le::zero(), [paid.as_bytes()].into_iter())
+            .unwrap());
+    }
+
+    #[test]
+    fn test_empty_filter() {
+        let transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_output(TransactionOutput::op_return(0, &[1; 32]))
+            .build();
+        let block = Block {
+            header: Default::default(),
+            transactions: vec![transaction],
+        };
+        let filter = BlockFilter::new_basic_filter(&block, no_prevouts).unwrap();
+        assert_eq!(filter.content, vec![0]);
+        assert!(!filter.match_any(&block.header.hash, [&[0x6a][..]].into_iter()).unwrap());
+        assert!(filter.match_all(&block.header.hash, [].into_iter()).unwrap());
+    }
+}
@@ -135,10 +135,7 @@ where
 
 impl TryFormat for TransactionInput {
     fn try_format<W: Writer>(&self, w: &mut W) -> Result<(), Error> {
-        let (previous_hash, previous_index) = match self.source {
-            TransactionInputSource::Coinbase(_) => (H256Le::zero(), u32::MAX),
-            TransactionInputSource::FromOutput(hash, index) => (hash, index),
-        };
+        let (previous_hash, previous_index) = self.outpoint();
         previous_hash.try_format(w)?;
         previous_index.try_format(w)?;
 
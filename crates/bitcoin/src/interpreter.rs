@@ -0,0 +1,967 @@
+//! Minimal script interpreter for standard spending conditions.
+//!
+//! Only a bounded subset of opcodes is supported: pushes, `IF`/`NOTIF`/`ELSE`/`ENDIF`, `VERIFY`,
+//! `RETURN`, `DUP`, `DROP`, `2DROP`, `SWAP`, `SIZE`, `EQUAL(VERIFY)`, the hashing opcodes,
+//! `CHECKSIG(VERIFY)`, `CHECKMULTISIG(VERIFY)`, `CHECKLOCKTIMEVERIFY` and `CHECKSEQUENCEVERIFY`.
+//! Any other executed opcode fails with [`Error::UnsupportedOpcode`]. Legacy, P2SH and segwit v0
+//! (native or nested in P2SH) spends are evaluated, taproot spends are not supported.
+//!
+//! The consensus rules of Bitcoin Core are followed for the supported subset, standardness (policy)
+//! rules are not enforced except that the scriptSig must only contain pushes and that signatures
+//! must use one of the defined sighash types.
+//! <https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/script/interpreter.cpp>
+
+use crate::{
+    global::SECP256K1,
+    sighash::SighashType,
+    timelock::{RelativeLockTime, SEQUENCE_FINAL},
+    types::*,
+    utils::sha256,
+    Address, Error, Script,
+};
+use bitcoin_hashes::{hash160, ripemd160, sha1, sha256d, Hash};
+use primitive_types::H160;
+use secp256k1::{Message, PublicKey as Secp256k1PublicKey, Signature as Secp256k1Signature};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+const MAX_SCRIPT_SIZE: usize = 10_000;
+const MAX_SCRIPT_ELEMENT_SIZE: usize = 520;
+const MAX_OPS_PER_SCRIPT: usize = 201;
+const MAX_STACK_SIZE: usize = 1_000;
+const MAX_PUBKEYS_PER_MULTISIG: i64 = 20;
+
+/// The sighash algorithm used by signature checks
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SigVersion {
+    Base,
+    WitnessV0,
+}
+
+type Stack = Vec<Vec<u8>>;
+
+/// An opcode and the pushed data, if it is a push
+pub(crate) type Instruction<'a> = (u8, Option<&'a [u8]>);
+
+/// Reads the next operation, advancing `position` past it
+pub(crate) fn next_instruction<'a>(script: &'a [u8], position: &mut usize) -> Result<Option<Instruction<'a>>, Error> {
+    let opcode = match script.get(*position) {
+        Some(&opcode) => opcode,
+        None => return Ok(None),
+    };
+    let mut read = |size: usize| -> Result<&'a [u8], Error> {
+        let start = *position;
+        let end = start.checked_add(size).ok_or(Error::InvalidScript)?;
+        let bytes = script.get(start..end).ok_or(Error::InvalidScript)?;
+        *position = end;
+        Ok(bytes)
+    };
+    let read_len = |bytes: &[u8]| {
+        let mut buffer = [0u8; 4];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        u32::from_le_bytes(buffer) as usize
+    };
+
+    read(1)?;
+    let len = match opcode {
+        0x00..=0x4b => opcode as usize,
+        op if op == OpCode::OpPushData1 as u8 => read_len(read(1)?),
+        op if op == OpCode::OpPushData2 as u8 => read_len(read(2)?),
+        op if op == OpCode::OpPushData4 as u8 => read_len(read(4)?),
+        _ => return Ok(Some((opcode, None))),
+    };
+    Ok(Some((opcode, Some(read(len)?))))
+}
+
+fn is_push_only(script: &[u8]) -> Result<bool, Error> {
+    let mut position = 0;
+    while let Some((opcode, _)) = next_instruction(script, &mut position)? {
+        if opcode > OpCode::Op16 as u8 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Removes every push of `data` from the script, see `FindAndDelete` in Bitcoin Core
+fn find_and_delete(script: &[u8], data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut pattern = Script::new();
+    pattern.push_data(data);
+    let pattern = pattern.as_bytes();
+
+    let mut ret = Vec::with_capacity(script.len());
+    let mut position = 0;
+    loop {
+        let start = position;
+        if next_instruction(script, &mut position)?.is_none() {
+            break;
+        }
+        if &script[start..position] != pattern {
+            ret.extend_from_slice(&script[start..position]);
+        }
+    }
+    Ok(ret)
+}
+
+/// Decodes a little endian, sign-magnitude number of at most `max_size` bytes
+fn decode_script_num(bytes: &[u8], max_size: usize) -> Result<i64, Error> {
+    if bytes.len() > max_size {
+        return Err(Error::InvalidStackOperation);
+    }
+    let last = match bytes.last() {
+        Some(&last) => last,
+        None => return Ok(0),
+    };
+    let mut result: i64 = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        result |= (byte as i64) << (8 * i);
+    }
+    if last & 0x80 != 0 {
+        Ok(-(result & !(0x80_i64 << (8 * (bytes.len() - 1)))))
+    } else {
+        Ok(result)
+    }
+}
+
+/// Any non-zero value is true, except for negative zero
+fn cast_to_bool(bytes: &[u8]) -> bool {
+    bytes
+        .iter()
+        .enumerate()
+        .any(|(i, &byte)| byte != 0 && !(i == bytes.len() - 1 && byte == 0x80))
+}
+
+fn pop(stack: &mut Stack) -> Result<Vec<u8>, Error> {
+    stack.pop().ok_or(Error::InvalidStackOperation)
+}
+
+fn top(stack: &Stack) -> Result<&Vec<u8>, Error> {
+    stack.last().ok_or(Error::InvalidStackOperation)
+}
+
+fn ensure_success(stack: &Stack) -> Result<(), Error> {
+    match stack.last() {
+        Some(value) if cast_to_bool(value) => Ok(()),
+        _ => Err(Error::ScriptVerificationFailed),
+    }
+}
+
+struct Interpreter<'a> {
+    transaction: &'a Transaction,
+    input_index: usize,
+    /// amount of the spent output, committed to by segwit signatures
+    value: Value,
+}
+
+impl<'a> Interpreter<'a> {
+    fn input(&self) -> &TransactionInput {
+        &self.transaction.inputs[self.input_index]
+    }
+
+    fn verify(&self, script_sig: &[u8], script_pub_key: &Script, witness: &[Vec<u8>]) -> Result<(), Error> {
+        if !is_push_only(script_sig)? {
+            return Err(Error::ScriptVerificationFailed);
+        }
+
+        let mut stack = Stack::new();
+        self.eval(script_sig, &mut stack, SigVersion::Base)?;
+        let p2sh_stack = if script_pub_key.is_p2sh() {
+            Some(stack.clone())
+        } else {
+            None
+        };
+        self.eval(script_pub_key.as_bytes(), &mut stack, SigVersion::Base)?;
+        ensure_success(&stack)?;
+
+        let mut has_witness_program = false;
+        if let Some((version, program)) = script_pub_key.witness_program() {
+            // the scriptSig of native witness spends must be empty
+            if !script_sig.is_empty() {
+                return Err(Error::ScriptVerificationFailed);
+            }
+            self.verify_witness_program(version, program, witness)?;
+            has_witness_program = true;
+        } else if let Some(mut stack) = p2sh_stack {
+            // the scriptSig is push only and the scriptPubKey checks the hash of the redeem script
+            let redeem_script = Script::from(pop(&mut stack)?);
+            self.eval(redeem_script.as_bytes(), &mut stack, SigVersion::Base)?;
+            ensure_success(&stack)?;
+
+            if let Some((version, program)) = redeem_script.witness_program() {
+                // the scriptSig of nested witness spends must be a single push of the redeem script
+                let mut expected_script_sig = Script::new();
+                expected_script_sig.push_data(redeem_script.as_bytes());
+                if script_sig != expected_script_sig.as_bytes() {
+                    return Err(Error::ScriptVerificationFailed);
+                }
+                self.verify_witness_program(version, program, witness)?;
+                has_witness_program = true;
+            }
+        }
+
+        if !has_witness_program && !witness.is_empty() {
+            return Err(Error::ScriptVerificationFailed);
+        }
+        Ok(())
+    }
+
+    fn verify_witness_program(&self, version: u8, program: &[u8], witness: &[Vec<u8>]) -> Result<(), Error> {
+        let (script, mut stack) = match (version, program.len()) {
+            (0, 20) => {
+                if witness.len() != 2 {
+                    return Err(Error::ScriptVerificationFailed);
+                }
+                let script = Address::P2PKH(H160::from_slice(program)).to_script_pub_key();
+                (script, witness.to_vec())
+            }
+            (0, 32) => {
+                let (witness_script, stack) = witness.split_last().ok_or(Error::ScriptVerificationFailed)?;
+                if sha256(witness_script) != program {
+                    return Err(Error::ScriptVerificationFailed);
+                }
+                (Script::from(witness_script.clone()), stack.to_vec())
+            }
+            (0, _) => return Err(Error::ScriptVerificationFailed),
+            _ => return Err(Error::UnsupportedInputFormat),
+        };
+
+        if stack.iter().any(|element| element.len() > MAX_SCRIPT_ELEMENT_SIZE) {
+            return Err(Error::BoundExceeded);
+        }
+        self.eval(script.as_bytes(), &mut stack, SigVersion::WitnessV0)?;
+
+        // witness scripts must leave exactly one true element
+        if stack.len() != 1 {
+            return Err(Error::ScriptVerificationFailed);
+        }
+        ensure_success(&stack)
+    }
+
+    fn eval(&self, script: &[u8], stack: &mut Stack, sig_version: SigVersion) -> Result<(), Error> {
+        if script.len() > MAX_SCRIPT_SIZE {
+            return Err(Error::BoundExceeded);
+        }
+
+        // for each nested IF, whether the current branch is executed
+        let mut exec_stack: Vec<bool> = Vec::new();
+        let mut op_count = 0;
+        let mut position = 0;
+
+        while let Some((opcode, data)) = next_instruction(script, &mut position)? {
+            let executing = exec_stack.iter().all(|&branch| branch);
+
+            if data.map_or(false, |data| data.len() > MAX_SCRIPT_ELEMENT_SIZE) {
+                return Err(Error::BoundExceeded);
+            }
+            if opcode > OpCode::Op16 as u8 {
+                op_count += 1;
+                if op_count > MAX_OPS_PER_SCRIPT {
+                    return Err(Error::BoundExceeded);
+                }
+            }
+
+            if let Some(data) = data {
+                if executing {
+                    stack.push(data.to_vec());
+                }
+            } else if executing || (OpCode::OpIf as u8..=OpCode::OpEndIf as u8).contains(&opcode) {
+                self.eval_opcode(opcode, script, stack, &mut exec_stack, &mut op_count, sig_version)?;
+            }
+
+            if stack.len() > MAX_STACK_SIZE {
+                return Err(Error::BoundExceeded);
+            }
+        }
+
+        if !exec_stack.is_empty() {
+            return Err(Error::UnbalancedConditional);
+        }
+        Ok(())
+    }
+
+    fn eval_opcode(
+        &self,
+        opcode: u8,
+        script: &[u8],
+        stack: &mut Stack,
+        exec_stack: &mut Vec<bool>,
+        op_count: &mut usize,
+        sig_version: SigVersion,
+    ) -> Result<(), Error> {
+        const OP_1: u8 = OpCode::Op1 as u8;
+        const OP_16: u8 = OpCode::Op16 as u8;
+        const OP_1NEGATE: u8 = OpCode::Op1Negate as u8;
+        const OP_NOP: u8 = OpCode::OpNop as u8;
+        const OP_IF: u8 = OpCode::OpIf as u8;
+        const OP_NOTIF: u8 = OpCode::OpNotIf as u8;
+        const OP_ELSE: u8 = OpCode::OpElse as u8;
+        const OP_ENDIF: u8 = OpCode::OpEndIf as u8;
+        const OP_VERIFY: u8 = OpCode::OpVerify as u8;
+        const OP_RETURN: u8 = OpCode::OpReturn as u8;
+        const OP_2DROP: u8 = OpCode::Op2Drop as u8;
+        const OP_DROP: u8 = OpCode::OpDrop as u8;
+        const OP_DUP: u8 = OpCode::OpDup as u8;
+        const OP_SWAP: u8 = OpCode::OpSwap as u8;
+        const OP_SIZE: u8 = OpCode::OpSize as u8;
+        const OP_EQUAL: u8 = OpCode::OpEqual as u8;
+        const OP_EQUALVERIFY: u8 = OpCode::OpEqualVerify as u8;
+        const OP_RIPEMD160: u8 = OpCode::OpRipemd160 as u8;
+        const OP_SHA1: u8 = OpCode::OpSha1 as u8;
+        const OP_SHA256: u8 = OpCode::OpSha256 as u8;
+        const OP_HASH160: u8 = OpCode::OpHash160 as u8;
+        const OP_HASH256: u8 = OpCode::OpHash256 as u8;
+        const OP_CHECKSIG: u8 = OpCode::OpCheckSig as u8;
+        const OP_CHECKSIGVERIFY: u8 = OpCode::OpCheckSigverify as u8;
+        const OP_CHECKMULTISIG: u8 = OpCode::OpCheckMultisig as u8;
+        const OP_CHECKMULTISIGVERIFY: u8 = OpCode::OpCheckMultisigVerify as u8;
+        const OP_CHECKLOCKTIMEVERIFY: u8 = OpCode::OpCheckLocktimeVerify as u8;
+        const OP_CHECKSEQUENCEVERIFY: u8 = OpCode::OpCheckSequenceVerify as u8;
+
+        let executing = exec_stack.iter().all(|&branch| branch);
+        match opcode {
+            OP_1NEGATE => stack.push(vec![0x81]),
+            OP_1..=OP_16 => stack.push(vec![opcode - (OP_1 - 1)]),
+            OP_NOP => {}
+            OP_IF | OP_NOTIF => {
+                let mut branch = false;
+                if executing {
+                    branch = cast_to_bool(&pop(stack)?) == (opcode == OP_IF);
+                }
+                exec_stack.push(branch);
+            }
+            OP_ELSE => {
+                let branch = exec_stack.last_mut().ok_or(Error::UnbalancedConditional)?;
+                *branch = !*branch;
+            }
+            OP_ENDIF => {
+                exec_stack.pop().ok_or(Error::UnbalancedConditional)?;
+            }
+            OP_VERIFY => {
+                if !cast_to_bool(&pop(stack)?) {
+                    return Err(Error::ScriptVerificationFailed);
+                }
+            }
+            OP_RETURN => return Err(Error::ScriptVerificationFailed),
+            OP_2DROP => {
+                pop(stack)?;
+                pop(stack)?;
+            }
+            OP_DROP => {
+                pop(stack)?;
+            }
+            OP_DUP => stack.push(top(stack)?.clone()),
+            OP_SWAP => {
+                let len = stack.len();
+                if len < 2 {
+                    return Err(Error::InvalidStackOperation);
+                }
+                stack.swap(len - 1, len - 2);
+            }
+            OP_SIZE => {
+                let size = top(stack)?.len();
+                let mut size_num = Vec::new();
+                let mut remaining = size;
+                while remaining > 0 {
+                    size_num.push(remaining as u8);
+                    remaining >>= 8;
+                }
+                if size_num.last().map_or(false, |&last| last & 0x80 != 0) {
+                    size_num.push(0);
+                }
+                stack.push(size_num);
+            }
+            OP_EQUAL | OP_EQUALVERIFY => {
+                let equal = pop(stack)? == pop(stack)?;
+                if opcode == OP_EQUALVERIFY {
+                    if !equal {
+                        return Err(Error::ScriptVerificationFailed);
+                    }
+                } else {
+                    stack.push(if equal { vec![1] } else { vec![] });
+                }
+            }
+            OP_RIPEMD160 | OP_SHA1 | OP_SHA256 | OP_HASH160 | OP_HASH256 => {
+                let data = pop(stack)?;
+                let hash = match opcode {
+                    OP_RIPEMD160 => ripemd160::Hash::hash(&data)[..].to_vec(),
+                    OP_SHA1 => sha1::Hash::hash(&data)[..].to_vec(),
+                    OP_SHA256 => sha256(&data).to_vec(),
+                    OP_HASH160 => hash160::Hash::hash(&data)[..].to_vec(),
+                    _ => sha256d::Hash::hash(&data)[..].to_vec(),
+                };
+                stack.push(hash);
+            }
+            OP_CHECKSIG | OP_CHECKSIGVERIFY => {
+                let public_key = pop(stack)?;
+                let signature = pop(stack)?;
+                let script_code = match sig_version {
+                    SigVersion::Base => find_and_delete(script, &signature)?,
+                    SigVersion::WitnessV0 => script.to_vec(),
+                };
+                let success = self.check_sig(&signature, &public_key, &script_code, sig_version)?;
+                if opcode == OP_CHECKSIGVERIFY {
+                    if !success {
+                        return Err(Error::ScriptVerificationFailed);
+                    }
+                } else {
+                    stack.push(if success { vec![1] } else { vec![] });
+                }
+            }
+            OP_CHECKMULTISIG | OP_CHECKMULTISIGVERIFY => {
+                let key_count = decode_script_num(&pop(stack)?, 4)?;
+                if !(0..=MAX_PUBKEYS_PER_MULTISIG).contains(&key_count) {
+                    return Err(Error::InvalidStackOperation);
+                }
+                *op_count += key_count as usize;
+                if *op_count > MAX_OPS_PER_SCRIPT {
+                    return Err(Error::BoundExceeded);
+                }
+                // keys and signatures are popped in reverse order, and are also matched in reverse
+                let public_keys = (0..key_count).map(|_| pop(stack)).collect::<Result<Vec<_>, _>>()?;
+                let signature_count = decode_script_num(&pop(stack)?, 4)?;
+                if !(0..=key_count).contains(&signature_count) {
+                    return Err(Error::InvalidStackOperation);
+                }
+                let signatures = (0..signature_count)
+                    .map(|_| pop(stack))
+                    .collect::<Result<Vec<_>, _>>()?;
+                // an extra element is consumed due to an off-by-one bug in the original implementation
+                pop(stack)?;
+
+                let mut script_code = script.to_vec();
+                if sig_version == SigVersion::Base {
+                    for signature in signatures.iter() {
+                        script_code = find_and_delete(&script_code, signature)?;
+                    }
+                }
+
+                let mut public_keys = public_keys.iter();
+                let mut success = true;
+                for (i, signature) in signatures.iter().enumerate() {
+                    let remaining_signatures = signatures.len() - i;
+                    loop {
+                        if public_keys.len() < remaining_signatures {
+                            success = false;
+                            break;
+                        }
+                        let public_key = public_keys.next().ok_or(Error::InvalidStackOperation)?;
+                        if self.check_sig(signature, public_key, &script_code, sig_version)? {
+                            break;
+                        }
+                    }
+                    if !success {
+                        break;
+                    }
+                }
+
+                if opcode == OP_CHECKMULTISIGVERIFY {
+                    if !success {
+                        return Err(Error::ScriptVerificationFailed);
+                    }
+                } else {
+                    stack.push(if success { vec![1] } else { vec![] });
+                }
+            }
+            OP_CHECKLOCKTIMEVERIFY => self.check_lock_time(decode_script_num(top(stack)?, 5)?)?,
+            OP_CHECKSEQUENCEVERIFY => self.check_sequence(decode_script_num(top(stack)?, 5)?)?,
+            _ => return Err(Error::UnsupportedOpcode),
+        }
+        Ok(())
+    }
+
+    /// Returns false if the signature does not match, signatures with an invalid encoding or sighash
+    /// type fail the script.
+    fn check_sig(
+        &self,
+        signature: &[u8],
+        public_key: &[u8],
+        script_code: &[u8],
+        sig_version: SigVersion,
+    ) -> Result<bool, Error> {
+        let (&sighash_type, signature) = match signature.split_last() {
+            Some(split) => split,
+            None => return Ok(false),
+        };
+        let sighash_type = match SighashType::from_u8(sighash_type) {
+            Ok(SighashType::Default) | Err(_) => return Err(Error::ScriptVerificationFailed),
+            Ok(sighash_type) => sighash_type,
+        };
+        let mut signature = Secp256k1Signature::from_der(signature).map_err(|_| Error::ScriptVerificationFailed)?;
+        // high S values are valid by consensus
+        signature.normalize_s();
+        let public_key = match Secp256k1PublicKey::from_slice(public_key) {
+            Ok(public_key) => public_key,
+            Err(_) => return Ok(false),
+        };
+
+        let script_code = Script::from(script_code.to_vec());
+        let sighash = match sig_version {
+            SigVersion::Base => self
+                .transaction
+                .legacy_sighash(self.input_index, &script_code, sighash_type)?,
+            SigVersion::WitnessV0 => {
+                self.transaction
+                    .segwit_v0_sighash(self.input_index, &script_code, self.value, sighash_type)?
+            }
+        };
+        let message = Message::from_slice(&sighash.to_bytes_le()).map_err(|_| Error::ScriptVerificationFailed)?;
+        Ok(SECP256K1.verify(&message, &signature, &public_key).is_ok())
+    }
+
+    /// <https://github.com/bitcoin/bips/blob/master/bip-0065.mediawiki>
+    fn check_lock_time(&self, lock_time: i64) -> Result<(), Error> {
+        // negative lock times and lock times exceeding any transaction lock time always fail
+        let lock_time = u32::try_from(lock_time).map_err(|_| Error::UnsatisfiedLockTime)?;
+        // both must be heights or both must be timestamps
+        if !LockTime::from_consensus(lock_time).is_implied_by(&self.transaction.lock_at) {
+            return Err(Error::UnsatisfiedLockTime);
+        }
+        // the lock time is not enforced if the input is final
+        if self.input().sequence == SEQUENCE_FINAL {
+            return Err(Error::UnsatisfiedLockTime);
+        }
+        Ok(())
+    }
+
+    /// <https://github.com/bitcoin/bips/blob/master/bip-0112.mediawiki>
+    fn check_sequence(&self, sequence: i64) -> Result<(), Error> {
+        if sequence < 0 {
+            return Err(Error::UnsatisfiedLockTime);
+        }
+        // behaves as a NOP if the disable flag is set, bits above the flag are ignored
+        let lock_time = match RelativeLockTime::from_sequence(sequence as u32) {
+            Some(lock_time) => lock_time,
+            None => return Ok(()),
+        };
+        // relative lock times are only enforced for version 2 transactions
+        if self.transaction.version < 2 {
+            return Err(Error::UnsatisfiedLockTime);
+        }
+        // both must be block based or both must be time based
+        match self.input().relative_lock_time() {
+            Some(tx_lock_time) if lock_time.is_implied_by(&tx_lock_time) => Ok(()),
+            _ => Err(Error::UnsatisfiedLockTime),
+        }
+    }
+}
+
+impl Transaction {
+    /// Verifies that the scriptSig and witness of the input satisfy the `script_pub_key` of the
+    /// spent output, see the [module documentation](crate::interpreter) for the supported scripts.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_index` - index of the input to verify
+    /// * `script_pub_key` - the script of the spent output
+    /// * `value` - the amount of the spent output, only used by segwit signatures
+    pub fn verify_input(&self, input_index: usize, script_pub_key: &Script, value: Value) -> Result<(), Error> {
+        let input = self.inputs.get(input_index).ok_or(Error::InvalidInputIndex)?;
+        if let TransactionInputSource::Coinbase(_) = input.source {
+            return Err(Error::UnsupportedInputFormat);
+        }
+        Interpreter {
+            transaction: self,
+            input_index,
+            value,
+        }
+        .verify(&input.script, script_pub_key, &input.witness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::parse_transaction, timelock::SEQUENCE_LOCKTIME_TYPE_FLAG, PublicKey};
+    use frame_support::{assert_err, assert_ok};
+    use secp256k1::{Secp256k1, SecretKey};
+
+    fn key_pair(seed: u8) -> (SecretKey, Vec<u8>) {
+        let secret_key = SecretKey::from_slice(&[seed; 32]).unwrap();
+        let public_key = Secp256k1PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+        (secret_key, public_key.serialize().to_vec())
+    }
+
+    fn script(build: impl FnOnce(&mut Script)) -> Script {
+        let mut script = Script::new();
+        build(&mut script);
+        script
+    }
+
+    fn spending_transaction(version: i32, lock_time: u32, sequence: u32) -> Transaction {
+        TransactionBuilder::new()
+            .with_version(version)
+            .with_block_height(lock_time)
+            .add_input(
+                TransactionInputBuilder::new()
+                    .with_source(TransactionInputSource::FromOutput(H256Le::from_bytes_le(&[1; 32]), 0))
+                    .with_sequence(sequence)
+                    .build(),
+            )
+            .add_output(TransactionOutput::payment(9_000, &Address::P2PKH(H160::zero())))
+            .build()
+    }
+
+    fn sign(
+        transaction: &Transaction,
+        script_code: &Script,
+        value: Value,
+        sig_version: SigVersion,
+        secret_key: &SecretKey,
+    ) -> Vec<u8> {
+        let sighash = match sig_version {
+            SigVersion::Base => transaction.legacy_sighash(0, script_code, SighashType::All),
+            SigVersion::WitnessV0 => transaction.segwit_v0_sighash(0, script_code, value, SighashType::All),
+        }
+        .unwrap();
+        let message = Message::from_slice(&sighash.to_bytes_le()).unwrap();
+        let mut signature = Secp256k1::new().sign(&message, secret_key).serialize_der().to_vec();
+        signature.push(SighashType::All as u8);
+        signature
+    }
+
+    #[test]
+    fn test_script_num() {
+        assert_eq!(decode_script_num(&[], 4), Ok(0));
+        assert_eq!(decode_script_num(&[0x81], 4), Ok(-1));
+        assert_eq!(decode_script_num(&[0xff, 0x00], 4), Ok(255));
+        assert_eq!(decode_script_num(&[0xff, 0x80], 4), Ok(-255));
+        assert_eq!(
+            decode_script_num(&[0xff, 0xff, 0xff, 0xff, 0x00], 5),
+            Ok(u32::MAX as i64)
+        );
+        assert_err!(decode_script_num(&[0; 5], 4), Error::InvalidStackOperation);
+
+        assert!(!cast_to_bool(&[]));
+        assert!(!cast_to_bool(&[0, 0]));
+        assert!(!cast_to_bool(&[0, 0x80]));
+        assert!(cast_to_bool(&[0x80, 0]));
+        assert!(cast_to_bool(&[1]));
+    }
+
+    #[test]
+    fn test_find_and_delete() {
+        let script = hex::decode("02abcd7602abcd02abce").unwrap();
+        assert_eq!(
+            hex::encode(find_and_delete(&script, &[0xab, 0xcd]).unwrap()),
+            "7602abce"
+        );
+        // pushes are not matched inside other pushes
+        let script = hex::decode("0402abcd76").unwrap();
+        assert_eq!(
+            hex::encode(find_and_delete(&script, &[0xab, 0xcd]).unwrap()),
+            "0402abcd76"
+        );
+    }
+
+    #[test]
+    fn test_verify_p2pk_input() {
+        // source: https://blockstream.info/tx/f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16?expand
+        let raw_tx = "0100000001c997a5e56e104102fa209c6a852dd90660a20b2d9c352423edce25857fcd3704000000004847304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d0901ffffffff0200ca9a3b00000000434104ae1a62fe09c5f51b13905f07f06b99a2f7159b2225f374cd378d71302fa28414e7aab37397f554a7df5f142c21c1b7303b8a0626f1baded5c72a704f7e6cd84cac00286bee0000000043410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac00000000";
+        let transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        // the spent output pays to the same key as the change output
+        let script_pub_key = transaction.outputs[1].script.clone();
+        assert_ok!(transaction.verify_input(0, &script_pub_key, 50_0000_0000));
+
+        let script_pub_key = transaction.outputs[0].script.clone();
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 50_0000_0000),
+            Error::ScriptVerificationFailed
+        );
+    }
+
+    #[test]
+    fn test_verify_p2pkh_input() {
+        // source: https://www.blockstream.info/testnet/tx/219a49b6a376e8f4ef86866e93483552679b5157318f0e4085430a3cee24e3d8?expand
+        let raw_tx = "010000000125314e40cfc816ae562c10cc1855df21ff2ed2fad43046a4b6dabbb35c393c20000000006a47304402200cd7aa9166960f3374bf655a5c5ba0a47801ae22f8231baa2412e8f47941792e02206b21c44642887b32fd87fb82a052363605c109b31d971ce502322e8148caf1670121023f3b8d04b9fac2ac10b8b8e7a4d5f033f259d26a74d2b0b77313f41585b3d1b5ffffffff0160e18709000000006952210218597441c292cb6d73174c1662ac9d60b76688fd359f90e2d653d1a089c9aba921022bda026d6aee8133f0290449a282f8cfbccafdc064b0b47068854457f38af3bc21030a230982d9706247d5997df1aea7144266c33a2e6c64c6a3a44c5cdf9c0ff58a53ae00000000";
+        let mut transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        let mut public_key = [0; 33];
+        public_key.copy_from_slice(&transaction.inputs[0].script[73..]);
+        let script_pub_key = Address::P2PKH(PublicKey(public_key).to_hash()).to_script_pub_key();
+        assert_ok!(transaction.verify_input(0, &script_pub_key, 0));
+
+        // wrong key hash
+        let other_script_pub_key = Address::P2PKH(H160::zero()).to_script_pub_key();
+        assert_err!(
+            transaction.verify_input(0, &other_script_pub_key, 0),
+            Error::ScriptVerificationFailed
+        );
+
+        // signature does not commit to the modified transaction
+        transaction.outputs[0].value -= 1;
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 0),
+            Error::ScriptVerificationFailed
+        );
+    }
+
+    #[test]
+    fn test_verify_p2wpkh_input() {
+        let (secret_key, public_key) = key_pair(1);
+        let key_hash = H160::from_slice(&hash160::Hash::hash(&public_key)[..]);
+        let script_pub_key = Address::P2WPKHv0(key_hash).to_script_pub_key();
+        let script_code = Address::P2PKH(key_hash).to_script_pub_key();
+
+        let mut transaction = spending_transaction(2, 0, SEQUENCE_FINAL);
+        let signature = sign(&transaction, &script_code, 10_000, SigVersion::WitnessV0, &secret_key);
+        transaction.inputs[0].witness = vec![signature, public_key];
+        assert_ok!(transaction.verify_input(0, &script_pub_key, 10_000));
+
+        // the amount is committed to
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 10_001),
+            Error::ScriptVerificationFailed
+        );
+
+        // non-empty scriptSig
+        transaction.inputs[0].script = vec![0x51];
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 10_000),
+            Error::ScriptVerificationFailed
+        );
+    }
+
+    #[test]
+    fn test_verify_p2sh_multisig_input() {
+        let keys: Vec<_> = (1..=3).map(key_pair).collect();
+        // 2-of-3 multisig
+        let redeem_script = script(|script| {
+            script.append(OpCode::Op2);
+            for (_, public_key) in keys.iter() {
+                script.push_data(public_key);
+            }
+            script.append(OpCode::Op3);
+            script.append(OpCode::OpCheckMultisig);
+        });
+        let script_pub_key =
+            Address::P2SH(H160::from_slice(&hash160::Hash::hash(redeem_script.as_bytes())[..])).to_script_pub_key();
+
+        let mut transaction = spending_transaction(2, 0, SEQUENCE_FINAL);
+        let signatures: Vec<_> = keys
+            .iter()
+            .map(|(secret_key, _)| sign(&transaction, &redeem_script, 0, SigVersion::Base, secret_key))
+            .collect();
+
+        let script_sig = |signatures: &[&Vec<u8>]| {
+            script(|script| {
+                script.append(OpCode::Op0);
+                for signature in signatures {
+                    script.push_data(signature);
+                }
+                script.push_data(redeem_script.as_bytes());
+            })
+        };
+
+        transaction.inputs[0].script = script_sig(&[&signatures[0], &signatures[2]]).as_bytes().to_vec();
+        assert_ok!(transaction.verify_input(0, &script_pub_key, 0));
+
+        // signatures must be in the same order as the keys
+        transaction.inputs[0].script = script_sig(&[&signatures[2], &signatures[0]]).as_bytes().to_vec();
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 0),
+            Error::ScriptVerificationFailed
+        );
+
+        // not enough signatures
+        transaction.inputs[0].script = script_sig(&[&signatures[1]]).as_bytes().to_vec();
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 0),
+            Error::InvalidStackOperation
+        );
+    }
+
+    /// Hashed timelock contract: the receiver can claim with the preimage, the sender can refund
+    /// after the timeout
+    fn htlc_script(hash: &[u8], receiver: &[u8], sender: &[u8], timeout: &[u8], relative: bool) -> Script {
+        script(|script| {
+            script.append(OpCode::OpIf);
+            script.append(OpCode::OpSize);
+            script.push_data(&[32]);
+            script.append(OpCode::OpEqualVerify);
+            script.append(OpCode::OpSha256);
+            script.push_data(hash);
+            script.append(OpCode::OpEqualVerify);
+            script.push_data(receiver);
+            script.append(OpCode::OpElse);
+            script.push_data(timeout);
+            if relative {
+                script.append(OpCode::OpCheckSequenceVerify);
+            } else {
+                script.append(OpCode::OpCheckLocktimeVerify);
+            }
+            script.append(OpCode::OpDrop);
+            script.push_data(sender);
+            script.append(OpCode::OpEndIf);
+            script.append(OpCode::OpCheckSig);
+        })
+    }
+
+    #[test]
+    fn test_verify_p2wsh_htlc_input() {
+        let (receiver_secret, receiver) = key_pair(1);
+        let (sender_secret, sender) = key_pair(2);
+        let preimage = [7; 32];
+        // block height 500 as script number
+        let witness_script = htlc_script(&sha256(&preimage), &receiver, &sender, &[0xf4, 0x01], false);
+        let script_pub_key = Address::P2WSHv0(sha256(witness_script.as_bytes()).into()).to_script_pub_key();
+
+        let claim = |transaction: &mut Transaction, preimage: &[u8]| {
+            let signature = sign(
+                transaction,
+                &witness_script,
+                10_000,
+                SigVersion::WitnessV0,
+                &receiver_secret,
+            );
+            transaction.inputs[0].witness = vec![
+                signature,
+                preimage.to_vec(),
+                vec![1],
+                witness_script.as_bytes().to_vec(),
+            ];
+        };
+        let refund = |transaction: &mut Transaction| {
+            let signature = sign(
+                transaction,
+                &witness_script,
+                10_000,
+                SigVersion::WitnessV0,
+                &sender_secret,
+            );
+            transaction.inputs[0].witness = vec![signature, vec![], witness_script.as_bytes().to_vec()];
+        };
+
+        let mut transaction = spending_transaction(2, 0, SEQUENCE_FINAL);
+        claim(&mut transaction, &preimage);
+        assert_ok!(transaction.verify_input(0, &script_pub_key, 10_000));
+        claim(&mut transaction, &[8; 32]);
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 10_000),
+            Error::ScriptVerificationFailed
+        );
+
+        // refund before the timeout
+        let mut transaction = spending_transaction(2, 499, 0);
+        refund(&mut transaction);
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 10_000),
+            Error::UnsatisfiedLockTime
+        );
+        // refund with a final input
+        let mut transaction = spending_transaction(2, 500, SEQUENCE_FINAL);
+        refund(&mut transaction);
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 10_000),
+            Error::UnsatisfiedLockTime
+        );
+        let mut transaction = spending_transaction(2, 500, 0);
+        refund(&mut transaction);
+        assert_ok!(transaction.verify_input(0, &script_pub_key, 10_000));
+
+        // the witness script must match the program
+        transaction.inputs[0]
+            .witness
+            .last_mut()
+            .unwrap()
+            .push(OpCode::OpNop as u8);
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 10_000),
+            Error::ScriptVerificationFailed
+        );
+    }
+
+    #[test]
+    fn test_verify_p2sh_p2wsh_csv_input() {
+        let (receiver_secret, receiver) = key_pair(1);
+        let (sender_secret, sender) = key_pair(2);
+        // relative lock of 144 blocks
+        let witness_script = htlc_script(&[0; 32], &receiver, &sender, &[0x90, 0x00], true);
+        let redeem_script = Address::P2WSHv0(sha256(witness_script.as_bytes()).into()).to_script_pub_key();
+        let script_pub_key =
+            Address::P2SH(H160::from_slice(&hash160::Hash::hash(redeem_script.as_bytes())[..])).to_script_pub_key();
+
+        let refund = |version: i32, sequence: u32| {
+            let mut transaction = spending_transaction(version, 0, sequence);
+            let signature = sign(
+                &transaction,
+                &witness_script,
+                10_000,
+                SigVersion::WitnessV0,
+                &sender_secret,
+            );
+            transaction.inputs[0].script = script(|script| script.push_data(redeem_script.as_bytes()))
+                .as_bytes()
+                .to_vec();
+            transaction.inputs[0].witness = vec![signature, vec![], witness_script.as_bytes().to_vec()];
+            transaction
+        };
+
+        assert_ok!(refund(2, 144).verify_input(0, &script_pub_key, 10_000));
+        assert_err!(
+            refund(2, 143).verify_input(0, &script_pub_key, 10_000),
+            Error::UnsatisfiedLockTime
+        );
+        // time based sequence
+        assert_err!(
+            refund(2, SEQUENCE_LOCKTIME_TYPE_FLAG | 144).verify_input(0, &script_pub_key, 10_000),
+            Error::UnsatisfiedLockTime
+        );
+        // relative lock times require version 2
+        assert_err!(
+            refund(1, 144).verify_input(0, &script_pub_key, 10_000),
+            Error::UnsatisfiedLockTime
+        );
+
+        // the receiver key does not satisfy the refund branch
+        let mut transaction = refund(2, 144);
+        transaction.inputs[0].witness[0] = sign(
+            &transaction,
+            &witness_script,
+            10_000,
+            SigVersion::WitnessV0,
+            &receiver_secret,
+        );
+        assert_err!(
+            transaction.verify_input(0, &script_pub_key, 10_000),
+            Error::ScriptVerificationFailed
+        );
+    }
+
+    #[test]
+    fn test_eval_fails() {
+        let transaction = spending_transaction(2, 0, SEQUENCE_FINAL);
+        let interpreter = Interpreter {
+            transaction: &transaction,
+            input_index: 0,
+            value: 0,
+        };
+        let eval = |script: &str| interpreter.eval(&hex::decode(script).unwrap(), &mut Stack::new(), SigVersion::Base);
+
+        // OP_1 OP_IF
+        assert_err!(eval("5163"), Error::UnbalancedConditional);
+        // OP_ENDIF
+        assert_err!(eval("68"), Error::UnbalancedConditional);
+        // OP_DROP
+        assert_err!(eval("75"), Error::InvalidStackOperation);
+        // OP_1 OP_1 OP_ADD
+        assert_err!(eval("515193"), Error::UnsupportedOpcode);
+        // unsupported opcodes in unexecuted branches are skipped: OP_0 OP_IF OP_ADD OP_ENDIF
+        assert_ok!(eval("00639368"));
+        // OP_RETURN
+        assert_err!(eval("6a"), Error::ScriptVerificationFailed);
+        // truncated push
+        assert_err!(eval("4c05abab"), Error::InvalidScript);
+        // element larger than 520 bytes
+        assert_err!(eval(&format!("4d0902{}", "00".repeat(521))), Error::BoundExceeded);
+        // more than 201 operations
+        assert_err!(eval(&"61".repeat(202)), Error::BoundExceeded);
+
+        // the scriptSig must be push only: OP_1 OP_DUP
+        let mut transaction = transaction.clone();
+        transaction.inputs[0].script = vec![0x51, 0x76];
+        assert_err!(
+            transaction.verify_input(0, &Script::from(vec![0x51]), 0),
+            Error::ScriptVerificationFailed
+        );
+    }
+}
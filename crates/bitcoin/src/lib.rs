@@ -12,7 +12,15 @@
 //!
 //! - (De)serialization of block headers, transactions and merkle proofs.
 //! - Script (address) construction and parsing.
+//! - Base58Check and Bech32/Bech32m address encoding.
 //! - Merkle proof construction and verification.
+//! - Transaction inclusion proofs from raw blocks (std only).
+//! - Signature hash computation for legacy, segwit v0 and taproot key path inputs.
+//! - Script evaluation for a subset of opcodes used by standard spending conditions.
+//! - Absolute and relative lock times and transaction finality.
+//! - Signet block solution verification.
+//! - Compact block filters (BIP-158).
+//! - (De)serialization of partially signed transactions (PSBT v0 and v2).
 //! - Elliptic curve multiplication over Secp256k1.
 
 #![deny(warnings)]
@@ -35,6 +43,13 @@ pub mod merkle;
 mod address;
 pub use address::*;
 
+mod network;
+pub use network::Network;
+
+mod base58;
+
+mod bech32;
+
 mod script;
 pub use script::Script;
 
@@ -42,9 +57,27 @@ pub mod types;
 
 pub mod formatter;
 
+pub mod sighash;
+
+pub mod interpreter;
+
+pub mod timelock;
+
 #[cfg(any(feature = "parser", test))]
 pub mod parser;
 
+#[cfg(any(feature = "parser", test))]
+pub mod signet;
+
+#[cfg(any(feature = "parser", test))]
+pub mod filter;
+
+#[cfg(all(feature = "std", any(feature = "parser", test)))]
+pub mod proof;
+
+#[cfg(any(feature = "psbt", test))]
+pub mod psbt;
+
 #[cfg(feature = "bitcoin-types-compat")]
 pub mod compat;
 
@@ -106,6 +106,41 @@ impl MerkleTree {
 impl PartialTransactionProof {
     /// Computes the merkle root of the proof partial merkle tree
     pub fn verify_proof(self) -> Result<ProofResult, Error> {
+        let (root, merkle_position, hash_position) = self.merkle_proof.extract_root()?;
+
+        let tx_id = self.transaction.tx_id_bounded(self.tx_encoded_len)?;
+
+        // fail if the transaction hash is invalid
+        if self.merkle_proof.hashes[hash_position] != tx_id {
+            return Err(Error::InvalidTxid);
+        }
+        // ensure!(self.merkle_proof.hashes[hash_position] == tx_id, Error::InvalidTxid);
+
+        Ok(ProofResult {
+            extracted_root: root,
+            transaction_hash: self.merkle_proof.hashes[hash_position],
+            transaction_position: merkle_position,
+            transaction: self.transaction,
+            tx_count: self.merkle_proof.transactions_count,
+            block_hash: self.merkle_proof.block_header.hash,
+        })
+    }
+}
+
+impl MerkleProof {
+    /// Returns the width of the partial merkle tree
+    pub fn compute_partial_tree_width(&self, height: u32) -> u32 {
+        MerkleTree::compute_width(self.transactions_count, height)
+    }
+
+    /// Returns the height of the partial merkle tree
+    pub fn compute_partial_tree_height(&self) -> u32 {
+        MerkleTree::compute_height(self.transactions_count)
+    }
+
+    /// Traverses the partial merkle tree, returning the merkle root, the position of the
+    /// matched leaf in the tree and the index of its hash in `hashes`
+    pub(crate) fn extract_root(&self) -> Result<(H256Le, u32, usize), Error> {
         let mut traversal = MerkleProofTraversal {
             bits_used: 0,
             hashes_used: 0,
@@ -114,30 +149,26 @@ impl PartialTransactionProof {
         };
 
         // fail if no transactions
-        if self.merkle_proof.transactions_count == 0 {
+        if self.transactions_count == 0 {
             return Err(Error::MalformedMerkleProof);
         }
 
         // fail if too many transactions
-        if self.merkle_proof.transactions_count > MAX_TRANSACTIONS_IN_PROOF {
+        if self.transactions_count > MAX_TRANSACTIONS_IN_PROOF {
             return Err(Error::MalformedMerkleProof);
         }
 
         // fail if not at least one bit per hash
-        if self.merkle_proof.flag_bits.len() < self.merkle_proof.hashes.len() {
+        if self.flag_bits.len() < self.hashes.len() {
             return Err(Error::MalformedMerkleProof);
         }
 
-        let root = self.merkle_proof.traverse_and_extract(
-            self.merkle_proof.compute_partial_tree_height(),
-            0,
-            &mut traversal,
-        )?;
+        let root = self.traverse_and_extract(self.compute_partial_tree_height(), 0, &mut traversal)?;
         let merkle_position = traversal.merkle_position.ok_or(Error::InvalidMerkleProof)?;
         let hash_position = traversal.hash_position.ok_or(Error::InvalidMerkleProof)?;
 
         // fail if all hashes are not used
-        if traversal.hashes_used != self.merkle_proof.hashes.len() {
+        if traversal.hashes_used != self.hashes.len() {
             return Err(Error::MalformedMerkleProof);
         }
 
@@ -149,7 +180,6 @@ impl PartialTransactionProof {
             .checked_div(8)
             .ok_or(Error::ArithmeticUnderflow)?
             != self
-                .merkle_proof
                 .flag_bits
                 .len()
                 .checked_add(7)
@@ -160,34 +190,7 @@ impl PartialTransactionProof {
             return Err(Error::MalformedMerkleProof);
         }
 
-        let tx_id = self.transaction.tx_id_bounded(self.tx_encoded_len)?;
-
-        // fail if the transaction hash is invalid
-        if self.merkle_proof.hashes[hash_position] != tx_id {
-            return Err(Error::InvalidTxid);
-        }
-        // ensure!(self.merkle_proof.hashes[hash_position] == tx_id, Error::InvalidTxid);
-
-        Ok(ProofResult {
-            extracted_root: root,
-            transaction_hash: self.merkle_proof.hashes[hash_position],
-            transaction_position: merkle_position,
-            transaction: self.transaction,
-            tx_count: self.merkle_proof.transactions_count,
-            block_hash: self.merkle_proof.block_header.hash,
-        })
-    }
-}
-
-impl MerkleProof {
-    /// Returns the width of the partial merkle tree
-    pub fn compute_partial_tree_width(&self, height: u32) -> u32 {
-        MerkleTree::compute_width(self.transactions_count, height)
-    }
-
-    /// Returns the height of the partial merkle tree
-    pub fn compute_partial_tree_height(&self) -> u32 {
-        MerkleTree::compute_height(self.transactions_count)
+        Ok((root, merkle_position, hash_position))
     }
 
     pub fn compute_merkle_root(&self, index: u32, height: u32, tx_ids: &[H256Le]) -> Result<H256Le, Error> {
@@ -0,0 +1,60 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+
+/// Bitcoin network. The Parachain follows SPV assumptions and does not need to know which
+/// network a payment is included in, but the human-readable encoding of an [`Address`](crate::Address)
+/// depends on it.
+#[derive(
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Default,
+    Ord,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Debug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(std::hash::Hash))]
+pub enum Network {
+    #[default]
+    Mainnet,
+    /// Testnet3 and Testnet4 use the same address prefixes.
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl Network {
+    /// Base58Check version byte of P2PKH addresses
+    // https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/kernel/chainparams.cpp#L145
+    pub const fn p2pkh_prefix(self) -> u8 {
+        match self {
+            Self::Mainnet => 0x00,
+            Self::Testnet | Self::Signet | Self::Regtest => 0x6f,
+        }
+    }
+
+    /// Base58Check version byte of P2SH addresses
+    pub const fn p2sh_prefix(self) -> u8 {
+        match self {
+            Self::Mainnet => 0x05,
+            Self::Testnet | Self::Signet | Self::Regtest => 0xc4,
+        }
+    }
+
+    /// Human-readable part of segwit (Bech32/Bech32m) addresses
+    pub const fn bech32_hrp(self) -> &'static str {
+        match self {
+            Self::Mainnet => "bc",
+            Self::Testnet | Self::Signet => "tb",
+            Self::Regtest => "bcrt",
+        }
+    }
+}
@@ -6,9 +6,6 @@ use alloc::{vec, vec::Vec};
 
 const SERIALIZE_TRANSACTION_NO_WITNESS: i32 = 0x4000_0000;
 
-// https://github.com/bitcoin/bitcoin/blob/7fcf53f7b4524572d1d0c9a5fdc388e87eb02416/src/script/script.h#L39
-const LOCKTIME_THRESHOLD: u32 = 500_000_000;
-
 /// Type to be parsed from a bytes array
 pub(crate) trait Parsable: Sized {
     fn parse(raw_bytes: &[u8], position: usize) -> Result<(Self, usize), Error>;
@@ -149,12 +146,7 @@ impl Parsable for Transaction {
         }
 
         // https://en.bitcoin.it/wiki/NLockTime
-        let locktime_or_blockheight: u32 = parser.parse()?;
-        let lock_at = if locktime_or_blockheight < LOCKTIME_THRESHOLD {
-            LockTime::BlockHeight(locktime_or_blockheight)
-        } else {
-            LockTime::Time(locktime_or_blockheight)
-        };
+        let lock_at = LockTime::from_consensus(parser.parse()?);
 
         if flags != 0 {
             return Err(Error::MalformedTransaction);
@@ -248,16 +240,19 @@ impl BytesParser {
     /// Reads `bytes_count` from the bytes parser and moves the head
     /// Fails if there are not enough bytes to read
     pub(crate) fn read(&mut self, bytes_count: usize) -> Result<Vec<u8>, Error> {
-        let bytes = self
-            .raw_bytes
-            .get(self.position..self.position + bytes_count)
-            .ok_or(Error::EndOfFile)?;
-        self.position = self
+        let end = self
             .position
             .checked_add(bytes_count)
             .ok_or(Error::ArithmeticOverflow)?;
+        let bytes = self.raw_bytes.get(self.position..end).ok_or(Error::EndOfFile)?;
+        self.position = end;
         Ok(Vec::from(bytes))
     }
+
+    /// Returns the number of bytes which have not been parsed yet
+    pub(crate) fn remaining(&self) -> usize {
+        self.raw_bytes.len().saturating_sub(self.position)
+    }
 }
 
 /// Allows to parse the given structure from little-endian encoded bytes
@@ -335,6 +330,22 @@ pub fn parse_transaction(raw_transaction: &[u8]) -> Result<Transaction, Error> {
     Transaction::parse(raw_transaction, 0).map(|(tx, _len)| tx)
 }
 
+/// Parses a serialized bitcoin block, i.e. the header followed by all transactions
+///
+/// # Arguments
+///
+/// * `raw_block` - the raw bytes of the block
+pub fn parse_block(raw_block: &[u8]) -> Result<Block, Error> {
+    let mut parser = BytesParser::new(raw_block);
+    let header: BlockHeader = parser.parse()?;
+    let transactions: Vec<Transaction> = parser.parse()?;
+    // fail if the transaction count does not cover all of the data
+    if parser.remaining() != 0 {
+        return Err(Error::MalformedTransaction);
+    }
+    Ok(Block { header, transactions })
+}
+
 /// Parses a transaction input
 fn parse_transaction_input(raw_input: &[u8], version: i32) -> Result<(TransactionInput, usize), Error> {
     let mut parser = BytesParser::new(raw_input);
@@ -732,6 +743,35 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_block() {
+        let address = Address::P2PKH(H160::from_low_u64_be(1));
+        let mut input = TransactionInputBuilder::new()
+            .with_source(TransactionInputSource::FromOutput(H256Le::from_bytes_le(&[1; 32]), 0))
+            .build();
+        input.with_witness(vec![vec![2; 72], vec![3; 33]]);
+        let block = BlockBuilder::new()
+            .with_coinbase(&address, 50, 3)
+            .add_transaction(
+                TransactionBuilder::new()
+                    .with_version(2)
+                    .add_input(input)
+                    .add_output(TransactionOutput::payment(100, &address))
+                    .build(),
+            )
+            .mine(U256::from(2).pow(254.into()))
+            .unwrap();
+        let mut raw_block = vec![];
+        block.try_format(&mut raw_block).unwrap();
+
+        assert_eq!(parse_block(&raw_block), Ok(block));
+
+        raw_block.push(0);
+        assert_err!(parse_block(&raw_block), Error::MalformedTransaction);
+        raw_block.truncate(raw_block.len() - 2);
+        assert_err!(parse_block(&raw_block), Error::EndOfFile);
+    }
+
     /*
     #[test]
     fn test_extract_address_invalid_p2pkh_fails() {
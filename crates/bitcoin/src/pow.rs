@@ -1,5 +1,8 @@
-use crate::{Error, GetCompact};
+use crate::{Error, GetCompact, SetCompact};
+use codec::{Decode, Encode, MaxEncodedLen};
 use primitive_types::U256;
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 
 /// Target Timespan: 2 weeks (1209600 seconds)
 // https://github.com/bitcoin/bitcoin/blob/5ba5becbb5d8c794efe579caeea7eea64f895a13/src/chainparams.cpp#L77
@@ -12,12 +15,94 @@ pub const TARGET_TIMESPAN_DIVISOR: u64 = 4;
 /// 0x00000000FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF
 pub const UNROUNDED_MAX_TARGET: U256 = U256([<u64>::MAX, <u64>::MAX, <u64>::MAX, 0x0000_0000_ffff_ffffu64]);
 
+/// Target Spacing: 10 minutes (600 seconds)
+pub const TARGET_SPACING: u64 = 10 * 60;
+
+/// Maximum number of seconds by which the first block of a difficulty period may precede the
+/// last block of the previous period (BIP-94)
+pub const MAX_TIMEWARP: u64 = 600;
+
+/// Proof-of-work rules of the bitcoin networks, which differ in the maximum target and in how
+/// the target of the next block is derived.
+// https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/kernel/chainparams.cpp
+#[derive(
+    Serialize, Deserialize, Encode, Decode, Clone, Copy, Default, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen,
+)]
+pub enum PowNetwork {
+    #[default]
+    Mainnet,
+    /// Allows minimum difficulty blocks if no block was found for 20 minutes.
+    Testnet3,
+    /// Same as testnet3, but retargets from the first block of the difficulty period (BIP-94).
+    Testnet4,
+    /// Mainnet rules with a lower difficulty, blocks must additionally satisfy the signet
+    /// challenge, see [`signet`](crate::signet).
+    Signet,
+    /// Allows minimum difficulty blocks and never retargets.
+    Regtest,
+}
+
+impl PowNetwork {
+    /// Returns the maximum (easiest) target of a block
+    pub fn pow_limit(self) -> U256 {
+        match self {
+            Self::Mainnet | Self::Testnet3 | Self::Testnet4 => UNROUNDED_MAX_TARGET,
+            // 0x00000377ae000000000000000000000000000000000000000000000000000000
+            Self::Signet => U256::from(0x0377ae) << 216,
+            // 0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff
+            Self::Regtest => U256::MAX >> 1,
+        }
+    }
+
+    /// Returns the target of blocks mined at the minimum difficulty, i.e. the pow limit rounded
+    /// to its compact representation
+    pub fn min_difficulty_target(self) -> Result<U256, Error> {
+        self.pow_limit()
+            .get_compact()
+            .and_then(U256::set_compact)
+            .ok_or(Error::InvalidCompact)
+    }
+
+    pub const fn allow_min_difficulty_blocks(self) -> bool {
+        matches!(self, Self::Testnet3 | Self::Testnet4 | Self::Regtest)
+    }
+
+    pub const fn no_retargeting(self) -> bool {
+        matches!(self, Self::Regtest)
+    }
+
+    pub const fn enforce_bip94(self) -> bool {
+        matches!(self, Self::Testnet4)
+    }
+
+    /// Returns true if a block at a height which is not a retarget height may be mined at the
+    /// minimum difficulty, which is the case if its timestamp is more than 20 minutes after the
+    /// previous block
+    // https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/pow.cpp#L24-L39
+    pub fn is_min_difficulty_block(self, block_time: u64, prev_block_time: u64) -> bool {
+        self.allow_min_difficulty_blocks() && block_time > prev_block_time.saturating_add(TARGET_SPACING * 2)
+    }
+}
+
+/// Computes the compact target of the first block of a difficulty period.
+///
+/// # Arguments
+///
+/// * `network` - the proof-of-work rules to apply
+/// * `previous_target` - the target of the last block of the previous period, or of its first block under BIP-94
+/// * `first_block_time` - timestamp of the first block of the previous period
+/// * `last_block_time` - timestamp of the last block of the previous period
 // https://github.com/bitcoin/bitcoin/blob/89b910711c004c21b7d67baa888073742f7f94f0/src/pow.cpp#L49-L72
 pub fn calculate_next_work_required(
+    network: PowNetwork,
     previous_target: U256,
     first_block_time: u64,
     last_block_time: u64,
 ) -> Result<u32, Error> {
+    if network.no_retargeting() {
+        return previous_target.get_compact().ok_or(Error::InvalidCompact);
+    }
+
     let mut actual_timespan = last_block_time.saturating_sub(first_block_time);
 
     if actual_timespan < TARGET_TIMESPAN / TARGET_TIMESPAN_DIVISOR {
@@ -32,20 +117,16 @@ pub fn calculate_next_work_required(
     let target = target / TARGET_TIMESPAN;
 
     // ensure target does not exceed max
-    if target > UNROUNDED_MAX_TARGET {
-        UNROUNDED_MAX_TARGET
-    } else {
-        target
-    }
-    .get_compact()
-    .ok_or(Error::InvalidCompact)
+    let pow_limit = network.pow_limit();
+    if target > pow_limit { pow_limit } else { target }
+        .get_compact()
+        .ok_or(Error::InvalidCompact)
 }
 
 // https://github.com/bitcoin/bitcoin/blob/7fcf53f7b4524572d1d0c9a5fdc388e87eb02416/src/test/pow_tests.cpp
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::SetCompact;
     use frame_support::assert_ok;
 
     fn target_set_compact(bits: u32) -> U256 {
@@ -58,7 +139,7 @@ mod tests {
         let first_block_time = 1261130161; // Block #30240
         let last_block_time = 1262152739; // Block #32255
         assert_ok!(
-            calculate_next_work_required(previous_target, first_block_time, last_block_time),
+            calculate_next_work_required(PowNetwork::Mainnet, previous_target, first_block_time, last_block_time),
             0x1d00d86a
         );
     }
@@ -69,7 +150,7 @@ mod tests {
         let first_block_time = 1231006505; // Block #0
         let last_block_time = 1233061996; // Block #2015
         assert_ok!(
-            calculate_next_work_required(previous_target, first_block_time, last_block_time),
+            calculate_next_work_required(PowNetwork::Mainnet, previous_target, first_block_time, last_block_time),
             0x1d00ffff
         );
     }
@@ -80,7 +161,7 @@ mod tests {
         let first_block_time = 1279008237; // Block #66528
         let last_block_time = 1279297671; // Block #68543
         assert_ok!(
-            calculate_next_work_required(previous_target, first_block_time, last_block_time),
+            calculate_next_work_required(PowNetwork::Mainnet, previous_target, first_block_time, last_block_time),
             0x1c0168fd
         );
     }
@@ -91,7 +172,7 @@ mod tests {
         let first_block_time = 1263163443; // NOTE: Not an actual block time
         let last_block_time = 1269211443; // Block #46367
         assert_ok!(
-            calculate_next_work_required(previous_target, first_block_time, last_block_time),
+            calculate_next_work_required(PowNetwork::Mainnet, previous_target, first_block_time, last_block_time),
             0x1d00e1fd
         );
     }
@@ -103,8 +184,54 @@ mod tests {
         let first_block_time = 1632234876; // Block #701568
         let last_block_time = 1633390031; // Block #703583
         assert_ok!(
-            calculate_next_work_required(previous_target, first_block_time, last_block_time),
+            calculate_next_work_required(PowNetwork::Mainnet, previous_target, first_block_time, last_block_time),
             0x170e2632 // Block #703584
         );
     }
+
+    #[test]
+    fn get_next_work_no_retargeting() {
+        let previous_target = target_set_compact(0x207fffff);
+        assert_ok!(
+            calculate_next_work_required(PowNetwork::Regtest, previous_target, 1296688602, 1296688602),
+            0x207fffff
+        );
+    }
+
+    #[test]
+    fn get_next_work_signet_pow_limit() {
+        // blocks were found too slowly, the target is capped at the signet limit
+        let previous_target = target_set_compact(0x1e0377ae);
+        assert_ok!(
+            calculate_next_work_required(PowNetwork::Signet, previous_target, 1598918400, 1601337600),
+            0x1e0377ae
+        );
+        // mainnet caps the same target at its (higher) limit
+        assert_ok!(
+            calculate_next_work_required(PowNetwork::Mainnet, previous_target, 1598918400, 1601337600),
+            0x1d00ffff
+        );
+    }
+
+    #[test]
+    fn pow_limits() {
+        assert_eq!(PowNetwork::Mainnet.pow_limit().get_compact(), Some(0x1d00ffff));
+        assert_eq!(PowNetwork::Testnet4.pow_limit().get_compact(), Some(0x1d00ffff));
+        assert_eq!(PowNetwork::Signet.pow_limit().get_compact(), Some(0x1e0377ae));
+        assert_eq!(PowNetwork::Regtest.pow_limit().get_compact(), Some(0x207fffff));
+        assert_eq!(
+            PowNetwork::Testnet3.min_difficulty_target(),
+            Ok(target_set_compact(0x1d00ffff))
+        );
+    }
+
+    #[test]
+    fn min_difficulty_blocks() {
+        let prev_block_time = 1_700_000_000;
+        assert!(PowNetwork::Testnet3.is_min_difficulty_block(prev_block_time + 1201, prev_block_time));
+        assert!(!PowNetwork::Testnet3.is_min_difficulty_block(prev_block_time + 1200, prev_block_time));
+        assert!(PowNetwork::Regtest.is_min_difficulty_block(prev_block_time + 1201, prev_block_time));
+        assert!(!PowNetwork::Mainnet.is_min_difficulty_block(prev_block_time + 1201, prev_block_time));
+        assert!(!PowNetwork::Signet.is_min_difficulty_block(prev_block_time + 1201, prev_block_time));
+    }
 }
@@ -0,0 +1,197 @@
+//! Construction of transaction inclusion proofs from raw blocks, for use by clients.
+//!
+//! The resulting [`FullTransactionProof`] is checked locally with the same rules as the
+//! btc-relay pallet applies on chain, except for the lookup of the block header in the relay.
+
+use crate::{
+    merkle::PartialTransactionProof,
+    parser::parse_block,
+    types::{Block, FullTransactionProof, H256Le, Transaction},
+    Error,
+};
+use codec::Encode;
+
+/// A transaction inclusion proof together with its SCALE encoding, ready to be submitted
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncodedTransactionProof {
+    pub proof: FullTransactionProof,
+    /// SCALE encoded proof
+    pub scale: Vec<u8>,
+    /// Hex encoding of `scale` with a `0x` prefix
+    pub hex: String,
+}
+
+/// Builds the inclusion proof of the transaction with `tx_id` in the serialized block
+///
+/// # Arguments
+///
+/// * `raw_block` - the raw bytes of the block
+/// * `tx_id` - the txid of the transaction to prove
+pub fn build_transaction_proof(raw_block: &[u8], tx_id: H256Le) -> Result<EncodedTransactionProof, Error> {
+    let block = parse_block(raw_block)?;
+    let proof = build_full_proof(&block, tx_id)?;
+    verify_transaction_proof(&proof, tx_id)?;
+
+    let scale = proof.encode();
+    let hex = format!("0x{}", hex::encode(&scale));
+    Ok(EncodedTransactionProof { proof, scale, hex })
+}
+
+/// Builds the inclusion proof of the transaction with `tx_id` in the block, without verifying it
+pub fn build_full_proof(block: &Block, tx_id: H256Le) -> Result<FullTransactionProof, Error> {
+    let coinbase = block.transactions.first().ok_or(Error::MalformedTransaction)?;
+    let transaction = block
+        .transactions
+        .iter()
+        .find(|transaction| transaction.tx_id() == tx_id)
+        .ok_or(Error::InvalidTxid)?;
+
+    Ok(FullTransactionProof {
+        user_tx_proof: partial_proof(block, transaction)?,
+        coinbase_proof: partial_proof(block, coinbase)?,
+    })
+}
+
+fn partial_proof(block: &Block, transaction: &Transaction) -> Result<PartialTransactionProof, Error> {
+    let mut encoded = Vec::new();
+    transaction.format_no_witness(&mut encoded)?;
+    Ok(PartialTransactionProof {
+        transaction: transaction.clone(),
+        tx_encoded_len: encoded.len() as u32,
+        merkle_proof: block.merkle_proof(&[transaction.tx_id()])?,
+    })
+}
+
+/// Verifies that the proof shows the inclusion of the transaction with `tx_id` in the block of
+/// the proof. This mirrors the checks of `btc_relay::Pallet::_verify_transaction_inclusion`.
+pub fn verify_transaction_proof(proof: &FullTransactionProof, tx_id: H256Le) -> Result<(), Error> {
+    let user_proof_result = proof.user_tx_proof.clone().verify_proof()?;
+    let coinbase_proof_result = proof.coinbase_proof.clone().verify_proof()?;
+
+    if user_proof_result.transaction_hash != tx_id {
+        return Err(Error::InvalidTxid);
+    }
+    // the coinbase must be the first transaction of the same block as the user transaction
+    if coinbase_proof_result.transaction_position != 0
+        || !coinbase_proof_result.transaction.is_coinbase()
+        || user_proof_result.block_hash != coinbase_proof_result.block_hash
+        || user_proof_result.tx_count != coinbase_proof_result.tx_count
+    {
+        return Err(Error::InvalidMerkleProof);
+    }
+    // both proofs must commit to the merkle root of the block header
+    let merkle_root = proof.user_tx_proof.merkle_proof.block_header.merkle_root;
+    if user_proof_result.extracted_root != merkle_root || coinbase_proof_result.extracted_root != merkle_root {
+        return Err(Error::InvalidMerkleProof);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatter::TryFormat,
+        types::{BlockBuilder, TransactionBuilder, TransactionInputBuilder, TransactionInputSource, TransactionOutput},
+        Address,
+    };
+    use codec::Decode;
+    use frame_support::assert_err;
+    use primitive_types::{H160, U256};
+
+    fn sample_block() -> Block {
+        let address = Address::P2PKH(H160::from_low_u64_be(1));
+        let transaction = |index: u8| {
+            TransactionBuilder::new()
+                .with_version(2)
+                .add_input(
+                    TransactionInputBuilder::new()
+                        .with_source(TransactionInputSource::FromOutput(
+                            H256Le::from_bytes_le(&[index; 32]),
+                            0,
+                        ))
+                        .with_script(&[index; 100])
+                        .build(),
+                )
+                .add_output(TransactionOutput::payment(100, &address))
+                .build()
+        };
+        let mut builder = BlockBuilder::new();
+        builder.with_coinbase(&address, 50, 3);
+        for index in 1..=4 {
+            builder.add_transaction(transaction(index));
+        }
+        builder.mine(U256::from(2).pow(254.into())).unwrap()
+    }
+
+    #[test]
+    fn test_build_transaction_proof() {
+        let block = sample_block();
+        let mut raw_block = vec![];
+        block.try_format(&mut raw_block).unwrap();
+
+        for transaction in block.transactions.iter() {
+            let tx_id = transaction.tx_id();
+            let result = build_transaction_proof(&raw_block, tx_id).unwrap();
+
+            assert_eq!(result.proof.user_tx_proof.transaction, *transaction);
+            assert_eq!(result.proof.coinbase_proof.transaction, block.transactions[0]);
+            assert_eq!(
+                FullTransactionProof::decode(&mut &result.scale[..]).unwrap(),
+                result.proof
+            );
+            assert_eq!(result.hex, format!("0x{}", hex::encode(&result.scale)));
+            assert_eq!(
+                result
+                    .proof
+                    .user_tx_proof
+                    .clone()
+                    .verify_proof()
+                    .unwrap()
+                    .extracted_root,
+                block.header.merkle_root
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_transaction_proof_fails_for_unknown_transaction() {
+        let block = sample_block();
+        let mut raw_block = vec![];
+        block.try_format(&mut raw_block).unwrap();
+
+        assert_err!(
+            build_transaction_proof(&raw_block, H256Le::from_bytes_le(&[9; 32])),
+            Error::InvalidTxid
+        );
+    }
+
+    #[test]
+    fn test_verify_transaction_proof_rejects_inconsistent_proofs() {
+        let block = sample_block();
+        let tx_id = block.transactions[2].tx_id();
+        let proof = build_full_proof(&block, tx_id).unwrap();
+        assert_eq!(verify_transaction_proof(&proof, tx_id), Ok(()));
+
+        assert_err!(
+            verify_transaction_proof(&proof, block.transactions[1].tx_id()),
+            Error::InvalidTxid
+        );
+
+        // the coinbase proof must be for the first transaction
+        let mut invalid_proof = proof.clone();
+        invalid_proof.coinbase_proof = proof.user_tx_proof.clone();
+        assert_err!(
+            verify_transaction_proof(&invalid_proof, tx_id),
+            Error::InvalidMerkleProof
+        );
+
+        // the proofs must commit to the merkle root of the header
+        let mut invalid_proof = proof.clone();
+        invalid_proof.user_tx_proof.merkle_proof.block_header.merkle_root = H256Le::zero();
+        assert_err!(
+            verify_transaction_proof(&invalid_proof, tx_id),
+            Error::InvalidMerkleProof
+        );
+    }
+}
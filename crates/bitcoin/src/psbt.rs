@@ -0,0 +1,884 @@
+//! Partially signed transactions as defined by
+//! [BIP-174](https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki) (version 0) and
+//! [BIP-370](https://github.com/bitcoin/bips/blob/master/bip-0370.mediawiki) (version 2).
+//!
+//! Only the fields required to construct, sign and finalize standard transactions are typed,
+//! all other key-value pairs (e.g. BIP-32 derivation paths, taproot or proprietary fields) are
+//! kept as raw bytes so that they survive a round trip.
+
+use crate::{
+    formatter::{TryFormat, Writer},
+    parser::{parse_compact_uint, BytesParser, Parsable},
+    timelock::{LOCKTIME_THRESHOLD, SEQUENCE_FINAL},
+    types::*,
+    Error, Script,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+const MAGIC: [u8; 5] = [b'p', b's', b'b', b't', 0xff];
+
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_GLOBAL_TX_VERSION: u8 = 0x02;
+const PSBT_GLOBAL_FALLBACK_LOCKTIME: u8 = 0x03;
+const PSBT_GLOBAL_INPUT_COUNT: u8 = 0x04;
+const PSBT_GLOBAL_OUTPUT_COUNT: u8 = 0x05;
+const PSBT_GLOBAL_TX_MODIFIABLE: u8 = 0x06;
+const PSBT_GLOBAL_VERSION: u8 = 0xfb;
+
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+const PSBT_IN_WITNESS_SCRIPT: u8 = 0x05;
+const PSBT_IN_FINAL_SCRIPTSIG: u8 = 0x07;
+const PSBT_IN_FINAL_SCRIPTWITNESS: u8 = 0x08;
+const PSBT_IN_PREVIOUS_TXID: u8 = 0x0e;
+const PSBT_IN_OUTPUT_INDEX: u8 = 0x0f;
+const PSBT_IN_SEQUENCE: u8 = 0x10;
+const PSBT_IN_REQUIRED_TIME_LOCKTIME: u8 = 0x11;
+const PSBT_IN_REQUIRED_HEIGHT_LOCKTIME: u8 = 0x12;
+
+const PSBT_OUT_REDEEM_SCRIPT: u8 = 0x00;
+const PSBT_OUT_WITNESS_SCRIPT: u8 = 0x01;
+const PSBT_OUT_AMOUNT: u8 = 0x03;
+const PSBT_OUT_SCRIPT: u8 = 0x04;
+
+/// Raw key-value pairs, the key includes the key type
+type Map = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Raw key-value pairs in the order they are added
+type Pairs = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Version of the PSBT format
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Version {
+    /// BIP-174, the unsigned transaction is stored in the global map
+    V0,
+    /// BIP-370, the transaction fields are stored in the input and output maps
+    V2,
+}
+
+/// Partially signed bitcoin transaction
+#[derive(PartialEq, Clone, Debug)]
+pub struct Psbt {
+    pub version: Version,
+    /// `PSBT_GLOBAL_UNSIGNED_TX`, only set for version 0
+    pub unsigned_tx: Option<Transaction>,
+    /// `PSBT_GLOBAL_TX_VERSION`, only set for version 2
+    pub tx_version: Option<i32>,
+    /// `PSBT_GLOBAL_FALLBACK_LOCKTIME`, only set for version 2
+    pub fallback_locktime: Option<u32>,
+    /// `PSBT_GLOBAL_TX_MODIFIABLE`, only set for version 2
+    pub tx_modifiable: Option<u8>,
+    /// Global key-value pairs not interpreted by this crate
+    pub unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+    pub inputs: Vec<Input>,
+    pub outputs: Vec<Output>,
+}
+
+/// Per-input fields of a PSBT
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Input {
+    /// The full transaction spent by this input
+    pub non_witness_utxo: Option<Transaction>,
+    /// The output spent by this input, for segwit inputs only
+    pub witness_utxo: Option<TransactionOutput>,
+    /// Signatures indexed by the serialized public key
+    pub partial_sigs: BTreeMap<Vec<u8>, Vec<u8>>,
+    pub sighash_type: Option<u32>,
+    pub redeem_script: Option<Script>,
+    pub witness_script: Option<Script>,
+    pub final_script_sig: Option<Script>,
+    pub final_script_witness: Option<Vec<Vec<u8>>>,
+    /// `PSBT_IN_PREVIOUS_TXID`, only set for version 2
+    pub previous_txid: Option<H256Le>,
+    /// `PSBT_IN_OUTPUT_INDEX`, only set for version 2
+    pub output_index: Option<u32>,
+    /// `PSBT_IN_SEQUENCE`, only set for version 2
+    pub sequence: Option<u32>,
+    /// `PSBT_IN_REQUIRED_TIME_LOCKTIME`, only set for version 2
+    pub required_time_locktime: Option<u32>,
+    /// `PSBT_IN_REQUIRED_HEIGHT_LOCKTIME`, only set for version 2
+    pub required_height_locktime: Option<u32>,
+    /// Input key-value pairs not interpreted by this crate
+    pub unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+/// Per-output fields of a PSBT
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Output {
+    pub redeem_script: Option<Script>,
+    pub witness_script: Option<Script>,
+    /// `PSBT_OUT_AMOUNT`, only set for version 2
+    pub amount: Option<Value>,
+    /// `PSBT_OUT_SCRIPT`, only set for version 2
+    pub script: Option<Script>,
+    /// Output key-value pairs not interpreted by this crate
+    pub unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+/// Parses `value` as `T`, failing if not all bytes are consumed
+fn decode<T: Parsable>(value: &[u8]) -> Result<T, Error> {
+    let mut parser = BytesParser::new(value);
+    let result = parser.parse()?;
+    if parser.remaining() != 0 {
+        return Err(Error::InvalidPsbt);
+    }
+    Ok(result)
+}
+
+fn encode<T: TryFormat>(value: T) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![];
+    value.try_format(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn key(key_type: u8, key_data: &[u8]) -> Vec<u8> {
+    let mut key = vec![key_type];
+    key.extend_from_slice(key_data);
+    key
+}
+
+fn ensure_no_key_data(key_data: &[u8]) -> Result<(), Error> {
+    if key_data.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidPsbt)
+    }
+}
+
+/// Parses a map of key-value pairs up to and including the separator
+fn parse_map(parser: &mut BytesParser) -> Result<Map, Error> {
+    let mut map = Map::new();
+    loop {
+        let key_len: CompactUint = parser.parse()?;
+        if key_len.value == 0 {
+            return Ok(map);
+        }
+        let key = parser.read(key_len.value as usize)?;
+        let value_len: CompactUint = parser.parse()?;
+        let value = parser.read(value_len.value as usize)?;
+        if map.insert(key, value).is_some() {
+            return Err(Error::DuplicatePsbtKey);
+        }
+    }
+}
+
+/// Splits a raw key into its type and data, types which do not fit into a byte are
+/// returned as `None` since they are never interpreted
+fn split_key(key: &[u8]) -> Result<(Option<u8>, &[u8]), Error> {
+    let (key_type, consumed) = parse_compact_uint(key)?;
+    Ok((u8::try_from(key_type).ok(), &key[consumed..]))
+}
+
+/// Formats the key-value pairs ordered by key, followed by the separator
+fn format_map<W: Writer>(mut pairs: Pairs, w: &mut W) -> Result<(), Error> {
+    pairs.sort();
+    for (key, value) in pairs {
+        key.try_format(w)?;
+        value.try_format(w)?;
+    }
+    0u8.try_format(w)
+}
+
+impl Psbt {
+    /// Creates a version 0 PSBT with empty input and output maps, fails if any input of
+    /// `transaction` has a script or witness
+    pub fn from_unsigned_tx(transaction: Transaction) -> Result<Psbt, Error> {
+        if transaction
+            .inputs
+            .iter()
+            .any(|input| !input.script.is_empty() || !input.witness.is_empty())
+        {
+            return Err(Error::InvalidPsbt);
+        }
+        Ok(Psbt {
+            version: Version::V0,
+            inputs: vec![Default::default(); transaction.inputs.len()],
+            outputs: vec![Default::default(); transaction.outputs.len()],
+            unsigned_tx: Some(transaction),
+            tx_version: None,
+            fallback_locktime: None,
+            tx_modifiable: None,
+            unknown: Default::default(),
+        })
+    }
+
+    /// Parses a serialized PSBT of either version
+    pub fn from_bytes(bytes: &[u8]) -> Result<Psbt, Error> {
+        let mut parser = BytesParser::new(bytes);
+        if parser.read(MAGIC.len()).map_err(|_| Error::InvalidPsbt)? != MAGIC {
+            return Err(Error::InvalidPsbt);
+        }
+
+        let (mut psbt, input_count, output_count) = Self::from_global_map(parse_map(&mut parser)?)?;
+        // every map takes up at least one byte for the separator
+        if input_count.saturating_add(output_count) > parser.remaining() as u64 {
+            return Err(Error::InvalidPsbt);
+        }
+        psbt.inputs = (0..input_count)
+            .map(|_| Input::from_map(parse_map(&mut parser)?, psbt.version))
+            .collect::<Result<_, _>>()?;
+        psbt.outputs = (0..output_count)
+            .map(|_| Output::from_map(parse_map(&mut parser)?, psbt.version))
+            .collect::<Result<_, _>>()?;
+        if parser.remaining() != 0 {
+            return Err(Error::InvalidPsbt);
+        }
+
+        // the full previous transaction must match the spent outpoint
+        let transaction = psbt.transaction()?;
+        for (input, tx_input) in psbt.inputs.iter().zip(transaction.inputs.iter()) {
+            if let Some(ref non_witness_utxo) = input.non_witness_utxo {
+                let (hash, index) = tx_input.outpoint();
+                if non_witness_utxo.tx_id() != hash || non_witness_utxo.outputs.len() <= index as usize {
+                    return Err(Error::InvalidPsbt);
+                }
+            }
+        }
+
+        Ok(psbt)
+    }
+
+    /// Serializes the PSBT
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        encode(self)
+    }
+
+    /// Parses the global map, returning the PSBT without input and output maps and their counts
+    fn from_global_map(map: Map) -> Result<(Psbt, u64, u64), Error> {
+        let version = match map.get(&key(PSBT_GLOBAL_VERSION, &[])) {
+            None => Version::V0,
+            Some(value) => match decode::<u32>(value)? {
+                0 => Version::V0,
+                2 => Version::V2,
+                _ => return Err(Error::UnsupportedPsbtVersion),
+            },
+        };
+
+        let mut psbt = Psbt {
+            version,
+            unsigned_tx: None,
+            tx_version: None,
+            fallback_locktime: None,
+            tx_modifiable: None,
+            unknown: Default::default(),
+            inputs: vec![],
+            outputs: vec![],
+        };
+        let mut counts = (None, None);
+
+        for (key, value) in map {
+            match (split_key(&key)?, version) {
+                ((Some(PSBT_GLOBAL_VERSION), key_data), _) => ensure_no_key_data(key_data)?,
+                ((Some(PSBT_GLOBAL_UNSIGNED_TX), key_data), Version::V0) => {
+                    ensure_no_key_data(key_data)?;
+                    let transaction: Transaction = decode(&value)?;
+                    // the unsigned transaction must not be serialized with witnesses
+                    if value.len() != transaction.size_no_witness() {
+                        return Err(Error::InvalidPsbt);
+                    }
+                    psbt.unsigned_tx = Some(transaction);
+                }
+                ((Some(PSBT_GLOBAL_TX_VERSION), key_data), Version::V2) => {
+                    ensure_no_key_data(key_data)?;
+                    psbt.tx_version = Some(decode(&value)?);
+                }
+                ((Some(PSBT_GLOBAL_FALLBACK_LOCKTIME), key_data), Version::V2) => {
+                    ensure_no_key_data(key_data)?;
+                    psbt.fallback_locktime = Some(decode(&value)?);
+                }
+                ((Some(PSBT_GLOBAL_INPUT_COUNT), key_data), Version::V2) => {
+                    ensure_no_key_data(key_data)?;
+                    counts.0 = Some(decode::<CompactUint>(&value)?.value);
+                }
+                ((Some(PSBT_GLOBAL_OUTPUT_COUNT), key_data), Version::V2) => {
+                    ensure_no_key_data(key_data)?;
+                    counts.1 = Some(decode::<CompactUint>(&value)?.value);
+                }
+                ((Some(PSBT_GLOBAL_TX_MODIFIABLE), key_data), Version::V2) => {
+                    ensure_no_key_data(key_data)?;
+                    psbt.tx_modifiable = Some(decode(&value)?);
+                }
+                (
+                    (
+                        Some(
+                            PSBT_GLOBAL_UNSIGNED_TX
+                            | PSBT_GLOBAL_TX_VERSION
+                            | PSBT_GLOBAL_FALLBACK_LOCKTIME
+                            | PSBT_GLOBAL_INPUT_COUNT
+                            | PSBT_GLOBAL_OUTPUT_COUNT
+                            | PSBT_GLOBAL_TX_MODIFIABLE,
+                        ),
+                        &[],
+                    ),
+                    _,
+                ) => {
+                    // field of the other version
+                    return Err(Error::InvalidPsbt);
+                }
+                _ => {
+                    psbt.unknown.insert(key, value);
+                }
+            }
+        }
+
+        let (input_count, output_count) = match version {
+            Version::V0 => {
+                let transaction = psbt.unsigned_tx.as_ref().ok_or(Error::InvalidPsbt)?;
+                if transaction
+                    .inputs
+                    .iter()
+                    .any(|input| !input.script.is_empty() || !input.witness.is_empty())
+                {
+                    return Err(Error::InvalidPsbt);
+                }
+                (transaction.inputs.len() as u64, transaction.outputs.len() as u64)
+            }
+            Version::V2 => {
+                if psbt.tx_version.ok_or(Error::InvalidPsbt)? < 2 {
+                    return Err(Error::InvalidPsbt);
+                }
+                match counts {
+                    (Some(input_count), Some(output_count)) => (input_count, output_count),
+                    _ => return Err(Error::InvalidPsbt),
+                }
+            }
+        };
+
+        Ok((psbt, input_count, output_count))
+    }
+
+    /// Returns the unsigned transaction, for version 2 it is constructed from the input and output
+    /// maps with the lock time determined as specified in BIP-370
+    pub fn transaction(&self) -> Result<Transaction, Error> {
+        if let Some(ref transaction) = self.unsigned_tx {
+            return Ok(transaction.clone());
+        }
+
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| {
+                Ok(TransactionInput {
+                    source: TransactionInputSource::FromOutput(
+                        input.previous_txid.ok_or(Error::InvalidPsbt)?,
+                        input.output_index.ok_or(Error::InvalidPsbt)?,
+                    ),
+                    script: vec![],
+                    sequence: input.sequence.unwrap_or(SEQUENCE_FINAL),
+                    witness: vec![],
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| {
+                Ok(TransactionOutput {
+                    value: output.amount.ok_or(Error::InvalidPsbt)?,
+                    script: output.script.clone().ok_or(Error::InvalidPsbt)?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Transaction {
+            version: self.tx_version.ok_or(Error::InvalidPsbt)?,
+            inputs,
+            outputs,
+            lock_at: LockTime::from_consensus(self.lock_time()?),
+        })
+    }
+
+    fn lock_time(&self) -> Result<u32, Error> {
+        let required = self
+            .inputs
+            .iter()
+            .filter(|input| input.required_time_locktime.is_some() || input.required_height_locktime.is_some());
+
+        let mut has_required = false;
+        let (mut supports_time, mut supports_height) = (true, true);
+        let (mut time, mut height) = (0, 0);
+        for input in required {
+            has_required = true;
+            match input.required_time_locktime {
+                Some(value) => time = time.max(value),
+                None => supports_time = false,
+            }
+            match input.required_height_locktime {
+                Some(value) => height = height.max(value),
+                None => supports_height = false,
+            }
+        }
+
+        if !has_required {
+            Ok(self.fallback_locktime.unwrap_or(0))
+        } else if supports_height {
+            Ok(height)
+        } else if supports_time {
+            Ok(time)
+        } else {
+            Err(Error::InvalidPsbt)
+        }
+    }
+
+    /// Converts the PSBT to the given version
+    pub fn into_version(mut self, version: Version) -> Result<Psbt, Error> {
+        if self.version == version {
+            return Ok(self);
+        }
+
+        let transaction = self.transaction()?;
+        match version {
+            Version::V0 => {
+                for input in self.inputs.iter_mut() {
+                    input.previous_txid = None;
+                    input.output_index = None;
+                    input.sequence = None;
+                    input.required_time_locktime = None;
+                    input.required_height_locktime = None;
+                }
+                for output in self.outputs.iter_mut() {
+                    output.amount = None;
+                    output.script = None;
+                }
+                self.tx_version = None;
+                self.fallback_locktime = None;
+                self.tx_modifiable = None;
+                self.unsigned_tx = Some(transaction);
+            }
+            Version::V2 => {
+                for (input, tx_input) in self.inputs.iter_mut().zip(transaction.inputs) {
+                    let (hash, index) = tx_input.outpoint();
+                    input.previous_txid = Some(hash);
+                    input.output_index = Some(index);
+                    input.sequence = Some(tx_input.sequence);
+                }
+                for (output, tx_output) in self.outputs.iter_mut().zip(transaction.outputs) {
+                    output.amount = Some(tx_output.value);
+                    output.script = Some(tx_output.script);
+                }
+                self.tx_version = Some(transaction.version.max(2));
+                self.fallback_locktime = Some(transaction.lock_at.to_consensus());
+                self.unsigned_tx = None;
+            }
+        }
+        self.version = version;
+        Ok(self)
+    }
+
+    /// Returns the network transaction, fails if any input has not been finalized
+    pub fn extract_tx(&self) -> Result<Transaction, Error> {
+        let mut transaction = self.transaction()?;
+        for (input, tx_input) in self.inputs.iter().zip(transaction.inputs.iter_mut()) {
+            if input.final_script_sig.is_none() && input.final_script_witness.is_none() {
+                return Err(Error::PsbtInputNotFinalized);
+            }
+            if let Some(ref script) = input.final_script_sig {
+                tx_input.script = script.as_bytes().to_vec();
+            }
+            if let Some(ref witness) = input.final_script_witness {
+                tx_input.with_witness(witness.clone());
+            }
+        }
+        Ok(transaction)
+    }
+
+    fn pairs(&self) -> Result<Pairs, Error> {
+        let mut pairs: Pairs = self.unknown.clone().into_iter().collect();
+        if let Some(ref transaction) = self.unsigned_tx {
+            let mut value = vec![];
+            transaction.format_no_witness(&mut value)?;
+            pairs.push((key(PSBT_GLOBAL_UNSIGNED_TX, &[]), value));
+        }
+        if let Some(tx_version) = self.tx_version {
+            pairs.push((key(PSBT_GLOBAL_TX_VERSION, &[]), encode(tx_version)?));
+        }
+        if let Some(fallback_locktime) = self.fallback_locktime {
+            pairs.push((key(PSBT_GLOBAL_FALLBACK_LOCKTIME, &[]), encode(fallback_locktime)?));
+        }
+        if self.version == Version::V2 {
+            pairs.push((
+                key(PSBT_GLOBAL_INPUT_COUNT, &[]),
+                encode(CompactUint::from_usize(self.inputs.len()))?,
+            ));
+            pairs.push((
+                key(PSBT_GLOBAL_OUTPUT_COUNT, &[]),
+                encode(CompactUint::from_usize(self.outputs.len()))?,
+            ));
+            pairs.push((key(PSBT_GLOBAL_VERSION, &[]), encode(2u32)?));
+        }
+        if let Some(tx_modifiable) = self.tx_modifiable {
+            pairs.push((key(PSBT_GLOBAL_TX_MODIFIABLE, &[]), encode(tx_modifiable)?));
+        }
+        Ok(pairs)
+    }
+}
+
+impl Input {
+    fn from_map(map: Map, version: Version) -> Result<Input, Error> {
+        let mut input = Input::default();
+        for (key, value) in map {
+            match (split_key(&key)?, version) {
+                ((Some(PSBT_IN_NON_WITNESS_UTXO), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    input.non_witness_utxo = Some(decode(&value)?);
+                }
+                ((Some(PSBT_IN_WITNESS_UTXO), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    input.witness_utxo = Some(decode(&value)?);
+                }
+                ((Some(PSBT_IN_PARTIAL_SIG), public_key), _) => {
+                    if public_key.len() != 33 && public_key.len() != 65 {
+                        return Err(Error::InvalidPsbt);
+                    }
+                    input.partial_sigs.insert(public_key.to_vec(), value);
+                }
+                ((Some(PSBT_IN_SIGHASH_TYPE), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    input.sighash_type = Some(decode(&value)?);
+                }
+                ((Some(PSBT_IN_REDEEM_SCRIPT), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    input.redeem_script = Some(value.into());
+                }
+                ((Some(PSBT_IN_WITNESS_SCRIPT), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    input.witness_script = Some(value.into());
+                }
+                ((Some(PSBT_IN_FINAL_SCRIPTSIG), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    input.final_script_sig = Some(value.into());
+                }
+                ((Some(PSBT_IN_FINAL_SCRIPTWITNESS), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    input.final_script_witness = Some(decode(&value)?);
+                }
+                ((Some(PSBT_IN_PREVIOUS_TXID), &[]), Version::V2) => {
+                    input.previous_txid = Some(decode(&value)?);
+                }
+                ((Some(PSBT_IN_OUTPUT_INDEX), &[]), Version::V2) => {
+                    input.output_index = Some(decode(&value)?);
+                }
+                ((Some(PSBT_IN_SEQUENCE), &[]), Version::V2) => {
+                    input.sequence = Some(decode(&value)?);
+                }
+                ((Some(PSBT_IN_REQUIRED_TIME_LOCKTIME), &[]), Version::V2) => {
+                    let value = decode(&value)?;
+                    if value < LOCKTIME_THRESHOLD {
+                        return Err(Error::InvalidPsbt);
+                    }
+                    input.required_time_locktime = Some(value);
+                }
+                ((Some(PSBT_IN_REQUIRED_HEIGHT_LOCKTIME), &[]), Version::V2) => {
+                    let value = decode(&value)?;
+                    if value == 0 || value >= LOCKTIME_THRESHOLD {
+                        return Err(Error::InvalidPsbt);
+                    }
+                    input.required_height_locktime = Some(value);
+                }
+                (
+                    (
+                        Some(
+                            PSBT_IN_PREVIOUS_TXID
+                            | PSBT_IN_OUTPUT_INDEX
+                            | PSBT_IN_SEQUENCE
+                            | PSBT_IN_REQUIRED_TIME_LOCKTIME
+                            | PSBT_IN_REQUIRED_HEIGHT_LOCKTIME,
+                        ),
+                        &[],
+                    ),
+                    Version::V0,
+                ) => {
+                    // version 2 fields are not allowed in version 0
+                    return Err(Error::InvalidPsbt);
+                }
+                _ => {
+                    input.unknown.insert(key, value);
+                }
+            }
+        }
+
+        if version == Version::V2 && (input.previous_txid.is_none() || input.output_index.is_none()) {
+            return Err(Error::InvalidPsbt);
+        }
+        Ok(input)
+    }
+
+    fn pairs(&self) -> Result<Pairs, Error> {
+        let mut pairs: Pairs = self.unknown.clone().into_iter().collect();
+        if let Some(ref transaction) = self.non_witness_utxo {
+            pairs.push((key(PSBT_IN_NON_WITNESS_UTXO, &[]), encode(transaction)?));
+        }
+        if let Some(ref output) = self.witness_utxo {
+            pairs.push((key(PSBT_IN_WITNESS_UTXO, &[]), encode(output)?));
+        }
+        for (public_key, signature) in self.partial_sigs.iter() {
+            pairs.push((key(PSBT_IN_PARTIAL_SIG, public_key), signature.clone()));
+        }
+        if let Some(sighash_type) = self.sighash_type {
+            pairs.push((key(PSBT_IN_SIGHASH_TYPE, &[]), encode(sighash_type)?));
+        }
+        if let Some(ref script) = self.redeem_script {
+            pairs.push((key(PSBT_IN_REDEEM_SCRIPT, &[]), script.as_bytes().to_vec()));
+        }
+        if let Some(ref script) = self.witness_script {
+            pairs.push((key(PSBT_IN_WITNESS_SCRIPT, &[]), script.as_bytes().to_vec()));
+        }
+        if let Some(ref script) = self.final_script_sig {
+            pairs.push((key(PSBT_IN_FINAL_SCRIPTSIG, &[]), script.as_bytes().to_vec()));
+        }
+        if let Some(ref witness) = self.final_script_witness {
+            pairs.push((key(PSBT_IN_FINAL_SCRIPTWITNESS, &[]), encode(witness)?));
+        }
+        if let Some(previous_txid) = self.previous_txid {
+            pairs.push((key(PSBT_IN_PREVIOUS_TXID, &[]), encode(previous_txid)?));
+        }
+        if let Some(output_index) = self.output_index {
+            pairs.push((key(PSBT_IN_OUTPUT_INDEX, &[]), encode(output_index)?));
+        }
+        if let Some(sequence) = self.sequence {
+            pairs.push((key(PSBT_IN_SEQUENCE, &[]), encode(sequence)?));
+        }
+        if let Some(locktime) = self.required_time_locktime {
+            pairs.push((key(PSBT_IN_REQUIRED_TIME_LOCKTIME, &[]), encode(locktime)?));
+        }
+        if let Some(locktime) = self.required_height_locktime {
+            pairs.push((key(PSBT_IN_REQUIRED_HEIGHT_LOCKTIME, &[]), encode(locktime)?));
+        }
+        Ok(pairs)
+    }
+}
+
+impl Output {
+    fn from_map(map: Map, version: Version) -> Result<Output, Error> {
+        let mut output = Output::default();
+        for (key, value) in map {
+            match (split_key(&key)?, version) {
+                ((Some(PSBT_OUT_REDEEM_SCRIPT), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    output.redeem_script = Some(value.into());
+                }
+                ((Some(PSBT_OUT_WITNESS_SCRIPT), key_data), _) => {
+                    ensure_no_key_data(key_data)?;
+                    output.witness_script = Some(value.into());
+                }
+                ((Some(PSBT_OUT_AMOUNT), &[]), Version::V2) => {
+                    output.amount = Some(decode(&value)?);
+                }
+                ((Some(PSBT_OUT_SCRIPT), &[]), Version::V2) => {
+                    output.script = Some(value.into());
+                }
+                ((Some(PSBT_OUT_AMOUNT | PSBT_OUT_SCRIPT), &[]), Version::V0) => {
+                    // version 2 fields are not allowed in version 0
+                    return Err(Error::InvalidPsbt);
+                }
+                _ => {
+                    output.unknown.insert(key, value);
+                }
+            }
+        }
+
+        if version == Version::V2 && (output.amount.is_none() || output.script.is_none()) {
+            return Err(Error::InvalidPsbt);
+        }
+        Ok(output)
+    }
+
+    fn pairs(&self) -> Result<Pairs, Error> {
+        let mut pairs: Pairs = self.unknown.clone().into_iter().collect();
+        if let Some(ref script) = self.redeem_script {
+            pairs.push((key(PSBT_OUT_REDEEM_SCRIPT, &[]), script.as_bytes().to_vec()));
+        }
+        if let Some(ref script) = self.witness_script {
+            pairs.push((key(PSBT_OUT_WITNESS_SCRIPT, &[]), script.as_bytes().to_vec()));
+        }
+        if let Some(amount) = self.amount {
+            pairs.push((key(PSBT_OUT_AMOUNT, &[]), encode(amount)?));
+        }
+        if let Some(ref script) = self.script {
+            pairs.push((key(PSBT_OUT_SCRIPT, &[]), script.as_bytes().to_vec()));
+        }
+        Ok(pairs)
+    }
+}
+
+impl TryFormat for Psbt {
+    fn try_format<W: Writer>(&self, w: &mut W) -> Result<(), Error> {
+        w.write(&MAGIC)?;
+        format_map(self.pairs()?, w)?;
+        for input in self.inputs.iter() {
+            format_map(input.pairs()?, w)?;
+        }
+        for output in self.outputs.iter() {
+            format_map(output.pairs()?, w)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki#test-vectors
+    const VALID_P2PKH: &str = "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab300000000000000";
+    const VALID_P2SH_P2WPKH: &str = "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac000000000001076a47304402204759661797c01b036b25928948686218347d89864b719e1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceedaf93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa882920001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb82308000000";
+    const VALID_SIGHASH: &str = "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001030401000000000000";
+    const VALID_PARTIAL_SIGS: &str = "70736274ff0100890200000001207ae985d787dfe6143d5c58fad79cc7105e0e799fcf033b7f2ba17e62d7b3200000000000ffffffff02563d03000000000022002019899534b9a011043c0dd57c3ff9a381c3522c5f27c6a42319085b56ca543a1d6adc020000000000220020618b47a07ebecca4e156edb1b9ea7c24bdee0139fc049237965ffdaf56d5ee73000000000001012b801a0600000000002200201148e93e9315e37dbed2121be5239257af35adc03ffdfc5d914b083afa44dab82202025fe7371376d53cf8a2783917c28bf30bd690b0a4d4a207690093ca2b920ee076473044022007e06b362e89912abd4661f47945430739b006a85d1b2a16c01dc1a4bd07acab022061576d7aa834988b7ab94ef21d8eebd996ea59ea20529a19b15f0c9cebe3d8ac01220202b3fe93530020a8294f0e527e33fbdff184f047eb6b5a1558a352f62c29972f8a473044022002787f926d6817504431ee281183b8119b6845bfaa6befae45e13b6d430c9d2f02202859f149a6cd26ae2f03a107e7f33c7d91730dade305fe077bae677b5d44952a01010547522102b3fe93530020a8294f0e527e33fbdff184f047eb6b5a1558a352f62c29972f8a21025fe7371376d53cf8a2783917c28bf30bd690b0a4d4a207690093ca2b920ee07652ae0001014752210283ef76537f2d58ae3aa3a4bd8ae41c3f230ccadffb1a0bd3ca504d871cff05e7210353d79cc0cb1396f4ce278d005f16d948e02a6aec9ed1109f13747ecb1507b37b52ae00010147522102b3937241777b6665e0d694e52f9c1b188433641df852da6fc42187b5d8a368a321034cdd474f01cc5aa7ff834ad8bcc882a87e854affc775486bc2a9f62e8f49bd7852ae00";
+    const VALID_UNKNOWN: &str = "70736274ff01003f0200000001ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0000000000ffffffff010000000000000000036a010000000000000a0f0102030405060708090f0102030405060708090a0b0c0d0e0f0000";
+
+    fn parse(hex: &str) -> Result<Psbt, Error> {
+        Psbt::from_bytes(&hex::decode(hex).unwrap())
+    }
+
+    #[test]
+    fn test_parse_valid_psbts() {
+        for hex in [
+            VALID_P2PKH,
+            VALID_P2SH_P2WPKH,
+            VALID_SIGHASH,
+            VALID_PARTIAL_SIGS,
+            VALID_UNKNOWN,
+        ] {
+            let psbt = parse(hex).unwrap();
+            assert_eq!(psbt.version, Version::V0);
+            assert_eq!(hex::encode(psbt.to_bytes().unwrap()), hex);
+        }
+    }
+
+    #[test]
+    fn test_parse_psbt_fields() {
+        let psbt = parse(VALID_SIGHASH).unwrap();
+        assert_eq!(psbt.inputs.len(), 1);
+        assert_eq!(psbt.outputs.len(), 2);
+        let transaction = psbt.transaction().unwrap();
+        assert_eq!(transaction.lock_at, LockTime::BlockHeight(1257139));
+        let non_witness_utxo = psbt.inputs[0].non_witness_utxo.as_ref().unwrap();
+        assert_eq!(non_witness_utxo.tx_id(), transaction.inputs[0].outpoint().0);
+        assert!(non_witness_utxo.outputs[0].script.is_p2pkh());
+        assert_eq!(psbt.inputs[0].sighash_type, Some(1));
+
+        let psbt = parse(VALID_P2SH_P2WPKH).unwrap();
+        assert!(psbt.inputs[0].final_script_sig.is_some());
+        let redeem_script = psbt.inputs[1].redeem_script.as_ref().unwrap();
+        assert!(redeem_script.is_p2wpkh_v0());
+        assert_eq!(
+            psbt.inputs[1].witness_utxo.as_ref().unwrap().script.as_bytes(),
+            hex::decode("a9143545e6e33b832c47050f24d3eeb93c9c03948bc787").unwrap()
+        );
+        assert_eq!(psbt.extract_tx(), Err(Error::PsbtInputNotFinalized));
+
+        let psbt = parse(VALID_PARTIAL_SIGS).unwrap();
+        assert_eq!(psbt.inputs[0].partial_sigs.len(), 2);
+        assert!(psbt.inputs[0].witness_script.is_some());
+
+        let psbt = parse(VALID_UNKNOWN).unwrap();
+        assert_eq!(
+            psbt.transaction().unwrap().tx_id(),
+            H256Le::from_hex_be("75c5c9665a570569ad77dd1279e6fd4628a093c4dcbf8d41532614044c14c115")
+        );
+        assert_eq!(
+            psbt.inputs[0]
+                .unknown
+                .get(&hex::decode("0f010203040506070809").unwrap()),
+            Some(&hex::decode("0102030405060708090a0b0c0d0e0f").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_psbts() {
+        // network transaction instead of a psbt
+        assert_eq!(
+            parse("0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf6000000006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435f09a34a7662a9dc251790a022001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76fc12edd95b3012102657d118d3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300"),
+            Err(Error::InvalidPsbt)
+        );
+        // unsigned transaction with a script
+        assert_eq!(
+            parse("70736274ff0100fd0a010200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be4000000006a47304402204759661797c01b036b25928948686218347d89864b719e1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceedaf93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa88292feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac00000000000001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb82308000000"),
+            Err(Error::InvalidPsbt)
+        );
+        // missing unsigned transaction
+        assert_eq!(
+            parse("70736274ff000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000000"),
+            Err(Error::InvalidPsbt)
+        );
+        // duplicate unsigned transaction
+        assert_eq!(
+            parse("70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001003f0200000001ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0000000000ffffffff010000000000000000036a010000000000000000"),
+            Err(Error::DuplicatePsbtKey)
+        );
+        // unsupported version
+        let mut bytes = hex::decode(VALID_UNKNOWN).unwrap();
+        bytes.splice(5..5, hex::decode("01fb0403000000").unwrap());
+        assert_eq!(Psbt::from_bytes(&bytes), Err(Error::UnsupportedPsbtVersion));
+        // version 2 field in a version 0 psbt
+        let mut bytes = hex::decode(VALID_UNKNOWN).unwrap();
+        bytes.splice(5..5, hex::decode("01020402000000").unwrap());
+        assert_eq!(Psbt::from_bytes(&bytes), Err(Error::InvalidPsbt));
+    }
+
+    #[test]
+    fn test_psbt_version_round_trip() {
+        let psbt_v0 = parse(VALID_P2PKH).unwrap();
+        let psbt_v2 = psbt_v0.clone().into_version(Version::V2).unwrap();
+        assert_eq!(psbt_v2.unsigned_tx, None);
+        assert_eq!(psbt_v2.inputs[0].output_index, Some(0));
+        assert_eq!(psbt_v2.outputs[1].amount, Some(100_000_000));
+
+        let bytes = psbt_v2.to_bytes().unwrap();
+        let parsed = Psbt::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, psbt_v2);
+        assert_eq!(parsed.transaction(), psbt_v0.transaction());
+        assert_eq!(parsed.into_version(Version::V0).unwrap(), psbt_v0);
+    }
+
+    #[test]
+    fn test_psbt_v2_lock_time() {
+        let mut psbt = parse(VALID_UNKNOWN).unwrap().into_version(Version::V2).unwrap();
+        psbt.inputs.push(psbt.inputs[0].clone());
+        psbt.fallback_locktime = Some(100);
+        assert_eq!(psbt.transaction().unwrap().lock_at, LockTime::BlockHeight(100));
+
+        psbt.inputs[0].required_time_locktime = Some(1_700_000_000);
+        assert_eq!(psbt.transaction().unwrap().lock_at, LockTime::Time(1_700_000_000));
+
+        // height is preferred if supported by all inputs
+        psbt.inputs[0].required_height_locktime = Some(800_000);
+        psbt.inputs[1].required_height_locktime = Some(810_000);
+        assert_eq!(psbt.transaction().unwrap().lock_at, LockTime::BlockHeight(810_000));
+
+        psbt.inputs[0].required_height_locktime = None;
+        assert_eq!(psbt.transaction(), Err(Error::InvalidPsbt));
+    }
+
+    #[test]
+    fn test_psbt_extract_tx() {
+        let transaction = crate::parser::parse_transaction(
+            &hex::decode(crate::parser::tests::sample_extended_transaction()).unwrap(),
+        )
+        .unwrap();
+        let mut unsigned_tx = transaction.clone();
+        for input in unsigned_tx.inputs.iter_mut() {
+            input.script.clear();
+            input.witness.clear();
+        }
+        assert_eq!(Psbt::from_unsigned_tx(transaction.clone()), Err(Error::InvalidPsbt));
+
+        let mut psbt = Psbt::from_unsigned_tx(unsigned_tx).unwrap();
+        assert_eq!(psbt.extract_tx(), Err(Error::PsbtInputNotFinalized));
+        for (input, tx_input) in psbt.inputs.iter_mut().zip(transaction.inputs.iter()) {
+            input.final_script_sig = Some(tx_input.script.clone().into());
+            if !tx_input.witness.is_empty() {
+                input.final_script_witness = Some(tx_input.witness.clone());
+            }
+        }
+        let psbt = Psbt::from_bytes(&psbt.to_bytes().unwrap()).unwrap();
+        assert_eq!(psbt.extract_tx().unwrap(), transaction);
+    }
+}
@@ -79,6 +79,32 @@ impl Script {
             && self.bytes[1] == HASH256_SIZE_HEX
     }
 
+    pub fn is_p2tr_v1(&self) -> bool {
+        // first byte is version
+        self.len() == P2TR_V1_SCRIPT_SIZE as usize
+            && self.bytes[0] == OpCode::Op1 as u8
+            && self.bytes[1] == HASH256_SIZE_HEX
+    }
+
+    /// Returns the witness version and program if this is a witness program as defined in
+    /// [BIP-141](https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#witness-program):
+    /// a version push (`OP_0` to `OP_16`) followed by a single direct push of 2 to 40 bytes.
+    // https://github.com/bitcoin/bitcoin/blob/b565485c24c0feacae559a7f6f7b83d7516ca58d/src/script/script.cpp#L220-L235
+    pub fn witness_program(&self) -> Option<(u8, &[u8])> {
+        if self.len() < 4 || self.len() > 42 {
+            return None;
+        }
+        let version = match self.bytes[0] {
+            op if op == OpCode::Op0 as u8 => 0,
+            op if op >= OpCode::Op1 as u8 && op <= OpCode::Op16 as u8 => op - (OpCode::Op1 as u8 - 1),
+            _ => return None,
+        };
+        if self.bytes[1] as usize + 2 != self.len() {
+            return None;
+        }
+        Some((version, &self.bytes[2..]))
+    }
+
     pub fn is_p2pkh(&self) -> bool {
         self.len() == P2PKH_SCRIPT_SIZE as usize
             && self.bytes[0] == OpCode::OpDup as u8
@@ -99,6 +125,35 @@ impl Script {
         value.try_format(&mut self.bytes).expect("Not bounded");
     }
 
+    /// Appends the smallest push operation of the data, note that `OP_0` to `OP_16` are not used
+    pub fn push_data(&mut self, data: &[u8]) {
+        let len = data.len();
+        if len < OpCode::OpPushData1 as usize {
+            self.append(len as u8);
+        } else if len <= u8::MAX as usize {
+            self.append(OpCode::OpPushData1);
+            self.append(len as u8);
+        } else if len <= u16::MAX as usize {
+            self.append(OpCode::OpPushData2);
+            self.append(len as u16);
+        } else {
+            self.append(OpCode::OpPushData4);
+            self.append(len as u32);
+        }
+        self.bytes.extend_from_slice(data);
+    }
+
+    /// Appends the smallest push operation of the number, using `OP_1NEGATE` and `OP_0` to `OP_16`
+    /// where possible
+    pub fn push_int(&mut self, value: i64) {
+        match value {
+            0 => self.append(OpCode::Op0),
+            -1 => self.append(OpCode::Op1Negate),
+            1..=16 => self.append(OpCode::Op1 as u8 + (value - 1) as u8),
+            _ => self.push_data(&encode_script_num(value)),
+        }
+    }
+
     pub fn extract_op_return_data(&self) -> Result<Vec<u8>, Error> {
         let output_script = &self.bytes;
         if *output_script.get(0).ok_or(Error::EndOfFile)? != OpCode::OpReturn as u8 {
@@ -137,6 +192,24 @@ impl Script {
     }
 }
 
+/// Encodes the number as little-endian sign-magnitude without superfluous bytes, which is
+/// how numbers are represented on the script stack
+fn encode_script_num(value: i64) -> Vec<u8> {
+    let mut bytes = vec![];
+    let mut abs = value.unsigned_abs();
+    while abs > 0 {
+        bytes.push((abs & 0xff) as u8);
+        abs >>= 8;
+    }
+    // the most significant bit of the last byte is the sign bit
+    match bytes.last_mut() {
+        Some(last) if *last & 0x80 != 0 => bytes.push(if value < 0 { 0x80 } else { 0x00 }),
+        Some(last) if value < 0 => *last |= 0x80,
+        _ => {}
+    }
+    bytes
+}
+
 impl From<Vec<u8>> for Script {
     fn from(bytes: Vec<u8>) -> Script {
         Script { bytes }
@@ -153,6 +226,51 @@ impl std::convert::TryFrom<&str> for Script {
     }
 }
 
+#[test]
+fn test_script_witness_program() {
+    // v0 keyhash
+    let script = Script::from(hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap());
+    assert_eq!(script.witness_program(), Some((0, &script.bytes[2..])));
+    assert!(script.is_p2wpkh_v0());
+
+    // v1 taproot output key
+    let script =
+        Script::from(hex::decode("512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap());
+    assert_eq!(script.witness_program(), Some((1, &script.bytes[2..])));
+    assert!(script.is_p2tr_v1());
+
+    // v16 with the minimum program length
+    let script = Script::from(hex::decode("6002751e").unwrap());
+    assert_eq!(script.witness_program(), Some((16, &script.bytes[2..])));
+
+    // push length does not match the script length
+    assert_eq!(
+        Script::from(hex::decode("0015751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()).witness_program(),
+        None
+    );
+    // program too short
+    assert_eq!(Script::from(hex::decode("5101aa").unwrap()).witness_program(), None);
+    // not a version opcode
+    assert_eq!(Script::from(hex::decode("4f02751e").unwrap()).witness_program(), None);
+}
+
+#[test]
+fn test_script_push_data() {
+    for (len, prefix) in [
+        (0, vec![0x00]),
+        (75, vec![75]),
+        (76, vec![0x4c, 76]),
+        (255, vec![0x4c, 0xff]),
+        (256, vec![0x4d, 0x00, 0x01]),
+        (65536, vec![0x4e, 0x00, 0x00, 0x01, 0x00]),
+    ] {
+        let mut script = Script::new();
+        script.push_data(&vec![1; len]);
+        assert_eq!(script.bytes[..prefix.len()], prefix);
+        assert_eq!(script.len(), prefix.len() + len);
+    }
+}
+
 #[test]
 fn test_script_height() {
     assert_eq!(Script::height(7).bytes, vec![1, 7]);
@@ -169,3 +287,25 @@ fn test_script_height() {
     assert_eq!(Script::height(65535).bytes, vec![3, 0xff, 0xff, 0x00]);
     assert_eq!(Script::height(65536).bytes, vec![3, 0x00, 0x00, 0x01]);
 }
+
+#[test]
+fn test_script_push_int() {
+    for (value, bytes) in [
+        (0, vec![0x00]),
+        (-1, vec![0x4f]),
+        (1, vec![0x51]),
+        (16, vec![0x60]),
+        (17, vec![0x01, 0x11]),
+        (-2, vec![0x01, 0x82]),
+        (127, vec![0x01, 0x7f]),
+        (128, vec![0x02, 0x80, 0x00]),
+        (-128, vec![0x02, 0x80, 0x80]),
+        (500, vec![0x02, 0xf4, 0x01]),
+        (0x40_0090, vec![0x03, 0x90, 0x00, 0x40]),
+        (0xffff_ffff, vec![0x05, 0xff, 0xff, 0xff, 0xff, 0x00]),
+    ] {
+        let mut script = Script::new();
+        script.push_int(value);
+        assert_eq!(script.bytes, bytes, "{}", value);
+    }
+}
@@ -0,0 +1,653 @@
+//! Computation of the message hashes that are signed by transaction inputs.
+//!
+//! - legacy: <https://en.bitcoin.it/wiki/OP_CHECKSIG>
+//! - segwit v0: <https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki>
+//! - taproot key path: <https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#common-signature-message>
+
+use crate::{
+    formatter::{TryFormat, Writer},
+    types::*,
+    utils::{sha256, sha256d},
+    Error, Script,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Determines which parts of the transaction are committed to by a signature.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SighashType {
+    /// Taproot only: same as `All`, but the sighash byte is omitted from the signature
+    Default = 0x00,
+    /// Sign all inputs and outputs
+    All = 0x01,
+    /// Sign all inputs but no outputs
+    None = 0x02,
+    /// Sign all inputs and the output with the same index
+    Single = 0x03,
+    /// Sign only this input and all outputs
+    AllPlusAnyoneCanPay = 0x81,
+    /// Sign only this input and no outputs
+    NonePlusAnyoneCanPay = 0x82,
+    /// Sign only this input and the output with the same index
+    SinglePlusAnyoneCanPay = 0x83,
+}
+
+impl SighashType {
+    const ANYONE_CAN_PAY: u8 = 0x80;
+
+    pub fn from_u8(value: u8) -> Result<Self, Error> {
+        Ok(match value {
+            0x00 => Self::Default,
+            0x01 => Self::All,
+            0x02 => Self::None,
+            0x03 => Self::Single,
+            0x81 => Self::AllPlusAnyoneCanPay,
+            0x82 => Self::NonePlusAnyoneCanPay,
+            0x83 => Self::SinglePlusAnyoneCanPay,
+            _ => return Err(Error::InvalidSighashType),
+        })
+    }
+
+    fn is_anyone_can_pay(self) -> bool {
+        self as u8 & Self::ANYONE_CAN_PAY != 0
+    }
+
+    /// Returns the type with the `ANYONECANPAY` flag cleared, `Default` is mapped to `All`
+    fn base(self) -> Self {
+        match self {
+            Self::Default | Self::All | Self::AllPlusAnyoneCanPay => Self::All,
+            Self::None | Self::NonePlusAnyoneCanPay => Self::None,
+            Self::Single | Self::SinglePlusAnyoneCanPay => Self::Single,
+        }
+    }
+
+    /// The `Default` type is only defined for taproot signatures
+    fn ensure_ecdsa(self) -> Result<(), Error> {
+        match self {
+            Self::Default => Err(Error::InvalidSighashType),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Single SHA256 hash prefixed with the tag, see BIP-340
+fn tagged_hash(tag: &[u8], message: &[u8]) -> [u8; 32] {
+    let tag_hash = sha256(tag);
+    let mut bytes = Vec::with_capacity(64 + message.len());
+    bytes.extend_from_slice(&tag_hash);
+    bytes.extend_from_slice(&tag_hash);
+    bytes.extend_from_slice(message);
+    sha256(&bytes)
+}
+
+/// Returns the script without any `OP_CODESEPARATOR`, data pushes are skipped as a whole.
+/// A truncated push at the end of the script is copied unmodified, consistent with Bitcoin Core.
+fn remove_code_separators(script: &[u8]) -> Vec<u8> {
+    const OP_PUSHDATA1: u8 = OpCode::OpPushData1 as u8;
+    const OP_PUSHDATA2: u8 = OpCode::OpPushData2 as u8;
+    const OP_PUSHDATA4: u8 = OpCode::OpPushData4 as u8;
+    const OP_CODESEPARATOR: u8 = OpCode::OpCodeSeparator as u8;
+
+    let read_len = |position: usize, size: usize| -> Option<usize> {
+        let bytes = script.get(position..position.checked_add(size)?)?;
+        let mut buffer = [0u8; 4];
+        buffer[..size].copy_from_slice(bytes);
+        Some(u32::from_le_bytes(buffer) as usize)
+    };
+
+    let mut ret = Vec::with_capacity(script.len());
+    let mut position = 0;
+    while let Some(&opcode) = script.get(position) {
+        let push = match opcode {
+            0x01..=0x4b => Some((1, opcode as usize)),
+            OP_PUSHDATA1 => read_len(position + 1, 1).map(|len| (2, len)),
+            OP_PUSHDATA2 => read_len(position + 1, 2).map(|len| (3, len)),
+            OP_PUSHDATA4 => read_len(position + 1, 4).map(|len| (5, len)),
+            _ => Some((1, 0)),
+        };
+        let next = push
+            .and_then(|(header, len)| position.checked_add(header)?.checked_add(len))
+            .filter(|&next| next <= script.len())
+            .unwrap_or(script.len());
+
+        if opcode != OP_CODESEPARATOR {
+            ret.extend_from_slice(&script[position..next]);
+        }
+        position = next;
+    }
+    ret
+}
+
+fn format_outpoint<W: Writer>(input: &TransactionInput, w: &mut W) -> Result<(), Error> {
+    let (previous_hash, previous_index) = input.outpoint();
+    previous_hash.try_format(w)?;
+    previous_index.try_format(w)
+}
+
+impl Transaction {
+    fn ensure_input_index(&self, input_index: usize) -> Result<&TransactionInput, Error> {
+        self.inputs.get(input_index).ok_or(Error::InvalidInputIndex)
+    }
+
+    /// Computes the signature hash of a legacy (pre-segwit) input. The hash is returned in
+    /// little endian such that [`H256Le::to_bytes_le`] returns the message to sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_index` - index of the input to sign
+    /// * `script_code` - the script being executed, i.e. the `scriptPubKey` of the spent output or the redeem script
+    ///   for P2SH
+    /// * `sighash_type` - the sighash type that is appended to the signature
+    pub fn legacy_sighash(
+        &self,
+        input_index: usize,
+        script_code: &Script,
+        sighash_type: SighashType,
+    ) -> Result<H256Le, Error> {
+        self.ensure_input_index(input_index)?;
+        sighash_type.ensure_ecdsa()?;
+        let base = sighash_type.base();
+
+        if base == SighashType::Single && input_index >= self.outputs.len() {
+            // consensus bug: the "hash" of one is signed instead of failing
+            // https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/script/interpreter.cpp#L1586-L1590
+            return Ok(H256Le::from_bytes_le(&{
+                let mut one = [0; 32];
+                one[0] = 1;
+                one
+            }));
+        }
+
+        let script_code = remove_code_separators(script_code.as_bytes());
+        let mut bytes = Vec::new();
+        self.version.try_format(&mut bytes)?;
+
+        let inputs: Vec<(usize, &TransactionInput)> = if sighash_type.is_anyone_can_pay() {
+            vec![(input_index, &self.inputs[input_index])]
+        } else {
+            self.inputs.iter().enumerate().collect()
+        };
+        CompactUint::from_usize(inputs.len()).try_format(&mut bytes)?;
+        for (index, input) in inputs {
+            format_outpoint(input, &mut bytes)?;
+            if index == input_index {
+                script_code.try_format(&mut bytes)?;
+                input.sequence.try_format(&mut bytes)?;
+            } else {
+                // empty script
+                CompactUint::from_usize(0).try_format(&mut bytes)?;
+                let sequence = match base {
+                    SighashType::None | SighashType::Single => 0,
+                    _ => input.sequence,
+                };
+                sequence.try_format(&mut bytes)?;
+            }
+        }
+
+        match base {
+            SighashType::None => CompactUint::from_usize(0).try_format(&mut bytes)?,
+            SighashType::Single => {
+                CompactUint::from_usize(input_index + 1).try_format(&mut bytes)?;
+                for _ in 0..input_index {
+                    // null output
+                    (-1 as Value).try_format(&mut bytes)?;
+                    Script::new().try_format(&mut bytes)?;
+                }
+                self.outputs[input_index].try_format(&mut bytes)?;
+            }
+            _ => self.outputs.try_format(&mut bytes)?,
+        }
+
+        self.lock_at.try_format(&mut bytes)?;
+        (sighash_type as u32).try_format(&mut bytes)?;
+        Ok(H256Le::from_bytes_le(&sha256d(&bytes)))
+    }
+
+    /// Computes the signature hash of a segwit v0 input as defined in BIP-143. The hash is returned
+    /// in little endian such that [`H256Le::to_bytes_le`] returns the message to sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_index` - index of the input to sign
+    /// * `script_code` - the P2PKH script of the key hash for P2WPKH, or the witness script for P2WSH
+    /// * `value` - amount of the spent output
+    /// * `sighash_type` - the sighash type that is appended to the signature
+    pub fn segwit_v0_sighash(
+        &self,
+        input_index: usize,
+        script_code: &Script,
+        value: Value,
+        sighash_type: SighashType,
+    ) -> Result<H256Le, Error> {
+        let input = self.ensure_input_index(input_index)?;
+        sighash_type.ensure_ecdsa()?;
+        let base = sighash_type.base();
+        let anyone_can_pay = sighash_type.is_anyone_can_pay();
+
+        let hash_prevouts = if anyone_can_pay {
+            [0; 32]
+        } else {
+            let mut bytes = Vec::new();
+            for input in self.inputs.iter() {
+                format_outpoint(input, &mut bytes)?;
+            }
+            sha256d(&bytes)
+        };
+
+        let hash_sequence = if anyone_can_pay || base != SighashType::All {
+            [0; 32]
+        } else {
+            let mut bytes = Vec::new();
+            for input in self.inputs.iter() {
+                input.sequence.try_format(&mut bytes)?;
+            }
+            sha256d(&bytes)
+        };
+
+        let hash_outputs = match base {
+            SighashType::All => {
+                let mut bytes = Vec::new();
+                for output in self.outputs.iter() {
+                    output.try_format(&mut bytes)?;
+                }
+                sha256d(&bytes)
+            }
+            SighashType::Single if input_index < self.outputs.len() => {
+                let mut bytes = Vec::new();
+                self.outputs[input_index].try_format(&mut bytes)?;
+                sha256d(&bytes)
+            }
+            _ => [0; 32],
+        };
+
+        let mut bytes = Vec::new();
+        self.version.try_format(&mut bytes)?;
+        hash_prevouts.as_slice().try_format(&mut bytes)?;
+        hash_sequence.as_slice().try_format(&mut bytes)?;
+        format_outpoint(input, &mut bytes)?;
+        script_code.try_format(&mut bytes)?;
+        value.try_format(&mut bytes)?;
+        input.sequence.try_format(&mut bytes)?;
+        hash_outputs.as_slice().try_format(&mut bytes)?;
+        self.lock_at.try_format(&mut bytes)?;
+        (sighash_type as u32).try_format(&mut bytes)?;
+        Ok(H256Le::from_bytes_le(&sha256d(&bytes)))
+    }
+
+    /// Computes the signature hash of a taproot key path spend as defined in BIP-341. The hash is
+    /// returned in little endian such that [`H256Le::to_bytes_le`] returns the message to sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_index` - index of the input to sign
+    /// * `prevouts` - the outputs spent by all inputs of the transaction, in order
+    /// * `annex` - the annex of the input's witness including its `0x50` prefix, if any
+    /// * `sighash_type` - the sighash type, appended to the signature unless it is `Default`
+    pub fn taproot_key_spend_sighash(
+        &self,
+        input_index: usize,
+        prevouts: &[TransactionOutput],
+        annex: Option<&[u8]>,
+        sighash_type: SighashType,
+    ) -> Result<H256Le, Error> {
+        let input = self.ensure_input_index(input_index)?;
+        if prevouts.len() != self.inputs.len() {
+            return Err(Error::InvalidPrevouts);
+        }
+        let base = sighash_type.base();
+        let anyone_can_pay = sighash_type.is_anyone_can_pay();
+        if base == SighashType::Single && input_index >= self.outputs.len() {
+            return Err(Error::InvalidSighashType);
+        }
+
+        // epoch
+        let mut bytes = vec![0u8];
+        (sighash_type as u8).try_format(&mut bytes)?;
+        self.version.try_format(&mut bytes)?;
+        self.lock_at.try_format(&mut bytes)?;
+
+        if !anyone_can_pay {
+            let (mut outpoints, mut amounts, mut script_pub_keys, mut sequences) =
+                (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+            for (input, prevout) in self.inputs.iter().zip(prevouts) {
+                format_outpoint(input, &mut outpoints)?;
+                prevout.value.try_format(&mut amounts)?;
+                prevout.script.try_format(&mut script_pub_keys)?;
+                input.sequence.try_format(&mut sequences)?;
+            }
+            for data in [outpoints, amounts, script_pub_keys, sequences] {
+                sha256(&data).as_slice().try_format(&mut bytes)?;
+            }
+        }
+
+        if base == SighashType::All {
+            let mut outputs = Vec::new();
+            for output in self.outputs.iter() {
+                output.try_format(&mut outputs)?;
+            }
+            sha256(&outputs).as_slice().try_format(&mut bytes)?;
+        }
+
+        // key path spends have no extension
+        let spend_type: u8 = annex.is_some() as u8;
+        spend_type.try_format(&mut bytes)?;
+
+        if anyone_can_pay {
+            format_outpoint(input, &mut bytes)?;
+            prevouts[input_index].value.try_format(&mut bytes)?;
+            prevouts[input_index].script.try_format(&mut bytes)?;
+            input.sequence.try_format(&mut bytes)?;
+        } else {
+            (input_index as u32).try_format(&mut bytes)?;
+        }
+
+        if let Some(annex) = annex {
+            let mut data = Vec::new();
+            annex.to_vec().try_format(&mut data)?;
+            sha256(&data).as_slice().try_format(&mut bytes)?;
+        }
+
+        if base == SighashType::Single {
+            let mut output = Vec::new();
+            self.outputs[input_index].try_format(&mut output)?;
+            sha256(&output).as_slice().try_format(&mut bytes)?;
+        }
+
+        Ok(H256Le::from_bytes_le(&tagged_hash(b"TapSighash", &bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::{parse_transaction, BytesParser},
+        Address, PublicKey,
+    };
+    use frame_support::assert_err;
+    use secp256k1::{Message, PublicKey as Secp256k1PublicKey, Secp256k1, Signature};
+
+    /// Verifies a DER encoded signature (with sighash byte) against the sighash
+    fn verify_ecdsa(sighash: H256Le, signature: &[u8], public_key: &[u8]) {
+        let (sighash_type, signature) = signature.split_last().unwrap();
+        assert_eq!(*sighash_type, SighashType::All as u8);
+        let mut signature = Signature::from_der(signature).unwrap();
+        signature.normalize_s();
+        let message = Message::from_slice(&sighash.to_bytes_le()).unwrap();
+        let public_key = Secp256k1PublicKey::from_slice(public_key).unwrap();
+        assert!(Secp256k1::verification_only()
+            .verify(&message, &signature, &public_key)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_remove_code_separators() {
+        // OP_1 OP_CODESEPARATOR PUSH(ab) OP_CODESEPARATOR OP_PUSHDATA1(ab ab) OP_CHECKSIG
+        let script = hex::decode("51ab01abab4c02abababac").unwrap();
+        assert_eq!(hex::encode(remove_code_separators(&script)), "5101ab4c02ababac");
+        // truncated push is copied unmodified
+        let script = hex::decode("ab4c05abab").unwrap();
+        assert_eq!(hex::encode(remove_code_separators(&script)), "4c05abab");
+    }
+
+    #[test]
+    fn test_legacy_sighash_p2pk() {
+        // source: https://blockstream.info/tx/f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16?expand
+        let raw_tx = "0100000001c997a5e56e104102fa209c6a852dd90660a20b2d9c352423edce25857fcd3704000000004847304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d0901ffffffff0200ca9a3b00000000434104ae1a62fe09c5f51b13905f07f06b99a2f7159b2225f374cd378d71302fa28414e7aab37397f554a7df5f142c21c1b7303b8a0626f1baded5c72a704f7e6cd84cac00286bee0000000043410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac00000000";
+        let transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        // output 0 of 0437cd7f8525ceed2324359c2d0ba26006d92d856a9c20fa0241106ee5a597c9
+        let public_key = hex::decode("0411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3").unwrap();
+        let mut script_pub_key = Script::new();
+        script_pub_key.append(public_key.clone());
+        script_pub_key.append(OpCode::OpCheckSig);
+
+        let sighash = transaction
+            .legacy_sighash(0, &script_pub_key, SighashType::All)
+            .unwrap();
+        // the scriptSig consists of a single push of the signature
+        verify_ecdsa(sighash, &transaction.inputs[0].script[1..], &public_key);
+    }
+
+    #[test]
+    fn test_legacy_sighash_p2pkh() {
+        // source: https://www.blockstream.info/testnet/tx/219a49b6a376e8f4ef86866e93483552679b5157318f0e4085430a3cee24e3d8?expand
+        let raw_tx = "010000000125314e40cfc816ae562c10cc1855df21ff2ed2fad43046a4b6dabbb35c393c20000000006a47304402200cd7aa9166960f3374bf655a5c5ba0a47801ae22f8231baa2412e8f47941792e02206b21c44642887b32fd87fb82a052363605c109b31d971ce502322e8148caf1670121023f3b8d04b9fac2ac10b8b8e7a4d5f033f259d26a74d2b0b77313f41585b3d1b5ffffffff0160e18709000000006952210218597441c292cb6d73174c1662ac9d60b76688fd359f90e2d653d1a089c9aba921022bda026d6aee8133f0290449a282f8cfbccafdc064b0b47068854457f38af3bc21030a230982d9706247d5997df1aea7144266c33a2e6c64c6a3a44c5cdf9c0ff58a53ae00000000";
+        let transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        let script_sig = &transaction.inputs[0].script;
+        let (signature, public_key) = (&script_sig[1..72], &script_sig[73..]);
+
+        let mut key = [0; 33];
+        key.copy_from_slice(public_key);
+        let script_pub_key = Address::P2PKH(PublicKey(key).to_hash()).to_script_pub_key();
+
+        let sighash = transaction
+            .legacy_sighash(0, &script_pub_key, SighashType::All)
+            .unwrap();
+        verify_ecdsa(sighash, signature, public_key);
+
+        // any other sighash type commits to a different message
+        for sighash_type in [
+            SighashType::None,
+            SighashType::Single,
+            SighashType::AllPlusAnyoneCanPay,
+            SighashType::NonePlusAnyoneCanPay,
+            SighashType::SinglePlusAnyoneCanPay,
+        ] {
+            assert_ne!(
+                transaction.legacy_sighash(0, &script_pub_key, sighash_type),
+                Ok(sighash)
+            );
+        }
+    }
+
+    #[test]
+    fn test_legacy_sighash_single_bug() {
+        let input = TransactionInputBuilder::new()
+            .with_source(TransactionInputSource::FromOutput(H256Le::zero(), 0))
+            .build();
+        let transaction = TransactionBuilder::new()
+            .add_input(input.clone())
+            .add_input(input)
+            .add_output(TransactionOutput::payment(0, &Address::default()))
+            .build();
+
+        assert_eq!(
+            transaction.legacy_sighash(1, &Script::new(), SighashType::Single),
+            Ok(H256Le::from_hex_be(
+                "0000000000000000000000000000000000000000000000000000000000000001"
+            ))
+        );
+        assert_err!(
+            transaction.legacy_sighash(2, &Script::new(), SighashType::All),
+            Error::InvalidInputIndex
+        );
+        assert_err!(
+            transaction.legacy_sighash(0, &Script::new(), SighashType::Default),
+            Error::InvalidSighashType
+        );
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#native-p2wpkh
+    #[test]
+    fn test_segwit_v0_sighash_p2wpkh() {
+        let raw_tx = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000";
+        let transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        let script_code = Script::try_from("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap();
+
+        let sighash = transaction
+            .segwit_v0_sighash(1, &script_code, 600_000_000, SighashType::All)
+            .unwrap();
+        assert_eq!(
+            sighash.to_hex_le(),
+            "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+        );
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#p2sh-p2wpkh
+    #[test]
+    fn test_segwit_v0_sighash_p2sh_p2wpkh() {
+        let raw_tx = "0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a54770100000000feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000";
+        let transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        let script_code = Script::try_from("76a91479091972186c449eb1ded22b78e40d009bdf008988ac").unwrap();
+
+        let sighash = transaction
+            .segwit_v0_sighash(0, &script_code, 1_000_000_000, SighashType::All)
+            .unwrap();
+        assert_eq!(
+            sighash.to_hex_le(),
+            "64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6"
+        );
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#p2sh-p2wsh
+    #[test]
+    fn test_segwit_v0_sighash_p2sh_p2wsh() {
+        let raw_tx = "010000000136641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e0100000000ffffffff0200e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688acc0832f05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac00000000";
+        let transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        let witness_script = Script::try_from("56210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba32103b28f0c28bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b21034b8113d703413d57761b8b9781957b8c0ac1dfe69f492580ca4195f50376ba4a21033400f6afecb833092a9a21cfdf1ed1376e58c5d1f47de74683123987e967a8f42103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b14862c07a1789aac162102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b56ae").unwrap();
+
+        let sighash = transaction
+            .segwit_v0_sighash(0, &witness_script, 987_654_321, SighashType::All)
+            .unwrap();
+        assert_eq!(
+            sighash.to_hex_le(),
+            "185c0be5263dce5b4bb50a047973c1b6272bfbd0103a89444597dc40b248ee7c"
+        );
+        assert_err!(
+            transaction.segwit_v0_sighash(0, &witness_script, 987_654_321, SighashType::Default),
+            Error::InvalidSighashType
+        );
+    }
+
+    fn test_taproot_sighash(
+        raw_tx: &str,
+        raw_prevouts: &str,
+        input_index: usize,
+        annex: Option<&str>,
+        sighash_type: SighashType,
+        expected: &str,
+    ) {
+        let mut tx_bytes = hex::decode(raw_tx).unwrap();
+        // the parser only accepts version 1 and 2 transactions, but the test vectors use any version
+        let version = i32::from_le_bytes(tx_bytes[..4].try_into().unwrap());
+        tx_bytes[..4].copy_from_slice(&2i32.to_le_bytes());
+        let mut transaction = parse_transaction(&tx_bytes).unwrap();
+        transaction.version = version;
+
+        let prevouts_bytes = hex::decode(raw_prevouts).unwrap();
+        let prevouts: Vec<TransactionOutput> = BytesParser::new(&prevouts_bytes).parse().unwrap();
+        let annex = annex.map(|annex| hex::decode(annex).unwrap());
+
+        let sighash = transaction
+            .taproot_key_spend_sighash(input_index, &prevouts, annex.as_deref(), sighash_type)
+            .unwrap();
+        assert_eq!(sighash.to_hex_le(), expected);
+    }
+
+    // https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/test/functional/feature_taproot.py
+    #[test]
+    fn test_taproot_key_spend_sighash() {
+        test_taproot_sighash(
+            "020000000164eb050a5e3da0c2a65e4786f26d753b7bc69691fabccafb11f7acef36641f1846010000003101b2b404392a22000000000017a9147f2bde86fe78bf68a0544a4f290e12f0b7e0a08c87580200000000000017a91425d11723074ecfb96a0a83c3956bfaf362ae0c908758020000000000001600147e20f938993641de67bb0cdd71682aa34c4d29ad5802000000000000160014c64984dc8761acfa99418bd6bedc79b9287d652d72000000",
+            "01365724000000000023542156b39dab4f8f3508e0432cfb41fab110170acaa2d4c42539cb90a4dc7c093bc500",
+            0,
+            None,
+            SighashType::Default,
+            "33ca0ebfb4a945eeee9569fc0f5040221275f88690b7f8592ada88ce3bdf6703",
+        );
+        test_taproot_sighash(
+            "0200000002fff49be59befe7566050737910f6ccdc5e749c7f8860ddc140386463d88c5ad0f3000000002cf68eb4a3d67f9d4c079249f7e4f27b8854815cb1ed13842d4fbf395f9e217fd605ee24090100000065235d9203f458520000000000160014b6d48333bb13b4c644e57c43a9a26df3a44b785e58020000000000001976a914eea9461a9e1e3f765d3af3e726162e0229fe3eb688ac58020000000000001976a9143a8869c9f2b5ea1d4ff3aeeb6a8fb2fffb1ad5fe88ac0ad7125c",
+            "02591f220000000000225120f25ad35583ea31998d968871d7de1abd2a52f6fe4178b54ea158274806ff4ece48fb310000000000225120f25ad35583ea31998d968871d7de1abd2a52f6fe4178b54ea158274806ff4ece",
+            1,
+            None,
+            SighashType::All,
+            "626ab955d58c9a8a600a0c580549d06dc7da4e802eb2a531f62a588e430967a8",
+        );
+        test_taproot_sighash(
+            "0200000001350005f65aa830ced2079df348e2d8c2bdb4f10e2dde6a161d8a07b40d1ad87dae000000001611d0d603d9dc0e000000000017a914459b6d7d6bbb4d8837b4bf7e9a4556f952da2f5c8758020000000000001976a9141dd70e1299ffc2d5b51f6f87de9dfe9398c33cbb88ac58020000000000001976a9141dd70e1299ffc2d5b51f6f87de9dfe9398c33cbb88aca71c1f4f",
+            "01c4811000000000002251201bf9297d0a2968ae6693aadd0fa514717afefd218087a239afb7418e2d22e65c",
+            0,
+            None,
+            SighashType::AllPlusAnyoneCanPay,
+            "dfa9437f9c9a1d1f9af271f79f2f5482f287cdb0d2e03fa92c8a9b216cc6061c",
+        );
+        test_taproot_sighash(
+            "020000000185bed1a6da2bffbd60ec681a1bfb71c5111d6395b99b3f8b2bf90167111bcb18f5010000007c83ace802ded24a00000000001600142c4698f9f7a773866879755aa78c516fb332af8e5802000000000000160014d38639dfbac4259323b98a472405db0c461b31fa61073747",
+            "0144c84d0000000000225120e3f2107989c88e67296ab2faca930efa2e3a5bd3ff0904835a11c9e807458621",
+            0,
+            None,
+            SighashType::None,
+            "3129de36a5d05fff97ffca31eb75fcccbbbc27b3147a7a36a9e4b45d8b625067",
+        );
+        test_taproot_sighash(
+            "eb93dbb901028c8515589dac980b6e7f8e4088b77ed866ca0d6d210a7218b6fd0f6b22dd6d7300000000eb4740a9047efc0e0000000000160014913da2128d8fcf292b3691db0e187414aa1783825802000000000000160014913da2128d8fcf292b3691db0e187414aa178382580200000000000017a9143dd27f01c6f7ef9bb9159937b17f17065ed01a0c875802000000000000160014d7630e19df70ada9905ede1722b800c0005f246641000000",
+            "013fed110000000000225120eb536ae8c33580290630fc495046e998086a64f8f33b93b07967d9029b265c55",
+            0,
+            None,
+            SighashType::NonePlusAnyoneCanPay,
+            "2441e8b0e063a2083ee790f14f2045022f07258ddde5ee01de543c9e789d80ae",
+        );
+        test_taproot_sighash(
+            "02000000017836b409a5fed32211407e44b971591f2032053f14701fb5b3a30c0ff382f2cc9c0100000061ac55f60288fb5600000000001976a9144ea02f6f182b082fb6ce47e36bbde390b6a41b5088ac58020000000000001976a9144ea02f6f182b082fb6ce47e36bbde390b6a41b5088ace4000000",
+            "01efa558000000000022512007071ea3dc7e331b0687d0193d1e6d6ed10e645ef36f10ef8831d5e522ac9e80",
+            0,
+            None,
+            SighashType::Single,
+            "30239345177cadd0e3ea413d49803580abb6cb27971b481b7788a78d35117a88",
+        );
+        test_taproot_sighash(
+            "0100000001aa6deae89d5e0aaca58714fc76ef6f3c8284224888089232d4e663843ed3ab3eae010000008b6657a60450cb4c0000000000160014a3d42b5413ef0c0701c4702f3cd7d4df222c147058020000000000001976a91430b4ed8723a4ee8992aa2c8814cfe5c3ad0ab9d988ac5802000000000000160014365b1166a6ed0a5e8e9dff17a6d00bbb43454bc758020000000000001976a914bc98c51a84fe7fad5dc380eb8b39586eff47241688ac4f313247",
+            "0107af4e00000000002251202c36d243dfc06cb56a248e62df27ecba7417307511a81ae61aa41c597a929c69",
+            0,
+            None,
+            SighashType::SinglePlusAnyoneCanPay,
+            "bf9c83f26c6dd16449e4921f813f551c4218e86f2ec906ca8611175b41b566df",
+        );
+    }
+
+    #[test]
+    fn test_taproot_key_spend_sighash_with_annex() {
+        test_taproot_sighash(
+            "0200000001df8123752e8f37d132c4e9f1ff7e4f9b986ade9211267e9ebd5fd22a5e718dec6d01000000ce4023b903cb7b23000000000017a914a18b36ea7a094db2f4940fc09edf154e86de7bd787580200000000000017a914afd0d512a2c5c2b40e25669e9cc460303c325b8b87580200000000000017a914a18b36ea7a094db2f4940fc09edf154e86de7bd787f6020000",
+            "01ea49260000000000225120ab5e9800806bf18cb246edcf5fe63441208fe955a4b5a35bbff65f5db622a010",
+            0,
+            Some("507b979802e62d397acb29f56743a791894b99372872fc5af06a4f6e8d242d0615cda53062bb20e6ec79756fe39183f0c128adfe85559a8fa042b042c018aa8010143799e44f0893c40e1e"),
+            SighashType::SinglePlusAnyoneCanPay,
+            "3b003000add359a364a156e73e02846782a59d0d95ca8c4638aaad99f2ef915c",
+        );
+    }
+
+    #[test]
+    fn test_taproot_key_spend_sighash_fails() {
+        let raw_tx = "02000000017836b409a5fed32211407e44b971591f2032053f14701fb5b3a30c0ff382f2cc9c0100000061ac55f60288fb5600000000001976a9144ea02f6f182b082fb6ce47e36bbde390b6a41b5088ac58020000000000001976a9144ea02f6f182b082fb6ce47e36bbde390b6a41b5088ace4000000";
+        let mut transaction = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        let prevout = TransactionOutput {
+            value: 5_809_647,
+            script: Script::try_from("512007071ea3dc7e331b0687d0193d1e6d6ed10e645ef36f10ef8831d5e522ac9e80").unwrap(),
+        };
+
+        assert_err!(
+            transaction.taproot_key_spend_sighash(0, &[], None, SighashType::Default),
+            Error::InvalidPrevouts
+        );
+        assert_err!(
+            transaction.taproot_key_spend_sighash(1, core::slice::from_ref(&prevout), None, SighashType::Default),
+            Error::InvalidInputIndex
+        );
+        transaction.outputs.clear();
+        assert_err!(
+            transaction.taproot_key_spend_sighash(0, &[prevout], None, SighashType::Single),
+            Error::InvalidSighashType
+        );
+    }
+
+    #[test]
+    fn test_sighash_type_from_u8() {
+        for value in [0x00, 0x01, 0x02, 0x03, 0x81, 0x82, 0x83] {
+            assert_eq!(SighashType::from_u8(value).unwrap() as u8, value);
+        }
+        assert_err!(SighashType::from_u8(0x04), Error::InvalidSighashType);
+        assert_err!(SighashType::from_u8(0x80), Error::InvalidSighashType);
+    }
+}
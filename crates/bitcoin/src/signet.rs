@@ -0,0 +1,350 @@
+//! Signet block solutions as defined by [BIP-325](https://github.com/bitcoin/bips/blob/master/bip-0325.mediawiki).
+//!
+//! Besides the proof-of-work, signet blocks must satisfy the block challenge of the network. The
+//! witness commitment output of the coinbase transaction carries a solution (scriptSig and
+//! witness) which spends a virtual transaction committing to the block. The solution itself is
+//! excluded from this commitment, so the merkle root is recomputed with the solution removed.
+//! This only requires the coinbase transaction and its merkle path, so the solution can also be
+//! checked against the coinbase proof of a [`FullTransactionProof`](crate::types::FullTransactionProof).
+
+use crate::{
+    formatter::TryFormat,
+    interpreter::next_instruction,
+    merkle::{MerkleTree, PartialTransactionProof},
+    parser::BytesParser,
+    types::*,
+    Error, Script,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+// https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/signet.cpp#L25
+const SIGNET_HEADER: [u8; 4] = [0xec, 0xc7, 0xda, 0xa2];
+
+/// Verifies that the block satisfies the signet `challenge`
+pub fn verify_block_solution(block: &Block, challenge: &Script) -> Result<(), Error> {
+    let mut transactions = block.transactions.iter();
+    let coinbase = transactions.next().ok_or(Error::InvalidSignetSolution)?;
+    verify_solution(&block.header, coinbase.clone(), challenge, |coinbase_id| {
+        let tx_ids: Vec<H256Le> = core::iter::once(coinbase_id)
+            .chain(transactions.map(Transaction::tx_id))
+            .collect();
+        let transactions_count = tx_ids.len() as u32;
+        let height = MerkleTree::compute_height(transactions_count);
+        MerkleTree::compute_root(0, height, transactions_count, &tx_ids)
+    })
+}
+
+/// Verifies that the block of the coinbase proof satisfies the signet `challenge`. The proof
+/// must already have been verified against the block header.
+pub fn verify_coinbase_solution(coinbase_proof: &PartialTransactionProof, challenge: &Script) -> Result<(), Error> {
+    let mut merkle_proof = coinbase_proof.merkle_proof.clone();
+    let (_, merkle_position, hash_position) = merkle_proof.extract_root()?;
+    if merkle_position != 0 {
+        return Err(Error::InvalidMerkleProof);
+    }
+    verify_solution(
+        &coinbase_proof.merkle_proof.block_header,
+        coinbase_proof.transaction.clone(),
+        challenge,
+        |coinbase_id| {
+            // the root of the same partial merkle tree with the coinbase txid replaced
+            merkle_proof.hashes[hash_position] = coinbase_id;
+            merkle_proof.extract_root().map(|(root, _, _)| root)
+        },
+    )
+}
+
+// https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/signet.cpp#L64-L147
+fn verify_solution(
+    header: &BlockHeader,
+    mut coinbase: Transaction,
+    challenge: &Script,
+    merkle_root: impl FnOnce(H256Le) -> Result<H256Le, Error>,
+) -> Result<(), Error> {
+    // the genesis block has no solution
+    if header.hash_prev_block == H256Le::zero() {
+        return Ok(());
+    }
+    // an empty solution is allowed to support trivial challenges
+    let (script_sig, witness) = match take_solution(&mut coinbase)? {
+        Some(solution) => parse_solution(&solution)?,
+        None => (vec![], vec![]),
+    };
+    let signet_merkle_root = merkle_root(coinbase.tx_id())?;
+
+    spending_transaction(header, signet_merkle_root, challenge, script_sig, witness)?
+        .verify_input(0, challenge, 0)
+        .map_err(|_| Error::InvalidSignetSolution)
+}
+
+/// Removes the solution from the witness commitment output of the coinbase transaction,
+/// leaving only the header, and returns the solution if there is one
+// https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/signet.cpp#L27-L54
+fn take_solution(coinbase: &mut Transaction) -> Result<Option<Vec<u8>>, Error> {
+    // a witness commitment is required, even if the block has no witness data
+    let index = coinbase
+        .witness_commitment_index()
+        .ok_or(Error::InvalidSignetSolution)?;
+    let script = coinbase.outputs[index].script.as_bytes();
+
+    let mut replacement = Script::new();
+    let mut solution = None;
+    let mut position = 0;
+    // the script is re-encoded with minimal pushes, and truncated at the first invalid push
+    while let Ok(Some((opcode, data))) = next_instruction(script, &mut position) {
+        match data {
+            Some(data) if !data.is_empty() => {
+                if solution.is_none() && data.len() > SIGNET_HEADER.len() && data.starts_with(&SIGNET_HEADER) {
+                    solution = Some(data[SIGNET_HEADER.len()..].to_vec());
+                    replacement.push_data(&SIGNET_HEADER);
+                } else {
+                    replacement.push_data(data);
+                }
+            }
+            _ => replacement.append(opcode),
+        }
+    }
+
+    if solution.is_some() {
+        coinbase.outputs[index].script = replacement;
+    }
+    Ok(solution)
+}
+
+/// Decodes the scriptSig and witness stack of a solution
+fn parse_solution(solution: &[u8]) -> Result<(Vec<u8>, Vec<Vec<u8>>), Error> {
+    let mut parser = BytesParser::new(solution);
+    let script_sig: Vec<u8> = parser.parse().map_err(|_| Error::InvalidSignetSolution)?;
+    let witness: Vec<Vec<u8>> = parser.parse().map_err(|_| Error::InvalidSignetSolution)?;
+    if parser.remaining() != 0 {
+        return Err(Error::InvalidSignetSolution);
+    }
+    Ok((script_sig, witness))
+}
+
+/// Builds the virtual transaction spending the output which commits to the block, which pays to
+/// the challenge, with the given scriptSig and witness
+fn spending_transaction(
+    header: &BlockHeader,
+    signet_merkle_root: H256Le,
+    challenge: &Script,
+    script_sig: Vec<u8>,
+    witness: Vec<Vec<u8>>,
+) -> Result<Transaction, Error> {
+    // the block header without bits and nonce
+    let mut block_data = vec![];
+    header.version.try_format(&mut block_data)?;
+    header.hash_prev_block.try_format(&mut block_data)?;
+    signet_merkle_root.try_format(&mut block_data)?;
+    header.timestamp.try_format(&mut block_data)?;
+
+    let mut commitment = Script::new();
+    commitment.append(OpCode::Op0);
+    commitment.push_data(&block_data);
+
+    let to_spend = Transaction {
+        version: 0,
+        inputs: vec![TransactionInput {
+            source: TransactionInputSource::FromOutput(H256Le::zero(), u32::MAX),
+            script: commitment.as_bytes().to_vec(),
+            sequence: 0,
+            witness: vec![],
+        }],
+        outputs: vec![TransactionOutput {
+            value: 0,
+            script: challenge.clone(),
+        }],
+        lock_at: LockTime::BlockHeight(0),
+    };
+
+    Ok(Transaction {
+        version: 0,
+        inputs: vec![TransactionInput {
+            source: TransactionInputSource::FromOutput(to_spend.tx_id(), 0),
+            script: script_sig,
+            sequence: 0,
+            witness,
+        }],
+        outputs: vec![TransactionOutput {
+            value: 0,
+            script: Script::from(vec![OpCode::OpReturn as u8]),
+        }],
+        lock_at: LockTime::BlockHeight(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sighash::SighashType, Address};
+    use primitive_types::{H160, U256};
+    use secp256k1::{Message, PublicKey as Secp256k1PublicKey, Secp256k1, SecretKey};
+
+    /// Builds a block with a witness commitment and a signature of `secret_key` as solution
+    fn signed_block(challenge: &Script, secret_key: &SecretKey) -> Block {
+        let address = Address::P2PKH(H160::zero());
+        let transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_input(
+                TransactionInputBuilder::new()
+                    .with_source(TransactionInputSource::FromOutput(H256Le::from_bytes_le(&[1; 32]), 0))
+                    .add_witness(&[1; 72])
+                    .build(),
+            )
+            .add_output(TransactionOutput::payment(100, &address))
+            .build();
+        let mut block = BlockBuilder::new()
+            .with_coinbase(&address, 50, 1)
+            .with_previous_hash(H256Le::from_bytes_le(&[2; 32]))
+            .add_transaction(transaction)
+            .mine(U256::from(2).pow(254.into()))
+            .unwrap();
+
+        // the solution is signed with an empty solution push
+        let index = block.transactions[0].witness_commitment_index().unwrap();
+        block.transactions[0].outputs[index].script.push_data(&SIGNET_HEADER);
+        let tx_ids: Vec<_> = block.transactions.iter().map(Transaction::tx_id).collect();
+        let signet_merkle_root = MerkleTree::compute_root(0, 1, 2, &tx_ids).unwrap();
+        let to_sign = spending_transaction(&block.header, signet_merkle_root, challenge, vec![], vec![]).unwrap();
+
+        let sighash = to_sign.legacy_sighash(0, challenge, SighashType::All).unwrap();
+        let message = Message::from_slice(&sighash.to_bytes_le()).unwrap();
+        let mut signature = Secp256k1::new().sign(&message, secret_key).serialize_der().to_vec();
+        signature.push(SighashType::All as u8);
+        let mut script_sig = Script::new();
+        script_sig.push_data(&signature);
+
+        let mut solution = SIGNET_HEADER.to_vec();
+        script_sig.as_bytes().to_vec().try_format(&mut solution).unwrap();
+        Vec::<Vec<u8>>::new().try_format(&mut solution).unwrap();
+        let script = &mut block.transactions[0].outputs[index].script;
+        *script = Script::from(script.as_bytes()[..script.len() - SIGNET_HEADER.len() - 1].to_vec());
+        script.push_data(&solution);
+
+        let tx_ids: Vec<_> = block.transactions.iter().map(Transaction::tx_id).collect();
+        block.header.merkle_root = MerkleTree::compute_root(0, 1, 2, &tx_ids).unwrap();
+        block
+    }
+
+    fn challenge(secret_key: &SecretKey) -> Script {
+        let public_key = Secp256k1PublicKey::from_secret_key(&Secp256k1::new(), secret_key);
+        let mut challenge = Script::new();
+        challenge.push_data(&public_key.serialize());
+        challenge.append(OpCode::OpCheckSig);
+        challenge
+    }
+
+    #[test]
+    fn test_verify_block_solution() {
+        let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let challenge = challenge(&secret_key);
+        let block = signed_block(&challenge, &secret_key);
+        assert_eq!(verify_block_solution(&block, &challenge), Ok(()));
+
+        // signed by a different key
+        let other_key = SecretKey::from_slice(&[2; 32]).unwrap();
+        assert_eq!(
+            verify_block_solution(&block, &self::challenge(&other_key)),
+            Err(Error::InvalidSignetSolution)
+        );
+
+        // the solution commits to the header
+        let mut modified = block.clone();
+        modified.header.timestamp += 1;
+        assert_eq!(
+            verify_block_solution(&modified, &challenge),
+            Err(Error::InvalidSignetSolution)
+        );
+        // but not to the nonce
+        let mut modified = block.clone();
+        modified.header.nonce += 1;
+        assert_eq!(verify_block_solution(&modified, &challenge), Ok(()));
+
+        // the solution commits to the transactions
+        let mut modified = block.clone();
+        modified.transactions[1].outputs[0].value += 1;
+        assert_eq!(
+            verify_block_solution(&modified, &challenge),
+            Err(Error::InvalidSignetSolution)
+        );
+
+        // a witness commitment is required
+        let mut modified = block;
+        let index = modified.transactions[0].witness_commitment_index().unwrap();
+        modified.transactions[0].outputs.remove(index);
+        assert_eq!(
+            verify_block_solution(&modified, &challenge),
+            Err(Error::InvalidSignetSolution)
+        );
+    }
+
+    #[test]
+    fn test_verify_coinbase_solution() {
+        let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let challenge = challenge(&secret_key);
+        let block = signed_block(&challenge, &secret_key);
+
+        let coinbase = block.transactions[0].clone();
+        let coinbase_proof = PartialTransactionProof {
+            merkle_proof: block.merkle_proof(&[coinbase.tx_id()]).unwrap(),
+            transaction: coinbase,
+            tx_encoded_len: u32::MAX,
+        };
+        assert_eq!(verify_coinbase_solution(&coinbase_proof, &challenge), Ok(()));
+
+        // the proof must be for the coinbase transaction
+        let transaction = block.transactions[1].clone();
+        let transaction_proof = PartialTransactionProof {
+            merkle_proof: block.merkle_proof(&[transaction.tx_id()]).unwrap(),
+            transaction,
+            tx_encoded_len: u32::MAX,
+        };
+        assert_eq!(
+            verify_coinbase_solution(&transaction_proof, &challenge),
+            Err(Error::InvalidMerkleProof)
+        );
+    }
+
+    #[test]
+    fn test_parse_solution() {
+        // empty scriptSig and witness
+        assert_eq!(parse_solution(&[0, 0]), Ok((vec![], vec![])));
+        assert_eq!(parse_solution(&[1, 7, 1, 2, 8, 9]), Ok((vec![7], vec![vec![8, 9]])));
+        // extraneous data
+        assert_eq!(parse_solution(&[0, 0, 0]), Err(Error::InvalidSignetSolution));
+        assert_eq!(parse_solution(&[1]), Err(Error::InvalidSignetSolution));
+    }
+
+    #[test]
+    fn test_take_solution_reencodes_commitment() {
+        let mut coinbase = TransactionBuilder::new()
+            .add_output(TransactionOutput {
+                value: 0,
+                // OP_RETURN <commitment> OP_PUSHDATA1 <header || solution>
+                script: Script::from(
+                    [
+                        &[0x6a, 0x24, 0xaa, 0x21, 0xa9, 0xed][..],
+                        &[0; 32],
+                        &[0x4c, 6],
+                        &SIGNET_HEADER,
+                        &[0, 0],
+                    ]
+                    .concat(),
+                ),
+            })
+            .build();
+        assert_eq!(take_solution(&mut coinbase), Ok(Some(vec![0, 0])));
+        assert_eq!(
+            coinbase.outputs[0].script.as_bytes(),
+            &[
+                &[0x6a, 0x24, 0xaa, 0x21, 0xa9, 0xed][..],
+                &[0; 32],
+                &[4],
+                &SIGNET_HEADER
+            ]
+            .concat()[..]
+        );
+    }
+}
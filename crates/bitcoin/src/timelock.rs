@@ -0,0 +1,336 @@
+//! Absolute ([BIP-65](https://github.com/bitcoin/bips/blob/master/bip-0065.mediawiki)) and relative
+//! ([BIP-68](https://github.com/bitcoin/bips/blob/master/bip-0068.mediawiki),
+//! [BIP-112](https://github.com/bitcoin/bips/blob/master/bip-0112.mediawiki)) lock times, and
+//! transaction finality using the median time past
+//! ([BIP-113](https://github.com/bitcoin/bips/blob/master/bip-0113.mediawiki)).
+
+use crate::{types::*, Error, Script};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+
+// https://github.com/bitcoin/bitcoin/blob/7fcf53f7b4524572d1d0c9a5fdc388e87eb02416/src/script/script.h#L39
+/// Lock times below this value are block heights, all others are unix timestamps
+pub const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+/// Sequence number of an input that opts out of lock times
+pub const SEQUENCE_FINAL: u32 = u32::MAX;
+/// If set, the sequence number is not interpreted as a relative lock time
+pub const SEQUENCE_LOCKTIME_DISABLE_FLAG: u32 = 1 << 31;
+/// If set, the relative lock time is specified in units of 512 seconds instead of blocks
+pub const SEQUENCE_LOCKTIME_TYPE_FLAG: u32 = 1 << 22;
+pub const SEQUENCE_LOCKTIME_MASK: u32 = 0x0000_ffff;
+/// Time based relative lock times have a granularity of 2^9 = 512 seconds
+pub const SEQUENCE_LOCKTIME_GRANULARITY: u32 = 9;
+
+impl LockTime {
+    /// Interprets the `nLockTime` field of a transaction
+    pub fn from_consensus(value: u32) -> Self {
+        if value < LOCKTIME_THRESHOLD {
+            LockTime::BlockHeight(value)
+        } else {
+            LockTime::Time(value)
+        }
+    }
+
+    /// Returns the value of the `nLockTime` field of a transaction
+    pub fn to_consensus(&self) -> u32 {
+        match *self {
+            LockTime::BlockHeight(value) | LockTime::Time(value) => value,
+        }
+    }
+
+    pub fn is_block_height(&self) -> bool {
+        matches!(self, LockTime::BlockHeight(_))
+    }
+
+    /// Returns true if a transaction with this lock time can be included in a block at
+    /// `block_height`, where `median_time_past` is the median time of the 11 blocks preceding it
+    pub fn is_satisfied_by(&self, block_height: u32, median_time_past: u32) -> bool {
+        match *self {
+            LockTime::BlockHeight(height) => height < block_height,
+            LockTime::Time(time) => time < median_time_past,
+        }
+    }
+
+    /// Returns true if a transaction locked with `other` satisfies this lock time, i.e. both are
+    /// of the same unit and `other` is at least as late (this is the check of `OP_CHECKLOCKTIMEVERIFY`)
+    pub fn is_implied_by(&self, other: &LockTime) -> bool {
+        self.is_block_height() == other.is_block_height() && self.to_consensus() <= other.to_consensus()
+    }
+}
+
+/// Relative lock time of an input, i.e. the time that has to pass after the spent output was
+/// included in a block before the input can be included in a block
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RelativeLockTime {
+    /// number of blocks
+    Blocks(u16),
+    /// number of 512 second intervals
+    Time(u16),
+}
+
+impl RelativeLockTime {
+    /// Interprets the sequence number of an input, returns `None` if the disable flag is set
+    pub fn from_sequence(sequence: u32) -> Option<Self> {
+        if sequence & SEQUENCE_LOCKTIME_DISABLE_FLAG != 0 {
+            return None;
+        }
+        let value = (sequence & SEQUENCE_LOCKTIME_MASK) as u16;
+        if sequence & SEQUENCE_LOCKTIME_TYPE_FLAG != 0 {
+            Some(RelativeLockTime::Time(value))
+        } else {
+            Some(RelativeLockTime::Blocks(value))
+        }
+    }
+
+    /// Returns the smallest time based lock time of at least `seconds`
+    pub fn from_seconds_ceil(seconds: u32) -> Result<Self, Error> {
+        let intervals = (seconds as u64 + (1 << SEQUENCE_LOCKTIME_GRANULARITY) - 1) >> SEQUENCE_LOCKTIME_GRANULARITY;
+        let intervals = u16::try_from(intervals).map_err(|_| Error::ArithmeticOverflow)?;
+        Ok(RelativeLockTime::Time(intervals))
+    }
+
+    /// Returns the sequence number of an input with this lock time
+    pub fn to_sequence(&self) -> u32 {
+        match *self {
+            RelativeLockTime::Blocks(value) => value as u32,
+            RelativeLockTime::Time(value) => SEQUENCE_LOCKTIME_TYPE_FLAG | value as u32,
+        }
+    }
+
+    /// Returns true if an input locked with `other` satisfies this lock time, i.e. both are of the
+    /// same unit and `other` is at least as long (this is the check of `OP_CHECKSEQUENCEVERIFY`)
+    pub fn is_implied_by(&self, other: &RelativeLockTime) -> bool {
+        match (*self, *other) {
+            (RelativeLockTime::Blocks(this), RelativeLockTime::Blocks(other))
+            | (RelativeLockTime::Time(this), RelativeLockTime::Time(other)) => this <= other,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the lock time has passed, given the number of blocks and the seconds of
+    /// median time past that elapsed since the spent output was included
+    pub fn is_satisfied_by(&self, elapsed_blocks: u32, elapsed_seconds: u32) -> bool {
+        match *self {
+            RelativeLockTime::Blocks(value) => value as u32 <= elapsed_blocks,
+            RelativeLockTime::Time(value) => (value as u32) << SEQUENCE_LOCKTIME_GRANULARITY <= elapsed_seconds,
+        }
+    }
+}
+
+impl TransactionInput {
+    /// Returns the relative lock time encoded in the sequence number, which is only enforced
+    /// for transactions with version 2 or higher
+    pub fn relative_lock_time(&self) -> Option<RelativeLockTime> {
+        RelativeLockTime::from_sequence(self.sequence)
+    }
+}
+
+impl Transaction {
+    /// Returns true if the transaction can be included in a block at `block_height`, where
+    /// `median_time_past` is the median time of the 11 blocks preceding it. Note that this
+    /// does not check the relative lock times of the inputs, see `check_sequence_locks`.
+    pub fn is_final(&self, block_height: u32, median_time_past: u32) -> bool {
+        self.lock_at.to_consensus() == 0
+            || self.lock_at.is_satisfied_by(block_height, median_time_past)
+            // the lock time is not enforced if all inputs are final
+            || self.inputs.iter().all(|input| input.sequence == SEQUENCE_FINAL)
+    }
+
+    /// Checks the relative lock times of the inputs for inclusion in a block at `block_height`,
+    /// where `median_time_past` is the median time of the 11 blocks preceding it.
+    ///
+    /// # Arguments
+    ///
+    /// * `prevouts` - for each input, the height of the block including the spent output and the median time past of
+    ///   the block preceding that block
+    /// * `block_height` - height of the block that would include the transaction
+    /// * `median_time_past` - median time past of the block preceding `block_height`
+    pub fn check_sequence_locks(
+        &self,
+        prevouts: &[(u32, u32)],
+        block_height: u32,
+        median_time_past: u32,
+    ) -> Result<(), Error> {
+        if prevouts.len() != self.inputs.len() {
+            return Err(Error::InvalidPrevouts);
+        }
+        // relative lock times are only enforced for version 2 transactions
+        if self.version < 2 {
+            return Ok(());
+        }
+        for (input, &(prevout_height, prevout_median_time_past)) in self.inputs.iter().zip(prevouts) {
+            if let Some(lock_time) = input.relative_lock_time() {
+                let elapsed_blocks = block_height.saturating_sub(prevout_height);
+                let elapsed_seconds = median_time_past.saturating_sub(prevout_median_time_past);
+                if !lock_time.is_satisfied_by(elapsed_blocks, elapsed_seconds) {
+                    return Err(Error::UnsatisfiedLockTime);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Script {
+    /// Appends `<lock_time> OP_CHECKLOCKTIMEVERIFY OP_DROP`, which requires the spending
+    /// transaction to be locked until at least `lock_time`
+    pub fn append_lock_time_verify(&mut self, lock_time: &LockTime) {
+        self.push_int(lock_time.to_consensus() as i64);
+        self.append(OpCode::OpCheckLocktimeVerify);
+        self.append(OpCode::OpDrop);
+    }
+
+    /// Appends `<lock_time> OP_CHECKSEQUENCEVERIFY OP_DROP`, which requires the spending input
+    /// to be locked for at least `lock_time` after the spent output was included
+    pub fn append_sequence_verify(&mut self, lock_time: &RelativeLockTime) {
+        self.push_int(lock_time.to_sequence() as i64);
+        self.append(OpCode::OpCheckSequenceVerify);
+        self.append(OpCode::OpDrop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(version: i32, lock_time: u32, sequences: &[u32]) -> Transaction {
+        let mut builder = TransactionBuilder::new();
+        builder.with_version(version);
+        for &sequence in sequences {
+            builder.add_input(
+                TransactionInputBuilder::new()
+                    .with_source(TransactionInputSource::FromOutput(H256Le::zero(), 0))
+                    .with_sequence(sequence)
+                    .build(),
+            );
+        }
+        let mut transaction = builder.build();
+        transaction.lock_at = LockTime::from_consensus(lock_time);
+        transaction
+    }
+
+    #[test]
+    fn test_lock_time_from_consensus() {
+        assert_eq!(LockTime::from_consensus(0), LockTime::BlockHeight(0));
+        assert_eq!(
+            LockTime::from_consensus(499_999_999),
+            LockTime::BlockHeight(499_999_999)
+        );
+        assert_eq!(LockTime::from_consensus(500_000_000), LockTime::Time(500_000_000));
+        assert_eq!(LockTime::Time(1_700_000_000).to_consensus(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_lock_time_is_implied_by() {
+        assert!(LockTime::BlockHeight(100).is_implied_by(&LockTime::BlockHeight(100)));
+        assert!(LockTime::BlockHeight(100).is_implied_by(&LockTime::BlockHeight(101)));
+        assert!(!LockTime::BlockHeight(100).is_implied_by(&LockTime::BlockHeight(99)));
+        assert!(!LockTime::BlockHeight(100).is_implied_by(&LockTime::Time(600_000_000)));
+        assert!(!LockTime::Time(600_000_000).is_implied_by(&LockTime::BlockHeight(100)));
+    }
+
+    #[test]
+    fn test_relative_lock_time_from_sequence() {
+        assert_eq!(
+            RelativeLockTime::from_sequence(144),
+            Some(RelativeLockTime::Blocks(144))
+        );
+        assert_eq!(
+            RelativeLockTime::from_sequence(SEQUENCE_LOCKTIME_TYPE_FLAG | 10),
+            Some(RelativeLockTime::Time(10))
+        );
+        // bits outside of the type flag and value are ignored
+        assert_eq!(
+            RelativeLockTime::from_sequence(0x0001_0000 | 144),
+            Some(RelativeLockTime::Blocks(144))
+        );
+        assert_eq!(RelativeLockTime::from_sequence(SEQUENCE_FINAL), None);
+        assert_eq!(
+            RelativeLockTime::from_sequence(SEQUENCE_LOCKTIME_DISABLE_FLAG | 144),
+            None
+        );
+
+        assert_eq!(
+            RelativeLockTime::Time(10).to_sequence(),
+            SEQUENCE_LOCKTIME_TYPE_FLAG | 10
+        );
+        assert_eq!(RelativeLockTime::from_seconds_ceil(512), Ok(RelativeLockTime::Time(1)));
+        assert_eq!(RelativeLockTime::from_seconds_ceil(513), Ok(RelativeLockTime::Time(2)));
+        assert_eq!(
+            RelativeLockTime::from_seconds_ceil(u32::MAX),
+            Err(Error::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_relative_lock_time_is_satisfied_by() {
+        assert!(RelativeLockTime::Blocks(144).is_satisfied_by(144, 0));
+        assert!(!RelativeLockTime::Blocks(144).is_satisfied_by(143, u32::MAX));
+        assert!(RelativeLockTime::Time(2).is_satisfied_by(0, 1024));
+        assert!(!RelativeLockTime::Time(2).is_satisfied_by(u32::MAX, 1023));
+
+        assert!(RelativeLockTime::Blocks(10).is_implied_by(&RelativeLockTime::Blocks(10)));
+        assert!(!RelativeLockTime::Blocks(10).is_implied_by(&RelativeLockTime::Blocks(9)));
+        assert!(!RelativeLockTime::Blocks(10).is_implied_by(&RelativeLockTime::Time(10)));
+    }
+
+    #[test]
+    fn test_transaction_is_final() {
+        // no lock time
+        assert!(transaction(2, 0, &[0]).is_final(1, 0));
+
+        // locked until block 100, i.e. can be included in block 101
+        let locked = transaction(2, 100, &[0]);
+        assert!(!locked.is_final(100, u32::MAX));
+        assert!(locked.is_final(101, 0));
+
+        // locked until the median time past exceeds the timestamp
+        let locked = transaction(2, 1_700_000_000, &[SEQUENCE_FINAL, 0]);
+        assert!(!locked.is_final(u32::MAX, 1_700_000_000));
+        assert!(locked.is_final(0, 1_700_000_001));
+
+        // final inputs disable the lock time
+        assert!(transaction(2, 100, &[SEQUENCE_FINAL, SEQUENCE_FINAL]).is_final(0, 0));
+    }
+
+    #[test]
+    fn test_transaction_check_sequence_locks() {
+        let locked = transaction(2, 0, &[144, SEQUENCE_LOCKTIME_TYPE_FLAG | 2]);
+        let prevouts = [(1000, 0), (1100, 1_600_000_000)];
+        assert_eq!(locked.check_sequence_locks(&prevouts, 1144, 1_600_001_024), Ok(()));
+        assert_eq!(
+            locked.check_sequence_locks(&prevouts, 1143, 1_600_001_024),
+            Err(Error::UnsatisfiedLockTime)
+        );
+        assert_eq!(
+            locked.check_sequence_locks(&prevouts, 1144, 1_600_001_023),
+            Err(Error::UnsatisfiedLockTime)
+        );
+        assert_eq!(
+            locked.check_sequence_locks(&prevouts[..1], 1144, 1_600_001_024),
+            Err(Error::InvalidPrevouts)
+        );
+
+        // disabled relative lock time
+        let unlocked = transaction(2, 0, &[SEQUENCE_LOCKTIME_DISABLE_FLAG | 144]);
+        assert_eq!(unlocked.check_sequence_locks(&[(1000, 0)], 1000, 0), Ok(()));
+        // relative lock times require version 2
+        let unlocked = transaction(1, 0, &[144]);
+        assert_eq!(unlocked.check_sequence_locks(&[(1000, 0)], 1000, 0), Ok(()));
+    }
+
+    #[test]
+    fn test_timelock_scripts() {
+        let mut script = Script::new();
+        script.append_lock_time_verify(&LockTime::BlockHeight(500));
+        assert_eq!(script.as_bytes(), &[0x02, 0xf4, 0x01, 0xb1, 0x75]);
+
+        let mut script = Script::new();
+        script.append_sequence_verify(&RelativeLockTime::Blocks(144));
+        assert_eq!(script.as_bytes(), &[0x02, 0x90, 0x00, 0xb2, 0x75]);
+
+        let mut script = Script::new();
+        script.append_sequence_verify(&RelativeLockTime::Time(1));
+        assert_eq!(script.as_bytes(), &[0x03, 0x01, 0x00, 0x40, 0xb2, 0x75]);
+    }
+}
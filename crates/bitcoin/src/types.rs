@@ -3,12 +3,15 @@ pub use primitive_types::{H160, H256, U256};
 
 use crate::{
     formatter::{BoundedWriter, TryFormat, Writer},
+    interpreter::next_instruction,
     merkle::{MerkleTree, PartialTransactionProof},
-    utils::{log2, reverse_endianness, sha256d_le},
+    utils::{log2, reverse_endianness, sha256, sha256d_le},
     Address, Error, PublicKey, Script,
 };
+use bitcoin_hashes::{hash160::Hash as Hash160, Hash};
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+use secp256k1::constants::PUBLIC_KEY_SIZE;
 
 #[cfg(any(feature = "parser", test))]
 use crate::parser::parse_block_header;
@@ -30,6 +33,60 @@ pub struct FullTransactionProof {
     pub coinbase_proof: PartialTransactionProof,
 }
 
+/// Extends the [`FullTransactionProof`] with a proof that the witness data of the user transaction is
+/// committed to by the coinbase transaction, see
+/// [BIP-141](https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#commitment-structure).
+#[derive(Encode, Decode, Clone, TypeInfo, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct WitnessTransactionProof {
+    pub transaction_proof: FullTransactionProof,
+    /// Partial merkle tree over the wtxids of the same block, in which the coinbase wtxid is zero
+    pub witness_proof: MerkleProof,
+    /// Bound on the length of the user transaction serialized with its witness
+    pub tx_encoded_len: u32,
+}
+
+impl WitnessTransactionProof {
+    /// Checks that the witness data of the user transaction is committed to by the coinbase transaction.
+    /// This does not verify the inclusion of the transactions in the block, which must still be checked
+    /// as for the [`FullTransactionProof`].
+    pub fn verify_witness_commitment(&self) -> Result<(), Error> {
+        let user_tx_proof = &self.transaction_proof.user_tx_proof;
+        let coinbase = &self.transaction_proof.coinbase_proof.transaction;
+
+        let commitment = match coinbase.witness_commitment() {
+            Some(commitment) => commitment,
+            // blocks without a commitment cannot contain witness data
+            None if !user_tx_proof.transaction.has_witness() => return Ok(()),
+            None => return Err(Error::InvalidWitnessCommitment),
+        };
+
+        // fail if the witness proof is not for the same block
+        if self.witness_proof.block_header != user_tx_proof.merkle_proof.block_header
+            || self.witness_proof.transactions_count != user_tx_proof.merkle_proof.transactions_count
+        {
+            return Err(Error::InvalidMerkleProof);
+        }
+
+        let (_, position, _) = user_tx_proof.merkle_proof.extract_root()?;
+        let (witness_root, witness_position, hash_position) = self.witness_proof.extract_root()?;
+        if position != witness_position {
+            return Err(Error::InvalidMerkleProof);
+        }
+        if self.witness_proof.hashes[hash_position] != user_tx_proof.transaction.wtxid_bounded(self.tx_encoded_len)? {
+            return Err(Error::InvalidWtxid);
+        }
+
+        let reserved_value = coinbase
+            .witness_reserved_value()
+            .ok_or(Error::InvalidWitnessCommitment)?;
+        if witness_commitment_hash(witness_root, reserved_value) != commitment {
+            return Err(Error::InvalidWitnessCommitment);
+        }
+        Ok(())
+    }
+}
+
 /// Bitcoin Script OpCodes
 /// <https://github.com/bitcoin/bitcoin/blob/master/src/script/script.h>
 #[derive(Copy, Clone)]
@@ -172,10 +229,15 @@ pub const P2PKH_SCRIPT_SIZE: u32 = 25;
 pub const P2SH_SCRIPT_SIZE: u32 = 23;
 pub const P2WPKH_V0_SCRIPT_SIZE: u32 = 22;
 pub const P2WSH_V0_SCRIPT_SIZE: u32 = 34;
+pub const P2TR_V1_SCRIPT_SIZE: u32 = 34;
 pub const HASH160_SIZE_HEX: u8 = 0x14;
 pub const HASH256_SIZE_HEX: u8 = 0x20;
 // TODO: reduce to H256 size + op code
 pub const MAX_OPRETURN_SIZE: usize = 83;
+// OP_RETURN, push of 36 bytes and the commitment header, see BIP-141
+const WITNESS_COMMITMENT_HEADER: [u8; 6] = [0x6a, 0x24, 0xaa, 0x21, 0xa9, 0xed];
+// weight units per byte of non-witness data, see BIP-141
+pub const WITNESS_SCALE_FACTOR: usize = 4;
 
 /// Structs
 
@@ -221,6 +283,17 @@ impl BlockHeader {
         Ok(self.hash)
     }
 
+    /// Returns the expected number of hashes needed to mine a block at the target of the header,
+    /// i.e. `2**256 / (target + 1)`
+    // https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/chain.cpp#L131-L144
+    pub fn work(&self) -> U256 {
+        // 2**256 does not fit, but 2**256 / (target + 1) == ~target / (target + 1) + 1
+        match self.target.checked_add(U256::one()) {
+            Some(divisor) => (!self.target / divisor).saturating_add(U256::one()),
+            None => U256::one(),
+        }
+    }
+
     /// Returns a block header from a bytes slice
     ///
     /// # Arguments
@@ -271,6 +344,51 @@ impl TransactionInput {
         self.witness = witness;
     }
 
+    /// Returns the hash and index of the spent output, coinbase inputs spend the null outpoint
+    pub(crate) fn outpoint(&self) -> (H256Le, u32) {
+        match self.source {
+            TransactionInputSource::Coinbase(_) => (H256Le::zero(), u32::MAX),
+            TransactionInputSource::FromOutput(hash, index) => (hash, index),
+        }
+    }
+
+    /// Derives the address of the spent output from the scriptSig and witness, this is only
+    /// possible for P2PKH, P2SH (including nested segwit), P2WPKH and P2WSH spends. Signatures
+    /// are not checked, inclusion in a relayed block is what proves the spend is valid.
+    pub fn extract_address(&self) -> Result<Address, Error> {
+        if let TransactionInputSource::Coinbase(_) = self.source {
+            return Err(Error::UnsupportedInputFormat);
+        }
+
+        let mut pushes = Vec::new();
+        let mut position = 0;
+        while let Some((opcode, data)) = next_instruction(&self.script, &mut position)? {
+            match data {
+                Some(data) => pushes.push((opcode, data)),
+                None => return Err(Error::UnsupportedInputFormat),
+            }
+        }
+
+        match (pushes.as_slice(), self.witness.as_slice()) {
+            // native segwit: the witness program is only committed to by the spent output
+            ([], [_signature, public_key]) if public_key.len() == PUBLIC_KEY_SIZE => {
+                Ok(Address::P2WPKHv0(hash160(public_key)))
+            }
+            ([], [_, .., witness_script]) => Ok(Address::P2WSHv0(H256(sha256(witness_script)))),
+            // nested segwit: the scriptSig only pushes the witness program
+            ([(_, witness_program)], [_, ..]) => Ok(Address::P2SH(hash160(witness_program))),
+            // the leading `OP_0` is the dummy element consumed by `CHECKMULTISIG`
+            ([(opcode, _), .., (_, redeem_script)], []) if *opcode == OpCode::Op0 as u8 => {
+                Ok(Address::P2SH(hash160(redeem_script)))
+            }
+            ([_signature, (_, public_key)], []) if public_key.len() == PUBLIC_KEY_SIZE => {
+                Ok(Address::P2PKH(hash160(public_key)))
+            }
+            ([_, .., (_, redeem_script)], []) => Ok(Address::P2SH(hash160(redeem_script))),
+            _ => Err(Error::UnsupportedInputFormat),
+        }
+    }
+
     // used by the benchmarks to make the
     // transaction be an expected length
     #[cfg(feature = "runtime-benchmarks")]
@@ -286,6 +404,10 @@ impl TransactionInput {
     }
 }
 
+fn hash160(bytes: &[u8]) -> H160 {
+    H160::from(Hash160::hash(bytes).into_inner())
+}
+
 pub type Value = i64;
 
 /// Bitcoin transaction output
@@ -353,12 +475,103 @@ impl Transaction {
         sha256d_le(&bytes)
     }
 
+    /// Returns the hash of the transaction including its witness data, see BIP-141.
+    /// This is equal to the txid for transactions without witness data.
+    pub fn wtxid(&self) -> H256Le {
+        self.hash()
+    }
+
+    pub fn wtxid_bounded(&self, length_bound: u32) -> Result<H256Le, Error> {
+        let mut bytes = BoundedWriter::new(length_bound);
+        self.try_format(&mut bytes)?;
+        Ok(sha256d_le(&bytes.result()))
+    }
+
+    /// Returns the witness commitment of a coinbase transaction, taken from the last output
+    /// matching the commitment structure of BIP-141
+    pub fn witness_commitment(&self) -> Option<H256Le> {
+        let script = self.outputs[self.witness_commitment_index()?].script.as_bytes();
+        let start = WITNESS_COMMITMENT_HEADER.len();
+        Some(H256Le::from_bytes_le(&script[start..start + 32]))
+    }
+
+    /// Returns the index of the output containing the witness commitment
+    pub(crate) fn witness_commitment_index(&self) -> Option<usize> {
+        self.outputs.iter().rposition(|output| {
+            let script = output.script.as_bytes();
+            script.len() >= WITNESS_COMMITMENT_HEADER.len() + 32 && script.starts_with(&WITNESS_COMMITMENT_HEADER)
+        })
+    }
+
+    /// Returns the witness reserved value of a coinbase transaction, which is combined with the
+    /// witness merkle root to compute the witness commitment
+    pub fn witness_reserved_value(&self) -> Option<&[u8]> {
+        match self.inputs.first()?.witness.as_slice() {
+            [reserved_value] if reserved_value.len() == 32 => Some(reserved_value),
+            _ => None,
+        }
+    }
+
     pub fn size_no_witness(&self) -> usize {
         let mut bytes = vec![];
         self.format_no_witness(&mut bytes).expect("Not bounded");
         bytes.len()
     }
 
+    /// Returns the size of the transaction including its witness data
+    pub fn size(&self) -> usize {
+        let mut bytes = vec![];
+        self.try_format(&mut bytes).expect("Not bounded");
+        bytes.len()
+    }
+
+    /// Returns the weight of the transaction as defined in BIP-141, i.e. three times the size
+    /// without witness data plus the size including witness data
+    pub fn weight(&self) -> usize {
+        self.size_no_witness() * (WITNESS_SCALE_FACTOR - 1) + self.size()
+    }
+
+    /// Returns the virtual size of the transaction, i.e. the weight divided by four and rounded up
+    pub fn vsize(&self) -> usize {
+        (self.weight() + WITNESS_SCALE_FACTOR - 1) / WITNESS_SCALE_FACTOR
+    }
+
+    /// Returns the fee paid by the transaction, given the values of the outputs spent by each
+    /// of its inputs (in input order)
+    pub fn fee(&self, prevout_values: &[Value]) -> Result<Value, Error> {
+        if prevout_values.len() != self.inputs.len() || prevout_values.iter().any(|&value| value < 0) {
+            return Err(Error::InvalidPrevouts);
+        }
+        if self.outputs.iter().any(|output| output.value < 0) {
+            return Err(Error::MalformedTransaction);
+        }
+        let total_in = prevout_values
+            .iter()
+            .try_fold(0 as Value, |acc, &value| acc.checked_add(value))
+            .ok_or(Error::ArithmeticOverflow)?;
+        let total_out = self
+            .outputs
+            .iter()
+            .try_fold(0 as Value, |acc, output| acc.checked_add(output.value))
+            .ok_or(Error::ArithmeticOverflow)?;
+        match total_in.checked_sub(total_out) {
+            Some(fee) if fee >= 0 => Ok(fee),
+            _ => Err(Error::ArithmeticUnderflow),
+        }
+    }
+
+    /// Checks that the transaction pays at least `min_sat_per_vbyte` satoshis per virtual byte,
+    /// given the values of the outputs spent by each of its inputs (in input order)
+    pub fn ensure_min_fee_rate(&self, prevout_values: &[Value], min_sat_per_vbyte: Value) -> Result<(), Error> {
+        let required_fee = (self.vsize() as Value)
+            .checked_mul(min_sat_per_vbyte)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if self.fee(prevout_values)? < required_fee {
+            return Err(Error::InsufficientFeeRate);
+        }
+        Ok(())
+    }
+
     pub(crate) fn has_witness(&self) -> bool {
         // check if any of the inputs has a witness
         self.inputs.iter().any(|v| !v.witness.is_empty())
@@ -374,6 +587,17 @@ impl Transaction {
                 })
             )
     }
+
+    /// Returns the block height committed to by a coinbase transaction (BIP-34), if any
+    pub fn coinbase_height(&self) -> Option<u32> {
+        match self.inputs.get(0) {
+            Some(TransactionInput {
+                source: TransactionInputSource::Coinbase(height),
+                ..
+            }) if self.is_coinbase() => *height,
+            _ => None,
+        }
+    }
 }
 
 // https://en.bitcoin.it/wiki/NLockTime
@@ -400,6 +624,36 @@ pub struct Block {
 
 impl Block {
     pub fn merkle_proof(&self, include: &[H256Le]) -> Result<MerkleProof, Error> {
+        let mut tx_ids = Vec::with_capacity(self.transactions.len());
+        for tx in self.transactions.iter() {
+            tx_ids.push(tx.tx_id());
+        }
+        self.partial_merkle_proof(&tx_ids, include)
+    }
+
+    /// Builds a partial merkle tree over the wtxids of the block for the transactions with
+    /// the txids in `include`
+    pub fn witness_merkle_proof(&self, include: &[H256Le]) -> Result<MerkleProof, Error> {
+        self.partial_merkle_proof(&self.wtxids(), include)
+    }
+
+    /// Returns the root of the merkle tree over the wtxids of the block
+    pub fn witness_root(&self) -> Result<H256Le, Error> {
+        let transactions_count = self.transactions.len() as u32;
+        let height = MerkleTree::compute_height(transactions_count);
+        MerkleTree::compute_root(0, height, transactions_count, &self.wtxids())
+    }
+
+    /// The coinbase wtxid is zero since the coinbase contains the commitment
+    fn wtxids(&self) -> Vec<H256Le> {
+        let mut wtxids = Vec::with_capacity(self.transactions.len());
+        for (index, tx) in self.transactions.iter().enumerate() {
+            wtxids.push(if index == 0 { H256Le::zero() } else { tx.wtxid() });
+        }
+        wtxids
+    }
+
+    fn partial_merkle_proof(&self, hashes: &[H256Le], include: &[H256Le]) -> Result<MerkleProof, Error> {
         let mut proof = MerkleProof {
             block_header: self.header,
             transactions_count: self.transactions.len() as u32,
@@ -407,11 +661,6 @@ impl Block {
             hashes: vec![],
         };
 
-        let mut tx_ids = Vec::with_capacity(self.transactions.len());
-        for tx in self.transactions.iter() {
-            tx_ids.push(tx.tx_id());
-        }
-
         let matches: Vec<bool> = self
             .transactions
             .iter()
@@ -419,11 +668,18 @@ impl Block {
             .collect();
 
         let height = proof.compute_partial_tree_height();
-        proof.traverse_and_build(height as u32, 0, &tx_ids, &matches)?;
+        proof.traverse_and_build(height as u32, 0, hashes, &matches)?;
         Ok(proof)
     }
 }
 
+/// Computes the witness commitment from the witness merkle root and the reserved value
+fn witness_commitment_hash(witness_root: H256Le, reserved_value: &[u8]) -> H256Le {
+    let mut bytes = witness_root.to_bytes_le().to_vec();
+    bytes.extend_from_slice(reserved_value);
+    sha256d_le(&bytes)
+}
+
 /// Generates a new block
 /// mined with the given difficulty
 ///
@@ -478,6 +734,7 @@ impl BlockBuilder {
         if self.block.transactions.is_empty() {
             panic!("trying to mine a block without a coinbase");
         }
+        self.add_witness_commitment()?;
         self.block.header.target = target;
         self.block.header.merkle_root = self.compute_merkle_root()?;
         let mut nonce: u32 = 0;
@@ -498,13 +755,29 @@ impl BlockBuilder {
     }
 
     pub fn with_coinbase(&mut self, address: &Address, reward: Value, height: u32) -> &mut Self {
-        // TODO: compute witness commitment
+        // the witness commitment is added when mining since it depends on all transactions
         self.block
             .transactions
             .insert(0, generate_coinbase_transaction(address, reward, height, None, None));
         self
     }
 
+    // https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#commitment-structure
+    fn add_witness_commitment(&mut self) -> Result<(), Error> {
+        let has_witness = self.block.transactions.iter().skip(1).any(Transaction::has_witness);
+        if !has_witness || self.block.transactions[0].witness_commitment().is_some() {
+            return Ok(());
+        }
+
+        let witness_root = self.block.witness_root()?;
+        let coinbase = &mut self.block.transactions[0];
+        let reserved_value = coinbase.witness_reserved_value().ok_or(Error::MalformedTransaction)?;
+        let mut commitment = WITNESS_COMMITMENT_HEADER[2..].to_vec();
+        commitment.extend_from_slice(&witness_commitment_hash(witness_root, reserved_value).to_bytes_le());
+        coinbase.outputs.push(TransactionOutput::op_return(0, &commitment));
+        Ok(())
+    }
+
     fn compute_merkle_root(&self) -> Result<H256Le, Error> {
         let height = log2(self.block.transactions.len() as u64);
         let mut tx_ids = Vec::with_capacity(self.block.transactions.len());
@@ -515,13 +788,13 @@ impl BlockBuilder {
     }
 
     #[cfg(feature = "runtime-benchmarks")]
-    pub fn build_max(previous_hash: H256Le, hashes: u32, transaction: Transaction) -> Block {
+    pub fn build_max(previous_hash: H256Le, timestamp: u32, hashes: u32, transaction: Transaction) -> Block {
         let mut block_builder = Self::new();
         block_builder
             .with_previous_hash(previous_hash)
             .with_version(4)
             .with_coinbase(&Address::default(), 50, 3)
-            .with_timestamp(u32::MAX);
+            .with_timestamp(timestamp);
 
         // we expect at least two hashes for payment + merkle root
         let tree_height = hashes - 1; // remove the merkle root to get height
@@ -888,7 +1161,7 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
-    use crate::{parser::parse_transaction, Address};
+    use crate::{parser::parse_transaction, Address, SetCompact};
 
     fn sample_example_real_rawtx() -> String {
         "0200000000010140d43a99926d43eb0e619bf0b3d83b4a31f60c176beecfb9d35bf45e54d0f7420100000017160014a4b4ca48de0b3fffc15404a1acdc8dbaae226955ffffffff0100e1f5050000000017a9144a1154d50b03292b3024370901711946cb7cccc387024830450221008604ef8f6d8afa892dee0f31259b6ce02dd70c545cfcfed8148179971876c54a022076d771d6e91bed212783c9b06e0de600fab2d518fad6f15a2b191d7fbd262a3e0121039d25ab79f41f75ceaf882411fd41fa670a4c672c23ffaf0e361a969cde0692e800000000".to_owned()
@@ -927,6 +1200,22 @@ mod tests {
         assert_eq!(h256_le, h256);
     }
 
+    #[test]
+    fn test_block_header_work() {
+        let header = |target: U256| BlockHeader {
+            target,
+            ..Default::default()
+        };
+        // chainwork of the mainnet genesis block
+        assert_eq!(
+            header(U256::set_compact(0x1d00ffff).unwrap()).work(),
+            U256::from(0x0001_0001_0001u64)
+        );
+        assert_eq!(header(U256::from(2).pow(254.into())).work(), U256::from(3));
+        assert_eq!(header(U256::MAX).work(), U256::one());
+        assert_eq!(header(U256::zero()).work(), U256::MAX);
+    }
+
     #[test]
     fn test_transaction_hash() {
         let raw_tx = hex::decode(&sample_example_real_rawtx()).unwrap();
@@ -962,6 +1251,54 @@ mod tests {
         assert_ne!(sha256d_le(&tx_bytes), expected_txid);
     }
 
+    #[test]
+    fn test_transaction_weight() {
+        // 106 bytes without witness, 216 bytes with witness
+        let transaction = parse_transaction(&hex::decode(sample_example_real_rawtx()).unwrap()).unwrap();
+        assert_eq!(transaction.size_no_witness(), 106);
+        assert_eq!(transaction.size(), 216);
+        assert_eq!(transaction.weight(), 534);
+        assert_eq!(transaction.vsize(), 134);
+
+        // without witness, the weight is four times the size
+        let mut transaction = transaction;
+        transaction.inputs[0].witness.clear();
+        assert_eq!(transaction.weight(), 4 * 106);
+        assert_eq!(transaction.vsize(), 106);
+    }
+
+    #[test]
+    fn test_transaction_fee() {
+        // spends a single output and pays 100_000_000 satoshis
+        let transaction = parse_transaction(&hex::decode(sample_example_real_rawtx()).unwrap()).unwrap();
+        assert_eq!(transaction.fee(&[100_013_400]), Ok(13_400));
+        assert_eq!(transaction.fee(&[99_999_999]), Err(Error::ArithmeticUnderflow));
+        assert_eq!(transaction.fee(&[]), Err(Error::InvalidPrevouts));
+        assert_eq!(transaction.fee(&[100_013_400, 1]), Err(Error::InvalidPrevouts));
+        assert_eq!(transaction.fee(&[-1]), Err(Error::InvalidPrevouts));
+        assert_eq!(transaction.fee(&[Value::MAX]), Ok(Value::MAX - 100_000_000));
+
+        let mut negative_output = transaction.clone();
+        negative_output.outputs[0].value = -1;
+        assert_eq!(negative_output.fee(&[100_013_400]), Err(Error::MalformedTransaction));
+
+        let mut overflowing_outputs = transaction.clone();
+        overflowing_outputs.outputs[0].value = Value::MAX;
+        overflowing_outputs.outputs.push(overflowing_outputs.outputs[0].clone());
+        assert_eq!(overflowing_outputs.fee(&[100_013_400]), Err(Error::ArithmeticOverflow));
+
+        // 134 vbytes at 100 sat/vbyte
+        assert_eq!(transaction.ensure_min_fee_rate(&[100_013_400], 100), Ok(()));
+        assert_eq!(
+            transaction.ensure_min_fee_rate(&[100_013_399], 100),
+            Err(Error::InsufficientFeeRate)
+        );
+        assert_eq!(
+            transaction.ensure_min_fee_rate(&[100_013_400], Value::MAX),
+            Err(Error::ArithmeticOverflow)
+        );
+    }
+
     #[test]
     fn test_transaction_txid_without_witness() {
         // the witness data should not be included in the input of the hashfunction that calculates the txid  - check
@@ -1116,6 +1453,123 @@ mod tests {
         MerkleProof::parse(&bytes).unwrap();
     }
 
+    #[test]
+    fn test_transaction_wtxid() {
+        clear_mocks();
+        let raw_tx = hex::decode(sample_example_real_rawtx()).unwrap();
+        let transaction = parse_transaction(&raw_tx).unwrap();
+        let expected_wtxid = H256Le::from_hex_be(&sample_example_real_transaction_hash());
+        assert_eq!(transaction.wtxid(), expected_wtxid);
+        assert_eq!(transaction.wtxid_bounded(raw_tx.len() as u32), Ok(expected_wtxid));
+        assert_err!(transaction.wtxid_bounded(raw_tx.len() as u32 - 1), Error::BoundExceeded);
+
+        let mut transaction = transaction;
+        transaction.inputs[0].witness.clear();
+        assert_eq!(transaction.wtxid(), transaction.tx_id());
+    }
+
+    #[test]
+    fn test_witness_commitment() {
+        // testnet - 1896103
+        let raw_tx = "020000000001010000000000000000000000000000000000000000000000000000000000000000ffffffff2e03a7ee1c20706f6f6c2e656e6a6f79626f646965732e636f6d2031343262393163303337f72631e9f5cd76000001ffffffff025c05af00000000001600140bdd9a64240a255ee1aac57bca1df5a0f9c6a82d0000000000000000266a24aa21a9ed173684441d99dd383ca57e6a073f62694c4f7c12a158964f050b84f69ba10ec30120000000000000000000000000000000000000000000000000000000000000000000000000";
+        let mut coinbase = parse_transaction(&hex::decode(raw_tx).unwrap()).unwrap();
+        let commitment = H256Le::from_bytes_le(
+            &hex::decode("173684441d99dd383ca57e6a073f62694c4f7c12a158964f050b84f69ba10ec3").unwrap(),
+        );
+        assert_eq!(coinbase.witness_commitment(), Some(commitment));
+        assert_eq!(coinbase.witness_reserved_value(), Some(&[0u8; 32][..]));
+
+        // the last matching output is used
+        let mut other = WITNESS_COMMITMENT_HEADER.to_vec();
+        other.extend_from_slice(&[1; 33]);
+        coinbase.outputs.push(TransactionOutput {
+            value: 0,
+            script: other.into(),
+        });
+        assert_eq!(coinbase.witness_commitment(), Some(H256Le::from_bytes_le(&[1; 32])));
+
+        coinbase.outputs.truncate(1);
+        coinbase.inputs[0].witness.clear();
+        assert_eq!(coinbase.witness_commitment(), None);
+        assert_eq!(coinbase.witness_reserved_value(), None);
+    }
+
+    #[test]
+    fn test_witness_transaction_proof() {
+        clear_mocks();
+        let address = Address::P2PKH(H160::from_str("66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+        let transaction = parse_transaction(&hex::decode(sample_example_real_rawtx()).unwrap()).unwrap();
+        let other_transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_input(TransactionInputBuilder::new().add_witness(&[1; 72]).build())
+            .add_output(TransactionOutput::payment(100, &address))
+            .build();
+
+        let block = BlockBuilder::new()
+            .with_version(4)
+            .with_coinbase(&address, 50, 3)
+            .with_timestamp(1588814835)
+            .add_transaction(other_transaction.clone())
+            .add_transaction(transaction.clone())
+            .mine(U256::from(2).pow(254.into()))
+            .unwrap();
+        let coinbase = block.transactions[0].clone();
+        assert!(coinbase.witness_commitment().is_some());
+
+        let partial_proof = |transaction: &Transaction| PartialTransactionProof {
+            transaction: transaction.clone(),
+            tx_encoded_len: u32::MAX,
+            merkle_proof: block.merkle_proof(&[transaction.tx_id()]).unwrap(),
+        };
+        let proof = WitnessTransactionProof {
+            transaction_proof: FullTransactionProof {
+                user_tx_proof: partial_proof(&transaction),
+                coinbase_proof: partial_proof(&coinbase),
+            },
+            witness_proof: block.witness_merkle_proof(&[transaction.tx_id()]).unwrap(),
+            tx_encoded_len: u32::MAX,
+        };
+        assert_eq!(
+            proof
+                .transaction_proof
+                .user_tx_proof
+                .clone()
+                .verify_proof()
+                .unwrap()
+                .extracted_root,
+            block.header.merkle_root
+        );
+        assert_eq!(proof.verify_witness_commitment(), Ok(()));
+
+        // the witness data is authenticated
+        let mut invalid_proof = proof.clone();
+        invalid_proof.transaction_proof.user_tx_proof.transaction.inputs[0].witness[0][0] ^= 1;
+        assert_err!(invalid_proof.verify_witness_commitment(), Error::InvalidWtxid);
+
+        let mut invalid_proof = proof.clone();
+        invalid_proof.transaction_proof.coinbase_proof.transaction.inputs[0].witness = vec![vec![1; 32]];
+        assert_err!(
+            invalid_proof.verify_witness_commitment(),
+            Error::InvalidWitnessCommitment
+        );
+
+        let mut invalid_proof = proof.clone();
+        invalid_proof.witness_proof = block.witness_merkle_proof(&[other_transaction.tx_id()]).unwrap();
+        assert_err!(invalid_proof.verify_witness_commitment(), Error::InvalidMerkleProof);
+
+        let mut invalid_proof = proof;
+        invalid_proof
+            .transaction_proof
+            .coinbase_proof
+            .transaction
+            .outputs
+            .truncate(1);
+        assert_err!(
+            invalid_proof.verify_witness_commitment(),
+            Error::InvalidWitnessCommitment
+        );
+    }
+
     #[test]
     fn extract_witness_address_native_p2ms_output() {
         // source: https://www.blockstream.info/testnet/tx/219a49b6a376e8f4ef86866e93483552679b5157318f0e4085430a3cee24e3d8?expand
@@ -1143,6 +1597,56 @@ mod tests {
         assert_eq!(&extr_address, &address);
     }
 
+    #[test]
+    fn extract_nested_witness_address_input() {
+        let raw_tx = hex::decode(&sample_example_real_rawtx()).unwrap();
+        let transaction = parse_transaction(&raw_tx).unwrap();
+
+        let address = Address::P2SH(H160::from_str("2928f43af18d2d60e8a843540d8086b305341339").unwrap());
+
+        assert_eq!(transaction.inputs[0].extract_address(), Ok(address));
+    }
+
+    #[test]
+    fn extract_address_of_standard_inputs() {
+        let public_key = PublicKey::dummy();
+        let redeem_script = public_key.to_redeem_script();
+        let signature = vec![1u8; 71];
+
+        let p2pkh = TransactionInputBuilder::new()
+            .with_p2pkh(&public_key, signature.clone())
+            .build();
+        assert_eq!(p2pkh.extract_address(), Ok(Address::P2PKH(public_key.to_hash())));
+
+        let p2sh = TransactionInputBuilder::new()
+            .with_p2sh(&public_key, signature.clone())
+            .build();
+        assert_eq!(p2sh.extract_address(), Ok(Address::P2SH(hash160(&redeem_script))));
+
+        let p2wpkh = TransactionInputBuilder::new()
+            .with_p2wpkh(&public_key, signature.clone())
+            .build();
+        assert_eq!(p2wpkh.extract_address(), Ok(Address::P2WPKHv0(public_key.to_hash())));
+
+        let p2wsh = TransactionInputBuilder::new()
+            .with_p2wsh(&public_key, signature)
+            .build();
+        assert_eq!(
+            p2wsh.extract_address(),
+            Ok(Address::P2WSHv0(H256(sha256(&redeem_script))))
+        );
+
+        let coinbase = TransactionInputBuilder::new()
+            .with_source(TransactionInputSource::Coinbase(None))
+            .with_script(&[1, 0])
+            .build();
+        assert_eq!(coinbase.extract_address(), Err(Error::UnsupportedInputFormat));
+
+        // taproot key path spends do not reveal the output key
+        let p2tr = TransactionInputBuilder::new().add_witness(&[1u8; 64]).build();
+        assert_eq!(p2tr.extract_address(), Err(Error::UnsupportedInputFormat));
+    }
+
     #[test]
     fn p2pk_not_allowed() {
         // source: https://blockstream.info/tx/f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16?expand
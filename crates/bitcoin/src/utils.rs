@@ -20,6 +20,16 @@ const SIGNATURE_SIZE: u32 = 72;
 const OP_RETURN_OUT_SIZE: u32 = 34;
 const P2WPKH_IN_WEIGHT: u32 = 271; // 67.75 * 4;
 
+/// Computes a single SHA256 hash over the input
+pub fn sha256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::default();
+    hasher.input(bytes);
+
+    let mut ret = [0; 32];
+    ret.copy_from_slice(&hasher.result()[..]);
+    ret
+}
+
 /// Computes Bitcoin's double SHA256 hash over a LE byte encoded input
 ///
 /// # Arguments
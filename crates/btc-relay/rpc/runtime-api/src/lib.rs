@@ -4,13 +4,46 @@
 
 use codec::Codec;
 use frame_support::dispatch::DispatchError;
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-    pub trait BtcRelayApi<H256Le> where
+    /// Version 2 adds the relayer, chain state and transaction inclusion queries
+    #[api_version(2)]
+    pub trait BtcRelayApi<H256Le, AccountId, RelayerStats, BlockHeaderInfo, ForkInfo, FullTransactionProof> where
         H256Le: Codec,
+        AccountId: Codec,
+        RelayerStats: Codec,
+        BlockHeaderInfo: Codec,
+        ForkInfo: Codec,
+        FullTransactionProof: Codec,
     {
         /// Verify that the block with the given block hash is relayed, has sufficient
         /// confirmations and is part of the main chain
         fn verify_block_header_inclusion(block_hash: H256Le) -> Result<(), DispatchError>;
+
+        /// Get the submission statistics and bond of a relayer
+        fn get_relayer_stats(relayer: AccountId) -> RelayerStats;
+
+        /// Get the hash of the best block of the main chain
+        fn get_best_block() -> H256Le;
+
+        /// Get the height of the best block of the main chain
+        fn get_best_block_height() -> u32;
+
+        /// Get the details of a relayed block header, if it is stored
+        fn get_block_header(block_hash: H256Le) -> Option<BlockHeaderInfo>;
+
+        /// Get the hash of the main chain block at the given height, if it is stored
+        fn get_block_hash(block_height: u32) -> Option<H256Le>;
+
+        /// Get the forks tracked by the relay
+        fn get_fork_info() -> Vec<ForkInfo>;
+
+        /// Get the number of confirmations a Bitcoin transaction needs to be considered stable
+        fn get_stable_confirmations() -> u32;
+
+        /// Verify that the transaction is included in the main chain and has sufficient
+        /// confirmations
+        fn is_tx_included(proof: FullTransactionProof) -> Result<(), DispatchError>;
     }
 }
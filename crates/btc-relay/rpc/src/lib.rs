@@ -1,6 +1,6 @@
 //! RPC interface for the BtcRelay Module.
 
-use codec::Codec;
+use codec::{Codec, Decode};
 use jsonrpsee::{
     core::{async_trait, Error as JsonRpseeError, RpcResult},
     proc_macros::rpc,
@@ -8,19 +8,45 @@ use jsonrpsee::{
 };
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_runtime::{traits::Block as BlockT, DispatchError};
 use std::sync::Arc;
 
 pub use btc_relay_rpc_runtime_api::BtcRelayApi as BtcRelayRuntimeApi;
 
 #[rpc(client, server)]
-pub trait BtcRelayApi<BlockHash, H256Le> {
+pub trait BtcRelayApi<BlockHash, H256Le, AccountId, RelayerStats, BlockHeaderInfo, ForkInfo> {
     #[method(name = "btcRelay_verifyBlockHeaderInclusion")]
     fn verify_block_header_inclusion(
         &self,
         block_hash: H256Le,
         at: Option<BlockHash>,
     ) -> RpcResult<Result<(), DispatchError>>;
+
+    #[method(name = "btcRelay_getRelayerStats")]
+    fn get_relayer_stats(&self, relayer: AccountId, at: Option<BlockHash>) -> RpcResult<RelayerStats>;
+
+    #[method(name = "btcRelay_getBestBlock")]
+    fn get_best_block(&self, at: Option<BlockHash>) -> RpcResult<H256Le>;
+
+    #[method(name = "btcRelay_getBestBlockHeight")]
+    fn get_best_block_height(&self, at: Option<BlockHash>) -> RpcResult<u32>;
+
+    #[method(name = "btcRelay_getBlockHeader")]
+    fn get_block_header(&self, block_hash: H256Le, at: Option<BlockHash>) -> RpcResult<Option<BlockHeaderInfo>>;
+
+    #[method(name = "btcRelay_getBlockHash")]
+    fn get_block_hash(&self, block_height: u32, at: Option<BlockHash>) -> RpcResult<Option<H256Le>>;
+
+    #[method(name = "btcRelay_getForkInfo")]
+    fn get_fork_info(&self, at: Option<BlockHash>) -> RpcResult<Vec<ForkInfo>>;
+
+    #[method(name = "btcRelay_getStableConfirmations")]
+    fn get_stable_confirmations(&self, at: Option<BlockHash>) -> RpcResult<u32>;
+
+    /// Takes the SCALE encoded `FullTransactionProof`
+    #[method(name = "btcRelay_isTxIncluded")]
+    fn is_tx_included(&self, proof: Bytes, at: Option<BlockHash>) -> RpcResult<Result<(), DispatchError>>;
 }
 
 fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
@@ -48,12 +74,19 @@ impl<C, B> BtcRelay<C, B> {
 }
 
 #[async_trait]
-impl<C, Block, H256Le> BtcRelayApiServer<<Block as BlockT>::Hash, H256Le> for BtcRelay<C, Block>
+impl<C, Block, H256Le, AccountId, RelayerStats, BlockHeaderInfo, ForkInfo, FullTransactionProof>
+    BtcRelayApiServer<<Block as BlockT>::Hash, H256Le, AccountId, RelayerStats, BlockHeaderInfo, ForkInfo>
+    for BtcRelay<C, Block>
 where
     Block: BlockT,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: BtcRelayRuntimeApi<Block, H256Le>,
+    C::Api: BtcRelayRuntimeApi<Block, H256Le, AccountId, RelayerStats, BlockHeaderInfo, ForkInfo, FullTransactionProof>,
     H256Le: Codec,
+    AccountId: Codec,
+    RelayerStats: Codec,
+    BlockHeaderInfo: Codec,
+    ForkInfo: Codec,
+    FullTransactionProof: Codec,
 {
     fn verify_block_header_inclusion(
         &self,
@@ -66,4 +99,79 @@ where
         api.verify_block_header_inclusion(at, block_hash)
             .map_err(|e| internal_err(format!("execution error: Unable to dry run extrinsic {:?}", e)))
     }
+
+    fn get_relayer_stats(&self, relayer: AccountId, at: Option<<Block as BlockT>::Hash>) -> RpcResult<RelayerStats> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_relayer_stats(at, relayer)
+            .map_err(|e| internal_err(format!("Unable to fetch relayer stats: {:?}", e)))
+    }
+
+    fn get_best_block(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<H256Le> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_best_block(at)
+            .map_err(|e| internal_err(format!("Unable to fetch best block: {:?}", e)))
+    }
+
+    fn get_best_block_height(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_best_block_height(at)
+            .map_err(|e| internal_err(format!("Unable to fetch best block height: {:?}", e)))
+    }
+
+    fn get_block_header(
+        &self,
+        block_hash: H256Le,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BlockHeaderInfo>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_block_header(at, block_hash)
+            .map_err(|e| internal_err(format!("Unable to fetch block header: {:?}", e)))
+    }
+
+    fn get_block_hash(&self, block_height: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<H256Le>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_block_hash(at, block_height)
+            .map_err(|e| internal_err(format!("Unable to fetch block hash: {:?}", e)))
+    }
+
+    fn get_fork_info(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<ForkInfo>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_fork_info(at)
+            .map_err(|e| internal_err(format!("Unable to fetch fork info: {:?}", e)))
+    }
+
+    fn get_stable_confirmations(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_stable_confirmations(at)
+            .map_err(|e| internal_err(format!("Unable to fetch stable confirmations: {:?}", e)))
+    }
+
+    fn is_tx_included(
+        &self,
+        proof: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<(), DispatchError>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let proof = FullTransactionProof::decode(&mut &proof[..])
+            .map_err(|e| internal_err(format!("Unable to decode transaction proof: {:?}", e)))?;
+
+        api.is_tx_included(at, proof)
+            .map_err(|e| internal_err(format!("Unable to check transaction inclusion: {:?}", e)))
+    }
 }
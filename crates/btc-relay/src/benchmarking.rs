@@ -1,8 +1,10 @@
 use super::*;
 use crate::Pallet as BtcRelay;
-use bitcoin::types::{Block, BlockBuilder, H256Le, TransactionBuilder, TransactionInputBuilder};
+use bitcoin::types::{
+    Block, BlockBuilder, H256Le, TransactionBuilder, TransactionInputBuilder, TransactionInputSource,
+};
 use frame_benchmarking::v2::*;
-use frame_support::assert_ok;
+use frame_support::{assert_ok, BoundedVec};
 use frame_system::RawOrigin;
 use sp_core::{H160, U256};
 use sp_std::prelude::*;
@@ -13,7 +15,7 @@ fn initialize_relay<T: Config>(caller: T::AccountId) -> Block {
     let init_block = BlockBuilder::new()
         .with_version(4)
         .with_coinbase(&BtcAddress::P2PKH(H160::from([0; 20])), 50, 3)
-        .with_timestamp(u32::MAX)
+        .with_timestamp(1588813835)
         .mine(U256::from(2).pow(254.into()))
         .unwrap();
     assert_ok!(BtcRelay::<T>::_initialize(caller, init_block.header, 0));
@@ -31,7 +33,7 @@ fn new_block<T: Config>(parent_hash: H256Le, seed: usize) -> Block {
                 .add_input(TransactionInputBuilder::new().with_script(&vec![0; seed]).build())
                 .build(),
         )
-        .with_timestamp(u32::MAX)
+        .with_timestamp(BtcRelay::<T>::get_median_time_past(parent_hash).unwrap() + 1)
         .mine(U256::from(2).pow(254.into()))
         .unwrap()
 }
@@ -54,7 +56,7 @@ pub mod benchmarks {
         let block = BlockBuilder::new()
             .with_version(4)
             .with_coinbase(&BtcAddress::P2PKH(H160::from([0; 20])), 50, 3)
-            .with_timestamp(u32::MAX)
+            .with_timestamp(1588813835)
             .mine(U256::from(2).pow(254.into()))
             .unwrap();
 
@@ -166,5 +168,235 @@ pub mod benchmarks {
         assert_eq!(rich_header.chain_id, MAIN_CHAIN_ID);
     }
 
+    #[benchmark]
+    pub fn store_block_headers(n: Linear<1, MAX_BLOCK_HEADERS_PER_CALL>) {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let init_block = initialize_relay::<T>(caller.clone());
+        let mut prev_block_header = init_block.header;
+        let block_headers: Vec<_> = (0..n)
+            .map(|_| {
+                prev_block_header = BlockBuilder::new()
+                    .with_previous_hash(prev_block_header.hash)
+                    .with_version(4)
+                    .with_coinbase(&BtcAddress::P2PKH(H160::from([0; 20])), 50, 3)
+                    .with_timestamp(prev_block_header.timestamp + 1)
+                    .mine(U256::from(2).pow(254.into()))
+                    .unwrap()
+                    .header;
+                prev_block_header
+            })
+            .collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), block_headers, u32::MAX);
+
+        // make sure all blocks are stored
+        assert_eq!(BestBlockHeight::<T>::get(), n);
+        assert_eq!(BestBlock::<T>::get(), prev_block_header.hash);
+    }
+
+    #[benchmark]
+    pub fn prune_block_header() {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let init_block = initialize_relay::<T>(caller.clone());
+        let block = add_new_block_to_relay::<T>(caller, init_block.header.hash, 0);
+
+        #[block]
+        {
+            BtcRelay::<T>::remove_block(MAIN_CHAIN_ID, 1);
+        }
+
+        // make sure block is removed
+        assert!(!BtcRelay::<T>::block_header_exists(block.header.hash));
+    }
+
+    #[benchmark]
+    pub fn prune_fork(f: Linear<1, 6>) {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let init_block = initialize_relay::<T>(caller.clone());
+        let init_block_hash = init_block.header.hash;
+        add_new_block_to_relay::<T>(caller.clone(), init_block_hash, 0);
+
+        for i in 1..=f {
+            add_new_block_to_relay::<T>(caller.clone(), init_block_hash, i as usize);
+        }
+
+        // the first fork has the highest position among the forks, so all others are moved
+        #[block]
+        {
+            let fork = ChainsIndex::<T>::iter_values()
+                .filter(|chain| chain.chain_id != MAIN_CHAIN_ID)
+                .min_by_key(|chain| chain.chain_id)
+                .unwrap();
+            assert_ok!(BtcRelay::<T>::remove_blockchain(fork.chain_id));
+        }
+
+        // make sure fork is removed
+        assert_eq!(ChainsIndex::<T>::iter().count(), f as usize);
+        assert!(!Chains::<T>::contains_key(f));
+    }
+
+    #[benchmark]
+    pub fn find_prunable_fork(f: Linear<1, 6>) {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let init_block = initialize_relay::<T>(caller.clone());
+        let init_block_hash = init_block.header.hash;
+        add_new_block_to_relay::<T>(caller.clone(), init_block_hash, 0);
+
+        for i in 1..=f {
+            add_new_block_to_relay::<T>(caller.clone(), init_block_hash, i as usize);
+        }
+        // the fork point of every fork is looked up, but none is below the checkpoint
+        assert_ok!(BtcRelay::<T>::_add_checkpoint(init_block.header, 0));
+
+        #[block]
+        {
+            assert!(BtcRelay::<T>::find_prunable_fork(0).is_none());
+        }
+    }
+
+    #[benchmark]
+    pub fn set_header_retention() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, MIN_HEADER_RETENTION);
+
+        assert_eq!(HeaderRetention::<T>::get(), MIN_HEADER_RETENTION);
+    }
+
+    #[benchmark]
+    pub fn set_relayer_incentives() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, 10u32.into(), 5u32.into());
+
+        assert_eq!(RelayerReward::<T>::get(), 10u32.into());
+    }
+
+    #[benchmark]
+    pub fn bond_relayer() {
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, 1_000_000u32.into());
+        HeaderRetention::<T>::put(MIN_HEADER_RETENTION);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), 1_000u32.into());
+
+        assert_eq!(BtcRelay::<T>::relayer_stats(&caller).bond, 1_000u32.into());
+    }
+
+    #[benchmark]
+    pub fn unbond_relayer() {
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, 1_000_000u32.into());
+        HeaderRetention::<T>::put(MIN_HEADER_RETENTION);
+        assert_ok!(BtcRelay::<T>::bond_relayer(
+            RawOrigin::Signed(caller.clone()).into(),
+            1_000u32.into()
+        ));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), 1_000u32.into());
+
+        assert!(BtcRelay::<T>::relayer_stats(&caller).bond.is_zero());
+    }
+
+    #[benchmark]
+    pub fn add_checkpoint() {
+        let caller: T::AccountId = whitelisted_caller();
+        let init_block = initialize_relay::<T>(caller);
+
+        // re-anchoring the main chain above the best block is the worst case
+        let block = new_block::<T>(init_block.header.hash, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, block.header, DIFFICULTY_ADJUSTMENT_INTERVAL);
+
+        assert_eq!(BtcRelay::<T>::get_best_block(), block.header.hash);
+        assert_eq!(
+            LatestCheckpoint::<T>::get(),
+            Some((DIFFICULTY_ADJUSTMENT_INTERVAL, block.header.hash))
+        );
+    }
+
+    #[benchmark]
+    pub fn on_initialize() {
+        initialize_relay::<T>(whitelisted_caller());
+        StalenessThreshold::<T>::put(BlockNumberFor::<T>::from(1u32));
+        let now = frame_system::Pallet::<T>::block_number() + 2u32.into();
+
+        #[block]
+        {
+            BtcRelay::<T>::check_relay_liveness(now);
+        }
+
+        assert!(ext::security::is_relay_stale::<T>());
+    }
+
+    #[benchmark]
+    pub fn set_staleness_threshold() {
+        initialize_relay::<T>(whitelisted_caller());
+        ext::security::set_relay_stale::<T>(true);
+
+        // disabling the check marks the relay as live again
+        #[extrinsic_call]
+        _(RawOrigin::Root, 0u32.into());
+
+        assert!(!ext::security::is_relay_stale::<T>());
+    }
+
+    #[benchmark]
+    pub fn verify_signet_solution(h: Linear<2, 10>, o: Linear<2, 3>, b: Linear<541, 2_048>) {
+        let caller: T::AccountId = whitelisted_caller();
+        let init_block = initialize_relay::<T>(caller);
+        BtcRelay::<T>::set_pow_network(PowNetwork::Signet);
+        // OP_TRUE, which accepts the empty solution
+        SignetChallenge::<T>::put(BoundedVec::try_from(vec![0x51]).unwrap());
+
+        let mut coinbase_builder = TransactionBuilder::new();
+        coinbase_builder.with_version(2).add_input(
+            TransactionInputBuilder::new()
+                .with_source(TransactionInputSource::Coinbase(Some(1)))
+                .build(),
+        );
+        for _ in 0..o {
+            coinbase_builder.add_output(TransactionOutput::payment(50, &BtcAddress::P2PKH(H160::from([0; 20]))));
+        }
+        let mut coinbase = coinbase_builder.build();
+        let padding = (b as usize)
+            .checked_sub(coinbase.size_no_witness())
+            .expect("Wrong length bound in benchmark");
+        coinbase.inputs[0].pad_script(padding);
+
+        let mut block_builder = BlockBuilder::new();
+        block_builder
+            .with_previous_hash(init_block.header.hash)
+            .with_version(4)
+            .with_timestamp(init_block.header.timestamp + 1)
+            .add_transaction(coinbase.clone());
+        for i in 1..2u32.pow(h - 1) {
+            block_builder.add_transaction(
+                TransactionBuilder::new()
+                    .with_version(2)
+                    .add_input(TransactionInputBuilder::new().with_script(&i.to_le_bytes()).build())
+                    .build(),
+            );
+        }
+        let block = block_builder.mine(U256::from(2).pow(254.into())).unwrap();
+        let coinbase_proof = PartialTransactionProof {
+            tx_encoded_len: coinbase.size_no_witness() as u32,
+            merkle_proof: block.merkle_proof(&[coinbase.tx_id()]).unwrap(),
+            transaction: coinbase,
+        };
+        assert_eq!(coinbase_proof.merkle_proof.hashes.len() as u32, h);
+
+        #[block]
+        {
+            assert_ok!(BtcRelay::<T>::verify_signet_block_header(&block.header, coinbase_proof));
+        }
+    }
+
     impl_benchmark_test_suite!(BtcRelay, crate::mock::ExtBuilder::build(), crate::mock::Test);
 }
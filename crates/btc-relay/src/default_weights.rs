@@ -42,6 +42,18 @@ pub trait WeightInfo {
 	fn store_block_header_new_fork_sorted(f: u32, ) -> Weight;
 	fn store_block_header_new_fork_unsorted(f: u32, ) -> Weight;
 	fn store_block_header_reorganize_chains(f: u32, ) -> Weight;
+	fn store_block_headers(n: u32, ) -> Weight;
+	fn prune_block_header() -> Weight;
+	fn prune_fork(f: u32, ) -> Weight;
+	fn set_header_retention() -> Weight;
+	fn set_relayer_incentives() -> Weight;
+	fn bond_relayer() -> Weight;
+	fn unbond_relayer() -> Weight;
+	fn add_checkpoint() -> Weight;
+	fn on_initialize() -> Weight;
+	fn set_staleness_threshold() -> Weight;
+	fn find_prunable_fork(f: u32, ) -> Weight;
+	fn verify_signet_solution(h: u32, o: u32, b: u32, ) -> Weight;
 }
 
 /// Weights for btc_relay using the Substrate node and recommended hardware.
@@ -67,18 +79,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BlockHeaders (r:0 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn initialize() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `4520`
 		// Minimum execution time: 57_676_000 picoseconds.
 		Weight::from_parts(58_347_000, 4520)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
-	/// Storage: BTCRelay ChainCounter (r:1 w:0)
-	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -92,18 +106,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
 	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn store_block_header() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1953`
-		//  Estimated: `11898`
+		//  Estimated: `38648`
 		// Minimum execution time: 65_610_000 picoseconds.
-		Weight::from_parts(66_748_000, 11898)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
+		Weight::from_parts(66_748_000, 38648)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: BTCRelay ChainCounter (r:1 w:1)
 	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:6 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -117,23 +133,27 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
 	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[1, 6]`.
 	fn store_block_header_new_fork_sorted(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1894 + f * (92 ±0)`
-		//  Estimated: `9870 + f * (5006 ±0)`
+		//  Estimated: `36620 + f * (5006 ±0)`
 		// Minimum execution time: 81_320_000 picoseconds.
-		Weight::from_parts(83_847_120, 9870)
+		Weight::from_parts(83_847_120, 36620)
 			// Standard Error: 384_640
 			.saturating_add(Weight::from_parts(11_883_224, 0).saturating_mul(f.into()))
-			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads(19_u64))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(f.into())))
-			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 			.saturating_add(Weight::from_parts(0, 5006).saturating_mul(f.into()))
 	}
 	/// Storage: BTCRelay ChainCounter (r:1 w:1)
 	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:2 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -147,24 +167,28 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
 	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[1, 6]`.
 	fn store_block_header_new_fork_unsorted(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1973 + f * (98 ±0)`
-		//  Estimated: `12911 + f * (2987 ±22)`
+		//  Estimated: `39661 + f * (2987 ±22)`
 		// Minimum execution time: 84_731_000 picoseconds.
-		Weight::from_parts(116_733_612, 12911)
+		Weight::from_parts(116_733_612, 39661)
 			// Standard Error: 720_816
 			.saturating_add(Weight::from_parts(5_329_595, 0).saturating_mul(f.into()))
-			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads(20_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(f.into())))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(f.into())))
 			.saturating_add(Weight::from_parts(0, 2987).saturating_mul(f.into()))
 	}
-	/// Storage: BTCRelay ChainCounter (r:1 w:0)
-	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:20 w:18)
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:30 w:18)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:3 w:2)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -182,20 +206,252 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
 	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[3, 6]`.
 	fn store_block_header_reorganize_chains(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6534 + f * (199 ±0)`
-		//  Estimated: `101752 + f * (1305 ±32)`
+		//  Estimated: `128502 + f * (1305 ±32)`
 		// Minimum execution time: 363_791_000 picoseconds.
-		Weight::from_parts(341_578_042, 101752)
+		Weight::from_parts(341_578_042, 128502)
 			// Standard Error: 162_430
 			.saturating_add(Weight::from_parts(9_039_414, 0).saturating_mul(f.into()))
-			.saturating_add(T::DbWeight::get().reads(42_u64))
+			.saturating_add(T::DbWeight::get().reads(53_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(f.into())))
 			.saturating_add(T::DbWeight::get().writes(46_u64))
 			.saturating_add(Weight::from_parts(0, 1305).saturating_mul(f.into()))
 	}
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:100)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableDifficultyCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:100 w:100)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:0 w:1)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn store_block_headers(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1953 + n * (117 ±0)`
+		//  Estimated: `11898 + n * (29429 ±0)`
+		// Minimum execution time: 66_102_000 picoseconds.
+		Weight::from_parts(67_012_000, 11898)
+			// Standard Error: 41_209
+			.saturating_add(Weight::from_parts(29_480_115, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 29429).saturating_mul(n.into()))
+	}
+	/// Storage: BTCRelay ChainsHashes (r:1 w:1)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:0 w:1)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockRelayers (r:1 w:1)
+	/// Proof: BTCRelay BlockRelayers (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn prune_block_header() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `517`
+		//  Estimated: `6170`
+		// Minimum execution time: 40_612_000 picoseconds.
+		Weight::from_parts(41_385_000, 6170)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: BTCRelay ChainsIndex (r:6 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay Chains (r:6 w:6)
+	/// Proof: BTCRelay Chains (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// The range of component `f` is `[1, 6]`.
+	fn prune_fork(f: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262 + f * (101 ±0)`
+		//  Estimated: `3497 + f * (5010 ±0)`
+		// Minimum execution time: 21_574_000 picoseconds.
+		Weight::from_parts(17_883_402, 3497)
+			// Standard Error: 31_870
+			.saturating_add(Weight::from_parts(6_415_219, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(f.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 5010).saturating_mul(f.into()))
+	}
+	/// Storage: BTCRelay HeaderRetention (r:0 w:1)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_header_retention() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_310_000 picoseconds.
+		Weight::from_parts(5_592_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: BTCRelay RelayerReward (r:0 w:1)
+	/// Proof: BTCRelay RelayerReward (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: BTCRelay OrphanedHeaderSlash (r:0 w:1)
+	/// Proof: BTCRelay OrphanedHeaderSlash (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn set_relayer_incentives() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_104_000 picoseconds.
+		Weight::from_parts(6_398_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderRetention (r:1 w:0)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn bond_relayer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `239`
+		//  Estimated: `3580`
+		// Minimum execution time: 31_522_000 picoseconds.
+		Weight::from_parts(32_190_000, 3580)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderRetention (r:1 w:0)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn unbond_relayer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3580`
+		// Minimum execution time: 34_871_000 picoseconds.
+		Weight::from_parts(35_630_000, 3580)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: BTCRelay LatestCheckpoint (r:1 w:1)
+	/// Proof: BTCRelay LatestCheckpoint (max_values: Some(1), max_size: Some(36), added: 531, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:1 w:1)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:1)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableDifficultyCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay Checkpoints (r:0 w:1)
+	/// Proof: BTCRelay Checkpoints (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn add_checkpoint() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1120`
+		//  Estimated: `6340`
+		// Minimum execution time: 48_315_000 picoseconds.
+		Weight::from_parts(49_204_000, 6340)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: BTCRelay StalenessThreshold (r:1 w:0)
+	/// Proof: BTCRelay StalenessThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:1 w:0)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Security IsRelayStale (r:1 w:1)
+	/// Proof: Security IsRelayStale (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockUpdatedAt (r:1 w:0)
+	/// Proof: BTCRelay BestBlockUpdatedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn on_initialize() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `1489`
+		// Minimum execution time: 14_782_000 picoseconds.
+		Weight::from_parts(15_310_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Security IsRelayStale (r:1 w:1)
+	/// Proof: Security IsRelayStale (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay StalenessThreshold (r:1 w:1)
+	/// Proof: BTCRelay StalenessThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockUpdatedAt (r:0 w:1)
+	/// Proof: BTCRelay BestBlockUpdatedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_staleness_threshold() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `214`
+		//  Estimated: `1489`
+		// Minimum execution time: 13_045_000 picoseconds.
+		Weight::from_parts(13_532_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: BTCRelay ChainsIndex (r:7 w:0)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay LatestCheckpoint (r:1 w:0)
+	/// Proof: BTCRelay LatestCheckpoint (max_values: Some(1), max_size: Some(36), added: 531, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:6 w:0)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:0)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// The range of component `f` is `[1, 6]`.
+	fn find_prunable_fork(f: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187 + f * (412 ±0)`
+		//  Estimated: `6004 + f * (7897 ±0)`
+		// Minimum execution time: 24_318_000 picoseconds.
+		Weight::from_parts(14_902_117, 6004)
+			// Standard Error: 42_583
+			.saturating_add(Weight::from_parts(11_287_406, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 7897).saturating_mul(f.into()))
+	}
+	/// Storage: BTCRelay SignetChallenge (r:1 w:0)
+	/// Proof: BTCRelay SignetChallenge (max_values: Some(1), max_size: Some(10002), added: 10497, mode: MaxEncodedLen)
+	/// The range of component `h` is `[2, 10]`.
+	/// The range of component `o` is `[2, 3]`.
+	/// The range of component `b` is `[541, 2048]`.
+	fn verify_signet_solution(h: u32, o: u32, b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1089`
+		//  Estimated: `11487`
+		// Minimum execution time: 98_214_000 picoseconds.
+		Weight::from_parts(91_538_000, 11487)
+			// Standard Error: 39_471
+			.saturating_add(Weight::from_parts(2_384_017, 0).saturating_mul(h.into()))
+			// Standard Error: 213_854
+			.saturating_add(Weight::from_parts(3_754_260, 0).saturating_mul(o.into()))
+			// Standard Error: 218
+			.saturating_add(Weight::from_parts(4_871, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -220,18 +476,20 @@ impl WeightInfo for () {
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BlockHeaders (r:0 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn initialize() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `4520`
 		// Minimum execution time: 57_676_000 picoseconds.
 		Weight::from_parts(58_347_000, 4520)
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
-			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
-	/// Storage: BTCRelay ChainCounter (r:1 w:0)
-	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -245,18 +503,20 @@ impl WeightInfo for () {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
 	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn store_block_header() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1953`
-		//  Estimated: `11898`
+		//  Estimated: `38648`
 		// Minimum execution time: 65_610_000 picoseconds.
-		Weight::from_parts(66_748_000, 11898)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
+		Weight::from_parts(66_748_000, 38648)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: BTCRelay ChainCounter (r:1 w:1)
 	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:6 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -270,23 +530,27 @@ impl WeightInfo for () {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
 	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[1, 6]`.
 	fn store_block_header_new_fork_sorted(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1894 + f * (92 ±0)`
-		//  Estimated: `9870 + f * (5006 ±0)`
+		//  Estimated: `36620 + f * (5006 ±0)`
 		// Minimum execution time: 81_320_000 picoseconds.
-		Weight::from_parts(83_847_120, 9870)
+		Weight::from_parts(83_847_120, 36620)
 			// Standard Error: 384_640
 			.saturating_add(Weight::from_parts(11_883_224, 0).saturating_mul(f.into()))
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
 			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(f.into())))
-			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
 			.saturating_add(Weight::from_parts(0, 5006).saturating_mul(f.into()))
 	}
 	/// Storage: BTCRelay ChainCounter (r:1 w:1)
 	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:2 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -300,24 +564,28 @@ impl WeightInfo for () {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
 	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[1, 6]`.
 	fn store_block_header_new_fork_unsorted(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1973 + f * (98 ±0)`
-		//  Estimated: `12911 + f * (2987 ±22)`
+		//  Estimated: `39661 + f * (2987 ±22)`
 		// Minimum execution time: 84_731_000 picoseconds.
-		Weight::from_parts(116_733_612, 12911)
+		Weight::from_parts(116_733_612, 39661)
 			// Standard Error: 720_816
 			.saturating_add(Weight::from_parts(5_329_595, 0).saturating_mul(f.into()))
-			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(f.into())))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(f.into())))
 			.saturating_add(Weight::from_parts(0, 2987).saturating_mul(f.into()))
 	}
-	/// Storage: BTCRelay ChainCounter (r:1 w:0)
-	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:20 w:18)
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:30 w:18)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:3 w:2)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -335,18 +603,250 @@ impl WeightInfo for () {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
 	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[3, 6]`.
 	fn store_block_header_reorganize_chains(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6534 + f * (199 ±0)`
-		//  Estimated: `101752 + f * (1305 ±32)`
+		//  Estimated: `128502 + f * (1305 ±32)`
 		// Minimum execution time: 363_791_000 picoseconds.
-		Weight::from_parts(341_578_042, 101752)
+		Weight::from_parts(341_578_042, 128502)
 			// Standard Error: 162_430
 			.saturating_add(Weight::from_parts(9_039_414, 0).saturating_mul(f.into()))
-			.saturating_add(RocksDbWeight::get().reads(42_u64))
+			.saturating_add(RocksDbWeight::get().reads(43_u64))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(f.into())))
 			.saturating_add(RocksDbWeight::get().writes(46_u64))
 			.saturating_add(Weight::from_parts(0, 1305).saturating_mul(f.into()))
 	}
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:100)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableDifficultyCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:100 w:100)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:0 w:1)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn store_block_headers(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1953 + n * (117 ±0)`
+		//  Estimated: `11898 + n * (29429 ±0)`
+		// Minimum execution time: 66_102_000 picoseconds.
+		Weight::from_parts(67_012_000, 11898)
+			// Standard Error: 41_209
+			.saturating_add(Weight::from_parts(29_480_115, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 29429).saturating_mul(n.into()))
+	}
+	/// Storage: BTCRelay ChainsHashes (r:1 w:1)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:0 w:1)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockRelayers (r:1 w:1)
+	/// Proof: BTCRelay BlockRelayers (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn prune_block_header() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `517`
+		//  Estimated: `6170`
+		// Minimum execution time: 40_612_000 picoseconds.
+		Weight::from_parts(41_385_000, 6170)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: BTCRelay ChainsIndex (r:6 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay Chains (r:6 w:6)
+	/// Proof: BTCRelay Chains (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// The range of component `f` is `[1, 6]`.
+	fn prune_fork(f: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262 + f * (101 ±0)`
+		//  Estimated: `3497 + f * (5010 ±0)`
+		// Minimum execution time: 21_574_000 picoseconds.
+		Weight::from_parts(17_883_402, 3497)
+			// Standard Error: 31_870
+			.saturating_add(Weight::from_parts(6_415_219, 0).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(f.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 5010).saturating_mul(f.into()))
+	}
+	/// Storage: BTCRelay HeaderRetention (r:0 w:1)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_header_retention() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_310_000 picoseconds.
+		Weight::from_parts(5_592_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: BTCRelay RelayerReward (r:0 w:1)
+	/// Proof: BTCRelay RelayerReward (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: BTCRelay OrphanedHeaderSlash (r:0 w:1)
+	/// Proof: BTCRelay OrphanedHeaderSlash (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn set_relayer_incentives() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_104_000 picoseconds.
+		Weight::from_parts(6_398_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderRetention (r:1 w:0)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn bond_relayer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `239`
+		//  Estimated: `3580`
+		// Minimum execution time: 31_522_000 picoseconds.
+		Weight::from_parts(32_190_000, 3580)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderRetention (r:1 w:0)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn unbond_relayer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3580`
+		// Minimum execution time: 34_871_000 picoseconds.
+		Weight::from_parts(35_630_000, 3580)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: BTCRelay LatestCheckpoint (r:1 w:1)
+	/// Proof: BTCRelay LatestCheckpoint (max_values: Some(1), max_size: Some(36), added: 531, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:1 w:1)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:1)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableDifficultyCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay Checkpoints (r:0 w:1)
+	/// Proof: BTCRelay Checkpoints (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn add_checkpoint() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1120`
+		//  Estimated: `6340`
+		// Minimum execution time: 48_315_000 picoseconds.
+		Weight::from_parts(49_204_000, 6340)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: BTCRelay StalenessThreshold (r:1 w:0)
+	/// Proof: BTCRelay StalenessThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:1 w:0)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Security IsRelayStale (r:1 w:1)
+	/// Proof: Security IsRelayStale (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockUpdatedAt (r:1 w:0)
+	/// Proof: BTCRelay BestBlockUpdatedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn on_initialize() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `1489`
+		// Minimum execution time: 14_782_000 picoseconds.
+		Weight::from_parts(15_310_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Security IsRelayStale (r:1 w:1)
+	/// Proof: Security IsRelayStale (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay StalenessThreshold (r:1 w:1)
+	/// Proof: BTCRelay StalenessThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockUpdatedAt (r:0 w:1)
+	/// Proof: BTCRelay BestBlockUpdatedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_staleness_threshold() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `214`
+		//  Estimated: `1489`
+		// Minimum execution time: 13_045_000 picoseconds.
+		Weight::from_parts(13_532_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: BTCRelay ChainsIndex (r:7 w:0)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay LatestCheckpoint (r:1 w:0)
+	/// Proof: BTCRelay LatestCheckpoint (max_values: Some(1), max_size: Some(36), added: 531, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:6 w:0)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:0)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// The range of component `f` is `[1, 6]`.
+	fn find_prunable_fork(f: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187 + f * (412 ±0)`
+		//  Estimated: `6004 + f * (7897 ±0)`
+		// Minimum execution time: 24_318_000 picoseconds.
+		Weight::from_parts(14_902_117, 6004)
+			// Standard Error: 42_583
+			.saturating_add(Weight::from_parts(11_287_406, 0).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 7897).saturating_mul(f.into()))
+	}
+	/// Storage: BTCRelay SignetChallenge (r:1 w:0)
+	/// Proof: BTCRelay SignetChallenge (max_values: Some(1), max_size: Some(10002), added: 10497, mode: MaxEncodedLen)
+	/// The range of component `h` is `[2, 10]`.
+	/// The range of component `o` is `[2, 3]`.
+	/// The range of component `b` is `[541, 2048]`.
+	fn verify_signet_solution(h: u32, o: u32, b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1089`
+		//  Estimated: `11487`
+		// Minimum execution time: 98_214_000 picoseconds.
+		Weight::from_parts(91_538_000, 11487)
+			// Standard Error: 39_471
+			.saturating_add(Weight::from_parts(2_384_017, 0).saturating_mul(h.into()))
+			// Standard Error: 213_854
+			.saturating_add(Weight::from_parts(3_754_260, 0).saturating_mul(o.into()))
+			// Standard Error: 218
+			.saturating_add(Weight::from_parts(4_871, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
 }
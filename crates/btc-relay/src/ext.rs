@@ -15,6 +15,14 @@ pub(crate) mod security {
         <security::Pallet<T>>::active_block_number()
     }
 
+    pub fn is_relay_stale<T: crate::Config>() -> bool {
+        <security::Pallet<T>>::is_relay_stale()
+    }
+
+    pub fn set_relay_stale<T: crate::Config>(is_stale: bool) {
+        <security::Pallet<T>>::set_relay_stale(is_stale)
+    }
+
     pub fn parachain_block_expired<T: crate::Config>(
         opentime: BlockNumberFor<T>,
         period: BlockNumberFor<T>,
@@ -3,7 +3,7 @@
 //! Based on the [specification](https://spec.interlay.io/spec/btc-relay/index.html).
 //!
 //! This pallet implements a Bitcoin light client to store and verify block headers in accordance
-//! with SPV assumptions - i.e. longest chain.
+//! with SPV assumptions - i.e. the chain with the most accumulated proof-of-work.
 //!
 //! Unless otherwise stated, the primary source of truth for code contained herein is the
 //! [Bitcoin Core repository](https://github.com/bitcoin/bitcoin), though implementation
@@ -39,6 +39,8 @@ mod benchmarking;
 mod default_weights;
 pub use default_weights::WeightInfo;
 
+pub mod migration;
+
 #[cfg(test)]
 mod tests;
 
@@ -55,41 +57,52 @@ use mocktopus::macros::mockable;
 use bitcoin::types::{BlockBuilder, TransactionBuilder, TransactionOutput};
 use bitcoin::{
     merkle::ProofResult,
+    pow::PowNetwork,
     types::{BlockChain, BlockHeader, H256Le, Transaction, Value},
-    Error as BitcoinError, SetCompact,
+    Error as BitcoinError, GetCompact, SetCompact,
 };
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure, runtime_print,
-    traits::Get,
+    traits::{Currency, ExistenceRequirement, Get, Imbalance, ReservableCurrency},
     transactional,
+    weights::Weight,
 };
-use frame_system::{ensure_signed, pallet_prelude::BlockNumberFor};
+use frame_system::{ensure_root, ensure_signed, pallet_prelude::BlockNumberFor};
 use sp_core::{H256, U256};
-use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, One};
+use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, One, Saturating, UniqueSaturatedInto, Zero};
 use sp_std::{
     convert::{TryFrom, TryInto},
     prelude::*,
 };
 
 pub use bitcoin::{
-    self, merkle::PartialTransactionProof, types::FullTransactionProof, Address as BtcAddress,
-    PublicKey as BtcPublicKey,
+    self,
+    merkle::PartialTransactionProof,
+    types::{FullTransactionProof, WitnessTransactionProof},
+    Address as BtcAddress, PublicKey as BtcPublicKey,
 };
 pub use pallet::*;
+pub use primitives::btc_relay::{BlockHeaderInfo, ForkInfo, RelayerStats};
 pub use types::{OpReturnPaymentData, RichBlockHeader};
 
+pub(crate) type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + security::Config {
+    pub trait Config: frame_system::Config + pallet_timestamp::Config + security::Config {
         /// The overarching event type.
         type RuntimeEvent: From<Event<Self>>
             + Into<<Self as frame_system::Config>::RuntimeEvent>
@@ -100,10 +113,33 @@ pub mod pallet {
 
         #[pallet::constant]
         type ParachainBlocksPerBitcoinBlock: Get<BlockNumberFor<Self>>;
+
+        /// The currency in which relayers are rewarded and bonded.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// The account from which relayer rewards are paid and to which slashed bonds are moved.
+        #[pallet::constant]
+        type RelayerRewardPot: Get<Self::AccountId>;
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::check_relay_liveness(n);
+            <T as Config>::WeightInfo::on_initialize()
+        }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            // headers are only pruned once they are all migrated to the current layout
+            let read_weight = T::DbWeight::get().reads(1);
+            let remaining_weight = remaining_weight.saturating_sub(read_weight);
+            let used_weight = match Self::header_migration_height() {
+                Some(block_height) => migration::v1::migrate_block_headers::<T>(block_height, remaining_weight),
+                None => Self::prune_block_headers(remaining_weight),
+            };
+            used_weight.saturating_add(read_weight)
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -146,13 +182,7 @@ pub mod pallet {
         /// - `O(F)` where `F` is the number of forks
         #[pallet::call_index(1)]
         #[pallet::weight((
-            {
-                let f = *fork_bound;
-                <T as Config>::WeightInfo::store_block_header()
-                    .max(<T as Config>::WeightInfo::store_block_header_new_fork_sorted(f))
-                    .max(<T as Config>::WeightInfo::store_block_header_new_fork_unsorted(f))
-                    .max(<T as Config>::WeightInfo::store_block_header_reorganize_chains(f))
-            },
+            store_block_header_weight::<T>(*fork_bound),
             DispatchClass::Operational
         ))]
         #[transactional]
@@ -164,16 +194,243 @@ pub mod pallet {
             let relayer = ensure_signed(origin)?;
 
             // the worst-case complexity is always dictated by the number of chains
-            // TODO: as the growth of `Chains` is unbounded this extrinsic may become
-            // prohibitively expensive, we should remove old forks from storage
             ensure!(
-                // ideally we would compare the number of entries in `Chains` here but
-                // since we never delete from that this should be equal to the length
-                Self::get_chain_counter().saturating_add(1) <= fork_bound,
+                Self::get_chains_count().saturating_add(1) <= fork_bound,
+                Error::<T>::WrongForkBound
+            );
+            ensure!(
+                Self::pow_network() != PowNetwork::Signet,
+                Error::<T>::SignetCoinbaseProofRequired
+            );
+
+            Self::_validate_block_header(&mut block_header)?;
+            Self::_store_block_header(&relayer, block_header)?;
+
+            // don't take tx fees on success
+            Ok(Pays::No.into())
+        }
+
+        /// Stores a contiguous run of new block headers, each extending the previous one.
+        /// Headers are stored up to the first invalid header, which is reported in a
+        /// `BlockHeaderRejected` event. The call fails if the first header is invalid or
+        /// if any header is malformed.
+        ///
+        /// # Arguments
+        ///
+        /// * `block_headers` - Bitcoin block headers, at most `MAX_BLOCK_HEADERS_PER_CALL`.
+        ///
+        /// ## Complexity
+        /// - `O(N + F)` where `N` is the number of headers and `F` is the number of forks
+        #[pallet::call_index(2)]
+        #[pallet::weight((
+            store_block_headers_weight::<T>(block_headers.len() as u32, *fork_bound),
+            DispatchClass::Operational
+        ))]
+        #[transactional]
+        pub fn store_block_headers(
+            origin: OriginFor<T>,
+            mut block_headers: Vec<BlockHeader>,
+            fork_bound: u32,
+        ) -> DispatchResultWithPostInfo {
+            let relayer = ensure_signed(origin)?;
+
+            ensure!(
+                block_headers.len() <= MAX_BLOCK_HEADERS_PER_CALL as usize,
+                Error::<T>::TooManyBlockHeaders
+            );
+            ensure!(
+                Self::get_chains_count().saturating_add(1) <= fork_bound,
+                Error::<T>::WrongForkBound
+            );
+            ensure!(
+                Self::pow_network() != PowNetwork::Signet,
+                Error::<T>::SignetCoinbaseProofRequired
+            );
+
+            for block_header in block_headers.iter_mut() {
+                Self::_validate_block_header(block_header)?;
+            }
+            let results = Self::_store_block_headers(&relayer, &block_headers)?;
+            if let Some(Err(error)) = results.last() {
+                let index = results.len() - 1;
+                // nothing was stored, so fail the whole call
+                ensure!(index > 0, *error);
+                Self::deposit_event(Event::<T>::BlockHeaderRejected {
+                    index: index as u32,
+                    block_hash: block_headers[index].hash,
+                    error: *error,
+                });
+            }
+
+            // don't take tx fees on success
+            Ok((
+                Some(store_block_headers_weight::<T>(results.len() as u32, fork_bound)),
+                Pays::No,
+            )
+                .into())
+        }
+
+        /// Sets the number of main chain blocks behind the best block whose headers are retained.
+        /// Older headers are pruned, except for the retarget headers at difficulty adjustment boundaries.
+        ///
+        /// # Arguments
+        ///
+        /// * `header_retention` - the retention window, at least `MIN_HEADER_RETENTION`, or zero
+        /// to disable pruning.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_header_retention())]
+        #[transactional]
+        pub fn set_header_retention(origin: OriginFor<T>, header_retention: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                header_retention == 0 || header_retention >= MIN_HEADER_RETENTION,
+                Error::<T>::InvalidHeaderRetention
+            );
+            HeaderRetention::<T>::put(header_retention);
+            Ok(())
+        }
+
+        /// Sets the reward paid per header that extends the main chain, and the amount slashed
+        /// from the bond of a relayer per submitted header that is pruned on an abandoned fork.
+        ///
+        /// # Arguments
+        ///
+        /// * `reward` - the reward per main chain header, zero to disable rewards
+        /// * `slash` - the slash per orphaned header
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_relayer_incentives())]
+        #[transactional]
+        pub fn set_relayer_incentives(
+            origin: OriginFor<T>,
+            reward: BalanceOf<T>,
+            slash: BalanceOf<T>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            RelayerReward::<T>::put(reward);
+            OrphanedHeaderSlash::<T>::put(slash);
+            Ok(())
+        }
+
+        /// Reserves an additional bond for the relayer. The headers submitted while bonded are
+        /// slashed if they end up on an abandoned fork. Bonding requires a header retention
+        /// window, since the bond stays locked until the submitted headers are pruned.
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - the amount to add to the bond
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::bond_relayer())]
+        #[transactional]
+        pub fn bond_relayer(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let relayer = ensure_signed(origin)?;
+            ensure!(Self::header_retention() != 0, Error::<T>::RelayerBondingDisabled);
+            T::Currency::reserve(&relayer, amount)?;
+            RelayerStatistics::<T>::mutate(&relayer, |stats| stats.bond.saturating_accrue(amount));
+            Self::deposit_event(Event::<T>::RelayerBonded {
+                relayer_id: relayer,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Releases (part of) the bond of the relayer, once all headers it submitted while bonded
+        /// have been pruned, i.e. once every fork containing them has been resolved.
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - the amount to release from the bond
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::unbond_relayer())]
+        #[transactional]
+        pub fn unbond_relayer(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let relayer = ensure_signed(origin)?;
+            let mut stats = Self::relayer_stats(&relayer);
+            ensure!(amount <= stats.bond, Error::<T>::InsufficientRelayerBond);
+            ensure!(stats.pending_headers == 0, Error::<T>::RelayerBondLocked);
+            T::Currency::unreserve(&relayer, amount);
+            stats.bond.saturating_reduce(amount);
+            RelayerStatistics::<T>::insert(&relayer, stats);
+            Self::deposit_event(Event::<T>::RelayerUnbonded {
+                relayer_id: relayer,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Registers a known-good block as checkpoint. Headers conflicting with the checkpoint are
+        /// rejected, and forks can no longer diverge from the main chain at or below it. A block
+        /// above the current best block re-anchors the main chain, e.g. after a long outage.
+        ///
+        /// # Arguments
+        ///
+        /// * `block_header` - the header of the checkpoint block
+        /// * `block_height` - the height of the checkpoint block
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_checkpoint())]
+        #[transactional]
+        pub fn add_checkpoint(origin: OriginFor<T>, block_header: BlockHeader, block_height: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            let mut block_header = block_header;
+            Self::_validate_block_header(&mut block_header)?;
+            Self::_add_checkpoint(block_header, block_height)
+        }
+
+        /// Sets the number of parachain blocks without a new best block after which the relay is
+        /// considered stale. While stale, new issue, redeem and replace requests are rejected and
+        /// the expiry of open requests is paused. Zero disables the check, enabling it starts the
+        /// count at the current parachain block.
+        ///
+        /// # Arguments
+        ///
+        /// * `staleness_threshold` - the number of parachain blocks
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_staleness_threshold())]
+        #[transactional]
+        pub fn set_staleness_threshold(origin: OriginFor<T>, staleness_threshold: BlockNumberFor<T>) -> DispatchResult {
+            ensure_root(origin)?;
+            let was_disabled = Self::staleness_threshold().is_zero();
+            StalenessThreshold::<T>::put(staleness_threshold);
+            if staleness_threshold.is_zero() {
+                Self::mark_relay_live();
+            } else if was_disabled {
+                // the best block may not have been updated since before the check was enabled
+                BestBlockUpdatedAt::<T>::put(frame_system::Pallet::<T>::block_number());
+            }
+            Ok(())
+        }
+
+        /// Stores a single new block header of a signet, which is taken from the merkle proof of
+        /// its coinbase transaction. Since anyone can mine blocks at the signet difficulty, the
+        /// coinbase transaction must carry a solution to the signet challenge, and signet headers
+        /// cannot be stored with `store_block_header` or `store_block_headers`.
+        ///
+        /// # Arguments
+        ///
+        /// * `coinbase_proof` - the coinbase transaction and its merkle proof, including the block header
+        ///
+        /// ## Complexity
+        /// - `O(F + P)` where `F` is the number of forks and `P` is the size of the coinbase proof
+        #[pallet::call_index(9)]
+        #[pallet::weight((
+            store_signet_block_header_weight::<T>(coinbase_proof, *fork_bound),
+            DispatchClass::Operational
+        ))]
+        #[transactional]
+        pub fn store_signet_block_header(
+            origin: OriginFor<T>,
+            coinbase_proof: PartialTransactionProof,
+            fork_bound: u32,
+        ) -> DispatchResultWithPostInfo {
+            let relayer = ensure_signed(origin)?;
+
+            ensure!(
+                Self::get_chains_count().saturating_add(1) <= fork_bound,
                 Error::<T>::WrongForkBound
             );
 
+            let mut block_header = coinbase_proof.merkle_proof.block_header;
             Self::_validate_block_header(&mut block_header)?;
+            Self::verify_signet_block_header(&block_header, coinbase_proof)?;
             Self::_store_block_header(&relayer, block_header)?;
 
             // don't take tx fees on success
@@ -210,6 +467,41 @@ pub mod pallet {
             fork_height: u32,
             fork_id: u32,
         },
+        BlockHeaderRejected {
+            index: u32,
+            block_hash: H256Le,
+            error: DispatchError,
+        },
+        RelayerRewarded {
+            relayer_id: T::AccountId,
+            block_hash: H256Le,
+            amount: BalanceOf<T>,
+        },
+        RelayerSlashed {
+            relayer_id: T::AccountId,
+            block_hash: H256Le,
+            amount: BalanceOf<T>,
+        },
+        RelayerBonded {
+            relayer_id: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        RelayerUnbonded {
+            relayer_id: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        CheckpointAdded {
+            block_height: u32,
+            block_hash: H256Le,
+            reanchored: bool,
+        },
+        RelayStale {
+            best_block_height: u32,
+            last_update: BlockNumberFor<T>,
+        },
+        RelayRecovered {
+            best_block_height: u32,
+        },
     }
 
     #[pallet::error]
@@ -318,6 +610,50 @@ pub mod pallet {
         BoundExceeded,
         /// Coinbase tx must be the first transaction in the block
         InvalidCoinbasePosition,
+        /// Transaction witness hash does not match the witness merkle proof
+        InvalidWtxid,
+        /// Witness data is not committed to by the coinbase transaction
+        InvalidWitnessCommitment,
+        /// Transaction does not pay the minimum fee rate
+        InsufficientFeeRate,
+        /// Transaction cannot be included in the next block because of its lock time
+        TransactionNotFinal,
+        /// Block does not satisfy the signet challenge
+        InvalidSignetSolution,
+        /// Block timestamp is not later than the median time past of the previous blocks
+        BlockTimestampTooOld,
+        /// Block timestamp is too far ahead of the parachain time
+        BlockTimestampTooNew,
+        /// Too many block headers submitted at once
+        TooManyBlockHeaders,
+        /// Block header does not extend the previous block header of the batch
+        NonContiguousBlockHeaders,
+        /// Header retention window is too short to verify new blocks
+        InvalidHeaderRetention,
+        /// Block is older than the header retention window and has been pruned
+        BlockPruned,
+        /// Amount exceeds the bond of the relayer
+        InsufficientRelayerBond,
+        /// Relayer has submitted headers which have not been pruned yet
+        RelayerBondLocked,
+        /// Block header conflicts with a checkpoint
+        CheckpointConflict,
+        /// Fork diverges from the main chain at or below the latest checkpoint
+        ForkBelowCheckpoint,
+        /// Checkpoint must be higher than the latest checkpoint
+        CheckpointHeightTooLow,
+        /// Checkpoint is stored on a fork or conflicts with the main chain
+        CheckpointNotOnMainChain,
+        /// BTC-Relay has not been updated within the staleness threshold
+        RelayStale,
+        /// Timestamp of the first block of a difficulty period is too far before the previous block
+        TimewarpAttack,
+        /// Relayers cannot bond while headers are never pruned
+        RelayerBondingDisabled,
+        /// Block headers are still being migrated to the current storage layout
+        BlockHeadersMigrating,
+        /// Signet block headers must be submitted with the proof of their coinbase transaction
+        SignetCoinbaseProofRequired,
     }
 
     /// Store Bitcoin block headers
@@ -329,7 +665,6 @@ pub mod pallet {
     /// The first index into this mapping (0) is considered to be the longest chain. The value
     /// of the entry is the index into `ChainsIndex` to retrieve the `BlockChain`.
     #[pallet::storage]
-    // TODO: migrate this to sorted vec
     pub(super) type Chains<T: Config> = StorageMap<_, Blake2_128Concat, u32, u32>;
 
     /// Auxiliary mapping of chains ids to `BlockChain` entries. The first index into this
@@ -358,6 +693,10 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type ChainCounter<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+    /// Number of entries in `Chains`, i.e. the main chain and the forks that were not pruned
+    #[pallet::storage]
+    pub(super) type ChainsCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
     /// Global security parameter k for stable Bitcoin transactions
     #[pallet::storage]
     #[pallet::getter(fn bitcoin_confirmations)]
@@ -378,6 +717,75 @@ pub mod pallet {
     #[pallet::getter(fn disable_inclusion_check)]
     pub(super) type DisableInclusionCheck<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    /// The proof-of-work rules of the relayed network.
+    #[pallet::storage]
+    #[pallet::getter(fn pow_network)]
+    pub(super) type ProofOfWorkNetwork<T: Config> = StorageValue<_, PowNetwork, ValueQuery>;
+
+    /// The script which signet blocks must satisfy, if the relayed network is a signet.
+    #[pallet::storage]
+    #[pallet::getter(fn signet_challenge)]
+    pub(super) type SignetChallenge<T: Config> =
+        StorageValue<_, BoundedVec<u8, ConstU32<MAX_SIGNET_CHALLENGE_SIZE>>, OptionQuery>;
+
+    /// Number of main chain blocks behind the best block whose headers are retained, in addition
+    /// to the retarget headers at difficulty adjustment boundaries. Zero disables pruning.
+    #[pallet::storage]
+    #[pallet::getter(fn header_retention)]
+    pub(super) type HeaderRetention<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Main chain height below which all headers except the retarget headers have been pruned.
+    #[pallet::storage]
+    pub(super) type PrunedBlockHeight<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Reward paid to the relayer of each header that extends the main chain.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_reward)]
+    pub(super) type RelayerReward<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Amount slashed from the bond of a relayer per submitted header that is pruned on an
+    /// abandoned fork.
+    #[pallet::storage]
+    #[pallet::getter(fn orphaned_header_slash)]
+    pub(super) type OrphanedHeaderSlash<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Submission statistics and bond of each relayer.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_stats)]
+    pub(super) type RelayerStatistics<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, RelayerStats<BalanceOf<T>>, ValueQuery>;
+
+    /// Governance-set block hashes by height which the relayed chains must contain.
+    #[pallet::storage]
+    #[pallet::getter(fn checkpoint)]
+    pub(super) type Checkpoints<T: Config> = StorageMap<_, Blake2_128Concat, u32, H256Le, OptionQuery>;
+
+    /// The height and hash of the highest checkpoint.
+    #[pallet::storage]
+    #[pallet::getter(fn latest_checkpoint)]
+    pub(super) type LatestCheckpoint<T: Config> = StorageValue<_, (u32, H256Le), OptionQuery>;
+
+    /// Number of parachain blocks without a new best block after which the relay is considered
+    /// stale. Zero disables the check.
+    #[pallet::storage]
+    #[pallet::getter(fn staleness_threshold)]
+    pub(super) type StalenessThreshold<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// The parachain block at which the best block was last updated.
+    #[pallet::storage]
+    #[pallet::getter(fn best_block_updated_at)]
+    pub(super) type BestBlockUpdatedAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// The relayer of each stored block header which was submitted while bonded.
+    #[pallet::storage]
+    pub(super) type BlockRelayers<T: Config> = StorageMap<_, Blake2_128Concat, H256Le, T::AccountId, OptionQuery>;
+
+    /// Height of the next block headers to migrate to the current layout, while the migration is
+    /// in progress. Headers are migrated in `on_idle`, and none can be stored until it completes.
+    #[pallet::storage]
+    #[pallet::getter(fn header_migration_height)]
+    pub(super) type HeaderMigrationHeight<T: Config> = StorageValue<_, u32, OptionQuery>;
+
     #[pallet::genesis_config]
     #[derive(frame_support::DefaultNoBound)]
     pub struct GenesisConfig<T: Config> {
@@ -389,6 +797,14 @@ pub mod pallet {
         pub disable_difficulty_check: bool,
         /// Whether the module should perform inclusion checks.
         pub disable_inclusion_check: bool,
+        /// The proof-of-work rules of the relayed network.
+        pub pow_network: PowNetwork,
+        /// The script which signet blocks must satisfy, ignored unless `pow_network` is signet.
+        pub signet_challenge: Vec<u8>,
+        /// Number of main chain blocks behind the best block whose headers are retained.
+        pub header_retention: u32,
+        /// Known-good `(height, hash)` pairs which the relayed chains must contain.
+        pub checkpoints: Vec<(u32, H256Le)>,
     }
 
     #[pallet::genesis_build]
@@ -398,6 +814,23 @@ pub mod pallet {
             StableParachainConfirmations::<T>::put(self.parachain_confirmations);
             DisableDifficultyCheck::<T>::put(self.disable_difficulty_check);
             DisableInclusionCheck::<T>::put(self.disable_inclusion_check);
+            ProofOfWorkNetwork::<T>::put(self.pow_network);
+            if self.pow_network == PowNetwork::Signet {
+                let signet_challenge = BoundedVec::try_from(self.signet_challenge.clone())
+                    .expect("signet challenge exceeds the maximum size");
+                SignetChallenge::<T>::put(signet_challenge);
+            }
+            assert!(
+                self.header_retention == 0 || self.header_retention >= MIN_HEADER_RETENTION,
+                "header retention is too short"
+            );
+            HeaderRetention::<T>::put(self.header_retention);
+            for (block_height, block_hash) in self.checkpoints.iter() {
+                Checkpoints::<T>::insert(block_height, block_hash);
+            }
+            if let Some(latest_checkpoint) = self.checkpoints.iter().max_by_key(|(block_height, _)| block_height) {
+                LatestCheckpoint::<T>::put(latest_checkpoint);
+            }
         }
     }
 }
@@ -420,6 +853,44 @@ pub const UNROUNDED_MAX_TARGET: U256 = U256([<u64>::MAX, <u64>::MAX, <u64>::MAX,
 /// Main chain id
 pub const MAIN_CHAIN_ID: u32 = 0;
 
+/// Number of blocks used to compute the median time past, see BIP-113
+pub const MEDIAN_TIME_SPAN: usize = 11;
+
+/// Maximum number of seconds a block timestamp may be ahead of the parachain time
+pub const MAX_FUTURE_BLOCK_TIME: u64 = 2 * 60 * 60;
+
+/// Minimum header retention window, so that the headers needed to verify new blocks are kept
+pub const MIN_HEADER_RETENTION: u32 = DIFFICULTY_ADJUSTMENT_INTERVAL;
+
+/// Maximum number of block headers stored in a single call
+pub const MAX_BLOCK_HEADERS_PER_CALL: u32 = 100;
+
+/// Maximum size of the signet challenge script
+pub const MAX_SIGNET_CHALLENGE_SIZE: u32 = 10_000;
+
+/// Worst-case weight of storing a single block header with the given fork bound
+fn store_block_header_weight<T: Config>(f: u32) -> Weight {
+    <T as Config>::WeightInfo::store_block_header()
+        .max(<T as Config>::WeightInfo::store_block_header_new_fork_sorted(f))
+        .max(<T as Config>::WeightInfo::store_block_header_new_fork_unsorted(f))
+        .max(<T as Config>::WeightInfo::store_block_header_reorganize_chains(f))
+}
+
+/// Worst-case weight of storing a contiguous run of block headers: the first header may create
+/// a fork and the chain may be reorganized once, the other headers only extend the chain
+fn store_block_headers_weight<T: Config>(headers: u32, fork_bound: u32) -> Weight {
+    <T as Config>::WeightInfo::store_block_headers(headers).saturating_add(store_block_header_weight::<T>(fork_bound))
+}
+
+/// Worst-case weight of storing a signet block header with the given coinbase proof and fork bound
+fn store_signet_block_header_weight<T: Config>(coinbase_proof: &PartialTransactionProof, fork_bound: u32) -> Weight {
+    store_block_header_weight::<T>(fork_bound).saturating_add(<T as Config>::WeightInfo::verify_signet_solution(
+        coinbase_proof.merkle_proof.hashes.len() as u32, // H
+        coinbase_proof.transaction.outputs.len() as u32, // O
+        coinbase_proof.tx_encoded_len,
+    ))
+}
+
 #[cfg_attr(test, mockable)]
 impl<T: Config> Pallet<T> {
     pub fn _initialize(relayer: T::AccountId, basic_block_header: BlockHeader, block_height: u32) -> DispatchResult {
@@ -428,10 +899,14 @@ impl<T: Config> Pallet<T> {
 
         // header must be the start of a difficulty period
         ensure!(
-            Self::disable_difficulty_check() || block_height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0,
+            Self::disable_difficulty_check()
+                || Self::pow_network().no_retargeting()
+                || block_height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0,
             Error::<T>::InvalidStartHeight
         );
 
+        Self::ensure_no_checkpoint_conflict(&basic_block_header, block_height)?;
+
         // construct the BlockChain struct
         Self::create_and_store_blockchain(block_height, &basic_block_header)?;
 
@@ -451,6 +926,10 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn _store_block_header(relayer: &T::AccountId, basic_block_header: BlockHeader) -> DispatchResult {
+        ensure!(
+            Self::header_migration_height().is_none(),
+            Error::<T>::BlockHeadersMigrating
+        );
         let prev_header = Self::get_block_header_from_hash(basic_block_header.hash_prev_block)?;
 
         // check if the prev block is the highest block in the chain
@@ -474,26 +953,44 @@ impl<T: Config> Pallet<T> {
         let is_new_fork = prev_blockchain.max_height != prev_block_height;
         runtime_print!("Fork detected: {:?}", is_new_fork);
 
-        let chain_id = if is_new_fork {
+        // forks may not diverge from the main chain at or below the latest checkpoint
+        if let Some((checkpoint_height, _)) = Self::latest_checkpoint() {
+            let fork_point = if is_new_fork && prev_blockchain.chain_id == MAIN_CHAIN_ID {
+                Some(current_block_height)
+            } else {
+                Self::get_fork_point(&prev_blockchain)?
+            };
+            ensure!(
+                fork_point.map_or(true, |fork_point| fork_point > checkpoint_height),
+                Error::<T>::ForkBelowCheckpoint
+            );
+        }
+
+        let blockchain = if is_new_fork {
             // create new blockchain element
-            Self::create_and_store_blockchain(current_block_height, &basic_block_header)?
+            let chain_id = Self::create_and_store_blockchain(current_block_height, &basic_block_header)?;
+            Self::get_block_chain_from_id(chain_id)?
         } else {
             // extend the current chain
-            let blockchain = Self::extend_blockchain(current_block_height, &basic_block_header, prev_blockchain)?;
-
-            if blockchain.chain_id != MAIN_CHAIN_ID {
-                // if we added a block to a fork, we may need to reorder the chains
-                Self::reorganize_chains(&blockchain)?;
-            } else {
-                Self::update_chain_head(&basic_block_header, current_block_height);
-            }
-            blockchain.chain_id
+            Self::extend_blockchain(current_block_height, &basic_block_header, prev_blockchain)?
         };
+        let chain_id = blockchain.chain_id;
+
+        if chain_id != MAIN_CHAIN_ID {
+            // if we added a block to a fork, we may need to reorder the chains. Even a new fork
+            // can overtake the main chain if its first block has enough work.
+            Self::reorganize_chains(&blockchain)?;
+        } else {
+            Self::update_chain_head(&basic_block_header, current_block_height);
+        }
 
         // Determine if this block extends the main chain or a fork
         let current_best_block = Self::get_best_block();
 
-        if current_best_block == basic_block_header.hash {
+        let on_main_chain = current_best_block == basic_block_header.hash;
+        Self::record_submission(relayer, basic_block_header.hash, current_block_height, on_main_chain);
+
+        if on_main_chain {
             // extends the main chain
             Self::deposit_event(Event::<T>::StoreMainChainHeader {
                 block_height: current_block_height,
@@ -513,6 +1010,106 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Stores a contiguous run of block headers. The first header may extend any chain, the
+    /// others must each extend the previous header. The chain is only written back and
+    /// reorganized once, after the last header.
+    ///
+    /// # Returns
+    ///
+    /// The result of each processed header; processing stops after the first invalid header.
+    pub fn _store_block_headers(
+        relayer: &T::AccountId,
+        block_headers: &[BlockHeader],
+    ) -> Result<Vec<DispatchResult>, DispatchError> {
+        let mut results = Vec::with_capacity(block_headers.len());
+        let (first_block_header, block_headers) = match block_headers.split_first() {
+            Some(split) => split,
+            None => return Ok(results),
+        };
+
+        // the first header may also create a new fork
+        let result = Self::_store_block_header(relayer, *first_block_header);
+        let failed = result.is_err();
+        results.push(result);
+        if failed {
+            return Ok(results);
+        }
+
+        // the remaining headers extend the chain of the first header in memory
+        let mut prev_header = Self::get_block_header_from_hash(first_block_header.hash)?;
+        let mut blockchain = Self::get_block_chain_from_id(prev_header.chain_id)?;
+        for basic_block_header in block_headers {
+            match Self::store_block_header_on_tip(relayer, basic_block_header, prev_header, &mut blockchain) {
+                Ok(block_header) => {
+                    prev_header = block_header;
+                    results.push(Ok(()));
+                }
+                Err(err) => {
+                    results.push(Err(err));
+                    break;
+                }
+            }
+        }
+
+        if prev_header.block_hash() != first_block_header.hash {
+            Self::set_block_chain_from_id(blockchain.chain_id, &blockchain);
+            if blockchain.chain_id == MAIN_CHAIN_ID {
+                Self::update_chain_head(&prev_header.block_header, prev_header.block_height);
+            } else {
+                Self::reorganize_chains(&blockchain)?;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Stores a block header which extends the tip of the in-memory `blockchain`, without
+    /// writing the chain itself
+    fn store_block_header_on_tip(
+        relayer: &T::AccountId,
+        basic_block_header: &BlockHeader,
+        prev_header: RichBlockHeader<BlockNumberFor<T>>,
+        blockchain: &mut BlockChain,
+    ) -> Result<RichBlockHeader<BlockNumberFor<T>>, DispatchError> {
+        ensure!(
+            basic_block_header.hash_prev_block == prev_header.block_hash(),
+            Error::<T>::NonContiguousBlockHeaders
+        );
+        let block_height = prev_header
+            .block_height
+            .checked_add(1)
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+        Self::verify_block_header(basic_block_header, block_height, prev_header)?;
+        ensure!(
+            !Self::block_exists(blockchain.chain_id, block_height),
+            Error::<T>::DuplicateBlock
+        );
+
+        Self::insert_block_hash(blockchain.chain_id, block_height, basic_block_header.hash);
+        blockchain.max_height = block_height;
+        let block_header = Self::store_rich_header(*basic_block_header, block_height, blockchain.chain_id);
+
+        let on_main_chain = blockchain.chain_id == MAIN_CHAIN_ID;
+        Self::record_submission(relayer, basic_block_header.hash, block_height, on_main_chain);
+
+        if on_main_chain {
+            Self::deposit_event(Event::<T>::StoreMainChainHeader {
+                block_height,
+                block_hash: basic_block_header.hash,
+                relayer_id: relayer.clone(),
+            });
+        } else {
+            Self::deposit_event(Event::<T>::StoreForkHeader {
+                chain_id: blockchain.chain_id,
+                fork_height: block_height,
+                block_hash: basic_block_header.hash,
+                relayer_id: relayer.clone(),
+            });
+        }
+        Ok(block_header)
+    }
+
     pub fn _validate_block_header(block_header: &mut BlockHeader) -> Result<(), DispatchError> {
         block_header.ensure_version().map_err(Error::<T>::from)?;
         block_header.update_hash().map_err(Error::<T>::from)?;
@@ -538,6 +1135,18 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Checks that the transaction can be included in the next bitcoin block, i.e. that its
+    /// lock time has passed with respect to the current best block (see BIP-113)
+    pub fn ensure_transaction_final(transaction: &Transaction) -> DispatchResult {
+        let median_time_past = Self::get_median_time_past(Self::get_best_block())?;
+        let next_block_height = Self::get_best_block_height().saturating_add(1);
+        ensure!(
+            transaction.is_final(next_block_height, median_time_past),
+            Error::<T>::TransactionNotFinal
+        );
+        Ok(())
+    }
+
     /// interface to the issue pallet; verifies inclusion and returns the payment amount
     pub fn get_and_verify_issue_payment<V: TryFrom<Value>>(
         unchecked_transaction: FullTransactionProof,
@@ -591,6 +1200,10 @@ impl<T: Config> Pallet<T> {
         if Self::disable_inclusion_check() {
             return Ok(unchecked_transaction.user_tx_proof.transaction);
         }
+        if Self::pow_network() == PowNetwork::Signet {
+            Self::verify_signet_solution(&unchecked_transaction.coinbase_proof)?;
+        }
+        let block_header = unchecked_transaction.user_tx_proof.merkle_proof.block_header;
         let user_proof_result = Self::verify_merkle_proof(unchecked_transaction.user_tx_proof)?;
         let coinbase_proof_result = Self::verify_merkle_proof(unchecked_transaction.coinbase_proof)?;
 
@@ -624,6 +1237,9 @@ impl<T: Config> Pallet<T> {
             Error::<T>::InvalidMerkleProof
         );
 
+        // give a clear error if the block is too old to still be stored
+        Self::ensure_block_not_pruned(&block_header, &coinbase_proof_result.transaction)?;
+
         let stored_block_header = Self::verify_block_header_inclusion(user_proof_result.block_hash, confirmations)?;
 
         // fail if the merkle root is invalid
@@ -635,6 +1251,20 @@ impl<T: Config> Pallet<T> {
         Ok(user_proof_result.transaction)
     }
 
+    /// Verifies the inclusion of the transaction as `_verify_transaction_inclusion` and additionally
+    /// checks that its witness data is committed to by the coinbase transaction
+    pub fn _verify_witness_transaction_inclusion(
+        unchecked_transaction: WitnessTransactionProof,
+        confirmations: Option<u32>,
+    ) -> Result<Transaction, DispatchError> {
+        if !Self::disable_inclusion_check() {
+            unchecked_transaction
+                .verify_witness_commitment()
+                .map_err(Error::<T>::from)?;
+        }
+        Self::_verify_transaction_inclusion(unchecked_transaction.transaction_proof, confirmations)
+    }
+
     // util function extracted for mocking purposes
     fn block_matches_merkle_root(block_header: &BlockHeader, proof_result: &ProofResult) -> bool {
         proof_result.extracted_root == block_header.merkle_root
@@ -644,8 +1274,8 @@ impl<T: Config> Pallet<T> {
         block_hash: H256Le,
         confirmations: Option<u32>,
     ) -> Result<BlockHeader, DispatchError> {
+        Self::ensure_no_ongoing_fork()?;
         let best_block_height = Self::get_best_block_height();
-        Self::ensure_no_ongoing_fork(best_block_height)?;
 
         let rich_header = Self::get_block_header_from_hash(block_hash)?;
 
@@ -662,6 +1292,46 @@ impl<T: Config> Pallet<T> {
         Ok(rich_header.block_header)
     }
 
+    /// Get the details of a stored block header, if any
+    pub fn get_block_header_info(block_hash: H256Le) -> Option<BlockHeaderInfo> {
+        let rich_header = Self::get_block_header_from_hash(block_hash).ok()?;
+        let block_header = rich_header.block_header;
+        Some(BlockHeaderInfo {
+            hash: block_header.hash,
+            block_height: rich_header.block_height,
+            chain_id: rich_header.chain_id,
+            on_main_chain: rich_header.chain_id == MAIN_CHAIN_ID,
+            version: block_header.version,
+            hash_prev_block: block_header.hash_prev_block,
+            merkle_root: block_header.merkle_root,
+            timestamp: block_header.timestamp,
+            bits: block_header.target.get_compact().unwrap_or_default(),
+            nonce: block_header.nonce,
+            median_time_past: rich_header.median_time_past,
+        })
+    }
+
+    /// Get the hash of the main chain block at the given height, if it is stored
+    pub fn get_main_chain_block_hash(block_height: u32) -> Option<H256Le> {
+        Self::get_block_hash(MAIN_CHAIN_ID, block_height).ok()
+    }
+
+    /// Get the forks tracked by the relay, in the order of their position
+    pub fn get_fork_info() -> Vec<ForkInfo> {
+        let best_block_height = Self::get_best_block_height();
+        (1u32..)
+            .map_while(Chains::<T>::get)
+            .filter_map(|chain_id| Self::get_block_chain_from_id(chain_id).ok())
+            .map(|blockchain| ForkInfo {
+                chain_id: blockchain.chain_id,
+                start_height: blockchain.start_height,
+                max_height: blockchain.max_height,
+                tip_hash: Self::get_block_hash(blockchain.chain_id, blockchain.max_height).unwrap_or_default(),
+                blocks_behind: best_block_height.saturating_sub(blockchain.max_height),
+            })
+            .collect()
+    }
+
     /// Checks if transaction is valid. Returns the return-to-self address, if any, for theft checking purposes
     fn validate_op_return_transaction<V: TryInto<i64>>(
         transaction: Transaction,
@@ -767,6 +1437,11 @@ impl<T: Config> Pallet<T> {
         ChainCounter::<T>::get()
     }
 
+    /// Get the number of stored chains
+    fn get_chains_count() -> u32 {
+        ChainsCount::<T>::get()
+    }
+
     /// Get a block hash from a blockchain
     ///
     /// # Arguments
@@ -775,16 +1450,54 @@ impl<T: Config> Pallet<T> {
     /// * `block_height`: the height of the block header
     fn get_block_hash(chain_id: u32, block_height: u32) -> Result<H256Le, DispatchError> {
         if !Self::block_exists(chain_id, block_height) {
+            if chain_id == MAIN_CHAIN_ID
+                && (StartBlockHeight::<T>::get()..PrunedBlockHeight::<T>::get()).contains(&block_height)
+            {
+                return Err(Error::<T>::BlockPruned.into());
+            }
             return Err(Error::<T>::MissingBlockHeight.into());
         }
         Ok(ChainsHashes::<T>::get(chain_id, block_height))
     }
 
+    /// Get the main chain height below which block headers may have been pruned
+    fn get_pruned_block_height() -> u32 {
+        PrunedBlockHeight::<T>::get().max(StartBlockHeight::<T>::get())
+    }
+
     /// Get a block header from its hash
     fn get_block_header_from_hash(block_hash: H256Le) -> Result<RichBlockHeader<BlockNumberFor<T>>, DispatchError> {
         BlockHeaders::<T>::try_get(block_hash).or(Err(Error::<T>::BlockNotFound.into()))
     }
 
+    /// Get the median time past of a stored block, i.e. the median timestamp of the block and its
+    /// (up to) ten stored predecessors
+    pub fn get_median_time_past(block_hash: H256Le) -> Result<u32, DispatchError> {
+        Ok(Self::get_block_header_from_hash(block_hash)?.median_time_past)
+    }
+
+    /// Compute the median timestamp of the block and its (up to) ten stored predecessors
+    fn compute_median_time_past(basic_block_header: &BlockHeader) -> u32 {
+        let mut block_header = *basic_block_header;
+        let mut timestamps = Vec::with_capacity(MEDIAN_TIME_SPAN);
+        timestamps.push(block_header.timestamp);
+        while timestamps.len() < MEDIAN_TIME_SPAN {
+            // headers before the initial block are not stored
+            match Self::get_block_header_from_hash(block_header.hash_prev_block) {
+                Ok(prev_block_header) => block_header = prev_block_header.block_header,
+                Err(_) => break,
+            }
+            timestamps.push(block_header.timestamp);
+        }
+        timestamps.sort_unstable();
+        timestamps[timestamps.len() / 2]
+    }
+
+    /// Get the current parachain time in seconds
+    fn get_current_time() -> u64 {
+        <pallet_timestamp::Pallet<T>>::get().unique_saturated_into() / 1000
+    }
+
     /// Check if a block header exists
     pub fn block_header_exists(block_hash: H256Le) -> bool {
         BlockHeaders::<T>::contains_key(block_hash)
@@ -856,10 +1569,12 @@ impl<T: Config> Pallet<T> {
         // Store a pointer to BlockChain in ChainsIndex
         Self::set_block_chain_from_id(blockchain.chain_id, &blockchain);
 
+        // the header is needed to sort the blockchain by its chainwork
+        Self::store_rich_header(basic_block_header.clone(), block_height, blockchain.chain_id);
+
         // Store the reference to the blockchain in Chains
         Self::insert_sorted(&blockchain)?;
-
-        Self::store_rich_header(basic_block_header.clone(), block_height, blockchain.chain_id);
+        ChainsCount::<T>::mutate(|count| count.saturating_inc());
 
         Ok(blockchain.chain_id)
     }
@@ -911,6 +1626,20 @@ impl<T: Config> Pallet<T> {
         Self::bitcoin_confirmations()
     }
 
+    /// Get the total work of the BlockChain, i.e. the chainwork of its last block
+    fn get_chain_work(blockchain: &BlockChain) -> Result<U256, DispatchError> {
+        Ok(Self::get_block_header_from_height(blockchain, blockchain.max_height)?.chain_work)
+    }
+
+    /// Get the work of the stable transaction confirmations at the difficulty of the last block of
+    /// the BlockChain. A fork must be this far ahead of the main chain to replace it.
+    fn get_stable_chain_work(blockchain: &BlockChain) -> Result<U256, DispatchError> {
+        let block_header = Self::get_block_header_from_height(blockchain, blockchain.max_height)?.block_header;
+        Ok(block_header
+            .work()
+            .saturating_mul(Self::get_stable_transaction_confirmations().into()))
+    }
+
     // *********************************
     // END: Storage getter functions
     // *********************************
@@ -922,6 +1651,40 @@ impl<T: Config> Pallet<T> {
             .map_err(|err| Error::<T>::from(err).into())
     }
 
+    /// Verifies that the block of the coinbase proof satisfies the signet challenge. The coinbase
+    /// proof must also be checked against the block header, which covers the header as well.
+    fn verify_signet_solution(coinbase_proof: &PartialTransactionProof) -> DispatchResult {
+        match Self::signet_challenge() {
+            Some(challenge) => {
+                bitcoin::signet::verify_coinbase_solution(coinbase_proof, &challenge.into_inner().into())
+                    .map_err(|err| Error::<T>::from(err).into())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies that the coinbase proof belongs to the block header and that the block satisfies
+    /// the signet challenge
+    fn verify_signet_block_header(
+        block_header: &BlockHeader,
+        coinbase_proof: PartialTransactionProof,
+    ) -> DispatchResult {
+        let coinbase_proof_result = Self::verify_merkle_proof(coinbase_proof.clone())?;
+        ensure!(
+            coinbase_proof_result.transaction_position == 0,
+            Error::<T>::InvalidCoinbasePosition
+        );
+        ensure!(
+            coinbase_proof_result.transaction.is_coinbase(),
+            Error::<T>::InvalidMerkleProof
+        );
+        ensure!(
+            Self::block_matches_merkle_root(block_header, &coinbase_proof_result),
+            Error::<T>::InvalidMerkleProof
+        );
+        Self::verify_signet_solution(&coinbase_proof)
+    }
+
     /// Verifies a Bitcoin block header.
     ///
     /// # Arguments
@@ -947,16 +1710,51 @@ impl<T: Config> Pallet<T> {
         // Check that the PoW hash satisfies the target set in the block header
         ensure!(block_header.hash.as_u256() < block_header.target, Error::<T>::LowDiff);
 
+        Self::ensure_no_checkpoint_conflict(block_header, block_height)?;
+
+        // Check that the timestamp is later than the median time past of the previous blocks
+        ensure!(
+            block_header.timestamp > prev_block_header.median_time_past,
+            Error::<T>::BlockTimestampTooOld
+        );
+
+        // Check that the timestamp is not too far in the future, the time is not set at genesis
+        let now = Self::get_current_time();
+        ensure!(
+            now == 0 || block_header.timestamp as u64 <= now.saturating_add(MAX_FUTURE_BLOCK_TIME),
+            Error::<T>::BlockTimestampTooNew
+        );
+
+        let network = Self::pow_network();
+        let is_retarget_height =
+            block_height >= DIFFICULTY_ADJUSTMENT_INTERVAL && block_height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0;
+
+        // BIP-94: the first block of a difficulty period may not be much older than the previous block
+        // https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/validation.cpp#L4198-L4204
+        ensure!(
+            !network.enforce_bip94()
+                || !is_retarget_height
+                || block_header.timestamp as u64
+                    >= (prev_block_header.block_header.timestamp as u64).saturating_sub(bitcoin::pow::MAX_TIMEWARP),
+            Error::<T>::TimewarpAttack
+        );
+
         if Self::disable_difficulty_check() {
             return Ok(());
         }
 
-        let expected_target =
-            if block_height >= DIFFICULTY_ADJUSTMENT_INTERVAL && block_height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0 {
-                Self::compute_new_target(&prev_block_header, block_height)?
-            } else {
-                prev_block_header.block_header.target
-            };
+        let expected_target = if is_retarget_height {
+            Self::compute_new_target(&prev_block_header, block_height)?
+        } else if network.is_min_difficulty_block(
+            block_header.timestamp as u64,
+            prev_block_header.block_header.timestamp as u64,
+        ) {
+            network.min_difficulty_target().map_err(Error::<T>::from)?
+        } else if network.allow_min_difficulty_blocks() {
+            prev_block_header.last_non_min_difficulty_target
+        } else {
+            prev_block_header.block_header.target
+        };
 
         ensure!(block_header.target == expected_target, Error::<T>::DiffTargetHeader);
 
@@ -973,19 +1771,57 @@ impl<T: Config> Pallet<T> {
         prev_block_header: &RichBlockHeader<BlockNumberFor<T>>,
         block_height: u32,
     ) -> Result<U256, DispatchError> {
+        let network = Self::pow_network();
+        if network.no_retargeting() {
+            return Ok(prev_block_header.block_header.target);
+        }
         // time of last retarget (first block in current difficulty period)
         let first_block_time = Self::get_last_retarget_time(prev_block_header.chain_id, block_height)?;
         let last_block_time = prev_block_header.block_header.timestamp as u64;
-        let previous_target = prev_block_header.block_header.target;
+        // BIP-94: the last block of the period may have been mined at the minimum difficulty
+        let previous_target = if network.enforce_bip94() {
+            Self::get_last_retarget_header(prev_block_header.chain_id, block_height)?
+                .block_header
+                .target
+        } else {
+            prev_block_header.block_header.target
+        };
 
         // compute new target
         Ok(U256::set_compact(
-            bitcoin::pow::calculate_next_work_required(previous_target, first_block_time, last_block_time)
+            bitcoin::pow::calculate_next_work_required(network, previous_target, first_block_time, last_block_time)
                 .map_err(Error::<T>::from)?,
         )
         .ok_or(Error::<T>::InvalidCompact)?)
     }
 
+    /// Returns the target of the last block up to and including the given block which was not
+    /// mined at the minimum difficulty, going back no further than the start of the difficulty
+    /// period or the first stored block. The result is stored with the header, so only the
+    /// previous header is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `basic_block_header`: the block header
+    /// * `block_height`: height of the block
+    // https://github.com/bitcoin/bitcoin/blob/2d3bca3fc2237dc9c6f4a3458ba2f3c53c8f6ccb/src/pow.cpp#L30-L36
+    fn compute_last_non_min_difficulty_target(basic_block_header: &BlockHeader, block_height: u32) -> U256 {
+        let network = Self::pow_network();
+        let is_min_difficulty_target = network.min_difficulty_target().map_or(false, |min_difficulty_target| {
+            basic_block_header.target == min_difficulty_target
+        });
+        if !network.allow_min_difficulty_blocks()
+            || block_height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0
+            || !is_min_difficulty_target
+        {
+            return basic_block_header.target;
+        }
+        // headers before the initial block are not stored
+        BlockHeaders::<T>::try_get(basic_block_header.hash_prev_block)
+            .map(|prev_block_header| prev_block_header.last_non_min_difficulty_target)
+            .unwrap_or(basic_block_header.target)
+    }
+
     /// Returns the timestamp of the last difficulty retarget on the specified BlockChain, given the current block
     /// height
     ///
@@ -994,10 +1830,24 @@ impl<T: Config> Pallet<T> {
     /// * `chain_id` - BlockChain identifier
     /// * `block_height` - current block height
     fn get_last_retarget_time(chain_id: u32, block_height: u32) -> Result<u64, DispatchError> {
+        let last_retarget_header = Self::get_last_retarget_header(chain_id, block_height)?;
+        Ok(last_retarget_header.block_header.timestamp as u64)
+    }
+
+    /// Returns the first block header of the current difficulty period on the specified BlockChain,
+    /// given the current block height
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_id` - BlockChain identifier
+    /// * `block_height` - current block height
+    fn get_last_retarget_header(
+        chain_id: u32,
+        block_height: u32,
+    ) -> Result<RichBlockHeader<BlockNumberFor<T>>, DispatchError> {
         let block_chain = Self::get_block_chain_from_id(chain_id)?;
         let period_start_height = block_height - DIFFICULTY_ADJUSTMENT_INTERVAL;
-        let last_retarget_header = Self::get_block_header_from_height(&block_chain, period_start_height)?;
-        Ok(last_retarget_header.block_header.timestamp as u64)
+        Self::get_block_header_from_height(&block_chain, period_start_height)
     }
 
     /// Swap the main chain with a fork. The fork is not necessarily a direct fork of the main
@@ -1022,6 +1872,7 @@ impl<T: Config> Pallet<T> {
         // Set BestBlock and BestBlockHeight to the submitted block
         Self::set_best_block(new_best_block);
         Self::set_best_block_height(fork.max_height);
+        Self::record_best_block_update();
 
         // traverse (child-parent) links until the mainchain is reached
         for pair in Self::enumerate_chain_links(new_best_block) {
@@ -1058,6 +1909,17 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        // a fork with more chainwork may be shorter than the main chain, in which case the blocks above
+        // its max_height are not replaced, so transfer them to `fork` as well
+        let old_main_max_height = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?.max_height;
+        for block_height in fork.max_height.saturating_add(1)..=old_main_max_height {
+            if let Ok(block_hash) = ChainsHashes::<T>::try_get(MAIN_CHAIN_ID, block_height) {
+                ChainsHashes::<T>::remove(MAIN_CHAIN_ID, block_height);
+                ChainsHashes::<T>::insert(fork.chain_id, block_height, block_hash);
+                BlockHeaders::<T>::mutate(&block_hash, |header| header.chain_id = fork.chain_id);
+            }
+        }
+
         // update the max_height of main chain
         Self::mutate_block_chain_from_id(
             MAIN_CHAIN_ID,
@@ -1067,7 +1929,7 @@ impl<T: Config> Pallet<T> {
             },
         );
 
-        // we swapped main chain and `fork`, so it will need to be resorted. The new max_height of this fork
+        // we swapped main chain and `fork`, so it will need to be resorted. The new chainwork of this fork
         // is strictly smaller than before, so do a single bubble sort pass to the right
         let start = Self::get_chain_position_from_chain_id(fork.chain_id)?;
         // ideally we'd iterate over start..Chains::<T>::len(), but unfortunately Chains does not implement
@@ -1079,9 +1941,9 @@ impl<T: Config> Pallet<T> {
                 break;
             }
 
-            let height1 = Self::get_block_chain_from_id(Self::get_chain_id_from_position(i)?)?.max_height;
-            let height2 = Self::get_block_chain_from_id(Self::get_chain_id_from_position(i + 1)?)?.max_height;
-            if height1 < height2 {
+            let chain1 = Self::get_block_chain_from_id(Self::get_chain_id_from_position(i)?)?;
+            let chain2 = Self::get_block_chain_from_id(Self::get_chain_id_from_position(i + 1)?)?;
+            if Self::get_chain_work(&chain1)? < Self::get_chain_work(&chain2)? {
                 Self::swap_chain(i, i + 1);
             } else {
                 break;
@@ -1145,8 +2007,8 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Checks if a newly inserted fork results in an update to the sorted
-    /// Chains mapping. This happens when the max height of the fork is greater
-    /// than the max height of the previous element in the Chains mapping.
+    /// Chains mapping. This happens when the chainwork of the fork is greater
+    /// than the chainwork of the previous element in the Chains mapping.
     ///
     /// # Arguments
     ///
@@ -1154,11 +2016,11 @@ impl<T: Config> Pallet<T> {
     fn reorganize_chains(fork: &BlockChain) -> Result<(), DispatchError> {
         // get the position of the fork in Chains
         let fork_position: u32 = Self::get_chain_position_from_chain_id(fork.chain_id)?;
-        // check if the previous element in Chains has a lower block_height
+        // check if the previous element in Chains has a lower chainwork
         let mut current_position = fork_position;
-        let mut current_height = fork.max_height;
+        let fork_work = Self::get_chain_work(fork)?;
 
-        // swap elements as long as previous block height is smaller
+        // swap elements as long as previous chainwork is smaller
         while current_position > 0 {
             // get the previous position
             let prev_position = current_position.saturating_sub(1);
@@ -1171,17 +2033,18 @@ impl<T: Config> Pallet<T> {
                 continue;
             };
 
-            // get the previous blockchain height
-            let prev_height = Self::get_block_chain_from_id(prev_blockchain_id)?.max_height;
-            // swap elements if block height is greater
-            if prev_height < current_height {
+            // get the previous blockchain chainwork
+            let prev_blockchain = Self::get_block_chain_from_id(prev_blockchain_id)?;
+            let prev_work = Self::get_chain_work(&prev_blockchain)?;
+            // swap elements if chainwork is greater
+            if prev_work < fork_work {
                 // Check if swap occurs on the main chain element
                 if prev_blockchain_id == MAIN_CHAIN_ID {
                     // if the previous position is the top element
-                    // and the current height is more than the
-                    // STABLE_TRANSACTION_CONFIRMATIONS ahead
+                    // and the fork has more than the work of
+                    // STABLE_TRANSACTION_CONFIRMATIONS blocks ahead
                     // we are swapping the main chain
-                    if prev_height.saturating_add(Self::get_stable_transaction_confirmations()) <= current_height {
+                    if prev_work.saturating_add(Self::get_stable_chain_work(&prev_blockchain)?) <= fork_work {
                         // Swap the mainchain. As an optimization, this function returns the
                         // new best block hash and its height
                         let (new_chain_tip_hash, new_chain_tip_height) = Self::swap_main_blockchain(&fork)?;
@@ -1195,7 +2058,7 @@ impl<T: Config> Pallet<T> {
                         });
                     } else {
                         Self::deposit_event(Event::<T>::ForkAheadOfMainChain {
-                            main_chain_height: prev_height,
+                            main_chain_height: prev_blockchain.max_height,
                             fork_height: fork.max_height,
                             fork_id: fork.chain_id,
                         });
@@ -1207,9 +2070,8 @@ impl<T: Config> Pallet<T> {
                     Self::swap_chain(prev_position, current_position);
                 }
 
-                // update the current chain to the previous one
+                // continue with the new position of the fork
                 current_position = prev_position;
-                current_height = prev_height;
             } else {
                 break;
             }
@@ -1218,7 +2080,7 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    /// Insert a new fork into the Chains mapping sorted by its max height
+    /// Insert a new fork into the Chains mapping sorted by its chainwork
     ///
     /// # Arguments
     ///
@@ -1235,18 +2097,19 @@ impl<T: Config> Pallet<T> {
         let mut position_blockchain = max_chain_element;
 
         // Starting from the second highest element, find where to insert the new fork
-        // the previous element's block height should be higher or equal
-        // the next element's block height should be lower or equal
+        // the previous element's chainwork should be higher or equal
+        // the next element's chainwork should be lower or equal
         // NOTE: we never want to insert a new main chain through this function
+        let chain_work = Self::get_chain_work(blockchain)?;
         for (curr_position, curr_chain_id) in chains.iter().skip(1) {
-            // get the height of the current chain_id
-            let curr_height = Self::get_block_chain_from_id(*curr_chain_id)?.max_height;
+            // get the chainwork of the current chain_id
+            let curr_work = Self::get_chain_work(&Self::get_block_chain_from_id(*curr_chain_id)?)?;
 
-            // if the height of the new blockchain is higher than
+            // if the chainwork of the new blockchain is higher than
             // the current blockchain, it should be inserted at that position
             // NOTE: inequality should be gt to prevent swapping chains
-            // at the same height
-            if blockchain.max_height > curr_height {
+            // with the same chainwork
+            if chain_work > curr_work {
                 position_blockchain = *curr_position;
                 break;
             };
@@ -1312,36 +2175,388 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn ensure_no_ongoing_fork(best_block_height: u32) -> Result<(), DispatchError> {
-        // check if there is a next best fork
-        match Self::get_chain_id_from_position(1) {
-            // if yes, check that the main chain is at least Self::confirmations() ahead
-            Ok(id) => {
-                let next_best_fork_height = Self::get_block_chain_from_id(id)?.max_height;
-
-                runtime_print!("Best block height: {}", best_block_height);
-                runtime_print!("Next best fork height: {}", next_best_fork_height);
+    fn ensure_no_ongoing_fork() -> Result<(), DispatchError> {
+        // check if there is a next best fork, ignoring forks below the latest checkpoint since they
+        // can never become the main chain
+        let mut position = 1;
+        let next_best_fork = loop {
+            match Self::get_chain_id_from_position(position) {
+                Ok(id) => {
+                    let fork = Self::get_block_chain_from_id(id)?;
+                    if !Self::is_below_checkpoint(&fork) {
+                        break Some(fork);
+                    }
+                    position.saturating_inc();
+                }
+                Err(_) => break None,
+            }
+        };
+        match next_best_fork {
+            // if yes, check that the main chain is at least the work of Self::confirmations() blocks ahead
+            Some(fork) => {
+                let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+                let main_chain_work = Self::get_chain_work(&main_chain)?;
+                let next_best_fork_work = Self::get_chain_work(&fork)?;
+
+                runtime_print!("Main chain work: {}", main_chain_work);
+                runtime_print!("Next best fork work: {}", next_best_fork_work);
                 // fail if there is an ongoing fork
                 ensure!(
-                    best_block_height >= next_best_fork_height + Self::get_stable_transaction_confirmations(),
+                    main_chain_work >= next_best_fork_work.saturating_add(Self::get_stable_chain_work(&main_chain)?),
                     Error::<T>::OngoingFork
                 );
             }
             // else, do nothing if there is no fork
-            Err(_) => {}
+            None => {}
         }
         Ok(())
     }
 
-    fn store_rich_header(basic_block_header: BlockHeader, block_height: u32, chain_id: u32) {
+    fn store_rich_header(
+        basic_block_header: BlockHeader,
+        block_height: u32,
+        chain_id: u32,
+    ) -> RichBlockHeader<BlockNumberFor<T>> {
         let para_height = ext::security::active_block_number::<T>();
-        let block_header = RichBlockHeader::new(basic_block_header, chain_id, block_height, para_height);
+        // the work of the headers before the initial block is not counted
+        let prev_chain_work = BlockHeaders::<T>::try_get(basic_block_header.hash_prev_block)
+            .map(|prev_block_header| prev_block_header.chain_work)
+            .unwrap_or_default();
+        let block_header = RichBlockHeader {
+            chain_work: prev_chain_work.saturating_add(basic_block_header.work()),
+            median_time_past: Self::compute_median_time_past(&basic_block_header),
+            last_non_min_difficulty_target: Self::compute_last_non_min_difficulty_target(
+                &basic_block_header,
+                block_height,
+            ),
+            ..RichBlockHeader::new(basic_block_header, chain_id, block_height, para_height)
+        };
         Self::set_block_header_from_hash(basic_block_header.hash, &block_header);
+        block_header
     }
 
     fn update_chain_head(basic_block_header: &BlockHeader, block_height: u32) {
         Self::set_best_block(basic_block_header.hash);
         Self::set_best_block_height(block_height);
+        Self::record_best_block_update();
+    }
+
+    /// Records that the best block was updated in the current parachain block, which ends a
+    /// stale period
+    fn record_best_block_update() {
+        BestBlockUpdatedAt::<T>::put(frame_system::Pallet::<T>::block_number());
+        Self::mark_relay_live();
+    }
+
+    fn mark_relay_live() {
+        if ext::security::is_relay_stale::<T>() {
+            ext::security::set_relay_stale::<T>(false);
+            Self::deposit_event(Event::<T>::RelayRecovered {
+                best_block_height: Self::get_best_block_height(),
+            });
+        }
+    }
+
+    /// Marks the relay as stale if the best block was not updated within the staleness threshold.
+    /// The active block count of the security pallet is paused while the relay is stale, which
+    /// extends the expiry of open requests.
+    pub fn check_relay_liveness(now: BlockNumberFor<T>) {
+        let staleness_threshold = Self::staleness_threshold();
+        if staleness_threshold.is_zero() || !Self::best_block_exists() || ext::security::is_relay_stale::<T>() {
+            return;
+        }
+        let last_update = Self::best_block_updated_at();
+        if now.saturating_sub(last_update) > staleness_threshold {
+            ext::security::set_relay_stale::<T>(true);
+            Self::deposit_event(Event::<T>::RelayStale {
+                best_block_height: Self::get_best_block_height(),
+                last_update,
+            });
+        }
+    }
+
+    /// Checks that the relay is not stale, used to reject new requests
+    pub fn ensure_relay_live() -> DispatchResult {
+        ensure!(!ext::security::is_relay_stale::<T>(), Error::<T>::RelayStale);
+        Ok(())
+    }
+
+    /// Checks that the given block has not been pruned from the main chain. Blocks which are not
+    /// stored are looked up by the height committed to in their coinbase transaction (BIP-34).
+    fn ensure_block_not_pruned(block_header: &BlockHeader, coinbase_transaction: &Transaction) -> DispatchResult {
+        if Self::block_header_exists(block_header.hash) {
+            return Ok(());
+        }
+        if let Some(block_height) = coinbase_transaction.coinbase_height() {
+            ensure!(
+                !(StartBlockHeight::<T>::get()..PrunedBlockHeight::<T>::get()).contains(&block_height),
+                Error::<T>::BlockPruned
+            );
+        }
+        Ok(())
+    }
+
+    /// Incrementally removes the main chain headers that are deeper than the header retention
+    /// window, except for the retarget headers at difficulty adjustment boundaries, followed by the
+    /// forks that ended before the window or diverge below the latest checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_weight` - the maximum weight that may be consumed
+    ///
+    /// # Returns
+    ///
+    /// The consumed weight
+    pub fn prune_block_headers(max_weight: Weight) -> Weight {
+        let mut used_weight = T::DbWeight::get().reads(5);
+        let header_retention = Self::header_retention();
+        if (header_retention == 0 && Self::latest_checkpoint().is_none()) || !used_weight.all_lte(max_weight) {
+            return used_weight;
+        }
+        // without a retention window, only the forks below the latest checkpoint are pruned
+        let prune_height = match header_retention {
+            0 => 0,
+            _ => Self::get_best_block_height().saturating_sub(header_retention),
+        };
+        let block_weight = <T as Config>::WeightInfo::prune_block_header();
+
+        // retarget headers are kept so that new blocks can still be verified
+        let mut block_height = Self::get_pruned_block_height();
+        let start_height = block_height;
+        while block_height < prune_height && used_weight.saturating_add(block_weight).all_lte(max_weight) {
+            if block_height % DIFFICULTY_ADJUSTMENT_INTERVAL != 0 {
+                Self::remove_block(MAIN_CHAIN_ID, block_height);
+            } else if let Ok(block_hash) = ChainsHashes::<T>::try_get(MAIN_CHAIN_ID, block_height) {
+                // the retarget header is kept, but it is settled on the main chain
+                Self::release_block_relayer(MAIN_CHAIN_ID, block_hash);
+            }
+            used_weight.saturating_accrue(block_weight);
+            block_height.saturating_inc();
+        }
+        if block_height != start_height {
+            PrunedBlockHeight::<T>::put(block_height);
+        }
+        if block_height < prune_height {
+            return used_weight;
+        }
+
+        // finding a prunable fork scans all forks, which is charged before each scan
+        let chains_count = Self::get_chains_count();
+        let fork_weight = <T as Config>::WeightInfo::find_prunable_fork(chains_count)
+            .saturating_add(<T as Config>::WeightInfo::prune_fork(chains_count));
+        while used_weight.saturating_add(fork_weight).all_lte(max_weight) {
+            used_weight.saturating_accrue(fork_weight);
+            let mut fork = match Self::find_prunable_fork(prune_height) {
+                Some(fork) => fork,
+                None => break,
+            };
+            while fork.start_height <= fork.max_height && used_weight.saturating_add(block_weight).all_lte(max_weight) {
+                Self::remove_block(fork.chain_id, fork.start_height);
+                used_weight.saturating_accrue(block_weight);
+                fork.start_height.saturating_inc();
+            }
+            if fork.start_height <= fork.max_height {
+                Self::set_block_chain_from_id(fork.chain_id, &fork);
+                break;
+            }
+            if Self::remove_blockchain(fork.chain_id).is_err() {
+                break;
+            }
+        }
+        used_weight
+    }
+
+    /// Returns a fork that ended below the given main chain height or that diverges from the main
+    /// chain at or below the latest checkpoint, if any
+    fn find_prunable_fork(prune_height: u32) -> Option<BlockChain> {
+        ChainsIndex::<T>::iter_values().find(|chain| {
+            chain.chain_id != MAIN_CHAIN_ID && (chain.max_height < prune_height || Self::is_below_checkpoint(chain))
+        })
+    }
+
+    /// Removes the block at the given height of a chain, if any. The bonded relayer of a block
+    /// removed from a fork is slashed.
+    fn remove_block(chain_id: u32, block_height: u32) {
+        if let Ok(block_hash) = ChainsHashes::<T>::try_get(chain_id, block_height) {
+            ChainsHashes::<T>::remove(chain_id, block_height);
+            BlockHeaders::<T>::remove(block_hash);
+            Self::release_block_relayer(chain_id, block_hash);
+        }
+    }
+
+    /// Releases the block from the pending headers of its bonded relayer, if any, slashing the
+    /// relayer if the block is on a fork
+    fn release_block_relayer(chain_id: u32, block_hash: H256Le) {
+        if let Some(relayer) = BlockRelayers::<T>::take(block_hash) {
+            RelayerStatistics::<T>::mutate(&relayer, |stats| stats.pending_headers.saturating_dec());
+            if chain_id != MAIN_CHAIN_ID {
+                Self::slash_relayer(&relayer, block_hash);
+            }
+        }
+    }
+
+    /// Updates the statistics of the relayer for a stored header, and pays the reward if the
+    /// header extends the main chain. Failing to pay the reward, e.g. because the pot is
+    /// exhausted, does not fail the submission.
+    fn record_submission(relayer: &T::AccountId, block_hash: H256Le, block_height: u32, on_main_chain: bool) {
+        let mut stats = Self::relayer_stats(relayer);
+        stats.last_submission_height = block_height;
+        if !stats.bond.is_zero() {
+            BlockRelayers::<T>::insert(block_hash, relayer);
+            stats.pending_headers.saturating_inc();
+        }
+
+        if on_main_chain {
+            stats.main_chain_headers.saturating_inc();
+            let reward = Self::relayer_reward();
+            if !reward.is_zero()
+                && T::Currency::transfer(
+                    &T::RelayerRewardPot::get(),
+                    relayer,
+                    reward,
+                    ExistenceRequirement::KeepAlive,
+                )
+                .is_ok()
+            {
+                stats.rewards.saturating_accrue(reward);
+                Self::deposit_event(Event::<T>::RelayerRewarded {
+                    relayer_id: relayer.clone(),
+                    block_hash,
+                    amount: reward,
+                });
+            }
+        } else {
+            stats.fork_headers.saturating_inc();
+        }
+        RelayerStatistics::<T>::insert(relayer, stats);
+    }
+
+    /// Slashes the bond of the relayer for an orphaned header, moving it to the reward pot
+    fn slash_relayer(relayer: &T::AccountId, block_hash: H256Le) {
+        let mut stats = Self::relayer_stats(relayer);
+        stats.orphaned_headers.saturating_inc();
+        let amount = Self::orphaned_header_slash().min(stats.bond);
+        if !amount.is_zero() {
+            // a part that can not be slashed is no longer reserved, so it leaves the bond as well
+            let (imbalance, _) = T::Currency::slash_reserved(relayer, amount);
+            let slashed = imbalance.peek();
+            T::Currency::resolve_creating(&T::RelayerRewardPot::get(), imbalance);
+            stats.bond.saturating_reduce(amount);
+            stats.slashed.saturating_accrue(slashed);
+            Self::deposit_event(Event::<T>::RelayerSlashed {
+                relayer_id: relayer.clone(),
+                block_hash,
+                amount: slashed,
+            });
+        }
+        RelayerStatistics::<T>::insert(relayer, stats);
+    }
+
+    /// Removes a fork from `Chains` and `ChainsIndex`
+    fn remove_blockchain(chain_id: u32) -> DispatchResult {
+        let mut position = Self::get_chain_position_from_chain_id(chain_id)?;
+        // keep the keys in `Chains` consecutive by moving the fork to the last position
+        while Chains::<T>::contains_key(position.saturating_add(1)) {
+            Self::swap_chain(position, position.saturating_add(1));
+            position.saturating_inc();
+        }
+        Chains::<T>::remove(position);
+        ChainsIndex::<T>::remove(chain_id);
+        ChainsCount::<T>::mutate(|count| count.saturating_dec());
+        Ok(())
+    }
+
+    /// Registers a checkpoint, re-anchoring the main chain if the block is above the best block
+    pub fn _add_checkpoint(block_header: BlockHeader, block_height: u32) -> DispatchResult {
+        if let Some((latest_checkpoint_height, _)) = Self::latest_checkpoint() {
+            ensure!(
+                block_height > latest_checkpoint_height,
+                Error::<T>::CheckpointHeightTooLow
+            );
+        }
+
+        let reanchored = Self::best_block_exists() && block_height > Self::get_best_block_height();
+        if reanchored {
+            ensure!(
+                !Self::block_header_exists(block_header.hash),
+                Error::<T>::CheckpointNotOnMainChain
+            );
+            Self::reanchor_main_chain(&block_header, block_height)?;
+        } else if Self::best_block_exists() {
+            ensure!(
+                Self::get_block_hash(MAIN_CHAIN_ID, block_height)? == block_header.hash,
+                Error::<T>::CheckpointNotOnMainChain
+            );
+        }
+
+        Checkpoints::<T>::insert(block_height, block_header.hash);
+        LatestCheckpoint::<T>::put((block_height, block_header.hash));
+        Self::deposit_event(Event::<T>::CheckpointAdded {
+            block_height,
+            block_hash: block_header.hash,
+            reanchored,
+        });
+        Ok(())
+    }
+
+    /// Makes the given block the tip of the main chain without requiring its ancestors, like
+    /// `_initialize`. All existing forks end up below the checkpoint and are pruned.
+    fn reanchor_main_chain(block_header: &BlockHeader, block_height: u32) -> DispatchResult {
+        // the next retarget must be computable from the stored headers
+        ensure!(
+            Self::disable_difficulty_check()
+                || Self::pow_network().no_retargeting()
+                || block_height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0,
+            Error::<T>::InvalidStartHeight
+        );
+
+        Self::insert_block_hash(MAIN_CHAIN_ID, block_height, block_header.hash);
+        Self::mutate_block_chain_from_id(
+            MAIN_CHAIN_ID,
+            BlockChain {
+                max_height: block_height,
+                ..Self::get_block_chain_from_id(MAIN_CHAIN_ID)?
+            },
+        );
+        Self::store_rich_header(*block_header, block_height, MAIN_CHAIN_ID);
+        Self::update_chain_head(block_header, block_height);
+        Ok(())
+    }
+
+    /// Checks that the block does not conflict with a checkpoint at its height
+    fn ensure_no_checkpoint_conflict(block_header: &BlockHeader, block_height: u32) -> DispatchResult {
+        if let Some(checkpoint) = Self::checkpoint(block_height) {
+            ensure!(checkpoint == block_header.hash, Error::<T>::CheckpointConflict);
+        }
+        Ok(())
+    }
+
+    /// Get the height of the first block of the chain that is not part of the main chain, if any
+    fn get_fork_point(blockchain: &BlockChain) -> Result<Option<u32>, DispatchError> {
+        let mut blockchain = blockchain.clone();
+        // every parent chain starts below its child chain, so this terminates
+        while blockchain.chain_id != MAIN_CHAIN_ID {
+            let first_block_header = Self::get_block_header_from_height(&blockchain, blockchain.start_height)?;
+            let parent_block_header =
+                Self::get_block_header_from_hash(first_block_header.block_header.hash_prev_block)?;
+            if parent_block_header.chain_id == MAIN_CHAIN_ID {
+                return Ok(Some(blockchain.start_height));
+            }
+            blockchain = Self::get_block_chain_from_id(parent_block_header.chain_id)?;
+        }
+        Ok(None)
+    }
+
+    /// Checks if the fork diverges from the main chain at or below the latest checkpoint. Forks
+    /// whose fork point can no longer be determined are being pruned and count as below.
+    fn is_below_checkpoint(blockchain: &BlockChain) -> bool {
+        match Self::latest_checkpoint() {
+            Some((checkpoint_height, _)) => match Self::get_fork_point(blockchain) {
+                Ok(fork_point) => fork_point.map_or(false, |fork_point| fork_point <= checkpoint_height),
+                Err(_) => true,
+            },
+            None => false,
+        }
     }
 
     /// For internal testing
@@ -1349,6 +2564,11 @@ impl<T: Config> Pallet<T> {
         DisableDifficultyCheck::<T>::put(disabled);
     }
 
+    /// For internal testing
+    pub fn set_pow_network(network: PowNetwork) {
+        ProofOfWorkNetwork::<T>::put(network);
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     pub fn initialize_and_store_max(
         relayer: T::AccountId,
@@ -1357,16 +2577,6 @@ impl<T: Config> Pallet<T> {
         vout: Vec<TransactionOutput>,
         max_tx_size: usize,
     ) -> FullTransactionProof {
-        let init_block = BlockBuilder::new()
-            .with_version(4)
-            .with_coinbase(&BtcAddress::default(), 50, 3)
-            .with_timestamp(u32::MAX)
-            .mine(U256::from(2).pow(254.into()))
-            .unwrap();
-        let init_block_hash = init_block.header.hash;
-        ext::security::set_active_block_number::<T>(1u32.into());
-        Self::_initialize(relayer.clone(), init_block.header, 0).unwrap();
-
         let mut transaction = TransactionBuilder::build_max(vin, vout);
         let min_tx_size = transaction.size_no_witness();
         let padding = max_tx_size
@@ -1376,7 +2586,32 @@ impl<T: Config> Pallet<T> {
         transaction.inputs[0].pad_script(padding);
         assert_eq!(transaction.size_no_witness(), max_tx_size, "Wrong transaction size");
 
-        let block = BlockBuilder::build_max(init_block_hash, hashes, transaction.clone());
+        Self::initialize_and_store_transaction(relayer, hashes, transaction)
+    }
+
+    /// Initializes the relay and stores a block containing `transaction`, returning its proof
+    #[cfg(feature = "runtime-benchmarks")]
+    pub fn initialize_and_store_transaction(
+        relayer: T::AccountId,
+        hashes: u32,
+        transaction: Transaction,
+    ) -> FullTransactionProof {
+        let init_block = BlockBuilder::new()
+            .with_version(4)
+            .with_coinbase(&BtcAddress::default(), 50, 3)
+            .with_timestamp(1588813835)
+            .mine(U256::from(2).pow(254.into()))
+            .unwrap();
+        let init_block_hash = init_block.header.hash;
+        ext::security::set_active_block_number::<T>(1u32.into());
+        Self::_initialize(relayer.clone(), init_block.header, 0).unwrap();
+
+        let block = BlockBuilder::build_max(
+            init_block_hash,
+            init_block.header.timestamp + 1,
+            hashes,
+            transaction.clone(),
+        );
         let tx_id = transaction.tx_id();
         let merkle_proof = block.merkle_proof(&[tx_id]).unwrap();
 
@@ -1411,7 +2646,7 @@ impl<T: Config> Pallet<T> {
                 .with_previous_hash(block_hash)
                 .with_version(4)
                 .with_coinbase(&BtcAddress::default(), 50, 3)
-                .with_timestamp(u32::MAX)
+                .with_timestamp(Self::get_median_time_past(block_hash).unwrap() + 1)
                 .mine(U256::from(2).pow(254.into()))
                 .unwrap();
             block_hash = block.header.hash;
@@ -1444,6 +2679,25 @@ impl<T: Config> From<BitcoinError> for Error<T> {
             BitcoinError::InvalidCompact => Self::InvalidCompact,
             BitcoinError::BoundExceeded => Self::BoundExceeded,
             BitcoinError::InvalidTxid => Self::InvalidTxid,
+            BitcoinError::InvalidAddressEncoding
+            | BitcoinError::InvalidAddressChecksum
+            | BitcoinError::AddressNetworkMismatch => Self::InvalidBtcAddress,
+            BitcoinError::InvalidSighashType | BitcoinError::InvalidInputIndex | BitcoinError::InvalidPrevouts => {
+                Self::MalformedTransaction
+            }
+            BitcoinError::UnsupportedOpcode
+            | BitcoinError::InvalidStackOperation
+            | BitcoinError::UnbalancedConditional
+            | BitcoinError::UnsatisfiedLockTime
+            | BitcoinError::ScriptVerificationFailed => Self::InvalidScript,
+            BitcoinError::InvalidPsbt
+            | BitcoinError::DuplicatePsbtKey
+            | BitcoinError::UnsupportedPsbtVersion
+            | BitcoinError::PsbtInputNotFinalized => Self::MalformedTransaction,
+            BitcoinError::InvalidWtxid => Self::InvalidWtxid,
+            BitcoinError::InvalidWitnessCommitment => Self::InvalidWitnessCommitment,
+            BitcoinError::InsufficientFeeRate => Self::InsufficientFeeRate,
+            BitcoinError::InvalidSignetSolution => Self::InvalidSignetSolution,
         }
     }
 }
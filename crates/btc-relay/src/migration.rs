@@ -0,0 +1,257 @@
+use super::*;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+/// The log target.
+const TARGET: &'static str = "runtime::btc-relay::migration::v1";
+
+/// The original data layout of the btc-relay pallet without a specific version number.
+mod v0 {
+    use super::*;
+
+    #[frame_support::storage_alias]
+    pub(super) type BlockHeaders<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, H256Le, RichBlockHeader<BlockNumberFor<T>>, OptionQuery>;
+
+    #[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+    pub struct RichBlockHeader<BlockNumber> {
+        pub block_header: BlockHeader,
+        /// height of the block in the bitcoin chain
+        pub block_height: u32,
+        /// id if the chain that this block belongs to
+        pub chain_id: u32,
+        /// active_block_number of the parachain at the time this block was submitted
+        pub para_height: BlockNumber,
+    }
+}
+
+pub mod v1 {
+    use super::*;
+
+    #[cfg(feature = "try-runtime")]
+    use sp_runtime::TryRuntimeError;
+
+    /// Migration which adds the cumulative chain work, the median time past and the last target
+    /// not at the minimum difficulty to the stored block headers. The headers are migrated over
+    /// multiple blocks by `migrate_block_headers`, this only records the height to start from.
+    pub struct Migration<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "can only upgrade from version 0");
+            Ok(Vec::new())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let weight = T::DbWeight::get().reads(1);
+            if StorageVersion::get::<Pallet<T>>() != 0 {
+                log::warn!(
+                    target: TARGET,
+                    "skipping on_runtime_upgrade: executed on wrong storage version.\
+                Expected version 0"
+                );
+                return weight;
+            }
+
+            if Pallet::<T>::best_block_exists() {
+                HeaderMigrationHeight::<T>::put(StartBlockHeight::<T>::get());
+            }
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            weight.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "must upgrade");
+            assert_eq!(
+                HeaderMigrationHeight::<T>::get().is_some(),
+                Pallet::<T>::best_block_exists(),
+                "must migrate the stored block headers"
+            );
+            Ok(())
+        }
+    }
+
+    /// Migrates the stored block headers in order of their height, starting at `block_height`,
+    /// for as many heights as fit in `max_weight`. All chains are migrated at a height before
+    /// moving on, since the first header of a fork follows a header of another chain.
+    ///
+    /// # Returns
+    ///
+    /// The consumed weight
+    pub fn migrate_block_headers<T: Config>(mut block_height: u32, max_weight: Weight) -> Weight {
+        let chains = ChainsIndex::<T>::iter_values().collect::<Vec<_>>();
+        let mut used_weight = T::DbWeight::get().reads_writes(chains.len() as u64, 1);
+        let max_height = chains.iter().map(|chain| chain.max_height).max().unwrap_or_default();
+        // the header, its predecessors for the chain work, median time past and last non-minimum
+        // difficulty target, the network and its hash
+        let header_weight = T::DbWeight::get().reads_writes(4 + MEDIAN_TIME_SPAN as u64, 1);
+
+        while block_height <= max_height {
+            let chain_ids = chains
+                .iter()
+                .filter(|chain| chain.start_height <= block_height && block_height <= chain.max_height)
+                .map(|chain| chain.chain_id)
+                .collect::<Vec<_>>();
+            let height_weight = header_weight.saturating_mul(chain_ids.len() as u64);
+            if !used_weight.saturating_add(height_weight).all_lte(max_weight) {
+                HeaderMigrationHeight::<T>::put(block_height);
+                return used_weight;
+            }
+            for chain_id in chain_ids {
+                if let Ok(block_hash) = ChainsHashes::<T>::try_get(chain_id, block_height) {
+                    migrate_block_header::<T>(block_hash);
+                }
+            }
+            used_weight.saturating_accrue(height_weight);
+            block_height.saturating_inc();
+        }
+
+        log::info!(target: TARGET, "migrated the block headers up to height {}", max_height);
+        HeaderMigrationHeight::<T>::kill();
+        // the forks were never removed before, so count the stored chains once
+        ChainsCount::<T>::put(chains.len() as u32);
+        used_weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    fn migrate_block_header<T: Config>(block_hash: H256Le) {
+        let old = match v0::BlockHeaders::<T>::get(block_hash) {
+            Some(old) => old,
+            None => return,
+        };
+        // the work of the headers before the initial block is not counted
+        let prev_chain_work = BlockHeaders::<T>::try_get(old.block_header.hash_prev_block)
+            .map(|prev_block_header| prev_block_header.chain_work)
+            .unwrap_or_default();
+        let new = RichBlockHeader {
+            chain_work: prev_chain_work.saturating_add(old.block_header.work()),
+            median_time_past: Pallet::<T>::compute_median_time_past(&old.block_header),
+            last_non_min_difficulty_target: Pallet::<T>::compute_last_non_min_difficulty_target(
+                &old.block_header,
+                old.block_height,
+            ),
+            ..RichBlockHeader::new(old.block_header, old.chain_id, old.block_height, old.para_height)
+        };
+        BlockHeaders::<T>::insert(block_hash, new);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{Test as T, *};
+    use frame_support::assert_err;
+
+    #[test]
+    fn migration_works() {
+        run_test(|| {
+            StorageVersion::new(0).put::<Pallet<T>>();
+
+            let mut block_headers = Vec::new();
+            let mut prev_hash = H256Le::zero();
+            for i in 0..3u32 {
+                let block_header = BlockHeader {
+                    target: U256::from(2).pow(254.into()) >> i,
+                    timestamp: 1_600_000_000 + i * 600,
+                    hash_prev_block: prev_hash,
+                    hash: H256Le::from_bytes_le(&[i as u8 + 1; 32]),
+                    ..Default::default()
+                };
+                prev_hash = block_header.hash;
+                block_headers.push(block_header);
+            }
+            // insert the headers out of order
+            for (block_height, block_header) in block_headers.iter().enumerate().rev() {
+                v0::BlockHeaders::<T>::insert(
+                    block_header.hash,
+                    v0::RichBlockHeader {
+                        block_header: *block_header,
+                        block_height: block_height as u32 + 10,
+                        chain_id: 0,
+                        para_height: 5,
+                    },
+                );
+            }
+
+            for (block_height, block_header) in block_headers.iter().enumerate() {
+                crate::ChainsHashes::<T>::insert(MAIN_CHAIN_ID, block_height as u32 + 10, block_header.hash);
+            }
+            crate::ChainsIndex::<T>::insert(
+                MAIN_CHAIN_ID,
+                BlockChain {
+                    chain_id: MAIN_CHAIN_ID,
+                    start_height: 10,
+                    max_height: 12,
+                },
+            );
+            crate::StartBlockHeight::<T>::put(10);
+            crate::BestBlock::<T>::put(block_headers[2].hash);
+
+            // a fork that diverges after the first block
+            let fork_header = BlockHeader {
+                target: U256::from(2).pow(254.into()),
+                timestamp: 1_600_000_000 + 300,
+                hash_prev_block: block_headers[0].hash,
+                hash: H256Le::from_bytes_le(&[9; 32]),
+                ..Default::default()
+            };
+            v0::BlockHeaders::<T>::insert(
+                fork_header.hash,
+                v0::RichBlockHeader {
+                    block_header: fork_header,
+                    block_height: 11,
+                    chain_id: 1,
+                    para_height: 5,
+                },
+            );
+            crate::ChainsHashes::<T>::insert(1, 11, fork_header.hash);
+            crate::ChainsIndex::<T>::insert(
+                1,
+                BlockChain {
+                    chain_id: 1,
+                    start_height: 11,
+                    max_height: 11,
+                },
+            );
+
+            v1::Migration::<T>::on_runtime_upgrade();
+
+            assert_eq!(BTCRelay::header_migration_height(), Some(10));
+            assert_err!(
+                BTCRelay::_store_block_header(
+                    &3,
+                    BlockHeader {
+                        hash_prev_block: block_headers[2].hash,
+                        hash: H256Le::from_bytes_le(&[4; 32]),
+                        ..Default::default()
+                    }
+                ),
+                TestError::BlockHeadersMigrating
+            );
+
+            BTCRelay::on_idle(1, Weight::MAX);
+
+            assert_eq!(BTCRelay::header_migration_height(), None);
+            assert_eq!(crate::ChainsCount::<T>::get(), 2);
+            let fork = crate::BlockHeaders::<T>::get(fork_header.hash);
+            assert_eq!(fork.chain_id, 1);
+            assert_eq!(fork.chain_work, block_headers[0].work() + fork_header.work());
+            assert_eq!(fork.median_time_past, 1_600_000_000 + 300);
+            assert_eq!(StorageVersion::get::<Pallet<T>>(), 1);
+            let mut chain_work = U256::zero();
+            for (i, block_header) in block_headers.iter().enumerate() {
+                chain_work = chain_work + block_header.work();
+                let new = crate::BlockHeaders::<T>::get(block_header.hash);
+                assert_eq!(new.block_header, *block_header);
+                assert_eq!(new.block_height, i as u32 + 10);
+                assert_eq!(new.chain_id, 0);
+                assert_eq!(new.para_height, 5);
+                assert_eq!(new.chain_work, chain_work);
+                // median of the timestamps of the block and its predecessors
+                assert_eq!(new.median_time_past, 1_600_000_000 + (i as u32 + 1) / 2 * 600);
+                assert_eq!(new.last_non_min_difficulty_target, block_header.target);
+            }
+        });
+    }
+}
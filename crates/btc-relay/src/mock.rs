@@ -22,6 +22,7 @@ frame_support::construct_runtime!(
     {
         System: frame_system::{Pallet, Call, Storage, Config<T>, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
 
         // Operational
         BTCRelay: btc_relay::{Pallet, Call, Config<T>, Storage, Event<T>},
@@ -30,6 +31,7 @@ frame_support::construct_runtime!(
 );
 
 pub type AccountId = u64;
+pub type Balance = u128;
 pub type BlockNumber = u64;
 pub type Moment = u64;
 pub type Nonce = u64;
@@ -56,7 +58,7 @@ impl frame_system::Config for Test {
     type BlockHashCount = BlockHashCount;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -65,14 +67,37 @@ impl frame_system::Config for Test {
     type MaxConsumers = ConstU32<16>;
 }
 
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type MaxHolds = ();
+}
+
 parameter_types! {
     pub const ParachainBlocksPerBitcoinBlock: BlockNumber = 100;
+    pub const RelayerRewardPot: AccountId = 1000;
 }
 
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ParachainBlocksPerBitcoinBlock = ParachainBlocksPerBitcoinBlock;
     type WeightInfo = ();
+    type Currency = Balances;
+    type RelayerRewardPot = RelayerRewardPot;
 }
 
 parameter_types! {
@@ -105,6 +130,10 @@ impl ExtBuilder {
             parachain_confirmations: PARACHAIN_CONFIRMATIONS,
             disable_difficulty_check: false,
             disable_inclusion_check: false,
+            pow_network: Default::default(),
+            signet_challenge: vec![],
+            header_retention: Default::default(),
+            checkpoints: Default::default(),
         }
         .assimilate_storage(&mut storage)
         .unwrap();
@@ -6,8 +6,8 @@ use crate::{ext, mock::*, types::*, BtcAddress, Error, DIFFICULTY_ADJUSTMENT_INT
 type Event = crate::Event<Test>;
 
 use crate::{Chains, ChainsIndex};
-use bitcoin::{merkle::*, parser::*, types::*};
-use frame_support::{assert_err, assert_ok};
+use bitcoin::{merkle::*, parser::*, pow::PowNetwork, types::*};
+use frame_support::{assert_err, assert_ok, weights::Weight};
 use mocktopus::mocking::*;
 use sp_std::{
     convert::{TryFrom, TryInto},
@@ -49,6 +49,8 @@ fn get_block_header_from_hash_succeeds() {
             block_height,
             chain_id,
             para_height: Default::default(),
+            chain_work: Default::default(),
+            median_time_past: Default::default(),
         };
 
         BTCRelay::set_block_header_from_hash(rich_header.block_hash(), &rich_header);
@@ -184,6 +186,8 @@ fn store_block_header_on_mainchain_succeeds() {
             block_height,
             chain_id,
             para_height: Default::default(),
+            chain_work: Default::default(),
+            median_time_past: Default::default(),
         };
         BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_header)));
 
@@ -218,6 +222,8 @@ fn store_block_header_on_fork_succeeds() {
             block_height: block_height - 1,
             chain_id,
             para_height: Default::default(),
+            chain_work: Default::default(),
+            median_time_past: Default::default(),
         };
         BTCRelay::get_block_header_from_hash.mock_safe(move |_| MockResult::Return(Ok(rich_header)));
 
@@ -340,7 +346,7 @@ mod store_block_header_tests {
     fn assert_is_block(height: u32, block_header: &BlockHeader) {
         Security::set_active_block_number(ext::security::active_block_number::<Test>() + 1000);
 
-        BTCRelay::ensure_no_ongoing_fork.mock_safe(|_| MockResult::Return(Ok(())));
+        BTCRelay::ensure_no_ongoing_fork.mock_safe(|| MockResult::Return(Ok(())));
         assert_ok!(BTCRelay::verify_block_header_inclusion(block_header.hash, Some(0)));
         BTCRelay::ensure_no_ongoing_fork.clear_mock();
 
@@ -399,10 +405,7 @@ mod store_block_header_tests {
     }
 
     fn assert_ongoing_fork() {
-        assert_err!(
-            BTCRelay::ensure_no_ongoing_fork(BTCRelay::get_best_block_height()),
-            TestError::OngoingFork
-        );
+        assert_err!(BTCRelay::ensure_no_ongoing_fork(), TestError::OngoingFork);
     }
 
     fn store_header_and_check_invariants(block: &BlockHeader) {
@@ -645,6 +648,7 @@ fn check_and_do_reorg_swap_fork_position() {
 
         assert_eq!(current_position, fork_position);
 
+        mock_chain_work_by_height();
         assert_ok!(BTCRelay::reorganize_chains(&fork));
         // assert that positions have been swapped
         let new_position = BTCRelay::get_chain_position_from_chain_id(fork_chain_id).unwrap();
@@ -688,6 +692,7 @@ fn check_and_do_reorg_new_fork_is_main_chain() {
 
         BTCRelay::swap_main_blockchain.mock_safe(move |_| MockResult::Return(Ok((best_block_hash, fork_block_height))));
 
+        mock_chain_work_by_height();
         assert_ok!(BTCRelay::reorganize_chains(&fork));
         // assert that the new main chain is set
         let reorg_event = TestEvent::BTCRelay(Event::ChainReorg {
@@ -730,6 +735,7 @@ fn check_and_do_reorg_new_fork_below_stable_transaction_confirmations() {
 
         BTCRelay::swap_main_blockchain.mock_safe(move |_| MockResult::Return(Ok((best_block_hash, fork_block_height))));
 
+        mock_chain_work_by_height();
         assert_ok!(BTCRelay::reorganize_chains(&fork));
         // assert that the fork has not overtaken the main chain
         let ahead_event = TestEvent::BTCRelay(Event::ForkAheadOfMainChain {
@@ -752,6 +758,7 @@ fn insert_sorted_succeeds() {
         let main_position: u32 = 0;
         let main = get_empty_block_chain_from_chain_id_and_height(main_chain_id, main_start_height, main_block_height);
         BTCRelay::set_block_chain_from_id(main_chain_id, &main);
+        mock_chain_work_by_height();
         assert_eq!(Ok(()), BTCRelay::insert_sorted(&main));
 
         let curr_main_pos = BTCRelay::get_chain_position_from_chain_id(main_chain_id).unwrap();
@@ -892,6 +899,81 @@ fn test_verify_block_header_missing_retarget_succeeds() {
     })
 }
 
+#[test]
+fn test_verify_block_header_rejects_timewarp() {
+    let target = U256::from(2).pow(254.into());
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+    let prev_block = BlockBuilder::new()
+        .with_coinbase(&miner, 50, 2015)
+        .with_timestamp(1_600_000_000)
+        .mine(target)
+        .unwrap();
+    let prev_block_header = RichBlockHeader::<BlockNumber>::new(
+        prev_block.header,
+        0,
+        DIFFICULTY_ADJUSTMENT_INTERVAL - 1,
+        Default::default(),
+    );
+    let mine = |timestamp: u32| {
+        BlockBuilder::new()
+            .with_coinbase(&miner, 50, DIFFICULTY_ADJUSTMENT_INTERVAL)
+            .with_timestamp(timestamp)
+            .with_previous_hash(prev_block.header.hash)
+            .mine(target)
+            .unwrap()
+            .header
+    };
+
+    run_test(|| {
+        BTCRelay::set_pow_network(PowNetwork::Testnet4);
+        BTCRelay::compute_new_target.mock_safe(move |_, _| MockResult::Return(Ok(target)));
+
+        // the first block of a period may be up to 10 minutes older than the previous block
+        let block_header = mine(1_600_000_000 - 601);
+        assert_err!(
+            BTCRelay::verify_block_header(&block_header, DIFFICULTY_ADJUSTMENT_INTERVAL, prev_block_header),
+            TestError::TimewarpAttack
+        );
+        let block_header = mine(1_600_000_000 - 600);
+        assert_ok!(BTCRelay::verify_block_header(
+            &block_header,
+            DIFFICULTY_ADJUSTMENT_INTERVAL,
+            prev_block_header
+        ));
+
+        // the rule only applies to the first block of a period
+        let mut prev_block_header = prev_block_header;
+        prev_block_header.block_height = DIFFICULTY_ADJUSTMENT_INTERVAL;
+        assert_ok!(BTCRelay::verify_block_header(
+            &mine(1_600_000_000 - 601),
+            DIFFICULTY_ADJUSTMENT_INTERVAL + 1,
+            prev_block_header
+        ));
+
+        // and only to networks which enforce BIP-94
+        BTCRelay::set_pow_network(PowNetwork::Mainnet);
+        prev_block_header.block_height = DIFFICULTY_ADJUSTMENT_INTERVAL - 1;
+        assert_ok!(BTCRelay::verify_block_header(
+            &mine(1_600_000_000 - 601),
+            DIFFICULTY_ADJUSTMENT_INTERVAL,
+            prev_block_header
+        ));
+    })
+}
+
+#[test]
+fn test_compute_new_target_without_retargeting() {
+    run_test(|| {
+        BTCRelay::set_pow_network(PowNetwork::Regtest);
+        let prev_block_header = sample_parsed_genesis_header(0, DIFFICULTY_ADJUSTMENT_INTERVAL - 1);
+        // no headers of the previous period need to be stored
+        assert_eq!(
+            BTCRelay::compute_new_target(&prev_block_header, DIFFICULTY_ADJUSTMENT_INTERVAL),
+            Ok(prev_block_header.block_header.target)
+        );
+    })
+}
+
 #[test]
 fn test_compute_new_target() {
     let chain_id: u32 = 0;
@@ -1363,6 +1445,30 @@ fn test_get_and_verify_issue_payment_with_tx_containing_taproot() {
     })
 }
 
+#[test]
+fn test_get_and_verify_issue_payment_to_taproot_address() {
+    run_test(|| {
+        BTCRelay::_verify_transaction_inclusion.mock_safe(|_, _| {
+            let raw_tx = "010000000001013413e41f47eecad702082578c35a2925217056fd0a837b22f1a205fe178a010d0500000000ffffffff19771000000000000017a91415f691c1905082c300362d48540846c30855162d877a1000000000000022512038234fa3e3ca718dfadfb540c320180e68798e67e0a9d4f10d98ea33d37caf047a100000000000001976a914d73838271ee26471aa3640915ed7274b49435b6688acee2000000000000016001470eab26ae0074a58802acc7c38cd9941619c408d14250000000000001976a91479ef95650e8284c3be439d888cf2ee2d1d8ef63088ac3129000000000000160014a558dd2db8167e069f580da2482a9b73dc4f5960217f0000000000001976a91409f3607112083fb1ffe3718214a8e5d5eb0da46188ac04a50000000000001600149215c14609d581aacaa54f629e823cc8abd17ee6c7cd00000000000017a9146da59c9a54a5465402884712bbbe140bc68a4f218728f700000000000017a914ed99cbd06b43b4e3741d1457f7af7b24c2e8d12487ae380100000000001976a91448296f6f29c497f59193ab4e7def5f2e03ef2f9988ac654901000000000017a914ba997376b5daaa3707aefdf30cc09745b579df2187a6a301000000000017a914ffed3c6e71adc2b73939d6951f4655ed1432909b87ec9202000000000017a9147759a1bffe2acca168afdb5b106250b02a703b2887d63603000000000016001439fef3095e8a3bce11ce471aa602bf3e3609d8ddae3703000000000017a914ea0d18bbd804d17a1f2f07ed9aa1670721777d2287cd370300000000001976a914bcc6bcffe584761176d8f510896e882f838208d988ac1d3803000000000016001470eb59ad925fdec71ca0ec50cf7c6b9bbe8dc7592f380300000000001976a91447eb6c94d7b2ac0c11eb3957c0844d333e21d02e88ac724803000000000016001470eb59ad925fdec71ca0ec50cf7c6b9bbe8dc759692e050000000000160014ae26178c1a9b4adb6f24f047fa119e034205900c381b10000000000017a914c9e20b0d7e46d07a878585955ca377db833d181587d32b20000000000017a914bbfcd0b601046e1656ba9b74a98ee8d362d5b63687402f200000000000160014ca146a720a30ca404e979df59d3ddca039e8fd58f22fea0000000000220020935f3eb059cd94bd307e6378bd590724f361f0316fd0964eb5952f274dfb7b4f0400483045022100c9fc44a423e31fc792f5d255ae09ffdc0b224cb70fcebacd52183ce2813ba11d022046c8530230f644be4a05f25bd6a2264b99afc7e3e38531d4bde12d477d03f18001473044022027f50b14154123b173286db76e189a32973a13b0b4ca425329533229cf7f8d9a02202cea81a657ee654c63ab4a01a741931378abae036435a1d695622216596d9e27016952210257bf4070df9735de32305f3bc25320d331edb10c662423e06cd1e50bc58d8fa7210246454540c4e36ba6a481347d0194ffe476640289aecfd2d3f3db1328415b9a5c210248e0a3385d6f744ae81779e10f8ccafbbed7d44debf08a2b0d5250e2f0a0e84853aef0210b00";
+            let tx_bytes = hex::decode(&raw_tx).unwrap();
+            let transaction = parse_transaction(&tx_bytes).unwrap();
+
+            MockResult::Return(Ok(transaction))
+        });
+
+        // check the second output address, which is a taproot output
+        let raw_address = "38234fa3e3ca718dfadfb540c320180e68798e67e0a9d4f10d98ea33d37caf04";
+        let address_bytes = hex::decode(&raw_address).unwrap();
+        let address_hash = H256::from_slice(&address_bytes);
+        let recipient_btc_address = BtcAddress::P2TRv1(address_hash);
+
+        assert_ok!(
+            BTCRelay::get_and_verify_issue_payment::<i64>(sample_unchecked_transaction(), recipient_btc_address),
+            4218
+        );
+    })
+}
+
 #[test]
 fn test_check_bitcoin_confirmations_insecure_succeeds() {
     run_test(|| {
@@ -1537,17 +1643,76 @@ fn fake_coinbase_gets_rejected() {
     })
 }
 
+#[test]
+fn test_verify_witness_transaction_inclusion() {
+    let target = U256::from(2).pow(254.into());
+    let some_address = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+
+    run_test(|| {
+        BTCRelay::check_parachain_confirmations.mock_safe(|_| MockResult::Return(Ok(())));
+
+        let transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_input(TransactionInputBuilder::new().add_witness(&[1; 72]).build())
+            .add_output(TransactionOutput::payment(100, &some_address))
+            .build();
+
+        // the witness commitment is added to the coinbase when mining
+        let block = BlockBuilder::new()
+            .with_coinbase(&some_address, 50, 0)
+            .with_timestamp(1588814835)
+            .add_transaction(transaction.clone())
+            .mine(target)
+            .unwrap();
+        let coinbase = block.transactions[0].clone();
+        assert!(coinbase.witness_commitment().is_some());
+        assert_ok!(BTCRelay::_initialize(3, block.header, 0));
+
+        let partial_proof = |transaction: &Transaction| PartialTransactionProof {
+            transaction: transaction.clone(),
+            tx_encoded_len: u32::MAX,
+            merkle_proof: block.merkle_proof(&[transaction.tx_id()]).unwrap(),
+        };
+        let proof = WitnessTransactionProof {
+            transaction_proof: FullTransactionProof {
+                user_tx_proof: partial_proof(&transaction),
+                coinbase_proof: partial_proof(&coinbase),
+            },
+            witness_proof: block.witness_merkle_proof(&[transaction.tx_id()]).unwrap(),
+            tx_encoded_len: u32::MAX,
+        };
+
+        assert_ok!(
+            BTCRelay::_verify_witness_transaction_inclusion(proof.clone(), Some(0)),
+            transaction
+        );
+
+        // the txid does not commit to the witness, so only the wtxid proof catches this
+        let mut tampered_proof = proof;
+        tampered_proof.transaction_proof.user_tx_proof.transaction.inputs[0].witness[0][0] ^= 1;
+        assert_err!(
+            BTCRelay::_verify_witness_transaction_inclusion(tampered_proof, Some(0)),
+            TestError::InvalidWtxid
+        );
+    })
+}
+
 #[test]
 fn store_generated_block_headers() {
     let target = U256::from(2).pow(254.into());
     let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
 
     run_test(|| {
-        let mut last_block = BlockBuilder::new().with_coinbase(&miner, 50, 0).mine(target).unwrap();
+        let mut last_block = BlockBuilder::new()
+            .with_coinbase(&miner, 50, 0)
+            .with_timestamp(1_600_000_000)
+            .mine(target)
+            .unwrap();
         assert_ok!(BTCRelay::_initialize(3, last_block.header, 0));
         for i in 1..20 {
             last_block = BlockBuilder::new()
                 .with_coinbase(&miner, 50, i)
+                .with_timestamp(1_600_000_000 + i * 600)
                 .with_previous_hash(last_block.header.hash)
                 .mine(target)
                 .unwrap();
@@ -1559,6 +1724,945 @@ fn store_generated_block_headers() {
     })
 }
 
+#[test]
+fn test_fork_choice_uses_chain_work() {
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+    let mine = |prev_hash: H256Le, height: u32, target: U256| {
+        BlockBuilder::new()
+            .with_coinbase(&miner, 50, height)
+            .with_timestamp(1_600_000_000 + height * 600)
+            .with_previous_hash(prev_hash)
+            .mine(target)
+            .unwrap()
+            .header
+    };
+    // a block at each target is worth 1, 3, 15 and 255 times the easiest one
+    let [target_1, target_3, target_15, target_255] =
+        [255, 254, 252, 248].map(|exp: u32| U256::from(2).pow(exp.into()));
+
+    run_test(|| {
+        BTCRelay::set_disable_difficulty_check(true);
+        let genesis = mine(H256Le::zero(), 0, target_3);
+        assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+
+        let mut main_tip = genesis;
+        for height in 1..=2 {
+            main_tip = mine(main_tip.hash, height, target_15);
+            assert_ok!(BTCRelay::_store_block_header(&3, main_tip));
+        }
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(main_tip.hash).unwrap().chain_work,
+            U256::from(33)
+        );
+
+        // a longer fork with less work does not replace the main chain
+        let mut fork_tip = genesis;
+        for height in 1..=10 {
+            fork_tip = mine(fork_tip.hash, height, target_1);
+            assert_ok!(BTCRelay::_store_block_header(&3, fork_tip));
+        }
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(fork_tip.hash).unwrap().chain_work,
+            U256::from(13)
+        );
+        assert_eq!(BTCRelay::get_best_block(), main_tip.hash);
+        assert_eq!(BTCRelay::get_best_block_height(), 2);
+
+        // a shorter fork replaces the main chain once it is the work of the stable confirmations ahead
+        let heavy_block = mine(genesis.hash, 1, target_255);
+        assert_ok!(BTCRelay::_store_block_header(&3, heavy_block));
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(heavy_block.hash)
+                .unwrap()
+                .chain_work,
+            U256::from(258)
+        );
+        assert_eq!(BTCRelay::get_best_block(), heavy_block.hash);
+        assert_eq!(BTCRelay::get_best_block_height(), 1);
+
+        // the replaced blocks are moved off the main chain
+        let main_chain = BTCRelay::get_block_chain_from_id(crate::MAIN_CHAIN_ID).unwrap();
+        assert_eq!(main_chain.max_height, 1);
+        assert_ne!(
+            BTCRelay::get_block_header_from_hash(main_tip.hash).unwrap().chain_id,
+            crate::MAIN_CHAIN_ID
+        );
+        assert!(!crate::ChainsHashes::<Test>::contains_key(crate::MAIN_CHAIN_ID, 2));
+    })
+}
+
+#[test]
+fn test_min_difficulty_blocks() {
+    let target = U256::from(2).pow(254.into());
+    let min_difficulty_target = PowNetwork::Regtest.min_difficulty_target().unwrap();
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+    let mine = |previous_block: &Block, height: u32, time_delta: u32, target: U256| {
+        BlockBuilder::new()
+            .with_coinbase(&miner, 50, height)
+            .with_timestamp(previous_block.header.timestamp + time_delta)
+            .with_previous_hash(previous_block.header.hash)
+            .mine(target)
+            .unwrap()
+    };
+
+    run_test(|| {
+        BTCRelay::set_pow_network(PowNetwork::Regtest);
+        let genesis_block = BlockBuilder::new()
+            .with_coinbase(&miner, 50, 1)
+            .with_timestamp(1_600_000_000)
+            .mine(target)
+            .unwrap();
+        // regtest never retargets, so any start height is valid
+        assert_ok!(BTCRelay::_initialize(3, genesis_block.header, 1));
+
+        // a block may be mined at the minimum difficulty after 20 minutes
+        let block_20_minutes = mine(&genesis_block, 2, 1200, min_difficulty_target);
+        assert_err!(
+            BTCRelay::_store_block_header(&3, block_20_minutes.header),
+            TestError::DiffTargetHeader
+        );
+        let min_difficulty_block = mine(&genesis_block, 2, 1201, min_difficulty_target);
+        assert_ok!(BTCRelay::_store_block_header(&3, min_difficulty_block.header));
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(min_difficulty_block.header.hash)
+                .unwrap()
+                .last_non_min_difficulty_target,
+            target
+        );
+
+        // later blocks use the target of the last block which was not mined at the minimum difficulty
+        let block = mine(&min_difficulty_block, 3, 600, min_difficulty_target);
+        assert_err!(
+            BTCRelay::_store_block_header(&3, block.header),
+            TestError::DiffTargetHeader
+        );
+        let block = mine(&min_difficulty_block, 3, 600, target);
+        assert_ok!(BTCRelay::_store_block_header(&3, block.header));
+
+        // mainnet does not allow minimum difficulty blocks
+        BTCRelay::set_pow_network(PowNetwork::Mainnet);
+        let block = mine(&block, 4, 1201, min_difficulty_target);
+        assert_err!(
+            BTCRelay::_store_block_header(&3, block.header),
+            TestError::DiffTargetHeader
+        );
+    })
+}
+
+#[test]
+fn test_transaction_finality_uses_median_time_past() {
+    let target = U256::from(2).pow(254.into());
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+
+    run_test(|| {
+        let mut last_block = BlockBuilder::new()
+            .with_coinbase(&miner, 50, 0)
+            .with_timestamp(1_600_000_000)
+            .mine(target)
+            .unwrap();
+        assert_ok!(BTCRelay::_initialize(3, last_block.header, 0));
+        // the median of a single block is its timestamp
+        assert_eq!(
+            BTCRelay::get_median_time_past(last_block.header.hash),
+            Ok(1_600_000_000)
+        );
+
+        for i in 1..20 {
+            last_block = BlockBuilder::new()
+                .with_coinbase(&miner, 50, i)
+                .with_timestamp(1_600_000_000 + i * 600)
+                .with_previous_hash(last_block.header.hash)
+                .mine(target)
+                .unwrap();
+            assert_ok!(BTCRelay::_store_block_header(&3, last_block.header));
+        }
+        // median of the blocks 9 to 19
+        let median_time_past = 1_600_000_000 + 14 * 600;
+        assert_eq!(
+            BTCRelay::get_median_time_past(last_block.header.hash),
+            Ok(median_time_past)
+        );
+
+        let locked = |lock_at: LockTime| Transaction {
+            lock_at,
+            ..TransactionBuilder::new()
+                .with_version(2)
+                .add_input(TransactionInputBuilder::new().with_sequence(0).build())
+                .build()
+        };
+        assert_ok!(BTCRelay::ensure_transaction_final(&locked(LockTime::BlockHeight(19))));
+        assert_err!(
+            BTCRelay::ensure_transaction_final(&locked(LockTime::BlockHeight(20))),
+            TestError::TransactionNotFinal
+        );
+        assert_ok!(BTCRelay::ensure_transaction_final(&locked(LockTime::Time(
+            median_time_past - 1
+        ))));
+        // the timestamp of the best block is not used
+        assert_err!(
+            BTCRelay::ensure_transaction_final(&locked(LockTime::Time(median_time_past))),
+            TestError::TransactionNotFinal
+        );
+    })
+}
+
+#[test]
+fn test_block_timestamp_checks() {
+    let target = U256::from(2).pow(254.into());
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+    let mine = |prev_hash: H256Le, height: u32, timestamp: u32| {
+        BlockBuilder::new()
+            .with_coinbase(&miner, 50, height)
+            .with_timestamp(timestamp)
+            .with_previous_hash(prev_hash)
+            .mine(target)
+            .unwrap()
+            .header
+    };
+
+    run_test(|| {
+        let mut last_block = mine(H256Le::zero(), 0, 1_600_000_000);
+        assert_ok!(BTCRelay::_initialize(3, last_block, 0));
+        for height in 1..=10 {
+            last_block = mine(last_block.hash, height, 1_600_000_000 + height * 600);
+            assert_ok!(BTCRelay::_store_block_header(&3, last_block));
+        }
+        // median of the blocks 0 to 10
+        let median_time_past = 1_600_000_000 + 5 * 600;
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(last_block.hash)
+                .unwrap()
+                .median_time_past,
+            median_time_past
+        );
+
+        // the timestamp must be later than the median time past, not the previous timestamp
+        assert_err!(
+            BTCRelay::_store_block_header(&3, mine(last_block.hash, 11, median_time_past)),
+            TestError::BlockTimestampTooOld
+        );
+        last_block = mine(last_block.hash, 11, median_time_past + 1);
+        assert_ok!(BTCRelay::_store_block_header(&3, last_block));
+
+        // the timestamp may be at most two hours ahead of the parachain time
+        let now = 1_600_010_000;
+        Timestamp::set_timestamp(now as u64 * 1000);
+        assert_err!(
+            BTCRelay::_store_block_header(&3, mine(last_block.hash, 12, now + 2 * 60 * 60 + 1)),
+            TestError::BlockTimestampTooNew
+        );
+        assert_ok!(BTCRelay::_store_block_header(
+            &3,
+            mine(last_block.hash, 12, now + 2 * 60 * 60)
+        ));
+    })
+}
+
+/// Mines `count` headers on top of `prev_block_header`, the `seed` distinguishes forks
+fn mine_block_headers(prev_block_header: BlockHeader, count: u32, seed: u32) -> Vec<BlockHeader> {
+    let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+    let mut prev_block_header = prev_block_header;
+    (0..count)
+        .map(|_| {
+            prev_block_header = BlockBuilder::new()
+                .with_coinbase(&miner, 50, seed)
+                .with_timestamp(prev_block_header.timestamp + 600)
+                .with_previous_hash(prev_block_header.hash)
+                .mine(U256::from(2).pow(254.into()))
+                .unwrap()
+                .header;
+            prev_block_header
+        })
+        .collect()
+}
+
+fn initialize_with_mined_genesis() -> BlockHeader {
+    let genesis = mine_block_headers(
+        BlockHeader {
+            timestamp: 1_600_000_000,
+            ..Default::default()
+        },
+        1,
+        0,
+    )[0];
+    assert_ok!(BTCRelay::_initialize(3, genesis, 0));
+    genesis
+}
+
+#[test]
+fn store_signet_block_header_requires_solution() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        BTCRelay::set_pow_network(PowNetwork::Signet);
+        BTCRelay::set_disable_difficulty_check(true);
+        let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+        let block = BlockBuilder::new()
+            .with_coinbase(&miner, 50, 1)
+            .with_timestamp(genesis.timestamp + 600)
+            .with_previous_hash(genesis.hash)
+            .mine(U256::from(2).pow(254.into()))
+            .unwrap();
+        let coinbase = block.transactions[0].clone();
+        let coinbase_proof = PartialTransactionProof {
+            tx_encoded_len: coinbase.size_no_witness() as u32,
+            merkle_proof: block.merkle_proof(&[coinbase.tx_id()]).unwrap(),
+            transaction: coinbase,
+        };
+
+        // signet headers can only be stored with their coinbase proof
+        assert_err!(
+            BTCRelay::store_block_header(RuntimeOrigin::signed(3), block.header, u32::MAX),
+            TestError::SignetCoinbaseProofRequired
+        );
+        assert_err!(
+            BTCRelay::store_block_headers(RuntimeOrigin::signed(3), vec![block.header], u32::MAX),
+            TestError::SignetCoinbaseProofRequired
+        );
+
+        // OP_FALSE cannot be satisfied
+        crate::SignetChallenge::<Test>::put(frame_support::BoundedVec::try_from(vec![0x00]).unwrap());
+        assert_err!(
+            BTCRelay::store_signet_block_header(RuntimeOrigin::signed(3), coinbase_proof.clone(), u32::MAX),
+            TestError::InvalidSignetSolution
+        );
+        assert!(!BTCRelay::block_header_exists(block.header.hash));
+
+        // OP_TRUE accepts the empty solution
+        crate::SignetChallenge::<Test>::put(frame_support::BoundedVec::try_from(vec![0x51]).unwrap());
+        assert_ok!(BTCRelay::store_signet_block_header(
+            RuntimeOrigin::signed(3),
+            coinbase_proof,
+            u32::MAX
+        ));
+        assert_eq!(BTCRelay::get_best_block(), block.header.hash);
+    })
+}
+
+#[test]
+fn store_block_headers_keeps_valid_prefix() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+
+        let mut block_headers = mine_block_headers(genesis, 5, 0);
+        // the fourth header does not extend the third one
+        block_headers[3] = mine_block_headers(block_headers[1], 1, 1)[0];
+
+        let results = BTCRelay::_store_block_headers(&3, &block_headers).unwrap();
+        assert_eq!(
+            results,
+            vec![Ok(()), Ok(()), Ok(()), Err(TestError::NonContiguousBlockHeaders.into())]
+        );
+        assert_eq!(BTCRelay::get_best_block(), block_headers[2].hash);
+        assert_eq!(BTCRelay::get_best_block_height(), 3);
+        let main_chain = BTCRelay::get_block_chain_from_id(crate::MAIN_CHAIN_ID).unwrap();
+        assert_eq!(main_chain.max_height, 3);
+        assert!(!BTCRelay::block_header_exists(block_headers[4].hash));
+
+        // a header with the wrong target within the batch is reported, the valid prefix is kept
+        let mut block_headers = mine_block_headers(block_headers[2], 3, 0);
+        let miner = BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap());
+        block_headers[2] = BlockBuilder::new()
+            .with_coinbase(&miner, 50, 0)
+            .with_timestamp(block_headers[1].timestamp + 600)
+            .with_previous_hash(block_headers[1].hash)
+            .mine(U256::from(2).pow(253.into()))
+            .unwrap()
+            .header;
+        assert_ok!(BTCRelay::store_block_headers(
+            RuntimeOrigin::signed(3),
+            block_headers.clone(),
+            u32::MAX
+        ));
+        assert_eq!(BTCRelay::get_best_block(), block_headers[1].hash);
+        let rejected_event = TestEvent::BTCRelay(Event::BlockHeaderRejected {
+            index: 2,
+            block_hash: block_headers[2].hash,
+            error: TestError::DiffTargetHeader.into(),
+        });
+        assert!(System::events().iter().any(|a| a.event == rejected_event));
+
+        // the call fails if the first header is invalid
+        assert_err!(
+            BTCRelay::store_block_headers(RuntimeOrigin::signed(3), block_headers[2..].to_vec(), u32::MAX),
+            TestError::DiffTargetHeader
+        );
+        assert_err!(
+            BTCRelay::store_block_headers(
+                RuntimeOrigin::signed(3),
+                vec![block_headers[2]; crate::MAX_BLOCK_HEADERS_PER_CALL as usize + 1],
+                u32::MAX
+            ),
+            TestError::TooManyBlockHeaders
+        );
+    })
+}
+
+#[test]
+fn store_block_headers_reorganizes_chains() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 2, 0);
+        assert_eq!(
+            BTCRelay::_store_block_headers(&3, &main_block_headers).unwrap(),
+            vec![Ok(()), Ok(())]
+        );
+
+        // a fork which is not yet the stable confirmations ahead of the main chain
+        let fork_block_headers = mine_block_headers(genesis, 3 + BITCOIN_CONFIRMATIONS, 1);
+        let (stored_fork_block_headers, block_headers) = fork_block_headers.split_at(BITCOIN_CONFIRMATIONS as usize);
+        assert!(BTCRelay::_store_block_headers(&3, stored_fork_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        assert_eq!(BTCRelay::get_best_block(), main_block_headers[1].hash);
+        let fork_id = BTCRelay::get_block_header_from_hash(stored_fork_block_headers[0].hash)
+            .unwrap()
+            .chain_id;
+        assert_ne!(fork_id, crate::MAIN_CHAIN_ID);
+
+        // the batch overtakes the main chain with its last header
+        assert_ok!(BTCRelay::store_block_headers(
+            RuntimeOrigin::signed(3),
+            block_headers.to_vec(),
+            u32::MAX
+        ));
+        assert_eq!(BTCRelay::get_best_block(), block_headers[2].hash);
+        assert_eq!(BTCRelay::get_best_block_height(), 3 + BITCOIN_CONFIRMATIONS);
+        for block_header in fork_block_headers.iter() {
+            assert_eq!(
+                BTCRelay::get_block_header_from_hash(block_header.hash)
+                    .unwrap()
+                    .chain_id,
+                crate::MAIN_CHAIN_ID
+            );
+        }
+        assert_ne!(
+            BTCRelay::get_block_header_from_hash(main_block_headers[1].hash)
+                .unwrap()
+                .chain_id,
+            crate::MAIN_CHAIN_ID
+        );
+    })
+}
+
+#[test]
+fn prune_block_headers_keeps_retention_window_and_checkpoints() {
+    run_test(|| {
+        BTCRelay::set_disable_difficulty_check(true);
+        let genesis = mine_block_headers(
+            BlockHeader {
+                timestamp: 1_600_000_000,
+                ..Default::default()
+            },
+            1,
+            0,
+        )[0];
+        assert_ok!(BTCRelay::_initialize(3, genesis, DIFFICULTY_ADJUSTMENT_INTERVAL - 2));
+        let block_headers = mine_block_headers(genesis, 6, 0);
+        assert!(BTCRelay::_store_block_headers(&3, &block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+
+        // pruning is disabled by default
+        BTCRelay::prune_block_headers(Weight::MAX);
+        assert!(BTCRelay::block_header_exists(genesis.hash));
+
+        // keep the best block and the two blocks behind it
+        crate::HeaderRetention::<Test>::put(2);
+        let block_weight = <() as crate::WeightInfo>::prune_block_header();
+        assert_eq!(BTCRelay::prune_block_headers(block_weight * 2), block_weight * 2);
+        assert!(!BTCRelay::block_header_exists(genesis.hash));
+        assert!(!BTCRelay::block_header_exists(block_headers[0].hash));
+        assert!(BTCRelay::block_header_exists(block_headers[2].hash));
+
+        BTCRelay::prune_block_headers(Weight::MAX);
+        // the checkpoint at the difficulty adjustment boundary is kept
+        assert!(BTCRelay::block_header_exists(block_headers[1].hash));
+        assert!(!BTCRelay::block_header_exists(block_headers[2].hash));
+        for block_header in block_headers[3..].iter() {
+            assert!(BTCRelay::block_header_exists(block_header.hash));
+        }
+        assert_err!(
+            BTCRelay::get_block_hash(crate::MAIN_CHAIN_ID, DIFFICULTY_ADJUSTMENT_INTERVAL - 1),
+            TestError::BlockPruned
+        );
+        assert_eq!(
+            BTCRelay::get_block_hash(crate::MAIN_CHAIN_ID, DIFFICULTY_ADJUSTMENT_INTERVAL),
+            Ok(block_headers[1].hash)
+        );
+
+        // proofs against pruned blocks fail with a clear error
+        let coinbase = |block_height: u32| {
+            TransactionBuilder::new()
+                .with_version(2)
+                .add_input(
+                    TransactionInputBuilder::new()
+                        .with_source(TransactionInputSource::Coinbase(Some(block_height)))
+                        .build(),
+                )
+                .build()
+        };
+        let unknown_block_header = mine_block_headers(genesis, 1, 1)[0];
+        assert_err!(
+            BTCRelay::ensure_block_not_pruned(&unknown_block_header, &coinbase(DIFFICULTY_ADJUSTMENT_INTERVAL - 1)),
+            TestError::BlockPruned
+        );
+        assert_ok!(BTCRelay::ensure_block_not_pruned(
+            &unknown_block_header,
+            &coinbase(DIFFICULTY_ADJUSTMENT_INTERVAL + 2)
+        ));
+        assert_ok!(BTCRelay::ensure_block_not_pruned(
+            &block_headers[3],
+            &coinbase(DIFFICULTY_ADJUSTMENT_INTERVAL - 1)
+        ));
+
+        // the chain can still be extended
+        assert_ok!(BTCRelay::_store_block_header(
+            &3,
+            mine_block_headers(block_headers[5], 1, 0)[0]
+        ));
+    })
+}
+
+#[test]
+fn prune_block_headers_removes_abandoned_forks() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 6, 0);
+        assert!(BTCRelay::_store_block_headers(&3, &main_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        let abandoned_fork = mine_block_headers(genesis, 2, 1);
+        assert!(BTCRelay::_store_block_headers(&3, &abandoned_fork)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        let recent_fork = mine_block_headers(main_block_headers[3], 1, 2);
+        assert_ok!(BTCRelay::_store_block_header(&3, recent_fork[0]));
+        let abandoned_fork_id = BTCRelay::get_block_header_from_hash(abandoned_fork[0].hash)
+            .unwrap()
+            .chain_id;
+        let recent_fork_id = BTCRelay::get_block_header_from_hash(recent_fork[0].hash)
+            .unwrap()
+            .chain_id;
+
+        assert_eq!(crate::ChainsCount::<Test>::get(), 3);
+
+        crate::HeaderRetention::<Test>::put(2);
+        BTCRelay::prune_block_headers(Weight::MAX);
+
+        for block_header in abandoned_fork.iter().chain(main_block_headers[..3].iter()) {
+            assert!(!BTCRelay::block_header_exists(block_header.hash));
+        }
+        assert!(BTCRelay::block_header_exists(recent_fork[0].hash));
+        assert!(BTCRelay::get_block_chain_from_id(abandoned_fork_id).is_err());
+        assert_eq!(Chains::<Test>::get(0), Some(crate::MAIN_CHAIN_ID));
+        assert_eq!(Chains::<Test>::get(1), Some(recent_fork_id));
+        assert_eq!(Chains::<Test>::get(2), None);
+        // the fork bound only has to cover the remaining chains
+        assert_eq!(crate::ChainsCount::<Test>::get(), 2);
+    })
+}
+
+#[test]
+fn remove_blockchain_keeps_chain_positions_consecutive() {
+    run_test(|| {
+        for (position, chain_id) in [(0, 0), (1, 4), (2, 2), (3, 7)] {
+            BTCRelay::set_chain_from_position_and_id(position, chain_id);
+            BTCRelay::set_block_chain_from_id(
+                chain_id,
+                &BlockChain {
+                    chain_id,
+                    ..Default::default()
+                },
+            );
+        }
+
+        assert_ok!(BTCRelay::remove_blockchain(4));
+        assert_eq!(
+            Chains::<Test>::iter().collect::<std::collections::BTreeMap<_, _>>(),
+            [(0, 0), (1, 2), (2, 7)].into_iter().collect()
+        );
+        assert!(!ChainsIndex::<Test>::contains_key(4));
+    })
+}
+
+#[test]
+fn set_header_retention_requires_root_and_minimum() {
+    run_test(|| {
+        assert_err!(
+            BTCRelay::set_header_retention(RuntimeOrigin::signed(3), crate::MIN_HEADER_RETENTION),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_err!(
+            BTCRelay::set_header_retention(RuntimeOrigin::root(), crate::MIN_HEADER_RETENTION - 1),
+            TestError::InvalidHeaderRetention
+        );
+        assert_ok!(BTCRelay::set_header_retention(
+            RuntimeOrigin::root(),
+            crate::MIN_HEADER_RETENTION
+        ));
+        assert_eq!(BTCRelay::header_retention(), crate::MIN_HEADER_RETENTION);
+        assert_ok!(BTCRelay::set_header_retention(RuntimeOrigin::root(), 0));
+    })
+}
+
+#[test]
+fn relayers_are_rewarded_for_main_chain_headers() {
+    run_test(|| {
+        Balances::make_free_balance_be(&RelayerRewardPot::get(), 25);
+        assert_err!(
+            BTCRelay::set_relayer_incentives(RuntimeOrigin::signed(3), 10, 5),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BTCRelay::set_relayer_incentives(RuntimeOrigin::root(), 10, 5));
+
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 3, 0);
+        assert_ok!(BTCRelay::_store_block_header(&3, main_block_headers[0]));
+        assert_ok!(BTCRelay::_store_block_header(&4, mine_block_headers(genesis, 1, 1)[0]));
+        assert!(System::events().iter().any(|a| a.event
+            == TestEvent::BTCRelay(Event::RelayerRewarded {
+                relayer_id: 3,
+                block_hash: main_block_headers[0].hash,
+                amount: 10,
+            })));
+
+        // the pot is exhausted, but the header is stored regardless
+        assert_ok!(BTCRelay::_store_block_header(&3, main_block_headers[1]));
+        assert_ok!(BTCRelay::_store_block_header(&3, main_block_headers[2]));
+        assert_eq!(BTCRelay::get_best_block(), main_block_headers[2].hash);
+
+        assert_eq!(Balances::free_balance(3), 20);
+        assert_eq!(Balances::free_balance(RelayerRewardPot::get()), 5);
+        assert_eq!(
+            BTCRelay::relayer_stats(3),
+            crate::RelayerStats {
+                main_chain_headers: 3,
+                last_submission_height: 3,
+                rewards: 20,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            BTCRelay::relayer_stats(4),
+            crate::RelayerStats {
+                fork_headers: 1,
+                last_submission_height: 1,
+                ..Default::default()
+            }
+        );
+    })
+}
+
+#[test]
+fn bonded_relayers_are_slashed_for_orphaned_headers() {
+    run_test(|| {
+        Balances::make_free_balance_be(&3, 100);
+        Balances::make_free_balance_be(&4, 100);
+        assert_ok!(BTCRelay::set_relayer_incentives(RuntimeOrigin::root(), 0, 20));
+        // without pruning, the bond could never be released
+        assert_err!(
+            BTCRelay::bond_relayer(RuntimeOrigin::signed(3), 30),
+            TestError::RelayerBondingDisabled
+        );
+        crate::HeaderRetention::<Test>::put(2);
+        assert_ok!(BTCRelay::bond_relayer(RuntimeOrigin::signed(3), 30));
+        assert_ok!(BTCRelay::bond_relayer(RuntimeOrigin::signed(4), 50));
+
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 6, 0);
+        assert!(BTCRelay::_store_block_headers(&3, &main_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        let orphaned_block_headers = mine_block_headers(genesis, 2, 1);
+        assert!(BTCRelay::_store_block_headers(&4, &orphaned_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        assert_eq!(BTCRelay::relayer_stats(4).pending_headers, 2);
+        // the orphaned headers are only resolved once their fork is pruned
+        assert_err!(
+            BTCRelay::unbond_relayer(RuntimeOrigin::signed(4), 10),
+            TestError::RelayerBondLocked
+        );
+
+        BTCRelay::prune_block_headers(Weight::MAX);
+
+        // main chain headers are pruned without slashing
+        assert_eq!(BTCRelay::relayer_stats(3).bond, 30);
+        assert_eq!(BTCRelay::relayer_stats(3).pending_headers, 3);
+        assert_eq!(
+            BTCRelay::relayer_stats(4),
+            crate::RelayerStats {
+                fork_headers: 2,
+                orphaned_headers: 2,
+                last_submission_height: 2,
+                bond: 10,
+                slashed: 40,
+                ..Default::default()
+            }
+        );
+        assert_eq!(Balances::reserved_balance(4), 10);
+        assert_eq!(Balances::free_balance(RelayerRewardPot::get()), 40);
+        assert!(System::events().iter().any(|a| a.event
+            == TestEvent::BTCRelay(Event::RelayerSlashed {
+                relayer_id: 4,
+                block_hash: orphaned_block_headers[1].hash,
+                amount: 20,
+            })));
+
+        // the bond is locked while submitted headers are still stored
+        assert_err!(
+            BTCRelay::unbond_relayer(RuntimeOrigin::signed(3), 30),
+            TestError::RelayerBondLocked
+        );
+        assert_err!(
+            BTCRelay::unbond_relayer(RuntimeOrigin::signed(4), 11),
+            TestError::InsufficientRelayerBond
+        );
+        assert_ok!(BTCRelay::unbond_relayer(RuntimeOrigin::signed(4), 10));
+        assert_eq!(Balances::free_balance(4), 60);
+        assert_eq!(BTCRelay::relayer_stats(4).bond, 0);
+    })
+}
+
+#[test]
+fn checkpoints_reject_conflicting_headers_and_forks() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 4, 0);
+        assert!(BTCRelay::_store_block_headers(&3, &main_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+
+        assert_err!(
+            BTCRelay::add_checkpoint(RuntimeOrigin::signed(3), main_block_headers[1], 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BTCRelay::add_checkpoint(
+            RuntimeOrigin::root(),
+            main_block_headers[1],
+            2
+        ));
+        assert!(System::events().iter().any(|a| a.event
+            == TestEvent::BTCRelay(Event::CheckpointAdded {
+                block_height: 2,
+                block_hash: main_block_headers[1].hash,
+                reanchored: false,
+            })));
+
+        // forks may only diverge above the checkpoint
+        assert_err!(
+            BTCRelay::_store_block_header(&3, mine_block_headers(genesis, 1, 1)[0]),
+            TestError::ForkBelowCheckpoint
+        );
+        assert_err!(
+            BTCRelay::_store_block_header(&3, mine_block_headers(main_block_headers[0], 1, 1)[0]),
+            TestError::CheckpointConflict
+        );
+        let fork_block_header = mine_block_headers(main_block_headers[1], 1, 1)[0];
+        assert_ok!(BTCRelay::_store_block_header(&3, fork_block_header));
+
+        assert_err!(
+            BTCRelay::add_checkpoint(RuntimeOrigin::root(), main_block_headers[0], 1),
+            TestError::CheckpointHeightTooLow
+        );
+        assert_err!(
+            BTCRelay::add_checkpoint(RuntimeOrigin::root(), fork_block_header, 3),
+            TestError::CheckpointNotOnMainChain
+        );
+    })
+}
+
+#[test]
+fn checkpoints_resolve_forks_below_them() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 4, 0);
+        assert!(BTCRelay::_store_block_headers(&3, &main_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        let fork_block_headers = mine_block_headers(genesis, 3, 1);
+        assert!(BTCRelay::_store_block_headers(&3, &fork_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        let fork_id = BTCRelay::get_block_header_from_hash(fork_block_headers[0].hash)
+            .unwrap()
+            .chain_id;
+        assert_err!(BTCRelay::ensure_no_ongoing_fork(), TestError::OngoingFork);
+
+        assert_ok!(BTCRelay::add_checkpoint(
+            RuntimeOrigin::root(),
+            main_block_headers[0],
+            1
+        ));
+        assert_ok!(BTCRelay::ensure_no_ongoing_fork());
+
+        // forks below the checkpoint are pruned even without a retention window
+        BTCRelay::prune_block_headers(Weight::MAX);
+        for block_header in fork_block_headers.iter() {
+            assert!(!BTCRelay::block_header_exists(block_header.hash));
+        }
+        assert!(BTCRelay::get_block_chain_from_id(fork_id).is_err());
+        assert_eq!(Chains::<Test>::get(1), None);
+        assert!(BTCRelay::block_header_exists(main_block_headers[0].hash));
+    })
+}
+
+#[test]
+fn checkpoints_reanchor_the_relay_above_the_best_block() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 2, 0);
+        assert!(BTCRelay::_store_block_headers(&3, &main_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+
+        // the relay was offline, so the checkpoint does not connect to the stored headers
+        let checkpoint_block_header = mine_block_headers(
+            BlockHeader {
+                timestamp: 1_700_000_000,
+                ..Default::default()
+            },
+            1,
+            2,
+        )[0];
+        assert_err!(
+            BTCRelay::add_checkpoint(RuntimeOrigin::root(), checkpoint_block_header, 2015),
+            TestError::InvalidStartHeight
+        );
+        assert_ok!(BTCRelay::add_checkpoint(
+            RuntimeOrigin::root(),
+            checkpoint_block_header,
+            2016
+        ));
+        assert!(System::events().iter().any(|a| a.event
+            == TestEvent::BTCRelay(Event::CheckpointAdded {
+                block_height: 2016,
+                block_hash: checkpoint_block_header.hash,
+                reanchored: true,
+            })));
+        assert_eq!(BTCRelay::get_best_block(), checkpoint_block_header.hash);
+        assert_eq!(BTCRelay::get_best_block_height(), 2016);
+
+        let next_block_header = mine_block_headers(checkpoint_block_header, 1, 0)[0];
+        assert_ok!(BTCRelay::_store_block_header(&3, next_block_header));
+        assert_eq!(BTCRelay::get_best_block(), next_block_header.hash);
+        assert_eq!(BTCRelay::get_best_block_height(), 2017);
+
+        // the headers from before the outage can no longer be extended
+        assert_err!(
+            BTCRelay::_store_block_header(&3, mine_block_headers(main_block_headers[1], 1, 1)[0]),
+            TestError::ForkBelowCheckpoint
+        );
+    })
+}
+
+#[test]
+fn runtime_api_helpers_describe_relayed_chains() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        let main_block_headers = mine_block_headers(genesis, 3, 0);
+        assert!(BTCRelay::_store_block_headers(&3, &main_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+        let fork_block_headers = mine_block_headers(genesis, 2, 1);
+        assert!(BTCRelay::_store_block_headers(&3, &fork_block_headers)
+            .unwrap()
+            .iter()
+            .all(Result::is_ok));
+
+        let block_header_info = BTCRelay::get_block_header_info(main_block_headers[1].hash).unwrap();
+        assert_eq!(block_header_info.block_height, 2);
+        assert!(block_header_info.on_main_chain);
+        assert_eq!(block_header_info.hash_prev_block, main_block_headers[0].hash);
+        assert_eq!(block_header_info.bits, 0x2040_0000);
+        assert!(
+            !BTCRelay::get_block_header_info(fork_block_headers[0].hash)
+                .unwrap()
+                .on_main_chain
+        );
+        assert_eq!(BTCRelay::get_block_header_info(H256Le::zero()), None);
+
+        assert_eq!(BTCRelay::get_main_chain_block_hash(3), Some(main_block_headers[2].hash));
+        assert_eq!(BTCRelay::get_main_chain_block_hash(4), None);
+
+        let fork_info = BTCRelay::get_fork_info();
+        assert_eq!(fork_info.len(), 1);
+        assert_eq!(fork_info[0].start_height, 1);
+        assert_eq!(fork_info[0].max_height, 2);
+        assert_eq!(fork_info[0].tip_hash, fork_block_headers[1].hash);
+        assert_eq!(fork_info[0].blocks_behind, 1);
+    })
+}
+
+#[test]
+fn relay_is_marked_stale_and_recovers_when_headers_resume() {
+    run_test(|| {
+        let genesis = initialize_with_mined_genesis();
+        assert_err!(
+            BTCRelay::set_staleness_threshold(RuntimeOrigin::signed(3), 10),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BTCRelay::set_staleness_threshold(RuntimeOrigin::root(), 10));
+
+        BTCRelay::check_relay_liveness(11);
+        assert_ok!(BTCRelay::ensure_relay_live());
+        BTCRelay::check_relay_liveness(12);
+        assert_err!(BTCRelay::ensure_relay_live(), TestError::RelayStale);
+        assert!(Security::is_relay_stale());
+        assert!(System::events().iter().any(|a| a.event
+            == TestEvent::BTCRelay(Event::RelayStale {
+                best_block_height: 0,
+                last_update: 1,
+            })));
+
+        System::set_block_number(20);
+        assert_ok!(BTCRelay::_store_block_header(&3, mine_block_headers(genesis, 1, 0)[0]));
+        assert_ok!(BTCRelay::ensure_relay_live());
+        assert!(!Security::is_relay_stale());
+        assert_eq!(BTCRelay::best_block_updated_at(), 20);
+        assert!(System::events()
+            .iter()
+            .any(|a| a.event == TestEvent::BTCRelay(Event::RelayRecovered { best_block_height: 1 })));
+
+        // disabling the check also ends a stale period
+        BTCRelay::check_relay_liveness(31);
+        assert_err!(BTCRelay::ensure_relay_live(), TestError::RelayStale);
+        assert_ok!(BTCRelay::set_staleness_threshold(RuntimeOrigin::root(), 0));
+        assert_ok!(BTCRelay::ensure_relay_live());
+    })
+}
+
+#[test]
+fn enabling_staleness_threshold_starts_from_current_block() {
+    run_test(|| {
+        initialize_with_mined_genesis();
+        System::set_block_number(100);
+        assert_ok!(BTCRelay::set_staleness_threshold(RuntimeOrigin::root(), 10));
+        assert_eq!(BTCRelay::best_block_updated_at(), 100);
+
+        BTCRelay::check_relay_liveness(110);
+        assert_ok!(BTCRelay::ensure_relay_live());
+
+        // changing an enabled threshold does not reset the count
+        System::set_block_number(105);
+        assert_ok!(BTCRelay::set_staleness_threshold(RuntimeOrigin::root(), 5));
+        assert_eq!(BTCRelay::best_block_updated_at(), 100);
+        BTCRelay::check_relay_liveness(106);
+        assert_err!(BTCRelay::ensure_relay_live(), TestError::RelayStale);
+    })
+}
+
 mod op_return_payment_data_tests {
     use super::*;
     use itertools::Itertools;
@@ -1908,6 +3012,7 @@ fn test_check_and_do_reorg() {
         );
 
         BTCRelay::swap_main_blockchain.mock_safe(|_| MockResult::Return(Ok((Default::default(), Default::default()))));
+        mock_chain_work_by_height();
 
         // we should skip empty `Chains`, this can occur if the
         // previous index is accidentally deleted
@@ -2001,6 +3106,12 @@ fn sample_unchecked_transaction() -> FullTransactionProof {
     }
 }
 
+/// Mocks the chainwork of block chains without stored headers as one unit of work per block
+fn mock_chain_work_by_height() {
+    BTCRelay::get_chain_work.mock_safe(|blockchain| MockResult::Return(Ok(blockchain.max_height.into())));
+    BTCRelay::get_stable_chain_work.mock_safe(|_| MockResult::Return(Ok(BITCOIN_CONFIRMATIONS.into())));
+}
+
 fn get_empty_block_chain_from_chain_id_and_height(chain_id: u32, start_height: u32, block_height: u32) -> BlockChain {
     let blockchain = BlockChain {
         chain_id,
@@ -2022,6 +3133,8 @@ fn sample_parsed_genesis_header(chain_id: u32, block_height: u32) -> RichBlockHe
         block_height,
         chain_id,
         para_height: Default::default(),
+        chain_work: Default::default(),
+        median_time_past: Default::default(),
     }
 }
 
@@ -2045,6 +3158,8 @@ fn sample_parsed_first_block(chain_id: u32, block_height: u32) -> RichBlockHeade
         block_height,
         chain_id,
         para_height: Default::default(),
+        chain_work: Default::default(),
+        median_time_past: Default::default(),
     }
 }
 
@@ -2077,6 +3192,8 @@ fn sample_rich_tx_block_header(chain_id: u32, block_height: u32) -> RichBlockHea
         block_height,
         chain_id,
         para_height: Default::default(),
+        chain_work: Default::default(),
+        median_time_past: Default::default(),
     }
 }
 
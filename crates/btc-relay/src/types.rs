@@ -4,7 +4,7 @@ pub use bitcoin::Address as BtcAddress;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{dispatch::DispatchError, ensure};
 use scale_info::TypeInfo;
-use sp_core::H256;
+use sp_core::{H256, U256};
 use sp_std::{convert::TryFrom, vec::Vec};
 
 /// Bitcoin Enriched Block Headers
@@ -17,6 +17,13 @@ pub struct RichBlockHeader<BlockNumber> {
     pub chain_id: u32,
     /// active_block_number of the parachain at the time this block was submitted
     pub para_height: BlockNumber,
+    /// total work of the chain up to and including this block, counted from the initial block
+    pub chain_work: U256,
+    /// median timestamp of this block and its (up to) ten stored predecessors
+    pub median_time_past: u32,
+    /// target of the last block up to and including this one which was not mined at the minimum
+    /// difficulty, going back no further than the start of the difficulty period
+    pub last_non_min_difficulty_target: U256,
 }
 
 impl<BlockNumber> RichBlockHeader<BlockNumber> {
@@ -35,6 +42,9 @@ impl<BlockNumber> RichBlockHeader<BlockNumber> {
             block_height,
             chain_id,
             para_height,
+            chain_work: U256::zero(),
+            median_time_past: 0,
+            last_non_min_difficulty_target: block_header.target,
         }
     }
 
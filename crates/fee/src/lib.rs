@@ -218,9 +218,7 @@ pub mod pallet {
             index: Option<T::Nonce>,
         ) -> DispatchResultWithPostInfo {
             let nominator_id = ensure_signed(origin)?;
-            for currency_id in [vault_id.wrapped_currency(), T::GetNativeCurrencyId::get()] {
-                Self::withdraw_vault_rewards(&vault_id, &nominator_id, index, currency_id)?;
-            }
+            Self::withdraw_all_vault_rewards(&vault_id, &nominator_id, index)?;
             Ok(().into())
         }
 
@@ -486,6 +484,19 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Withdraw the rewards of `nominator_id` in all reward currencies from the pool of the vault at `index`,
+    /// or the current pool if `None`.
+    pub fn withdraw_all_vault_rewards(
+        vault_id: &DefaultVaultId<T>,
+        nominator_id: &T::AccountId,
+        index: Option<T::Nonce>,
+    ) -> DispatchResult {
+        for currency_id in [vault_id.wrapped_currency(), T::GetNativeCurrencyId::get()] {
+            Self::withdraw_vault_rewards(vault_id, nominator_id, index, currency_id)?;
+        }
+        Ok(())
+    }
+
     // Private functions internal to this pallet
 
     /// Withdraw rewards from a pool and transfer to `account_id`.
@@ -189,6 +189,7 @@ impl traits::NominationApi<VaultId<AccountId, CurrencyId>, currency::Amount<Test
     fn ensure_opted_in_to_nomination(_vault_id: &VaultId<AccountId, CurrencyId>) -> Result<(), DispatchError> {
         Ok(())
     }
+    fn clear_nomination_opt_in(_vault_id: &VaultId<AccountId, CurrencyId>) {}
 
     #[cfg(any(feature = "runtime-benchmarks", test))]
     fn opt_in_to_nomination(_vault_id: &VaultId<AccountId, CurrencyId>) {}
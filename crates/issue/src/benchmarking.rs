@@ -193,7 +193,7 @@ pub mod benchmarks {
         let init_block = BlockBuilder::new()
             .with_version(4)
             .with_coinbase(&BtcAddress::dummy(), 50, 3)
-            .with_timestamp(u32::MAX)
+            .with_timestamp(1588813835)
             .mine(U256::from(2).pow(254.into()))
             .unwrap();
 
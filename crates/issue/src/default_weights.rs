@@ -89,6 +89,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Issue IssuePeriod (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Issue IssueRequests (r:0 w:1)
 	/// Proof: Issue IssueRequests (max_values: None, max_size: Some(261), added: 2736, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ReservedAddresses (r:0 w:1)
+	/// Proof: VaultRegistry ReservedAddresses (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry VaultDepositAddresses (r:0 w:1)
+	/// Proof: VaultRegistry VaultDepositAddresses (max_values: None, max_size: Some(137), added: 2612, mode: MaxEncodedLen)
 	fn request_issue() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3572`
@@ -96,7 +100,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 450_294_000 picoseconds.
 		Weight::from_parts(452_439_000, 26156)
 			.saturating_add(T::DbWeight::get().reads(19_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
 	/// Storage: Issue IssueRequests (r:1 w:1)
 	/// Proof: Issue IssueRequests (max_values: None, max_size: Some(261), added: 2736, mode: MaxEncodedLen)
@@ -451,6 +455,10 @@ impl WeightInfo for () {
 	/// Proof: Issue IssuePeriod (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Issue IssueRequests (r:0 w:1)
 	/// Proof: Issue IssueRequests (max_values: None, max_size: Some(261), added: 2736, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ReservedAddresses (r:0 w:1)
+	/// Proof: VaultRegistry ReservedAddresses (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry VaultDepositAddresses (r:0 w:1)
+	/// Proof: VaultRegistry VaultDepositAddresses (max_values: None, max_size: Some(137), added: 2612, mode: MaxEncodedLen)
 	fn request_issue() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3572`
@@ -458,7 +466,7 @@ impl WeightInfo for () {
 		// Minimum execution time: 450_294_000 picoseconds.
 		Weight::from_parts(452_439_000, 26156)
 			.saturating_add(RocksDbWeight::get().reads(19_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
 	/// Storage: Issue IssueRequests (r:1 w:1)
 	/// Proof: Issue IssueRequests (max_values: None, max_size: Some(261), added: 2736, mode: MaxEncodedLen)
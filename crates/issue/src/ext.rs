@@ -5,7 +5,7 @@ use mocktopus::macros::mockable;
 pub(crate) mod btc_relay {
     use bitcoin::types::{FullTransactionProof, Value};
     use btc_relay::BtcAddress;
-    use frame_support::dispatch::DispatchError;
+    use frame_support::dispatch::{DispatchError, DispatchResult};
     use frame_system::pallet_prelude::BlockNumberFor;
     use sp_std::convert::TryFrom;
 
@@ -31,6 +31,10 @@ pub(crate) mod btc_relay {
     ) -> Result<bool, DispatchError> {
         <btc_relay::Pallet<T>>::has_request_expired(opentime, btc_open_height, period)
     }
+
+    pub fn ensure_relay_live<T: crate::Config>() -> DispatchResult {
+        <btc_relay::Pallet<T>>::ensure_relay_live()
+    }
 }
 
 #[cfg_attr(test, mockable)]
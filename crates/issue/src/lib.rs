@@ -305,6 +305,7 @@ impl<T: Config> Pallet<T> {
             ext::btc_relay::is_fully_initialized::<T>()?,
             Error::<T>::WaitingForRelayerInitialization
         );
+        ext::btc_relay::ensure_relay_live::<T>()?;
 
         let vault = ext::vault_registry::get_active_vault_from_id::<T>(&vault_id)?;
 
@@ -166,6 +166,7 @@ impl vault_registry::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
+    type RequestPayments = ();
 }
 
 impl nomination::Config for Test {
@@ -202,12 +203,15 @@ impl currency::Config for Test {
 
 parameter_types! {
     pub const ParachainBlocksPerBitcoinBlock: BlockNumber = 100;
+    pub const RelayerRewardPot: AccountId = 1000;
 }
 
 impl btc_relay::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ParachainBlocksPerBitcoinBlock = ParachainBlocksPerBitcoinBlock;
     type WeightInfo = ();
+    type Currency = orml_tokens::CurrencyAdapter<Test, GetNativeCurrencyId>;
+    type RelayerRewardPot = RelayerRewardPot;
 }
 
 impl security::Config for Test {
@@ -326,6 +330,7 @@ impl ExtBuilder {
         vault_registry::GenesisConfig::<Test> {
             minimum_collateral_vault: vec![(DEFAULT_COLLATERAL_CURRENCY, 0)],
             punishment_delay: 8,
+            exit_period: 8,
             system_collateral_ceiling: vec![(PAIR, 1_000_000_000_000)],
             secure_collateral_threshold: vec![(PAIR, UnsignedFixedPoint::checked_from_rational(200, 100).unwrap())],
             premium_redeem_threshold: vec![(PAIR, UnsignedFixedPoint::checked_from_rational(120, 100).unwrap())],
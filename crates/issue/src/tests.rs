@@ -110,6 +110,15 @@ fn test_request_issue_banned_fails() {
     })
 }
 
+#[test]
+fn test_request_issue_relay_stale_fails() {
+    run_test(|| {
+        ext::btc_relay::ensure_relay_live::<Test>
+            .mock_safe(|| MockResult::Return(Err(btc_relay::Error::<Test>::RelayStale.into())));
+        assert_noop!(request_issue(USER, 3, VAULT), btc_relay::Error::<Test>::RelayStale);
+    })
+}
+
 #[test]
 fn test_request_issue_succeeds() {
     run_test(|| {
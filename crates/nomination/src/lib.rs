@@ -374,6 +374,15 @@ impl<T: Config> traits::NominationApi<DefaultVaultId<T>, Amount<T>> for Pallet<T
         Ok(())
     }
 
+    fn clear_nomination_opt_in(vault_id: &DefaultVaultId<T>) {
+        if Self::is_opted_in(vault_id) {
+            <Vaults<T>>::remove(vault_id);
+            Self::deposit_event(Event::<T>::NominationOptOut {
+                vault_id: vault_id.clone(),
+            });
+        }
+    }
+
     #[cfg(any(feature = "runtime-benchmarks", test))]
     fn opt_in_to_nomination(vault_id: &DefaultVaultId<T>) {
         Vaults::<T>::insert(vault_id, true);
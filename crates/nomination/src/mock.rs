@@ -37,6 +37,7 @@ frame_support::construct_runtime!(
         VaultStaking: staking::{Pallet, Storage, Event<T>},
 
         // Operational
+        BTCRelay: btc_relay::{Pallet, Call, Config<T>, Storage, Event<T>},
         Security: security::{Pallet, Call, Storage, Event<T>},
         VaultRegistry: vault_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
         Fee: fee::{Pallet, Call, Config<T>, Storage},
@@ -170,6 +171,7 @@ impl vault_registry::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
+    type RequestPayments = ();
 }
 
 pub struct CurrencyConvert;
@@ -199,6 +201,19 @@ impl currency::Config for Test {
     type CurrencyConversion = CurrencyConvert;
 }
 
+parameter_types! {
+    pub const ParachainBlocksPerBitcoinBlock: u64 = 100;
+    pub const RelayerRewardPot: AccountId = 1000;
+}
+
+impl btc_relay::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type ParachainBlocksPerBitcoinBlock = ParachainBlocksPerBitcoinBlock;
+    type WeightInfo = ();
+    type Currency = orml_tokens::CurrencyAdapter<Test, GetNativeCurrencyId>;
+    type RelayerRewardPot = RelayerRewardPot;
+}
+
 impl security::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
@@ -313,6 +328,7 @@ impl ExtBuilder {
         vault_registry::GenesisConfig::<Test> {
             minimum_collateral_vault: vec![(DEFAULT_COLLATERAL_CURRENCY, 0)],
             punishment_delay: 8,
+            exit_period: 8,
             system_collateral_ceiling: vec![(DEFAULT_CURRENCY_PAIR, 1_000_000_000_000)],
             secure_collateral_threshold: vec![(
                 DEFAULT_CURRENCY_PAIR,
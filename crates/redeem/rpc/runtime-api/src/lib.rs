@@ -8,12 +8,15 @@ use oracle_rpc_runtime_api::BalanceWrapper;
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-    pub trait RedeemApi<VaultId, Balance, AccountId, H256, RedeemRequest> where
+    /// Version 2 adds the multi-vault redeem queries
+    #[api_version(2)]
+    pub trait RedeemApi<VaultId, Balance, AccountId, H256, RedeemRequest, RedeemRequestGroupStatus> where
         VaultId: Codec,
         Balance: Codec,
         AccountId: Codec,
         H256: Codec,
         RedeemRequest: Codec,
+        RedeemRequestGroupStatus: Codec,
     {
         /// Get all redeem requests for a particular account
         fn get_redeem_requests(account_id: AccountId) -> Vec<H256>;
@@ -23,5 +26,14 @@ sp_api::decl_runtime_apis! {
 
         /// Get all vaults below the premium redeem threshold, ordered in descending order of this amount
         fn get_premium_redeem_vaults() -> Result<Vec<(VaultId, BalanceWrapper<Balance>)>, DispatchError>;
+
+        /// Get the aggregate status of the redeem requests opened by a multi-vault redeem
+        fn get_redeem_request_group_status(parent_id: H256) -> Result<RedeemRequestGroupStatus, DispatchError>;
+
+        /// Get the parent id of a redeem request opened by a multi-vault redeem
+        fn get_redeem_request_parent(redeem_id: H256) -> Option<H256>;
+
+        /// Split a redeem of the given amount across vaults, to be submitted with `request_redeem_multi`
+        fn get_redeem_allocation(amount_wrapped: BalanceWrapper<Balance>) -> Result<Vec<(VaultId, BalanceWrapper<Balance>)>, DispatchError>;
     }
 }
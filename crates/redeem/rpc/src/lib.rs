@@ -24,7 +24,7 @@ fn handle_response<T, E: std::fmt::Debug>(result: Result<Result<T, DispatchError
 }
 
 #[rpc(client, server)]
-pub trait RedeemApi<BlockHash, VaultId, Balance, AccountId, H256, RedeemRequest>
+pub trait RedeemApi<BlockHash, VaultId, Balance, AccountId, H256, RedeemRequest, RedeemRequestGroupStatus>
 where
     Balance: Codec + MaybeDisplay + MaybeFromStr,
 {
@@ -36,6 +36,23 @@ where
 
     #[method(name = "redeem_getPremiumRedeemVaults", aliases = ["vaultRegistry_getPremiumRedeemVaults"])]
     fn get_premium_redeem_vaults(&self, at: Option<BlockHash>) -> RpcResult<Vec<(VaultId, BalanceWrapper<Balance>)>>;
+
+    #[method(name = "redeem_getRedeemRequestGroupStatus")]
+    fn get_redeem_request_group_status(
+        &self,
+        parent_id: H256,
+        at: Option<BlockHash>,
+    ) -> RpcResult<RedeemRequestGroupStatus>;
+
+    #[method(name = "redeem_getRedeemRequestParent")]
+    fn get_redeem_request_parent(&self, redeem_id: H256, at: Option<BlockHash>) -> RpcResult<Option<H256>>;
+
+    #[method(name = "redeem_getRedeemAllocation")]
+    fn get_redeem_allocation(
+        &self,
+        amount_wrapped: BalanceWrapper<Balance>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(VaultId, BalanceWrapper<Balance>)>>;
 }
 
 fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
@@ -63,17 +80,19 @@ impl<C, B> Redeem<C, B> {
 }
 
 #[async_trait]
-impl<C, Block, VaultId, Balance, AccountId, H256, RedeemRequest>
-    RedeemApiServer<<Block as BlockT>::Hash, VaultId, Balance, AccountId, H256, RedeemRequest> for Redeem<C, Block>
+impl<C, Block, VaultId, Balance, AccountId, H256, RedeemRequest, RedeemRequestGroupStatus>
+    RedeemApiServer<<Block as BlockT>::Hash, VaultId, Balance, AccountId, H256, RedeemRequest, RedeemRequestGroupStatus>
+    for Redeem<C, Block>
 where
     Block: BlockT,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: RedeemRuntimeApi<Block, VaultId, Balance, AccountId, H256, RedeemRequest>,
+    C::Api: RedeemRuntimeApi<Block, VaultId, Balance, AccountId, H256, RedeemRequest, RedeemRequestGroupStatus>,
     VaultId: Codec,
     Balance: Codec + MaybeDisplay + MaybeFromStr,
     AccountId: Codec,
     H256: Codec,
     RedeemRequest: Codec,
+    RedeemRequestGroupStatus: Codec,
 {
     fn get_redeem_requests(&self, account_id: AccountId, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<H256>> {
         let api = self.client.runtime_api();
@@ -107,4 +126,44 @@ where
             "Unable to find a vault below the premium redeem threshold".into(),
         )
     }
+
+    fn get_redeem_request_group_status(
+        &self,
+        parent_id: H256,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<RedeemRequestGroupStatus> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        handle_response(
+            api.get_redeem_request_group_status(at, parent_id),
+            "Unable to fetch redeem request group".into(),
+        )
+    }
+
+    fn get_redeem_request_parent(
+        &self,
+        redeem_id: H256,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<H256>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_redeem_request_parent(at, redeem_id)
+            .map_err(|e| internal_err(format!("Unable to fetch redeem request parent: {:?}", e)))
+    }
+
+    fn get_redeem_allocation(
+        &self,
+        amount_wrapped: BalanceWrapper<Balance>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(VaultId, BalanceWrapper<Balance>)>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        handle_response(
+            api.get_redeem_allocation(at, amount_wrapped),
+            "Unable to allocate the redeem across vaults".into(),
+        )
+    }
 }
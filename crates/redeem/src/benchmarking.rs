@@ -90,7 +90,7 @@ fn initialize_and_mine_blocks_until_expiry<T: crate::Config>(request: &DefaultRe
     let init_block = BlockBuilder::new()
         .with_version(4)
         .with_coinbase(&BtcAddress::default(), 50, 3)
-        .with_timestamp(u32::MAX)
+        .with_timestamp(1588813835)
         .mine(U256::from(2).pow(254.into()))
         .unwrap();
 
@@ -167,6 +167,57 @@ pub mod benchmarks {
         assert!(redeem_struct.premium > 0);
     }
 
+    #[benchmark]
+    pub fn request_redeem_multi(n: Linear<1, { T::MaxRedeemSubRequests::get() }>) {
+        let caller = whitelisted_caller();
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        let amount_per_vault = Redeem::<T>::redeem_btc_dust_value() * BalanceOf::<T>::from(100u32);
+        let amount = amount_per_vault * BalanceOf::<T>::from(n);
+        let btc_address = BtcAddress::dummy();
+
+        initialize_oracle::<T>();
+
+        register_public_key::<T>(vault_id.clone());
+
+        let allocation = (0..n)
+            .map(|i| {
+                let sub_vault_id = DefaultVaultId::<T>::new(
+                    account("Vault", i, 0),
+                    vault_id.collateral_currency(),
+                    vault_id.wrapped_currency(),
+                );
+                let vault = Vault {
+                    issued_tokens: amount_per_vault,
+                    id: sub_vault_id.clone(),
+                    ..Vault::new(sub_vault_id.clone())
+                };
+                VaultRegistry::<T>::insert_vault(&sub_vault_id, vault);
+                (sub_vault_id, amount_per_vault)
+            })
+            .collect::<Vec<_>>();
+
+        mint_wrapped::<T>(&caller, amount);
+
+        assert_ok!(Oracle::<T>::_set_exchange_rate(
+            get_collateral_currency_id::<T>(),
+            UnsignedFixedPoint::<T>::one()
+        ));
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            amount,
+            btc_address,
+            get_wrapped_currency_id::<T>(),
+            allocation.try_into().unwrap(),
+        );
+
+        let (_, redeem_ids) = RedeemRequestGroups::<T>::iter()
+            .next()
+            .unwrap_or_else(|| panic!("No redeem request group found"));
+        assert_eq!(redeem_ids.len(), n as usize);
+    }
+
     #[benchmark]
     pub fn liquidation_redeem() {
         assert_ok!(Oracle::<T>::_set_exchange_rate(
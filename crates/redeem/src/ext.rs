@@ -5,7 +5,7 @@ use mocktopus::macros::mockable;
 pub(crate) mod btc_relay {
     use bitcoin::types::{FullTransactionProof, Value};
     use btc_relay::BtcAddress;
-    use frame_support::dispatch::DispatchError;
+    use frame_support::dispatch::{DispatchError, DispatchResult};
     use frame_system::pallet_prelude::BlockNumberFor;
     use sp_core::H256;
     use sp_std::convert::TryInto;
@@ -35,6 +35,10 @@ pub(crate) mod btc_relay {
     ) -> Result<bool, DispatchError> {
         <btc_relay::Pallet<T>>::has_request_expired(opentime, btc_open_height, period)
     }
+
+    pub fn ensure_relay_live<T: crate::Config>() -> DispatchResult {
+        <btc_relay::Pallet<T>>::ensure_relay_live()
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -43,7 +47,7 @@ pub(crate) mod vault_registry {
     use currency::Amount;
     use frame_support::dispatch::{DispatchError, DispatchResult};
     use sp_std::vec::Vec;
-    use vault_registry::types::{CurrencyId, CurrencySource, DefaultVault};
+    use vault_registry::types::{CurrencyId, CurrencySource, DefaultVault, UnsignedFixedPoint};
 
     pub fn calculate_inclusion_fee<T: crate::Config>(
         wrapped_currency: CurrencyId<T>,
@@ -52,6 +56,18 @@ pub(crate) mod vault_registry {
         <vault_registry::Pallet<T>>::calculate_inclusion_fee(wrapped_currency, redeem_transaction_size)
     }
 
+    pub fn get_vaults_with_redeemable_tokens<T: crate::Config>(
+    ) -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
+        <vault_registry::Pallet<T>>::get_vaults_with_redeemable_tokens()
+    }
+
+    pub fn get_collateralization_from_vault<T: crate::Config>(
+        vault_id: DefaultVaultId<T>,
+        only_issued: bool,
+    ) -> Result<UnsignedFixedPoint<T>, DispatchError> {
+        <vault_registry::Pallet<T>>::get_collateralization_from_vault(vault_id, only_issued)
+    }
+
     pub fn get_premium_redeem_vaults<T: crate::Config>(
         redeem_transaction_size: u32,
     ) -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
@@ -127,6 +143,20 @@ pub(crate) mod vault_registry {
         <vault_registry::Pallet<T>>::decrease_liquidated_collateral(vault_id, amount)
     }
 
+    pub fn slash_liquidated_basket_collateral<T: crate::Config>(
+        vault_id: &DefaultVaultId<T>,
+        tokens: &Amount<T>,
+        to_be_redeemed_tokens: &Amount<T>,
+        reimbursed_redeemer: Option<&T::AccountId>,
+    ) -> DispatchResult {
+        <vault_registry::Pallet<T>>::slash_liquidated_basket_collateral(
+            vault_id,
+            tokens,
+            to_be_redeemed_tokens,
+            reimbursed_redeemer,
+        )
+    }
+
     pub fn redeem_tokens_liquidation<T: crate::Config>(
         currency_id: CurrencyId<T>,
         redeemer_id: &T::AccountId,
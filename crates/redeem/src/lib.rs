@@ -27,7 +27,9 @@ mod ext;
 pub mod types;
 
 #[doc(inline)]
-pub use crate::types::{DefaultRedeemRequest, RedeemRequest, RedeemRequestStatus};
+pub use crate::types::{
+    DefaultRedeemRequest, DefaultRedeemRequestGroupStatus, RedeemRequest, RedeemRequestGroupStatus, RedeemRequestStatus,
+};
 
 use crate::types::{BalanceOf, RedeemRequestExt, Version};
 use bitcoin::types::FullTransactionProof;
@@ -37,14 +39,16 @@ use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
     pallet_prelude::Weight,
-    transactional,
+    traits::Get,
+    transactional, BoundedVec,
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_core::H256;
+use sp_runtime::traits::{Bounded, Saturating};
 use sp_std::{convert::TryInto, vec::Vec};
 use types::DefaultVaultId;
 use vault_registry::{
-    types::{CurrencyId, DefaultVaultCurrencyPair},
+    types::{CurrencyId, DefaultVaultCurrencyPair, UnsignedFixedPoint},
     CurrencySource,
 };
 
@@ -71,6 +75,14 @@ fn weight_for_execute_redeem<T: Config>(proof: &FullTransactionProof) -> Weight
     ))
 }
 
+/// Complexity:
+/// - `O(N)` where `N` is the number of sub-requests, bounded by `MaxRedeemSubRequests`
+fn weight_for_request_redeem_multi<T: Config>(
+    allocation: &BoundedVec<(DefaultVaultId<T>, BalanceOf<T>), T::MaxRedeemSubRequests>,
+) -> Weight {
+    <T as Config>::WeightInfo::request_redeem_multi(allocation.len() as u32)
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -88,6 +100,10 @@ pub mod pallet {
 
         /// Weight information for the extrinsics in this module.
         type WeightInfo: WeightInfo;
+
+        /// The maximum number of vaults a single multi-vault redeem can be split across.
+        #[pallet::constant]
+        type MaxRedeemSubRequests: Get<u32>;
     }
 
     #[pallet::event]
@@ -103,6 +119,12 @@ pub mod pallet {
             btc_address: BtcAddress,
             transfer_fee: BalanceOf<T>,
         },
+        RequestRedeemMulti {
+            parent_id: H256,
+            redeemer: T::AccountId,
+            amount: BalanceOf<T>,
+            redeem_ids: Vec<H256>,
+        },
         LiquidationRedeem {
             redeemer: T::AccountId,
             amount: BalanceOf<T>,
@@ -157,6 +179,12 @@ pub mod pallet {
         TryIntoIntError,
         /// Redeem amount is too small.
         AmountBelowDustAmount,
+        /// The vault allocation is empty, uses another wrapped currency or does not add up to the redeem amount.
+        InvalidRedeemAllocation,
+        /// The vaults do not have enough redeemable tokens to fill the redeem.
+        InsufficientRedeemableTokens,
+        /// Redeem request group not found.
+        RedeemRequestGroupNotFound,
     }
 
     /// The time difference in number of blocks between a redeem request is created and required completion time by a
@@ -173,6 +201,17 @@ pub mod pallet {
     pub(super) type RedeemRequests<T: Config> =
         StorageMap<_, Blake2_128Concat, H256, DefaultRedeemRequest<T>, OptionQuery>;
 
+    /// The redeem requests opened by a single multi-vault redeem, keyed by their shared parent id.
+    #[pallet::storage]
+    #[pallet::getter(fn redeem_request_groups)]
+    pub(super) type RedeemRequestGroups<T: Config> =
+        StorageMap<_, Blake2_128Concat, H256, BoundedVec<H256, T::MaxRedeemSubRequests>, OptionQuery>;
+
+    /// Maps a redeem request opened by a multi-vault redeem to the parent id of its group.
+    #[pallet::storage]
+    #[pallet::getter(fn redeem_request_parent)]
+    pub(super) type RedeemRequestParents<T: Config> = StorageMap<_, Blake2_128Concat, H256, H256, OptionQuery>;
+
     /// The minimum amount of btc that is accepted for redeem requests; any lower values would
     /// risk the bitcoin client to reject the payment
     #[pallet::storage]
@@ -373,6 +412,32 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Initializes a redeem that is split across multiple vaults, for amounts that no single
+        /// vault can fill. A regular redeem request is opened against every vault; these
+        /// sub-requests are linked by a shared parent id, which is returned in the event.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - sender of the transaction
+        /// * `amount_wrapped` - total amount of issued tokens
+        /// * `btc_address` - the address to receive BTC
+        /// * `wrapped_currency` - currency of the wrapped token to burn
+        /// * `allocation` - `(vault, amount)` pairs, see `get_redeem_allocation`
+        #[pallet::call_index(7)]
+        #[pallet::weight(weight_for_request_redeem_multi::<T>(allocation))]
+        #[transactional]
+        pub fn request_redeem_multi(
+            origin: OriginFor<T>,
+            #[pallet::compact] amount_wrapped: BalanceOf<T>,
+            btc_address: BtcAddress,
+            wrapped_currency: CurrencyId<T>,
+            allocation: BoundedVec<(DefaultVaultId<T>, BalanceOf<T>), T::MaxRedeemSubRequests>,
+        ) -> DispatchResultWithPostInfo {
+            let redeemer = ensure_signed(origin)?;
+            Self::_request_redeem_multi(redeemer, amount_wrapped, btc_address, wrapped_currency, allocation)?;
+            Ok(().into())
+        }
     }
 }
 
@@ -463,6 +528,8 @@ impl<T: Config> Pallet<T> {
         btc_address: BtcAddress,
         vault_id: DefaultVaultId<T>,
     ) -> Result<H256, DispatchError> {
+        ext::btc_relay::ensure_relay_live::<T>()?;
+
         let amount_wrapped = Amount::new(amount_wrapped, vault_id.wrapped_currency());
 
         let redeemer_balance = ext::treasury::get_balance::<T>(&redeemer, vault_id.wrapped_currency());
@@ -554,6 +621,127 @@ impl<T: Config> Pallet<T> {
         Ok(redeem_id)
     }
 
+    fn _request_redeem_multi(
+        redeemer: T::AccountId,
+        amount_wrapped: BalanceOf<T>,
+        btc_address: BtcAddress,
+        wrapped_currency: CurrencyId<T>,
+        allocation: BoundedVec<(DefaultVaultId<T>, BalanceOf<T>), T::MaxRedeemSubRequests>,
+    ) -> Result<H256, DispatchError> {
+        let amount_wrapped = Amount::new(amount_wrapped, wrapped_currency);
+
+        let redeemer_balance = ext::treasury::get_balance::<T>(&redeemer, wrapped_currency);
+        ensure!(
+            amount_wrapped.le(&redeemer_balance)?,
+            Error::<T>::AmountExceedsUserBalance
+        );
+
+        ensure!(!allocation.is_empty(), Error::<T>::InvalidRedeemAllocation);
+        let mut allocated = Amount::zero(wrapped_currency);
+        for (vault_id, amount) in allocation.iter() {
+            let amount = Amount::new(*amount, wrapped_currency);
+            ensure!(
+                vault_id.wrapped_currency() == wrapped_currency && !amount.is_zero(),
+                Error::<T>::InvalidRedeemAllocation
+            );
+            allocated.checked_accrue(&amount)?;
+        }
+        ensure!(allocated.eq(&amount_wrapped)?, Error::<T>::InvalidRedeemAllocation);
+
+        let parent_id = ext::security::get_secure_id::<T>(&redeemer);
+        let redeem_ids = allocation
+            .into_iter()
+            .map(|(vault_id, amount)| Self::_request_redeem(redeemer.clone(), amount, btc_address, vault_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for redeem_id in redeem_ids.iter() {
+            <RedeemRequestParents<T>>::insert(redeem_id, parent_id);
+        }
+        // there is one redeem id per vault in the allocation, so this never truncates
+        <RedeemRequestGroups<T>>::insert(parent_id, BoundedVec::truncate_from(redeem_ids.clone()));
+
+        Self::deposit_event(Event::<T>::RequestRedeemMulti {
+            parent_id,
+            redeemer,
+            amount: amount_wrapped.amount(),
+            redeem_ids,
+        });
+
+        Ok(parent_id)
+    }
+
+    /// Split a redeem across the vaults with free redeemable tokens. Vaults below the premium
+    /// redeem threshold are used first, then the remaining vaults in ascending order of
+    /// collateralization. Vaults whose share would fall below the dust value are skipped, and at
+    /// most `MaxRedeemSubRequests` vaults are used. The result can be passed to
+    /// `request_redeem_multi` as the allocation.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_wrapped` - total amount of issued tokens to redeem
+    pub fn get_redeem_allocation(
+        amount_wrapped: &Amount<T>,
+    ) -> Result<Vec<(DefaultVaultId<T>, BalanceOf<T>)>, DispatchError> {
+        let wrapped_currency = amount_wrapped.currency();
+
+        let premium_vaults = Self::get_premium_redeem_vaults()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(vault_id, _)| vault_id)
+            .collect::<Vec<_>>();
+
+        let mut other_vaults = ext::vault_registry::get_vaults_with_redeemable_tokens::<T>()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(vault_id, _)| vault_id)
+            .filter(|vault_id| !premium_vaults.contains(vault_id))
+            .collect::<Vec<_>>();
+        other_vaults.sort_by_cached_key(|vault_id| {
+            ext::vault_registry::get_collateralization_from_vault::<T>(vault_id.clone(), false)
+                .unwrap_or_else(|_| UnsignedFixedPoint::<T>::max_value())
+        });
+
+        let mut remaining = amount_wrapped.clone();
+        let mut allocation = Vec::new();
+        for vault_id in premium_vaults.into_iter().chain(other_vaults) {
+            if remaining.is_zero() || allocation.len() >= T::MaxRedeemSubRequests::get() as usize {
+                break;
+            }
+            if vault_id.wrapped_currency() != wrapped_currency
+                || ext::vault_registry::ensure_not_banned::<T>(&vault_id).is_err()
+            {
+                continue;
+            }
+            let redeemable_tokens = match ext::vault_registry::get_free_redeemable_tokens::<T>(&vault_id) {
+                Ok(redeemable_tokens) => redeemable_tokens,
+                Err(_) => continue,
+            };
+            let amount = remaining.min(&redeemable_tokens)?;
+            if !Self::exceeds_dust_value(&amount)? {
+                continue;
+            }
+            remaining = remaining.checked_sub(&amount)?;
+            allocation.push((vault_id, amount.amount()));
+        }
+
+        ensure!(remaining.is_zero(), Error::<T>::InsufficientRedeemableTokens);
+        Ok(allocation)
+    }
+
+    /// Whether a redeem of `amount_wrapped` would still be above the dust value after deducting
+    /// the redeem fee and the bitcoin inclusion fee.
+    fn exceeds_dust_value(amount_wrapped: &Amount<T>) -> Result<bool, DispatchError> {
+        let fee_wrapped = ext::fee::get_redeem_fee::<T>(amount_wrapped)?;
+        let inclusion_fee = Self::get_current_inclusion_fee(amount_wrapped.currency())?;
+        match amount_wrapped
+            .checked_sub(&fee_wrapped)
+            .and_then(|amount| amount.checked_sub(&inclusion_fee))
+        {
+            Ok(amount_btc) => amount_btc.ge(&Self::get_dust_value(amount_wrapped.currency())),
+            Err(_) => Ok(false),
+        }
+    }
+
     fn _liquidation_redeem(
         redeemer: T::AccountId,
         currencies: DefaultVaultCurrencyPair<T>,
@@ -656,6 +844,13 @@ impl<T: Config> Pallet<T> {
                 slashing_destination,
                 &confiscated_collateral,
             )?;
+            // the basket collateral backing the tokens follows the collateral
+            ext::vault_registry::slash_liquidated_basket_collateral::<T>(
+                &vault_id,
+                &vault_to_be_burned_tokens,
+                &vault_to_be_redeemed_tokens,
+                if reimburse { Some(&redeemer) } else { None },
+            )?;
 
             confiscated_collateral
         } else {
@@ -819,6 +1014,38 @@ impl<T: Config> Pallet<T> {
             .collect::<Vec<_>>()
     }
 
+    /// Get the aggregate status of the redeem requests opened by a single multi-vault redeem.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent_id` - the parent id shared by the redeem requests
+    pub fn get_redeem_request_group_status(
+        parent_id: H256,
+    ) -> Result<DefaultRedeemRequestGroupStatus<T>, DispatchError> {
+        let redeem_ids = <RedeemRequestGroups<T>>::get(parent_id).ok_or(Error::<T>::RedeemRequestGroupNotFound)?;
+
+        let mut status = RedeemRequestGroupStatus {
+            redeem_ids: redeem_ids.to_vec(),
+            ..Default::default()
+        };
+        for redeem_id in redeem_ids.iter() {
+            let request = <RedeemRequests<T>>::get(redeem_id).ok_or(Error::<T>::RedeemIdNotFound)?;
+            status.amount_btc = status.amount_btc.saturating_add(request.amount_btc);
+            match request.status {
+                RedeemRequestStatus::Pending => status.pending = status.pending.saturating_add(1),
+                RedeemRequestStatus::Completed => {
+                    status.completed = status.completed.saturating_add(1);
+                    status.completed_amount_btc = status.completed_amount_btc.saturating_add(request.amount_btc);
+                }
+                RedeemRequestStatus::Reimbursed(_) | RedeemRequestStatus::Retried => {
+                    status.cancelled = status.cancelled.saturating_add(1);
+                }
+            }
+        }
+
+        Ok(status)
+    }
+
     pub fn get_premium_redeem_vaults() -> Result<Vec<(DefaultVaultId<T>, Amount<T>)>, DispatchError> {
         let size: u32 = Self::redeem_transaction_size();
         ext::vault_registry::get_premium_redeem_vaults::<T>(size)
@@ -876,3 +1103,28 @@ impl<T: Config> Pallet<T> {
         Ok(request)
     }
 }
+
+impl<T: Config> traits::RequestPaymentApi<DefaultVaultId<T>, H256, BtcAddress, BalanceOf<T>> for Pallet<T> {
+    fn get_payable_request(redeem_id: &H256, vault_id: &DefaultVaultId<T>) -> Option<(BtcAddress, BalanceOf<T>)> {
+        let request = RedeemRequests::<T>::get(redeem_id).filter(|request| &request.vault == vault_id)?;
+        let payable = match request.status {
+            RedeemRequestStatus::Pending => true,
+            RedeemRequestStatus::Completed => false,
+            // a payment sent shortly before the cancellation may still confirm, so the request stays
+            // payable for another redeem period after its expiry
+            RedeemRequestStatus::Reimbursed(_) | RedeemRequestStatus::Retried => {
+                !ext::btc_relay::has_request_expired::<T>(
+                    request.opentime,
+                    request.btc_height,
+                    Self::redeem_period().max(request.period).saturating_mul(2u32.into()),
+                )
+                .unwrap_or(true)
+            }
+        };
+        if payable {
+            Some((request.btc_address, request.amount_btc))
+        } else {
+            None
+        }
+    }
+}
@@ -145,6 +145,7 @@ impl vault_registry::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
+    type RequestPayments = ();
 }
 
 impl nomination::Config for Test {
@@ -200,12 +201,15 @@ impl staking::Config for Test {
 
 parameter_types! {
     pub const ParachainBlocksPerBitcoinBlock: BlockNumber = 100;
+    pub const RelayerRewardPot: AccountId = 1000;
 }
 
 impl btc_relay::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ParachainBlocksPerBitcoinBlock = ParachainBlocksPerBitcoinBlock;
     type WeightInfo = ();
+    type Currency = orml_tokens::CurrencyAdapter<Test, GetNativeCurrencyId>;
+    type RelayerRewardPot = RelayerRewardPot;
 }
 
 impl security::Config for Test {
@@ -268,6 +272,7 @@ impl loans::Config for Test {
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type MaxRedeemSubRequests = ConstU32<10>;
 }
 
 pub type TestEvent = RuntimeEvent;
@@ -310,6 +315,7 @@ impl ExtBuilder {
         vault_registry::GenesisConfig::<Test> {
             minimum_collateral_vault: vec![(DEFAULT_COLLATERAL_CURRENCY, 0)],
             punishment_delay: 8,
+            exit_period: 8,
             system_collateral_ceiling: vec![(DEFAULT_CURRENCY_PAIR, 1_000_000_000_000)],
             secure_collateral_threshold: vec![(
                 DEFAULT_CURRENCY_PAIR,
@@ -8,6 +8,7 @@ use frame_support::{assert_err, assert_noop, assert_ok, dispatch::DispatchError}
 use mocktopus::mocking::*;
 use security::Pallet as Security;
 use sp_core::{H160, H256};
+use traits::RequestPaymentApi;
 use vault_registry::{DefaultVault, VaultStatus};
 
 type Event = crate::Event<Test>;
@@ -107,6 +108,18 @@ fn test_request_redeem_fails_with_amount_below_minimum() {
     })
 }
 
+#[test]
+fn test_request_redeem_fails_with_relay_stale() {
+    run_test(|| {
+        ext::btc_relay::ensure_relay_live::<Test>
+            .mock_safe(|| MockResult::Return(Err(btc_relay::Error::<Test>::RelayStale.into())));
+        assert_err!(
+            Redeem::request_redeem(RuntimeOrigin::signed(USER), 1500, BtcAddress::random(), VAULT),
+            btc_relay::Error::<Test>::RelayStale
+        );
+    })
+}
+
 #[test]
 fn test_request_redeem_fails_with_vault_not_found() {
     run_test(|| {
@@ -665,6 +678,51 @@ fn test_cancel_redeem_succeeds() {
     })
 }
 
+#[test]
+fn test_get_payable_request_only_returns_pending_or_just_cancelled_requests() {
+    run_test(|| {
+        let btc_address = BtcAddress::random();
+        let redeem_id = H256([0u8; 32]);
+        let inject_with_status = |status| {
+            inject_redeem_request(
+                redeem_id,
+                RedeemRequest {
+                    period: 0,
+                    vault: VAULT,
+                    opentime: 10,
+                    fee: 0,
+                    amount_btc: 10,
+                    premium: 0,
+                    redeemer: USER,
+                    btc_address,
+                    btc_height: 0,
+                    status,
+                    transfer_fee_btc: 1,
+                },
+            )
+        };
+        let payable_request = || Redeem::get_payable_request(&redeem_id, &VAULT);
+
+        inject_with_status(RedeemRequestStatus::Pending);
+        assert_eq!(payable_request(), Some((btc_address, 10)));
+        assert_eq!(Redeem::get_payable_request(&H256([1u8; 32]), &VAULT), None);
+        let mut other_vault = VAULT;
+        other_vault.account_id += 1;
+        assert_eq!(Redeem::get_payable_request(&redeem_id, &other_vault), None);
+
+        // the id of a completed redeem can not be reused to pay out BTC
+        inject_with_status(RedeemRequestStatus::Completed);
+        assert_eq!(payable_request(), None);
+
+        // a cancelled request stays payable for another redeem period
+        inject_with_status(RedeemRequestStatus::Retried);
+        ext::btc_relay::has_request_expired::<Test>.mock_safe(|_, _, _| MockResult::Return(Ok(false)));
+        assert_eq!(payable_request(), Some((btc_address, 10)));
+        ext::btc_relay::has_request_expired::<Test>.mock_safe(|_, _, _| MockResult::Return(Ok(true)));
+        assert_eq!(payable_request(), None);
+    })
+}
+
 #[test]
 fn test_mint_tokens_for_reimbursed_redeem() {
     // PRECONDITION: The vault MUST NOT be banned.
@@ -1028,3 +1086,178 @@ mod spec_based_tests {
         })
     }
 }
+
+mod request_redeem_multi_tests {
+    use super::*;
+    use crate::{types::DefaultVaultId, Config, RedeemRequestGroups};
+    use codec::{Decode, Encode};
+    use frame_support::BoundedVec;
+
+    fn other_vault() -> DefaultVaultId<Test> {
+        let mut vault_id = VAULT;
+        vault_id.account_id = CAROL;
+        vault_id
+    }
+
+    fn insert_vaults() {
+        for vault_id in [VAULT, other_vault()] {
+            <vault_registry::Pallet<Test>>::insert_vault(
+                &vault_id,
+                vault_registry::Vault {
+                    id: vault_id.clone(),
+                    issued_tokens: 100,
+                    ..default_vault()
+                },
+            );
+        }
+    }
+
+    fn request_redeem_multi(amount: u128, vaults: Vec<(DefaultVaultId<Test>, u128)>) -> Result<(), DispatchError> {
+        Redeem::request_redeem_multi(
+            RuntimeOrigin::signed(USER),
+            amount,
+            BtcAddress::random(),
+            DEFAULT_WRAPPED_CURRENCY,
+            vaults.try_into().unwrap(),
+        )
+        .map(|_| ())
+        .map_err(|err| err.error)
+    }
+
+    #[test]
+    fn test_request_redeem_multi_links_sub_requests() {
+        run_test(|| {
+            convert_to.mock_safe(|_, x| MockResult::Return(Ok(x)));
+            ext::vault_registry::try_increase_to_be_redeemed_tokens::<Test>
+                .mock_safe(|_, _| MockResult::Return(Ok(())));
+            insert_vaults();
+
+            assert_ok!(request_redeem_multi(150, vec![(VAULT, 100), (other_vault(), 50)]));
+
+            let (parent_id, redeem_ids) = RedeemRequestGroups::<Test>::iter().next().unwrap();
+            assert_eq!(redeem_ids.len(), 2);
+            assert_emitted!(Event::RequestRedeemMulti {
+                parent_id,
+                redeemer: USER,
+                amount: 150,
+                redeem_ids: redeem_ids.to_vec()
+            });
+
+            let requests = redeem_ids
+                .iter()
+                .map(|redeem_id| Redeem::get_open_redeem_request_from_id(redeem_id).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(requests[0].vault, VAULT);
+            assert_eq!(requests[1].vault, other_vault());
+            for redeem_id in redeem_ids.iter() {
+                assert_eq!(Redeem::redeem_request_parent(redeem_id), Some(parent_id));
+            }
+
+            let status = Redeem::get_redeem_request_group_status(parent_id).unwrap();
+            assert_eq!(status.redeem_ids, redeem_ids.to_vec());
+            assert_eq!(status.pending, 2);
+            assert_eq!(status.amount_btc, requests[0].amount_btc + requests[1].amount_btc);
+
+            Redeem::set_redeem_status(redeem_ids[0], RedeemRequestStatus::Completed);
+            Redeem::set_redeem_status(redeem_ids[1], RedeemRequestStatus::Retried);
+            let status = Redeem::get_redeem_request_group_status(parent_id).unwrap();
+            assert_eq!(status.pending, 0);
+            assert_eq!(status.completed, 1);
+            assert_eq!(status.cancelled, 1);
+            assert_eq!(status.completed_amount_btc, requests[0].amount_btc);
+        })
+    }
+
+    #[test]
+    fn test_request_redeem_multi_fails_with_invalid_allocation() {
+        run_test(|| {
+            insert_vaults();
+
+            assert_noop!(request_redeem_multi(150, vec![]), TestError::InvalidRedeemAllocation);
+            assert_noop!(
+                request_redeem_multi(150, vec![(VAULT, 100), (other_vault(), 40)]),
+                TestError::InvalidRedeemAllocation
+            );
+            assert_noop!(
+                request_redeem_multi(ALICE_BALANCE + 1, vec![(VAULT, ALICE_BALANCE + 1)]),
+                TestError::AmountExceedsUserBalance
+            );
+            // allocations over `MaxRedeemSubRequests` can not be decoded
+            let encoded = vec![(VAULT, 10u128); 11].encode();
+            assert!(
+                BoundedVec::<(DefaultVaultId<Test>, u128), <Test as Config>::MaxRedeemSubRequests>::decode(
+                    &mut &encoded[..]
+                )
+                .is_err()
+            );
+            assert_err!(
+                Redeem::get_redeem_request_group_status(H256::zero()),
+                TestError::RedeemRequestGroupNotFound
+            );
+        })
+    }
+
+    #[test]
+    fn test_get_redeem_allocation_prefers_premium_vaults_then_lowest_collateralization() {
+        run_test(|| {
+            let third_vault = {
+                let mut vault_id = VAULT;
+                vault_id.account_id = 4;
+                vault_id
+            };
+            let premium_vault = other_vault();
+            let redeemable_vault = third_vault.clone();
+            ext::vault_registry::get_premium_redeem_vaults::<Test>
+                .mock_safe(move |_| MockResult::Return(Ok(vec![(premium_vault.clone(), wrapped(10))])));
+            ext::vault_registry::get_vaults_with_redeemable_tokens::<Test>.mock_safe(move || {
+                MockResult::Return(Ok(vec![
+                    (VAULT, wrapped(60)),
+                    (redeemable_vault.clone(), wrapped(60)),
+                    (other_vault(), wrapped(60)),
+                ]))
+            });
+            ext::vault_registry::get_collateralization_from_vault::<Test>.mock_safe(move |vault_id, _| {
+                let collateralization = if vault_id == VAULT { 200 } else { 150 };
+                MockResult::Return(Ok(FixedU128::checked_from_rational(collateralization, 100).unwrap()))
+            });
+            ext::vault_registry::get_free_redeemable_tokens::<Test>.mock_safe(|_| MockResult::Return(Ok(wrapped(60))));
+            ext::vault_registry::ensure_not_banned::<Test>.mock_safe(|_| MockResult::Return(Ok(())));
+
+            assert_eq!(
+                Redeem::get_redeem_allocation(&wrapped(150)),
+                Ok(vec![(other_vault(), 60), (third_vault, 60), (VAULT, 30)])
+            );
+            assert_err!(
+                Redeem::get_redeem_allocation(&wrapped(200)),
+                TestError::InsufficientRedeemableTokens
+            );
+        })
+    }
+    #[test]
+    fn test_get_redeem_allocation_uses_at_most_max_sub_requests() {
+        run_test(|| {
+            ext::vault_registry::get_premium_redeem_vaults::<Test>.mock_safe(|_| MockResult::Return(Ok(vec![])));
+            ext::vault_registry::get_vaults_with_redeemable_tokens::<Test>.mock_safe(|| {
+                let vaults = (0..12)
+                    .map(|account_id| {
+                        let mut vault_id = VAULT;
+                        vault_id.account_id = 10 + account_id;
+                        (vault_id, wrapped(60))
+                    })
+                    .collect();
+                MockResult::Return(Ok(vaults))
+            });
+            ext::vault_registry::get_free_redeemable_tokens::<Test>.mock_safe(|_| MockResult::Return(Ok(wrapped(60))));
+            ext::vault_registry::ensure_not_banned::<Test>.mock_safe(|_| MockResult::Return(Ok(())));
+
+            assert_eq!(
+                Redeem::get_redeem_allocation(&wrapped(600)).map(|vaults| vaults.len()),
+                Ok(10)
+            );
+            assert_err!(
+                Redeem::get_redeem_allocation(&wrapped(660)),
+                TestError::InsufficientRedeemableTokens
+            );
+        })
+    }
+}
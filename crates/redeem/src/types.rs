@@ -1,4 +1,4 @@
-pub use primitives::redeem::{RedeemRequest, RedeemRequestStatus};
+pub use primitives::redeem::{RedeemRequest, RedeemRequestGroupStatus, RedeemRequestStatus};
 use primitives::VaultId;
 use scale_info::TypeInfo;
 use sp_runtime::DispatchError;
@@ -23,6 +23,8 @@ pub(crate) type DefaultVaultId<T> = VaultId<<T as frame_system::Config>::Account
 pub type DefaultRedeemRequest<T> =
     RedeemRequest<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, BalanceOf<T>, CurrencyId<T>>;
 
+pub type DefaultRedeemRequestGroupStatus<T> = RedeemRequestGroupStatus<BalanceOf<T>>;
+
 pub trait RedeemRequestExt<T: Config> {
     fn amount_btc(&self) -> Amount<T>;
     fn fee(&self) -> Amount<T>;
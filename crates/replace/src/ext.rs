@@ -5,7 +5,7 @@ use mocktopus::macros::mockable;
 pub(crate) mod btc_relay {
     use bitcoin::types::{FullTransactionProof, Value};
     use btc_relay::BtcAddress;
-    use frame_support::dispatch::DispatchError;
+    use frame_support::dispatch::{DispatchError, DispatchResult};
     use frame_system::pallet_prelude::BlockNumberFor;
     use sp_core::H256;
     use sp_std::convert::TryInto;
@@ -35,6 +35,10 @@ pub(crate) mod btc_relay {
     ) -> Result<bool, DispatchError> {
         <btc_relay::Pallet<T>>::has_request_expired(opentime, btc_open_height, period)
     }
+
+    pub fn ensure_relay_live<T: crate::Config>() -> DispatchResult {
+        <btc_relay::Pallet<T>>::ensure_relay_live()
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -84,6 +88,10 @@ pub(crate) mod vault_registry {
         <vault_registry::Pallet<T>>::ensure_not_banned(vault_id)
     }
 
+    pub fn ensure_not_exiting<T: crate::Config>(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        <vault_registry::Pallet<T>>::ensure_not_exiting(vault_id)
+    }
+
     pub fn try_increase_to_be_issued_tokens<T: crate::Config>(
         vault_id: &DefaultVaultId<T>,
         amount: &Amount<T>,
@@ -40,6 +40,7 @@ use frame_support::{
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_core::H256;
+use sp_runtime::traits::Saturating;
 use sp_std::vec::Vec;
 use types::DefaultVaultId;
 use vault_registry::{types::CurrencyId, CurrencySource};
@@ -336,6 +337,8 @@ pub mod pallet {
 #[cfg_attr(test, mockable)]
 impl<T: Config> Pallet<T> {
     fn _request_replace(vault_id: DefaultVaultId<T>, amount_btc: BalanceOf<T>) -> DispatchResult {
+        ext::btc_relay::ensure_relay_live::<T>()?;
+
         // check vault is not banned
         ext::vault_registry::ensure_not_banned::<T>(&vault_id)?;
 
@@ -422,6 +425,8 @@ impl<T: Config> Pallet<T> {
         collateral: BalanceOf<T>,
         btc_address: BtcAddress,
     ) -> Result<(), DispatchError> {
+        ext::btc_relay::ensure_relay_live::<T>()?;
+
         let new_vault_currency_id = new_vault_id.collateral_currency();
         let amount_btc = Amount::new(amount_btc, old_vault_id.wrapped_currency());
         let collateral = Amount::new(collateral, new_vault_currency_id);
@@ -439,6 +444,9 @@ impl<T: Config> Pallet<T> {
         // Check that new vault is not currently banned
         ext::vault_registry::ensure_not_banned::<T>(&new_vault_id)?;
 
+        // an exiting vault must not take on new tokens
+        ext::vault_registry::ensure_not_exiting::<T>(&new_vault_id)?;
+
         // decrease old-vault's to-be-replaced tokens
         let (redeemable_tokens, griefing_collateral) =
             ext::vault_registry::decrease_to_be_replaced_tokens::<T>(&old_vault_id, &amount_btc)?;
@@ -678,3 +686,26 @@ impl<T: Config> Pallet<T> {
         Amount::new(ReplaceBtcDustValue::<T>::get(), currency_id)
     }
 }
+
+impl<T: Config> traits::RequestPaymentApi<DefaultVaultId<T>, H256, BtcAddress, BalanceOf<T>> for Pallet<T> {
+    fn get_payable_request(replace_id: &H256, vault_id: &DefaultVaultId<T>) -> Option<(BtcAddress, BalanceOf<T>)> {
+        let request = <ReplaceRequests<T>>::get(replace_id).filter(|request| &request.old_vault == vault_id)?;
+        let payable = match request.status {
+            ReplaceRequestStatus::Pending => true,
+            ReplaceRequestStatus::Completed => false,
+            // a cancelled request may still be executed, as long as the payment was sent shortly
+            // before the cancellation
+            ReplaceRequestStatus::Cancelled => !ext::btc_relay::has_request_expired::<T>(
+                request.accept_time,
+                request.btc_height,
+                Self::replace_period().max(request.period).saturating_mul(2u32.into()),
+            )
+            .unwrap_or(true),
+        };
+        if payable {
+            Some((request.btc_address, request.amount))
+        } else {
+            None
+        }
+    }
+}
@@ -123,6 +123,19 @@ mod request_replace_tests {
             assert_err!(Replace::_request_replace(OLD_VAULT, 0), TestError::ReplaceAmountZero);
         })
     }
+
+    #[test]
+    fn test_request_replace_relay_stale_fails() {
+        run_test(|| {
+            setup_mocks();
+            ext::btc_relay::ensure_relay_live::<Test>
+                .mock_safe(|| MockResult::Return(Err(btc_relay::Error::<Test>::RelayStale.into())));
+            assert_err!(
+                Replace::_request_replace(OLD_VAULT, 1),
+                btc_relay::Error::<Test>::RelayStale
+            );
+        })
+    }
 }
 
 mod accept_replace_tests {
@@ -187,6 +200,19 @@ mod accept_replace_tests {
             );
         })
     }
+
+    #[test]
+    fn test_accept_replace_relay_stale_fails() {
+        run_test(|| {
+            setup_mocks();
+            ext::btc_relay::ensure_relay_live::<Test>
+                .mock_safe(|| MockResult::Return(Err(btc_relay::Error::<Test>::RelayStale.into())));
+            assert_err!(
+                Replace::_accept_replace(OLD_VAULT, NEW_VAULT, 5, 10, BtcAddress::random()),
+                btc_relay::Error::<Test>::RelayStale
+            );
+        })
+    }
 }
 
 mod execute_replace_test {
@@ -46,6 +46,8 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: Security ParachainStatus (r:1 w:0)
 	/// Proof Skipped: Security ParachainStatus (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Security IsRelayStale (r:1 w:0)
+	/// Proof Skipped: Security IsRelayStale (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Security ActiveBlockCount (r:1 w:1)
 	/// Proof Skipped: Security ActiveBlockCount (max_values: Some(1), max_size: None, mode: Measured)
 	fn on_initialize() -> Weight {
@@ -54,7 +56,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		//  Estimated: `2864`
 		// Minimum execution time: 18_873_000 picoseconds.
 		Weight::from_parts(19_407_000, 2864)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	/// Storage: Security ParachainStatus (r:0 w:1)
@@ -73,6 +75,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 impl WeightInfo for () {
 	/// Storage: Security ParachainStatus (r:1 w:0)
 	/// Proof Skipped: Security ParachainStatus (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Security IsRelayStale (r:1 w:0)
+	/// Proof Skipped: Security IsRelayStale (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Security ActiveBlockCount (r:1 w:1)
 	/// Proof Skipped: Security ActiveBlockCount (max_values: Some(1), max_size: None, mode: Measured)
 	fn on_initialize() -> Weight {
@@ -81,7 +85,7 @@ impl WeightInfo for () {
 		//  Estimated: `2864`
 		// Minimum execution time: 18_873_000 picoseconds.
 		Weight::from_parts(19_407_000, 2864)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	/// Storage: Security ParachainStatus (r:0 w:1)
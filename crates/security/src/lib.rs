@@ -87,6 +87,12 @@ pub mod pallet {
     #[pallet::storage]
     pub type IsDeactivated<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    /// Set while the BTC-Relay has not been updated within its staleness threshold. Payment proofs
+    /// cannot be verified against a stale relay, so the active block count is paused as well.
+    #[pallet::storage]
+    #[pallet::getter(fn is_relay_stale)]
+    pub type IsRelayStale<T: Config> = StorageValue<_, bool, ValueQuery>;
+
     #[pallet::pallet]
     pub struct Pallet<T>(_);
 
@@ -122,6 +128,11 @@ impl<T: Config> Pallet<T> {
         Ok(Self::active_block_number() > expiration_block)
     }
 
+    /// Marks the BTC-Relay as stale or live, pausing or resuming the active block count.
+    pub fn set_relay_stale(is_stale: bool) {
+        IsRelayStale::<T>::set(is_stale);
+    }
+
     /// Increment and return the `Nonce`.
     fn get_nonce() -> U256 {
         <Nonce<T>>::mutate(|n| {
@@ -132,7 +143,7 @@ impl<T: Config> Pallet<T> {
     }
 
     fn increment_active_block() {
-        if IsDeactivated::<T>::get() {
+        if IsDeactivated::<T>::get() || IsRelayStale::<T>::get() {
             return;
         }
 
@@ -42,6 +42,21 @@ fn test_get_active_block_not_incremented_if_inactive() {
     })
 }
 
+#[test]
+fn test_get_active_block_not_incremented_if_relay_stale() {
+    run_test(|| {
+        let initial_active_block = Security::active_block_number();
+
+        Security::set_relay_stale(true);
+        Security::increment_active_block();
+        assert_eq!(Security::active_block_number(), initial_active_block);
+
+        Security::set_relay_stale(false);
+        Security::increment_active_block();
+        assert_eq!(Security::active_block_number(), initial_active_block + 1);
+    })
+}
+
 mod spec_based_tests {
     use super::*;
     use sp_core::U256;
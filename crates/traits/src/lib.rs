@@ -23,11 +23,33 @@ pub trait OracleApi<Amount, CurrencyId> {
 pub trait NominationApi<VaultId, Amount> {
     fn deposit_vault_collateral(vault_id: &VaultId, amount: &Amount) -> Result<(), DispatchError>;
     fn ensure_opted_in_to_nomination(vault_id: &VaultId) -> Result<(), DispatchError>;
+    /// Clears the nomination opt-in of a vault that left the system, its nominators must already be kicked
+    fn clear_nomination_opt_in(vault_id: &VaultId);
 
     #[cfg(any(feature = "runtime-benchmarks", test))]
     fn opt_in_to_nomination(vault_id: &VaultId);
 }
 
+/// Lets the vault registry check whether a Bitcoin transaction pays out a request of a vault
+pub trait RequestPaymentApi<VaultId, RequestId, Address, Balance> {
+    /// Returns the recipient and the amount of the payment that `vault_id` may make for
+    /// `request_id`, if the request is pending or was cancelled so recently that the payment may
+    /// still be in flight
+    fn get_payable_request(request_id: &RequestId, vault_id: &VaultId) -> Option<(Address, Balance)>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(3)]
+impl<VaultId, RequestId, Address, Balance> RequestPaymentApi<VaultId, RequestId, Address, Balance> for Tuple {
+    fn get_payable_request(request_id: &RequestId, vault_id: &VaultId) -> Option<(Address, Balance)> {
+        for_tuples!( #(
+            if let Some(payment) = Tuple::get_payable_request(request_id, vault_id) {
+                return Some(payment);
+            }
+        )* );
+        None
+    }
+}
+
 pub trait OnExchangeRateChange<CurrencyId> {
     fn on_exchange_rate_change(currency_id: &CurrencyId);
 }
@@ -2,14 +2,18 @@
 
 use super::*;
 use crate::{types::BtcPublicKey, Pallet as VaultRegistry};
+use bitcoin::types::{TransactionBuilder, TransactionInputBuilder, TransactionOutput};
+use btc_relay::Pallet as BtcRelay;
 use currency::getters::{get_relay_chain_currency_id as get_collateral_currency_id, *};
-use frame_benchmarking::v2::{account, benchmarks, impl_benchmark_test_suite};
+use frame_benchmarking::v2::{account, benchmarks, impl_benchmark_test_suite, Linear};
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
 use loans::{InterestRateModel, JumpModel, Market, MarketState, Pallet as Loans};
 use oracle::Pallet as Oracle;
 use orml_traits::MultiCurrency;
 use primitives::{CurrencyId, Rate, Ratio};
+use security::Pallet as Security;
+use sp_core::H160;
 use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 use traits::LoansApi;
@@ -273,6 +277,237 @@ pub mod benchmarks {
         recover_vault_id(RawOrigin::Signed(vault_id.account_id), vault_id.currencies.clone());
     }
 
+    #[benchmark]
+    fn report_vault_theft(h: Linear<2, 10>, i: Linear<2, 10>, o: Linear<1, 3>, b: Linear<541, 2_048>) {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+        let relayer_id: T::AccountId = account("Relayer", 0, 0);
+        let origin: T::AccountId = account("Origin", 0, 0);
+
+        let issue_id = H256::zero();
+        VaultRegistry::<T>::register_deposit_address(&vault_id, issue_id).unwrap();
+        let deposit_public_key = BtcPublicKey::dummy().new_deposit_public_key(issue_id).unwrap();
+
+        // the padded first input is not from the vault, so all inputs are checked
+        let mut transaction_builder = TransactionBuilder::new();
+        transaction_builder.with_version(2);
+        transaction_builder.add_input(TransactionInputBuilder::build_max(1));
+        for _ in 1..i {
+            transaction_builder.add_input(
+                TransactionInputBuilder::new()
+                    .with_p2wpkh(&deposit_public_key, vec![0; 72])
+                    .build(),
+            );
+        }
+        for _ in 0..o {
+            transaction_builder.add_output(TransactionOutput::payment(100, &BtcAddress::P2PKH(H160::zero())));
+        }
+        let mut transaction = transaction_builder.build();
+        let padding = (b as usize)
+            .checked_sub(transaction.size_no_witness())
+            .expect("Wrong length bound in benchmark");
+        transaction.inputs[0].pad_script(padding);
+
+        let unchecked_transaction = BtcRelay::<T>::initialize_and_store_transaction(relayer_id, h, transaction);
+
+        #[extrinsic_call]
+        report_vault_theft(RawOrigin::Signed(origin), vault_id.clone(), unchecked_transaction);
+    }
+
+    #[benchmark]
+    fn request_vault_exit() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+
+        #[extrinsic_call]
+        request_vault_exit(RawOrigin::Signed(vault_id.account_id), vault_id.currencies.clone());
+    }
+
+    #[benchmark]
+    fn execute_vault_exit() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+        VaultRegistry::<T>::_request_vault_exit(&vault_id).unwrap();
+
+        #[extrinsic_call]
+        execute_vault_exit(
+            RawOrigin::Signed(vault_id.account_id.clone()),
+            vault_id.currencies.clone(),
+        );
+
+        assert!(VaultRegistry::<T>::get_vault_from_id(&vault_id).is_err());
+    }
+
+    #[benchmark]
+    fn liquidate_expired_vault_exit() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        let origin: T::AccountId = account("Origin", 0, 0);
+        register_vault_with_collateral::<T>(vault_id.clone());
+
+        let amount = VaultRegistry::<T>::get_issuable_tokens_from_vault(&vault_id).unwrap();
+        VaultRegistry::<T>::try_increase_to_be_issued_tokens(&vault_id, &amount).unwrap();
+        VaultRegistry::<T>::issue_tokens(&vault_id, &amount).unwrap();
+
+        VaultRegistry::<T>::_request_vault_exit(&vault_id).unwrap();
+        Security::<T>::set_active_block_number(
+            Security::<T>::active_block_number() + VaultRegistry::<T>::exit_period() + 1u32.into(),
+        );
+
+        #[extrinsic_call]
+        liquidate_expired_vault_exit(RawOrigin::Signed(origin), vault_id.clone());
+    }
+
+    #[benchmark]
+    fn set_exit_period() {
+        #[extrinsic_call]
+        set_exit_period(RawOrigin::Root, 100u32.into());
+    }
+
+    #[benchmark]
+    fn rotate_public_key() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+        VaultRegistry::<T>::_request_public_key_rotation(&vault_id.account_id, BtcPublicKey::default()).unwrap();
+
+        #[extrinsic_call]
+        rotate_public_key(RawOrigin::Signed(vault_id.account_id), None);
+    }
+
+    #[benchmark]
+    fn rotate_public_key_with_proof(h: Linear<2, 10>, i: Linear<2, 10>, o: Linear<1, 3>, b: Linear<541, 2_048>) {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+        let relayer_id: T::AccountId = account("Relayer", 0, 0);
+        let new_public_key = BtcPublicKey::default();
+        VaultRegistry::<T>::_request_public_key_rotation(&vault_id.account_id, new_public_key.clone()).unwrap();
+
+        let issue_id = H256::zero();
+        VaultRegistry::<T>::register_deposit_address(&vault_id, issue_id).unwrap();
+        let deposit_public_key = BtcPublicKey::dummy().new_deposit_public_key(issue_id).unwrap();
+
+        // the padded first input is not from the vault, so all inputs are checked
+        let mut transaction_builder = TransactionBuilder::new();
+        transaction_builder.with_version(2);
+        transaction_builder.add_input(TransactionInputBuilder::build_max(1));
+        for _ in 1..i {
+            transaction_builder.add_input(
+                TransactionInputBuilder::new()
+                    .with_p2wpkh(&deposit_public_key, vec![0; 72])
+                    .build(),
+            );
+        }
+        for _ in 0..o {
+            transaction_builder.add_output(TransactionOutput::payment(
+                100,
+                &BtcAddress::P2WPKHv0(new_public_key.to_hash()),
+            ));
+        }
+        let mut transaction = transaction_builder.build();
+        let padding = (b as usize)
+            .checked_sub(transaction.size_no_witness())
+            .expect("Wrong length bound in benchmark");
+        transaction.inputs[0].pad_script(padding);
+
+        let unchecked_transaction = BtcRelay::<T>::initialize_and_store_transaction(relayer_id, h, transaction);
+
+        #[extrinsic_call]
+        rotate_public_key(RawOrigin::Signed(vault_id.account_id), Some(unchecked_transaction));
+    }
+
+    #[benchmark]
+    fn set_require_key_migration_proof() {
+        #[extrinsic_call]
+        set_require_key_migration_proof(RawOrigin::Root, true);
+    }
+
+    #[benchmark]
+    fn deposit_basket_collateral() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+        let currency_id = get_native_currency_id::<T>();
+        mint_collateral::<T>(&vault_id.account_id, 1000u32.into());
+        VaultRegistry::<T>::set_basket_collateral_haircut(
+            RawOrigin::Root.into(),
+            currency_id,
+            Some(UnsignedFixedPoint::<T>::zero()),
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        deposit_basket_collateral(
+            RawOrigin::Signed(vault_id.account_id),
+            vault_id.currencies.clone(),
+            currency_id,
+            1000u32.into(),
+        );
+    }
+
+    #[benchmark]
+    fn withdraw_basket_collateral() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+        let currency_id = get_native_currency_id::<T>();
+        mint_collateral::<T>(&vault_id.account_id, 1000u32.into());
+        Oracle::<T>::_set_exchange_rate(currency_id, UnsignedFixedPoint::<T>::one()).unwrap();
+        VaultRegistry::<T>::set_basket_collateral_haircut(
+            RawOrigin::Root.into(),
+            currency_id,
+            Some(UnsignedFixedPoint::<T>::zero()),
+        )
+        .unwrap();
+        VaultRegistry::<T>::_deposit_basket_collateral(&vault_id, &Amount::new(1000u32.into(), currency_id)).unwrap();
+
+        #[extrinsic_call]
+        withdraw_basket_collateral(
+            RawOrigin::Signed(vault_id.account_id),
+            vault_id.currencies.clone(),
+            currency_id,
+            1000u32.into(),
+        );
+    }
+
+    #[benchmark]
+    fn set_basket_collateral_haircut() {
+        #[extrinsic_call]
+        set_basket_collateral_haircut(
+            RawOrigin::Root,
+            get_native_currency_id::<T>(),
+            Some(UnsignedFixedPoint::<T>::zero()),
+        );
+    }
+
+    #[benchmark]
+    fn swap_collateral_currency() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+        let new_vault_id = VaultId::new(
+            vault_id.account_id.clone(),
+            get_collateral_currency_id::<T>(),
+            vault_id.wrapped_currency(),
+        );
+        set_collateral_config::<T>(&new_vault_id);
+        mint_collateral::<T>(&vault_id.account_id, 100_000u32.into());
+
+        #[extrinsic_call]
+        swap_collateral_currency(
+            RawOrigin::Signed(vault_id.account_id.clone()),
+            vault_id.currencies.clone(),
+            new_vault_id.collateral_currency(),
+            100_000u32.into(),
+        );
+
+        assert!(VaultRegistry::<T>::vault_exists(&new_vault_id));
+    }
+
+    #[benchmark]
+    fn request_public_key_rotation() {
+        let vault_id = activate_lending_and_get_vault_id::<T>();
+        register_vault_with_collateral::<T>(vault_id.clone());
+
+        #[extrinsic_call]
+        request_public_key_rotation(RawOrigin::Signed(vault_id.account_id), BtcPublicKey::default());
+    }
+
     impl_benchmark_test_suite! {
         VaultRegistry,
         crate::mock::ExtBuilder::build_with(Default::default()),
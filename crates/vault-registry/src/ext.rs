@@ -130,6 +130,13 @@ pub(crate) mod fee {
         <fee::Pallet<T>>::distribute_all_vault_rewards(vault_id)
     }
 
+    pub fn withdraw_all_vault_rewards<T: crate::Config>(
+        vault_id: &DefaultVaultId<T>,
+        nominator_id: &T::AccountId,
+    ) -> DispatchResult {
+        <fee::Pallet<T>>::withdraw_all_vault_rewards(vault_id, nominator_id, None)
+    }
+
     pub fn premium_redeem_reward_rate<T: crate::Config>() -> UnsignedFixedPoint<T> {
         <fee::Pallet<T>>::premium_redeem_reward_rate()
     }
@@ -138,3 +145,15 @@ pub(crate) mod fee {
         <fee::Pallet<T>>::get_redeem_fee_value()
     }
 }
+
+#[cfg_attr(test, mockable)]
+pub(crate) mod btc_relay {
+    use bitcoin::types::{FullTransactionProof, Transaction};
+    use frame_support::dispatch::DispatchError;
+
+    pub fn verify_transaction_inclusion<T: crate::Config>(
+        unchecked_transaction: FullTransactionProof,
+    ) -> Result<Transaction, DispatchError> {
+        <btc_relay::Pallet<T>>::_verify_transaction_inclusion(unchecked_transaction, None)
+    }
+}
@@ -40,11 +40,13 @@ use crate::types::DefaultVaultCurrencyPair;
 pub use crate::types::{
     BtcPublicKey, CurrencySource, DefaultVault, DefaultVaultId, SystemVault, Vault, VaultId, VaultStatus,
 };
+use bitcoin::types::{FullTransactionProof, H256Le, Transaction, Value};
 pub use currency::Amount;
 use currency::Rounding;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
+    pallet_prelude::Weight,
     traits::Get,
     transactional, PalletId,
 };
@@ -60,13 +62,53 @@ use sp_runtime::{
     ArithmeticError, FixedPointNumber,
 };
 use sp_std::{convert::TryInto, vec::Vec};
-use traits::NominationApi;
+use traits::{NominationApi, RequestPaymentApi};
 
 // value taken from https://github.com/substrate-developer-hub/recipes/blob/master/pallets/ocw-demo/src/lib.rs
 pub const UNSIGNED_TXS_PRIORITY: u64 = 100;
 
 pub use pallet::*;
 
+/// Complexity:
+/// - `O(H + I + O + B)` where:
+///   - `H` is the number of hashes in the merkle tree
+///   - `I` is the number of transaction inputs
+///   - `O` is the number of transaction outputs
+///   - `B` is `transaction` size in bytes (length-fee-bounded)
+fn weight_for_report_vault_theft<T: Config>(proof: &FullTransactionProof) -> Weight {
+    <T as Config>::WeightInfo::report_vault_theft(
+        proof.user_tx_proof.merkle_proof.hashes.len() as u32, // H
+        proof.user_tx_proof.transaction.inputs.len() as u32,  // I
+        proof.user_tx_proof.transaction.outputs.len() as u32, // O
+        proof.user_tx_proof.tx_encoded_len,
+    )
+    .saturating_add(<T as Config>::WeightInfo::report_vault_theft(
+        proof.coinbase_proof.merkle_proof.hashes.len() as u32, // H
+        proof.coinbase_proof.transaction.inputs.len() as u32,  // I
+        proof.coinbase_proof.transaction.outputs.len() as u32, // O
+        proof.coinbase_proof.tx_encoded_len,
+    ))
+}
+
+/// Complexity: `O(1)` without a migration proof, otherwise the same as `weight_for_report_vault_theft`
+fn weight_for_rotate_public_key<T: Config>(migration_proof: &Option<FullTransactionProof>) -> Weight {
+    match migration_proof {
+        None => <T as Config>::WeightInfo::rotate_public_key(),
+        Some(proof) => <T as Config>::WeightInfo::rotate_public_key_with_proof(
+            proof.user_tx_proof.merkle_proof.hashes.len() as u32, // H
+            proof.user_tx_proof.transaction.inputs.len() as u32,  // I
+            proof.user_tx_proof.transaction.outputs.len() as u32, // O
+            proof.user_tx_proof.tx_encoded_len,
+        )
+        .saturating_add(<T as Config>::WeightInfo::rotate_public_key_with_proof(
+            proof.coinbase_proof.merkle_proof.hashes.len() as u32, // H
+            proof.coinbase_proof.transaction.inputs.len() as u32,  // I
+            proof.coinbase_proof.transaction.outputs.len() as u32, // O
+            proof.coinbase_proof.tx_encoded_len,
+        )),
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use crate::types::DefaultVaultCurrencyPair;
@@ -86,6 +128,7 @@ pub mod pallet {
         + security::Config
         + currency::Config
         + fee::Config
+        + btc_relay::Config
     {
         /// The vault module id, used for deriving its sovereign account ID.
         #[pallet::constant] // put the constant in metadata
@@ -102,6 +145,9 @@ pub mod pallet {
         /// Currency used for griefing collateral, e.g. DOT.
         #[pallet::constant]
         type GetGriefingCollateralCurrencyId: Get<CurrencyId<Self>>;
+
+        /// Api used to check whether a Bitcoin transaction of a vault pays one of its requests.
+        type RequestPayments: RequestPaymentApi<DefaultVaultId<Self>, H256, BtcAddress, BalanceOf<Self>>;
     }
 
     #[pallet::hooks]
@@ -114,6 +160,10 @@ pub mod pallet {
         fn on_runtime_upgrade() -> frame_support::weights::Weight {
             crate::types::v1::migrate_v1_to_v6::<T>()
         }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            crate::types::v6::migrate_v6_to_v7::<T>(remaining_weight)
+        }
     }
 
     #[pallet::validate_unsigned]
@@ -209,6 +259,9 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let account_id = ensure_signed(origin)?;
             let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            if accept_new_issues {
+                Self::ensure_not_exiting(&vault_id)?;
+            }
             let mut vault = Self::get_active_rich_vault_from_id(&vault_id)?;
             vault.set_accept_new_issues(accept_new_issues)?;
             PoolManager::<T>::on_vault_settings_change(&vault_id)?;
@@ -389,6 +442,277 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Reports a Bitcoin transaction in which the vault spent BTC from one of its deposit
+        /// addresses without paying a redeem or replace request, which liquidates the vault.
+        ///
+        /// # Arguments
+        /// * `vault_id` - the vault that signed the transaction
+        /// * `unchecked_transaction` - the transaction together with its inclusion proof
+        ///
+        /// # Errors
+        /// * `TheftAlreadyReported` - if the transaction was already reported for this vault
+        /// * `TransactionNotFromVault` - if no input spends from an address of the vault
+        /// * `ValidVaultTransaction` - if the transaction pays a pending or just cancelled request of the vault, only
+        ///   moves BTC between the vault's addresses or migrated its BTC to a new public key
+        #[pallet::call_index(11)]
+        #[pallet::weight(weight_for_report_vault_theft::<T>(unchecked_transaction))]
+        #[transactional]
+        pub fn report_vault_theft(
+            origin: OriginFor<T>,
+            vault_id: DefaultVaultId<T>,
+            unchecked_transaction: FullTransactionProof,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::_report_vault_theft(&vault_id, unchecked_transaction)?;
+            Ok(().into())
+        }
+
+        /// Starts the exit of the vault. The vault stops accepting new issue and replace requests and
+        /// must get all of its tokens redeemed or replaced within `ExitPeriod` blocks, after which it
+        /// can execute the exit.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the currency pair of the vault
+        ///
+        /// # Errors
+        /// * `VaultExitAlreadyRequested` - if the vault is already exiting
+        /// * `VaultLiquidated` - if the vault was liquidated
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_vault_exit())]
+        #[transactional]
+        pub fn request_vault_exit(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::_request_vault_exit(&vault_id)?;
+            Ok(().into())
+        }
+
+        /// Completes the exit of a vault without outstanding tokens. Its rewards are withdrawn,
+        /// nominators are kicked, its collateral is released and the vault is removed.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the currency pair of the vault
+        ///
+        /// # Errors
+        /// * `VaultExitNotRequested` - if the vault did not request to exit
+        /// * `VaultExitPending` - if the vault still has tokens or open requests
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::execute_vault_exit())]
+        #[transactional]
+        pub fn execute_vault_exit(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::_execute_vault_exit(&vault_id)?;
+            Ok(().into())
+        }
+
+        /// Liquidates an exiting vault that did not get rid of its tokens before its exit deadline.
+        /// Can be called by anyone.
+        ///
+        /// # Arguments
+        /// * `vault_id` - the exiting vault
+        ///
+        /// # Errors
+        /// * `VaultExitNotRequested` - if the vault did not request to exit
+        /// * `VaultExitNotExpired` - if the exit deadline has not passed yet
+        /// * `VaultExitExecutable` - if the vault has no outstanding tokens
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::liquidate_expired_vault_exit())]
+        #[transactional]
+        pub fn liquidate_expired_vault_exit(
+            origin: OriginFor<T>,
+            vault_id: DefaultVaultId<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::_liquidate_expired_vault_exit(&vault_id)?;
+            Ok(().into())
+        }
+
+        /// Changes the number of blocks an exiting vault has to get its tokens redeemed or replaced
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `period` - the new exit period
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_exit_period())]
+        #[transactional]
+        pub fn set_exit_period(origin: OriginFor<T>, period: BlockNumberFor<T>) -> DispatchResult {
+            ensure_root(origin)?;
+            ExitPeriod::<T>::set(period);
+            Ok(())
+        }
+
+        /// Replaces the registered BTC public key of the vault by the key announced with
+        /// `request_public_key_rotation`. Only new requests derive their deposit addresses from
+        /// the new key, the addresses of open requests remain valid.
+        ///
+        /// # Arguments
+        /// * `migration_proof` - optional inclusion proof of a transaction that moved BTC from the vault's deposit
+        ///   addresses to addresses of the new key, required if `RequireKeyMigrationProof` is set
+        ///
+        /// # Errors
+        /// * `NoPendingPublicKey` - if the vault did not announce a new public key
+        /// * `MissingKeyMigrationProof` - if a migration proof is required but not given
+        /// * `InvalidKeyMigrationProof` - if the transaction does not move BTC of the vault to the new key
+        #[pallet::call_index(16)]
+        #[pallet::weight(weight_for_rotate_public_key::<T>(migration_proof))]
+        #[transactional]
+        pub fn rotate_public_key(
+            origin: OriginFor<T>,
+            migration_proof: Option<FullTransactionProof>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            Self::_rotate_public_key(&account_id, migration_proof)?;
+            Ok(().into())
+        }
+
+        /// Sets whether vaults must prove the migration of their BTC when rotating their public key
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `required` - whether a migration proof is required
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_require_key_migration_proof())]
+        #[transactional]
+        pub fn set_require_key_migration_proof(origin: OriginFor<T>, required: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            RequireKeyMigrationProof::<T>::set(required);
+            Ok(())
+        }
+
+        /// Locks collateral of another currency in the basket of the vault. The basket collateral
+        /// counts towards the collateralization of the vault at its haircut value.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the currency pair of the vault
+        /// * `currency_id` - the currency of the basket collateral
+        /// * `amount` - the amount to lock
+        ///
+        /// # Errors
+        /// * `NotBasketCollateral` - if the currency is not accepted as basket collateral
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::deposit_basket_collateral())]
+        #[transactional]
+        pub fn deposit_basket_collateral(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::_deposit_basket_collateral(&vault_id, &Amount::new(amount, currency_id))?;
+            Ok(().into())
+        }
+
+        /// Unlocks basket collateral of the vault, as long as the vault stays above its secure threshold.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the currency pair of the vault
+        /// * `currency_id` - the currency of the basket collateral
+        /// * `amount` - the amount to unlock
+        ///
+        /// # Errors
+        /// * `InsufficientBasketCollateral` - if the vault locked less than `amount`
+        /// * `InsufficientCollateral` - if the vault would fall below its secure threshold
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_basket_collateral())]
+        #[transactional]
+        pub fn withdraw_basket_collateral(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::_withdraw_basket_collateral(&vault_id, &Amount::new(amount, currency_id))?;
+            Ok(().into())
+        }
+
+        /// Accepts a currency as basket collateral with the given haircut, or stops accepting it
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        /// * `currency_id` - the currency of the basket collateral
+        /// * `haircut` - the share of the value that is not counted as collateral, `None` to stop accepting the
+        ///   currency, after which deposited amounts no longer count as collateral
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_basket_collateral_haircut())]
+        #[transactional]
+        pub fn set_basket_collateral_haircut(
+            origin: OriginFor<T>,
+            currency_id: CurrencyId<T>,
+            haircut: Option<UnsignedFixedPoint<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(haircut) = haircut {
+                ensure!(haircut < UnsignedFixedPoint::<T>::one(), Error::<T>::InvalidHaircut);
+            }
+            BasketCollateralHaircut::<T>::set(currency_id, haircut);
+            Ok(())
+        }
+
+        /// Moves the vault to another collateral currency. The collateral of the vault is released
+        /// and `collateral` of the new currency is locked from its free balance, e.g. after swapping
+        /// the released collateral. Issued tokens are carried over to the vault with the new
+        /// currency pair, and nominators are refunded. The vault must not have any open issue,
+        /// redeem or replace requests, i.e. to-be-issued, to-be-redeemed and to-be-replaced tokens
+        /// are not carried over. Payments of requests that were cancelled before the swap are still
+        /// recognized as valid vault transactions of the new vault id.
+        ///
+        /// # Arguments
+        /// * `currency_pair` - the current currency pair of the vault
+        /// * `collateral_currency` - the new collateral currency
+        /// * `collateral` - the amount of the new collateral to lock
+        ///
+        /// # Errors
+        /// * `VaultHasOpenRequests` - if the vault has open issue, redeem or replace requests
+        /// * `VaultAlreadyRegistered` - if the account already has a vault with the new currency pair
+        /// * `InsufficientCollateral` - if the vault would be below the secure threshold with the new collateral
+        #[pallet::call_index(21)]
+        #[pallet::weight(<T as Config>::WeightInfo::swap_collateral_currency())]
+        #[transactional]
+        pub fn swap_collateral_currency(
+            origin: OriginFor<T>,
+            currency_pair: DefaultVaultCurrencyPair<T>,
+            collateral_currency: CurrencyId<T>,
+            collateral: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let vault_id = VaultId::new(account_id, currency_pair.collateral, currency_pair.wrapped);
+            Self::_swap_collateral_currency(&vault_id, collateral_currency, collateral)?;
+            Ok(().into())
+        }
+
+        /// Announces the BTC public key that the vault will rotate to. Until the rotation is
+        /// finalized with `rotate_public_key`, the vault may move its BTC to addresses of the new
+        /// key without this being reported as theft.
+        ///
+        /// # Arguments
+        /// * `public_key` - the new BTC public key of the vault
+        ///
+        /// # Errors
+        /// * `NoBitcoinPublicKey` - if the vault did not register a public key yet
+        /// * `PublicKeyUnchanged` - if the new key equals the registered key
+        #[pallet::call_index(22)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_public_key_rotation())]
+        #[transactional]
+        pub fn request_public_key_rotation(
+            origin: OriginFor<T>,
+            public_key: BtcPublicKey,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            Self::_request_public_key_rotation(&account_id, public_key)?;
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -509,6 +833,51 @@ pub mod pallet {
             vault_id: DefaultVaultId<T>,
             custom_threshold: Option<UnsignedFixedPoint<T>>,
         },
+        VaultTheft {
+            vault_id: DefaultVaultId<T>,
+            tx_id: H256Le,
+        },
+        RequestVaultExit {
+            vault_id: DefaultVaultId<T>,
+            deadline: BlockNumberFor<T>,
+        },
+        ExecuteVaultExit {
+            vault_id: DefaultVaultId<T>,
+            collateral: BalanceOf<T>,
+        },
+        ExpireVaultExit {
+            vault_id: DefaultVaultId<T>,
+        },
+        RotatePublicKey {
+            account_id: T::AccountId,
+            public_key: BtcPublicKey,
+            migration_tx: Option<H256Le>,
+        },
+        SwapCollateralCurrency {
+            old_vault_id: DefaultVaultId<T>,
+            new_vault_id: DefaultVaultId<T>,
+            released_collateral: BalanceOf<T>,
+            locked_collateral: BalanceOf<T>,
+        },
+        DepositBasketCollateral {
+            vault_id: DefaultVaultId<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+        },
+        WithdrawBasketCollateral {
+            vault_id: DefaultVaultId<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+        },
+        LiquidateBasketCollateral {
+            vault_id: DefaultVaultId<T>,
+            currency_id: CurrencyId<T>,
+            amount: BalanceOf<T>,
+        },
+        RequestPublicKeyRotation {
+            account_id: T::AccountId,
+            public_key: BtcPublicKey,
+        },
     }
 
     #[pallet::error]
@@ -567,6 +936,42 @@ pub mod pallet {
 
         // Minimum collateral was not found for the given currency
         MinimumCollateralNotSet,
+
+        /// The transaction was already reported as theft for this vault.
+        TheftAlreadyReported,
+        /// No input of the reported transaction spends from an address of the vault.
+        TransactionNotFromVault,
+        /// The reported transaction pays a request of the vault, transfers to its own addresses or
+        /// migrates its BTC to a new public key.
+        ValidVaultTransaction,
+        /// The vault already requested to exit.
+        VaultExitAlreadyRequested,
+        /// The vault did not request to exit.
+        VaultExitNotRequested,
+        /// Action not allowed on a vault that is exiting.
+        VaultExiting,
+        /// The exiting vault still has tokens or open requests.
+        VaultExitPending,
+        /// The exit deadline of the vault has not passed yet.
+        VaultExitNotExpired,
+        /// The exiting vault has no outstanding tokens and can execute its exit.
+        VaultExitExecutable,
+        /// The new public key is the same as the registered one.
+        PublicKeyUnchanged,
+        /// Rotating the public key requires a proof that the BTC of the vault was migrated.
+        MissingKeyMigrationProof,
+        /// The transaction does not move BTC from the vault's addresses to the new public key.
+        InvalidKeyMigrationProof,
+        /// The currency is not accepted as basket collateral.
+        NotBasketCollateral,
+        /// The vault locked less basket collateral than requested.
+        InsufficientBasketCollateral,
+        /// The haircut must be below one.
+        InvalidHaircut,
+        /// The vault has open issue, redeem or replace requests.
+        VaultHasOpenRequests,
+        /// The vault did not announce a new public key.
+        NoPendingPublicKey,
     }
 
     /// The minimum collateral (e.g. DOT/KSM) a Vault needs to provide to register.
@@ -628,6 +1033,95 @@ pub mod pallet {
     pub(super) type ReservedAddresses<T: Config> =
         StorageMap<_, Blake2_128Concat, BtcAddress, DefaultVaultId<T>, OptionQuery>;
 
+    /// The reserved BTC addresses of each vault, such that they can be released when the vault exits
+    #[pallet::storage]
+    pub(super) type VaultDepositAddresses<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, DefaultVaultId<T>, Blake2_128Concat, BtcAddress, (), OptionQuery>;
+
+    /// The last issue request whose deposit address was reserved by the migration to `V7`
+    #[pallet::storage]
+    pub(super) type DepositAddressMigrationCursor<T: Config> = StorageValue<_, H256, OptionQuery>;
+
+    /// The BTC public key that an account announced to rotate to, until the rotation is finalized
+    #[pallet::storage]
+    pub(super) type PendingBitcoinPublicKey<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BtcPublicKey, OptionQuery>;
+
+    /// Bitcoin transactions that migrated the BTC of an account's vaults to its new public key
+    #[pallet::storage]
+    pub(super) type KeyMigrationTransactions<T: Config> =
+        StorageMap<_, Blake2_128Concat, H256Le, T::AccountId, OptionQuery>;
+
+    /// The ids that a vault had before swapping its collateral currency, keyed by its current id,
+    /// since cancelled requests of the vault may still be paid
+    #[pallet::storage]
+    pub(super) type PreviousVaultIds<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, DefaultVaultId<T>, Blake2_128Concat, DefaultVaultId<T>, (), OptionQuery>;
+
+    /// Bitcoin transactions that were reported as theft, keyed by the vault that signed them
+    #[pallet::storage]
+    pub(super) type TheftReports<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, DefaultVaultId<T>, Blake2_128Concat, H256Le, (), OptionQuery>;
+
+    /// Number of blocks within which an exiting vault must get all of its tokens redeemed or replaced.
+    #[pallet::storage]
+    #[pallet::getter(fn exit_period)]
+    pub(super) type ExitPeriod<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Vaults that requested to exit, with the block number by which they must have no tokens left
+    #[pallet::storage]
+    pub(super) type VaultExits<T: Config> =
+        StorageMap<_, Blake2_128Concat, DefaultVaultId<T>, BlockNumberFor<T>, OptionQuery>;
+
+    /// Whether rotating the BTC public key of a vault requires a proof that its BTC was migrated
+    #[pallet::storage]
+    #[pallet::getter(fn require_key_migration_proof)]
+    pub(super) type RequireKeyMigrationProof<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Currencies accepted as basket collateral, with the share of their value that is not counted
+    /// as collateral
+    #[pallet::storage]
+    #[pallet::getter(fn basket_collateral_haircut)]
+    pub(super) type BasketCollateralHaircut<T: Config> =
+        StorageMap<_, Blake2_128Concat, CurrencyId<T>, UnsignedFixedPoint<T>, OptionQuery>;
+
+    /// Basket collateral locked by the vaults, in addition to the collateral of their currency pair
+    #[pallet::storage]
+    pub(super) type BasketCollateral<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultId<T>,
+        Blake2_128Concat,
+        CurrencyId<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Basket collateral of liquidated vaults that backs their to-be-redeemed tokens, released once
+    /// the tokens are redeemed
+    #[pallet::storage]
+    pub(super) type LiquidatedBasketCollateral<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultId<T>,
+        Blake2_128Concat,
+        CurrencyId<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Basket collateral seized from liquidated vaults, paid out to the redeemers of the liquidation vault
+    #[pallet::storage]
+    pub(super) type LiquidationBasketCollateral<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DefaultVaultCurrencyPair<T>,
+        Blake2_128Concat,
+        CurrencyId<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
     /// Total collateral used for collateral tokens issued by active vaults, excluding the liquidation vault
     #[pallet::storage]
     pub(super) type TotalUserVaultCollateral<T: Config> =
@@ -648,6 +1142,7 @@ pub mod pallet {
     pub struct GenesisConfig<T: Config> {
         pub minimum_collateral_vault: Vec<(CurrencyId<T>, BalanceOf<T>)>,
         pub punishment_delay: BlockNumberFor<T>,
+        pub exit_period: BlockNumberFor<T>,
         pub system_collateral_ceiling: Vec<(DefaultVaultCurrencyPair<T>, BalanceOf<T>)>,
         pub secure_collateral_threshold: Vec<(DefaultVaultCurrencyPair<T>, UnsignedFixedPoint<T>)>,
         pub premium_redeem_threshold: Vec<(DefaultVaultCurrencyPair<T>, UnsignedFixedPoint<T>)>,
@@ -658,6 +1153,7 @@ pub mod pallet {
     impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
         fn build(&self) {
             PunishmentDelay::<T>::put(self.punishment_delay);
+            ExitPeriod::<T>::put(self.exit_period);
             for (currency_id, minimum) in self.minimum_collateral_vault.iter() {
                 MinimumCollateralVault::<T>::insert(currency_id, minimum);
             }
@@ -695,27 +1191,33 @@ impl<T: Config> Pallet<T> {
         <T as Config>::PalletId::get().into_account_truncating()
     }
 
-    pub fn _register_vault(vault_id: DefaultVaultId<T>, collateral: BalanceOf<T>) -> DispatchResult {
+    /// Checks that vaults can be registered for the currency pair
+    fn ensure_currency_pair_configured(currency_pair: &DefaultVaultCurrencyPair<T>) -> DispatchResult {
         ensure!(
-            SecureCollateralThreshold::<T>::contains_key(&vault_id.currencies),
+            SecureCollateralThreshold::<T>::contains_key(currency_pair),
             Error::<T>::ThresholdNotSet
         );
         ensure!(
-            PremiumRedeemThreshold::<T>::contains_key(&vault_id.currencies),
+            PremiumRedeemThreshold::<T>::contains_key(currency_pair),
             Error::<T>::ThresholdNotSet
         );
         ensure!(
-            LiquidationCollateralThreshold::<T>::contains_key(&vault_id.currencies),
+            LiquidationCollateralThreshold::<T>::contains_key(currency_pair),
             Error::<T>::ThresholdNotSet
         );
         ensure!(
-            MinimumCollateralVault::<T>::contains_key(vault_id.collateral_currency()),
+            MinimumCollateralVault::<T>::contains_key(currency_pair.collateral),
             Error::<T>::MinimumCollateralNotSet
         );
         ensure!(
-            SystemCollateralCeiling::<T>::contains_key(&vault_id.currencies),
+            SystemCollateralCeiling::<T>::contains_key(currency_pair),
             Error::<T>::CeilingNotSet
         );
+        Ok(())
+    }
+
+    pub fn _register_vault(vault_id: DefaultVaultId<T>, collateral: BalanceOf<T>) -> DispatchResult {
+        Self::ensure_currency_pair_configured(&vault_id.currencies)?;
 
         // make sure a public key is registered
         let _ = Self::get_bitcoin_public_key(&vault_id.account_id)?;
@@ -804,7 +1306,7 @@ impl<T: Config> Pallet<T> {
 
         let required_collateral =
             Self::get_required_collateral_for_wrapped(&to_be_backed_tokens, vault_id.collateral_currency())?;
-        let current_collateral = Self::get_backing_collateral(&vault_id)?;
+        let current_collateral = Self::get_effective_collateral(&vault_id)?;
         let missing_collateral = required_collateral.saturating_sub(&current_collateral)?;
 
         // factor = fee / (secure - fee)
@@ -886,6 +1388,8 @@ impl<T: Config> Pallet<T> {
             Error::<T>::InsufficientVaultCollateralAmount
         );
 
+        // the basket collateral also counts towards the secure threshold
+        let new_collateral = new_collateral.checked_add(&Self::get_basket_collateral_value(vault_id)?)?;
         let is_below_threshold =
             Pallet::<T>::is_collateral_below_vault_secure_threshold(&new_collateral, &vault.backed_tokens()?, &vault)?;
         Ok(!is_below_threshold)
@@ -1024,6 +1528,7 @@ impl<T: Config> Pallet<T> {
     pub fn register_deposit_address(vault_id: &DefaultVaultId<T>, issue_id: H256) -> Result<BtcAddress, DispatchError> {
         let mut vault = Self::get_active_rich_vault_from_id(&vault_id)?;
         let btc_address = vault.new_deposit_address(issue_id)?;
+        Self::reserve_address(&vault.id(), btc_address);
         Self::deposit_event(Event::<T>::RegisterAddress {
             vault_id: vault.id(),
             address: btc_address,
@@ -1031,6 +1536,12 @@ impl<T: Config> Pallet<T> {
         Ok(btc_address)
     }
 
+    /// Reserves the BTC address for the vault, so that spending from it counts as a transaction of the vault
+    pub fn reserve_address(vault_id: &DefaultVaultId<T>, btc_address: BtcAddress) {
+        ReservedAddresses::<T>::insert(&btc_address, vault_id);
+        VaultDepositAddresses::<T>::insert(vault_id, btc_address, ());
+    }
+
     /// returns the amount of tokens that a vault can request to be replaced on top of the
     /// current to-be-replaced tokens
     pub fn requestable_to_be_replaced_tokens(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
@@ -1285,6 +1796,7 @@ impl<T: Config> Pallet<T> {
 
             // release the collateral back to the free balance of the vault
             to_be_released.unlock_on(&vault_id.account_id)?;
+            Self::restore_liquidated_basket_collateral(vault_id, tokens, &to_be_redeemed_tokens)?;
 
             Self::deposit_event(Event::<T>::RedeemTokensLiquidatedVault {
                 vault_id: vault_id.clone(),
@@ -1328,11 +1840,13 @@ impl<T: Config> Pallet<T> {
         let source_liquidation_vault = CurrencySource::<T>::LiquidationVault(currency_pair.clone());
 
         // transfer liquidated collateral to redeemer
+        let to_be_backed_tokens = liquidation_vault.to_be_backed_tokens()?;
         let to_transfer = Self::calculate_collateral(
             &source_liquidation_vault.current_balance(currency_id)?,
             amount_wrapped,
-            &liquidation_vault.to_be_backed_tokens()?,
+            &to_be_backed_tokens,
         )?;
+        Self::redeem_basket_collateral_liquidation(&currency_pair, redeemer_id, amount_wrapped, &to_be_backed_tokens)?;
 
         Self::transfer_funds(
             source_liquidation_vault,
@@ -1385,6 +1899,7 @@ impl<T: Config> Pallet<T> {
 
             // deposit old-vault's collateral (this was withdrawn on liquidation)
             PoolManager::<T>::deposit_collateral(old_vault_id, &old_vault_id.account_id, &to_be_released)?;
+            Self::restore_liquidated_basket_collateral(old_vault_id, tokens, &old_vault.to_be_redeemed_tokens())?;
         }
 
         old_vault.execute_redeem_tokens(tokens)?;
@@ -1430,6 +1945,7 @@ impl<T: Config> Pallet<T> {
                 CurrencySource::LiquidationVault(old_vault_id.currencies.clone()),
                 &to_be_transferred,
             )?;
+            Self::slash_liquidated_basket_collateral(old_vault_id, tokens, &old_vault.to_be_redeemed_tokens(), None)?;
         }
 
         old_vault.cancel_redeem_tokens(tokens)?;
@@ -1470,6 +1986,636 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    pub fn _report_vault_theft(
+        vault_id: &DefaultVaultId<T>,
+        unchecked_transaction: FullTransactionProof,
+    ) -> DispatchResult {
+        let transaction = ext::btc_relay::verify_transaction_inclusion::<T>(unchecked_transaction)?;
+        let tx_id = transaction.tx_id();
+        ensure!(
+            !TheftReports::<T>::contains_key(vault_id, tx_id),
+            Error::<T>::TheftAlreadyReported
+        );
+
+        // the transaction was accepted by the Bitcoin network, so spending from a deposit
+        // address of the vault proves that the vault signed it
+        ensure!(
+            transaction.inputs.iter().any(|input| input
+                .extract_address()
+                .map_or(false, |address| Self::is_vault_address(vault_id, &address))),
+            Error::<T>::TransactionNotFromVault
+        );
+        ensure!(
+            KeyMigrationTransactions::<T>::get(tx_id).as_ref() != Some(&vault_id.account_id)
+                && !Self::is_valid_vault_transaction(vault_id, &transaction),
+            Error::<T>::ValidVaultTransaction
+        );
+
+        TheftReports::<T>::insert(vault_id, tx_id, ());
+        Self::liquidate_vault(vault_id)?;
+        Self::deposit_event(Event::<T>::VaultTheft {
+            vault_id: vault_id.clone(),
+            tx_id,
+        });
+        Ok(())
+    }
+
+    pub fn is_vault_address(vault_id: &DefaultVaultId<T>, address: &BtcAddress) -> bool {
+        ReservedAddresses::<T>::get(address).as_ref() == Some(vault_id)
+    }
+
+    /// A vault may pay its pending (or just cancelled) redeem and replace requests, which are
+    /// identified by an op_return output, and move BTC between its own addresses, including those
+    /// of its registered and announced public keys.
+    fn is_valid_vault_transaction(vault_id: &DefaultVaultId<T>, transaction: &Transaction) -> bool {
+        let pays_request = transaction
+            .outputs
+            .iter()
+            .any(|output| match output.script.extract_op_return_data() {
+                Ok(data) if data.len() == 32 => Self::pays_request(vault_id, transaction, &H256::from_slice(&data)),
+                _ => false,
+            });
+        let key_addresses = VaultBitcoinPublicKey::<T>::get(&vault_id.account_id)
+            .into_iter()
+            .chain(PendingBitcoinPublicKey::<T>::get(&vault_id.account_id))
+            .flat_map(|public_key| Self::public_key_addresses(&public_key))
+            .collect::<Vec<_>>();
+        pays_request
+            || transaction.outputs.iter().all(|output| {
+                output.value == 0
+                    || output.extract_address().map_or(false, |address| {
+                        Self::is_vault_address(vault_id, &address) || key_addresses.contains(&address)
+                    })
+            })
+    }
+
+    /// The addresses that pay directly to the public key
+    fn public_key_addresses(public_key: &BtcPublicKey) -> [BtcAddress; 2] {
+        [
+            BtcAddress::P2WPKHv0(public_key.to_hash()),
+            BtcAddress::P2PKH(public_key.to_hash()),
+        ]
+    }
+
+    /// Returns true if the transaction pays at least the amount of the request to its recipient
+    fn pays_request(vault_id: &DefaultVaultId<T>, transaction: &Transaction, request_id: &H256) -> bool {
+        // requests cancelled before a swap of the collateral currency refer to a previous id
+        let payment = sp_std::iter::once(vault_id.clone())
+            .chain(PreviousVaultIds::<T>::iter_key_prefix(vault_id))
+            .find_map(|id| T::RequestPayments::get_payable_request(request_id, &id));
+        let (btc_address, amount) = match payment {
+            Some(payment) => payment,
+            None => return false,
+        };
+        let amount: Value = match amount.try_into() {
+            Ok(amount) => amount,
+            Err(_) => return false,
+        };
+        transaction.outputs.iter().any(|output| {
+            output.value >= amount && output.extract_address().map_or(false, |address| address == btc_address)
+        })
+    }
+
+    pub fn _request_public_key_rotation(account_id: &T::AccountId, public_key: BtcPublicKey) -> DispatchResult {
+        let old_public_key = Self::get_bitcoin_public_key(account_id)?;
+        ensure!(old_public_key != public_key, Error::<T>::PublicKeyUnchanged);
+
+        // the BTC of the vault may be moved to the new key from now on
+        PendingBitcoinPublicKey::<T>::insert(account_id, &public_key);
+
+        Self::deposit_event(Event::<T>::RequestPublicKeyRotation {
+            account_id: account_id.clone(),
+            public_key,
+        });
+        Ok(())
+    }
+
+    pub fn _rotate_public_key(
+        account_id: &T::AccountId,
+        migration_proof: Option<FullTransactionProof>,
+    ) -> DispatchResult {
+        let public_key = PendingBitcoinPublicKey::<T>::take(account_id).ok_or(Error::<T>::NoPendingPublicKey)?;
+
+        let migration_tx = match migration_proof {
+            Some(proof) => Some(Self::verify_key_migration(account_id, &public_key, proof)?),
+            None => {
+                ensure!(
+                    !Self::require_key_migration_proof(),
+                    Error::<T>::MissingKeyMigrationProof
+                );
+                None
+            }
+        };
+
+        // the migration can not be reported as theft, even once the key is rotated again
+        if let Some(tx_id) = migration_tx {
+            KeyMigrationTransactions::<T>::insert(tx_id, account_id);
+        }
+        // open requests store their deposit addresses, which stay reserved for the vault
+        VaultBitcoinPublicKey::<T>::insert(account_id, &public_key);
+
+        Self::deposit_event(Event::<T>::RotatePublicKey {
+            account_id: account_id.clone(),
+            public_key,
+            migration_tx,
+        });
+        Ok(())
+    }
+
+    /// Checks that the included transaction spends from a deposit address of one of the account's
+    /// vaults and only pays to addresses of the new public key
+    fn verify_key_migration(
+        account_id: &T::AccountId,
+        public_key: &BtcPublicKey,
+        migration_proof: FullTransactionProof,
+    ) -> Result<H256Le, DispatchError> {
+        let transaction = ext::btc_relay::verify_transaction_inclusion::<T>(migration_proof)?;
+        let new_addresses = Self::public_key_addresses(public_key);
+
+        ensure!(
+            transaction
+                .inputs
+                .iter()
+                .any(|input| input.extract_address().map_or(false, |address| {
+                    ReservedAddresses::<T>::get(address).map_or(false, |vault_id| &vault_id.account_id == account_id)
+                })),
+            Error::<T>::InvalidKeyMigrationProof
+        );
+        ensure!(
+            transaction.outputs.iter().all(|output| {
+                output.value == 0
+                    || output
+                        .extract_address()
+                        .map_or(false, |address| new_addresses.contains(&address))
+            }),
+            Error::<T>::InvalidKeyMigrationProof
+        );
+        Ok(transaction.tx_id())
+    }
+
+    pub fn _request_vault_exit(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        ensure!(
+            !VaultExits::<T>::contains_key(vault_id),
+            Error::<T>::VaultExitAlreadyRequested
+        );
+        let mut vault = Self::get_active_rich_vault_from_id(vault_id)?;
+        vault.set_accept_new_issues(false)?;
+        PoolManager::<T>::on_vault_settings_change(vault_id)?;
+
+        let deadline = ext::security::active_block_number::<T>()
+            .checked_add(&Self::exit_period())
+            .ok_or(ArithmeticError::Overflow)?;
+        VaultExits::<T>::insert(vault_id, deadline);
+
+        Self::deposit_event(Event::<T>::RequestVaultExit {
+            vault_id: vault_id.clone(),
+            deadline,
+        });
+        Ok(())
+    }
+
+    pub fn _execute_vault_exit(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        ensure!(
+            VaultExits::<T>::contains_key(vault_id),
+            Error::<T>::VaultExitNotRequested
+        );
+        let vault = Self::get_active_rich_vault_from_id(vault_id)?;
+        ensure!(!Self::has_outstanding_tokens(&vault), Error::<T>::VaultExitPending);
+
+        // release the griefing collateral left over from withdrawn replace requests
+        let replace_collateral = Amount::new(vault.data.replace_collateral, T::GetGriefingCollateralCurrencyId::get());
+        if !replace_collateral.is_zero() {
+            Self::transfer_funds(
+                CurrencySource::AvailableReplaceCollateral(vault_id.clone()),
+                CurrencySource::FreeBalance(vault_id.account_id.clone()),
+                &replace_collateral,
+            )?;
+        }
+
+        // rewards are accounted in the current staking pool, which is replaced when kicking the nominators
+        ext::fee::withdraw_all_vault_rewards::<T>(vault_id, &vault_id.account_id)?;
+
+        // nominators withdraw their collateral from the stale pool
+        let refunded_collateral = PoolManager::<T>::kick_nominators(vault_id)?;
+        Self::decrease_total_backing_collateral(&vault_id.currencies, &refunded_collateral)?;
+        T::NominationApi::clear_nomination_opt_in(vault_id);
+
+        let collateral = Self::get_backing_collateral(vault_id)?;
+        if !collateral.is_zero() {
+            Self::force_withdraw_collateral(vault_id, &collateral)?;
+        }
+        let basket: Vec<_> = BasketCollateral::<T>::iter_prefix(vault_id).collect();
+        for (currency_id, amount) in basket {
+            Self::release_basket_collateral(vault_id, &Amount::new(amount, currency_id))?;
+        }
+
+        // a vault registered later under the same id must not be held liable for these addresses
+        for (btc_address, _) in VaultDepositAddresses::<T>::drain_prefix(vault_id) {
+            ReservedAddresses::<T>::remove(btc_address);
+        }
+
+        Vaults::<T>::remove(vault_id);
+        VaultExits::<T>::remove(vault_id);
+
+        Self::deposit_event(Event::<T>::ExecuteVaultExit {
+            vault_id: vault_id.clone(),
+            collateral: collateral.amount(),
+        });
+        Ok(())
+    }
+
+    pub fn _liquidate_expired_vault_exit(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        let deadline = VaultExits::<T>::get(vault_id).ok_or(Error::<T>::VaultExitNotRequested)?;
+        ensure!(
+            ext::security::active_block_number::<T>() > deadline,
+            Error::<T>::VaultExitNotExpired
+        );
+        let vault = Self::get_active_rich_vault_from_id(vault_id)?;
+        ensure!(Self::has_outstanding_tokens(&vault), Error::<T>::VaultExitExecutable);
+
+        Self::liquidate_vault(vault_id)?;
+        Self::deposit_event(Event::<T>::ExpireVaultExit {
+            vault_id: vault_id.clone(),
+        });
+        Ok(())
+    }
+
+    /// Tokens or requests that keep the vault from exiting
+    fn has_outstanding_tokens(vault: &RichVault<T>) -> bool {
+        !vault.issued_tokens().is_zero()
+            || !vault.to_be_issued_tokens().is_zero()
+            || !vault.to_be_redeemed_tokens().is_zero()
+            || !vault.to_be_replaced_tokens().is_zero()
+            || !vault.data.active_replace_collateral.is_zero()
+    }
+
+    pub fn is_vault_exiting(vault_id: &DefaultVaultId<T>) -> bool {
+        VaultExits::<T>::contains_key(vault_id)
+    }
+
+    pub fn ensure_not_exiting(vault_id: &DefaultVaultId<T>) -> DispatchResult {
+        ensure!(!Self::is_vault_exiting(vault_id), Error::<T>::VaultExiting);
+        Ok(())
+    }
+
+    pub fn _swap_collateral_currency(
+        vault_id: &DefaultVaultId<T>,
+        collateral_currency: CurrencyId<T>,
+        collateral: BalanceOf<T>,
+    ) -> DispatchResult {
+        let new_vault_id = VaultId::new(
+            vault_id.account_id.clone(),
+            collateral_currency,
+            vault_id.wrapped_currency(),
+        );
+        ensure!(
+            collateral_currency != vault_id.collateral_currency()
+                && BasketCollateral::<T>::get(vault_id, collateral_currency).is_zero(),
+            Error::<T>::InvalidCurrency
+        );
+        Self::ensure_currency_pair_configured(&new_vault_id.currencies)?;
+        ensure!(!Self::vault_exists(&new_vault_id), Error::<T>::VaultAlreadyRegistered);
+        Self::ensure_not_exiting(vault_id)?;
+
+        let vault = Self::get_active_rich_vault_from_id(vault_id)?;
+        vault.ensure_not_banned()?;
+        // open requests refer to the vault id and to amounts in the old collateral currency
+        ensure!(
+            vault.to_be_issued_tokens().is_zero()
+                && vault.to_be_redeemed_tokens().is_zero()
+                && vault.to_be_replaced_tokens().is_zero()
+                && vault.data.active_replace_collateral.is_zero(),
+            Error::<T>::VaultHasOpenRequests
+        );
+
+        let new_collateral = Amount::new(collateral, collateral_currency);
+        ensure!(
+            new_collateral.ge(&Self::get_minimum_collateral_vault(collateral_currency))?,
+            Error::<T>::InsufficientVaultCollateralAmount
+        );
+
+        // rewards and nominations are accounted per vault id
+        ext::fee::withdraw_all_vault_rewards::<T>(vault_id, &vault_id.account_id)?;
+        let refunded_collateral = PoolManager::<T>::kick_nominators(vault_id)?;
+        Self::decrease_total_backing_collateral(&vault_id.currencies, &refunded_collateral)?;
+        T::NominationApi::clear_nomination_opt_in(vault_id);
+
+        let released_collateral = Self::get_backing_collateral(vault_id)?;
+        if !released_collateral.is_zero() {
+            Self::force_withdraw_collateral(vault_id, &released_collateral)?;
+        }
+
+        // carry the tokens, replace collateral and settings over to the new vault id
+        let mut vault = Self::get_vault_from_id(vault_id)?;
+        vault.id = new_vault_id.clone();
+        Vaults::<T>::remove(vault_id);
+        Self::insert_vault(&new_vault_id, vault);
+        for (currency_id, amount) in BasketCollateral::<T>::drain_prefix(vault_id) {
+            BasketCollateral::<T>::insert(&new_vault_id, currency_id, amount);
+        }
+        // the vault stays liable for the BTC on its deposit addresses
+        for (btc_address, _) in VaultDepositAddresses::<T>::drain_prefix(vault_id) {
+            Self::reserve_address(&new_vault_id, btc_address);
+        }
+        for (tx_id, _) in TheftReports::<T>::drain_prefix(vault_id) {
+            TheftReports::<T>::insert(&new_vault_id, tx_id, ());
+        }
+        // the vault may swap back to a collateral currency it had before
+        for (previous_vault_id, _) in PreviousVaultIds::<T>::drain_prefix(vault_id) {
+            if previous_vault_id != new_vault_id {
+                PreviousVaultIds::<T>::insert(&new_vault_id, previous_vault_id, ());
+            }
+        }
+        PreviousVaultIds::<T>::insert(&new_vault_id, vault_id, ());
+
+        Self::try_deposit_collateral(&new_vault_id, &new_collateral)?;
+        ensure!(
+            !Self::is_vault_below_secure_threshold(&new_vault_id)?,
+            Error::<T>::InsufficientCollateral
+        );
+
+        Self::deposit_event(Event::<T>::SwapCollateralCurrency {
+            old_vault_id: vault_id.clone(),
+            new_vault_id,
+            released_collateral: released_collateral.amount(),
+            locked_collateral: collateral,
+        });
+        Ok(())
+    }
+
+    pub fn _deposit_basket_collateral(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        let currency_id = amount.currency();
+        ensure!(
+            currency_id != vault_id.collateral_currency()
+                && currency_id != vault_id.wrapped_currency()
+                && BasketCollateralHaircut::<T>::contains_key(currency_id),
+            Error::<T>::NotBasketCollateral
+        );
+        let _ = Self::get_active_vault_from_id(vault_id)?;
+
+        amount.lock_on(&vault_id.account_id)?;
+        BasketCollateral::<T>::try_mutate(vault_id, currency_id, |locked| -> DispatchResult {
+            *locked = locked.checked_add(&amount.amount()).ok_or(ArithmeticError::Overflow)?;
+            Ok(())
+        })?;
+
+        Self::deposit_event(Event::<T>::DepositBasketCollateral {
+            vault_id: vault_id.clone(),
+            currency_id,
+            amount: amount.amount(),
+        });
+        Ok(())
+    }
+
+    pub fn _withdraw_basket_collateral(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        let currency_id = amount.currency();
+        let locked = Self::get_basket_collateral(vault_id, currency_id);
+        ensure!(locked.ge(amount)?, Error::<T>::InsufficientBasketCollateral);
+        ensure!(
+            Self::is_allowed_to_withdraw_basket_collateral(vault_id, amount)?,
+            Error::<T>::InsufficientCollateral
+        );
+
+        Self::release_basket_collateral(vault_id, amount)?;
+
+        Self::deposit_event(Event::<T>::WithdrawBasketCollateral {
+            vault_id: vault_id.clone(),
+            currency_id,
+            amount: amount.amount(),
+        });
+        Ok(())
+    }
+
+    fn release_basket_collateral(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        amount.unlock_on(&vault_id.account_id)?;
+        Self::decrease_basket_collateral(vault_id, amount)
+    }
+
+    fn decrease_basket_collateral(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        let remaining = Self::get_basket_collateral(vault_id, amount.currency()).checked_sub(amount)?;
+        if remaining.is_zero() {
+            BasketCollateral::<T>::remove(vault_id, amount.currency());
+        } else {
+            BasketCollateral::<T>::insert(vault_id, amount.currency(), remaining.amount());
+        }
+        Ok(())
+    }
+
+    /// Checks if the vault would be above the secure threshold after withdrawing basket collateral
+    fn is_allowed_to_withdraw_basket_collateral(
+        vault_id: &DefaultVaultId<T>,
+        amount: &Amount<T>,
+    ) -> Result<bool, DispatchError> {
+        let vault = Self::get_rich_vault_from_id(vault_id)?;
+        if vault.data.is_liquidated() {
+            // the share backing the to-be-redeemed tokens was moved to `LiquidatedBasketCollateral`
+            return Ok(true);
+        }
+
+        let withdrawn_value = Self::get_basket_collateral_value_of(amount, vault_id.collateral_currency())?;
+        let new_collateral = Self::get_effective_collateral(vault_id)?.saturating_sub(&withdrawn_value)?;
+        let is_below_threshold =
+            Self::is_collateral_below_vault_secure_threshold(&new_collateral, &vault.backed_tokens()?, &vault)?;
+        Ok(!is_below_threshold)
+    }
+
+    pub fn get_basket_collateral(vault_id: &DefaultVaultId<T>, currency_id: CurrencyId<T>) -> Amount<T> {
+        Amount::new(BasketCollateral::<T>::get(vault_id, currency_id), currency_id)
+    }
+
+    /// The value of the basket collateral in the collateral currency of the vault, after haircuts
+    pub fn get_basket_collateral_value(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
+        BasketCollateral::<T>::iter_prefix(vault_id).try_fold(
+            Amount::zero(vault_id.collateral_currency()),
+            |total, (currency_id, amount)| {
+                let value = Self::get_basket_collateral_value_of(
+                    &Amount::new(amount, currency_id),
+                    vault_id.collateral_currency(),
+                )?;
+                total.checked_add(&value)
+            },
+        )
+    }
+
+    fn get_basket_collateral_value_of(
+        amount: &Amount<T>,
+        collateral_currency: CurrencyId<T>,
+    ) -> Result<Amount<T>, DispatchError> {
+        let haircut = match Self::basket_collateral_haircut(amount.currency()) {
+            Some(haircut) => haircut,
+            // collateral in currencies that are no longer accepted does not count
+            None => return Ok(Amount::zero(collateral_currency)),
+        };
+        match amount.convert_to(collateral_currency) {
+            Ok(value) => value.checked_mul(&UnsignedFixedPoint::<T>::one().saturating_sub(haircut)),
+            // a currency without a price does not count either, so that it can not block liquidations
+            Err(_) => Ok(Amount::zero(collateral_currency)),
+        }
+    }
+
+    /// The backing collateral together with the value of the basket collateral, used for the
+    /// collateralization of the vault
+    pub fn get_effective_collateral(vault_id: &DefaultVaultId<T>) -> Result<Amount<T>, DispatchError> {
+        Self::get_backing_collateral(vault_id)?.checked_add(&Self::get_basket_collateral_value(vault_id)?)
+    }
+
+    /// Moves the share of the basket collateral of a vault that backs its liquidated tokens to the
+    /// liquidation vault, in the same proportion as its backing collateral. The share that backs the
+    /// to-be-redeemed tokens is kept in `LiquidatedBasketCollateral`.
+    ///
+    /// # Arguments
+    /// * `vault_id` - the vault being liquidated
+    /// * `used_collateral` - the value of the collateral that is seized
+    /// * `effective_collateral` - the value of all collateral of the vault
+    /// * `liquidated_tokens` - the tokens moved to the liquidation vault
+    /// * `backed_tokens` - all tokens backed by the vault
+    pub(crate) fn slash_basket_to_liquidation_vault(
+        vault_id: &DefaultVaultId<T>,
+        used_collateral: &Amount<T>,
+        effective_collateral: &Amount<T>,
+        liquidated_tokens: &Amount<T>,
+        backed_tokens: &Amount<T>,
+    ) -> DispatchResult {
+        let basket: Vec<_> = BasketCollateral::<T>::iter_prefix(vault_id).collect();
+        for (currency_id, amount) in basket {
+            let seized =
+                Self::calculate_collateral(&Amount::new(amount, currency_id), used_collateral, effective_collateral)?;
+            let to_slash = Self::calculate_collateral(&seized, liquidated_tokens, backed_tokens)?;
+
+            // the share for the to-be-redeemed tokens stays locked on the vault
+            Self::decrease_basket_collateral(vault_id, &seized)?;
+            let for_to_be_redeemed = seized.checked_sub(&to_slash)?;
+            if !for_to_be_redeemed.is_zero() {
+                LiquidatedBasketCollateral::<T>::try_mutate(vault_id, currency_id, |locked| -> DispatchResult {
+                    *locked = locked
+                        .checked_add(&for_to_be_redeemed.amount())
+                        .ok_or(ArithmeticError::Overflow)?;
+                    Ok(())
+                })?;
+            }
+
+            if to_slash.is_zero() {
+                continue;
+            }
+            to_slash.unlock_on(&vault_id.account_id)?;
+            Self::deposit_basket_to_liquidation_vault(vault_id, &to_slash)?;
+
+            Self::deposit_event(Event::<T>::LiquidateBasketCollateral {
+                vault_id: vault_id.clone(),
+                currency_id,
+                amount: to_slash.amount(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Transfers unlocked basket collateral of a liquidated vault to the liquidation vault
+    fn deposit_basket_to_liquidation_vault(vault_id: &DefaultVaultId<T>, amount: &Amount<T>) -> DispatchResult {
+        let liquidation_account_id = Self::liquidation_vault_account_id();
+        amount.transfer(&vault_id.account_id, &liquidation_account_id)?;
+        amount.lock_on(&liquidation_account_id)?;
+        LiquidationBasketCollateral::<T>::try_mutate(
+            &vault_id.currencies,
+            amount.currency(),
+            |locked| -> DispatchResult {
+                *locked = locked.checked_add(&amount.amount()).ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            },
+        )
+    }
+
+    /// Takes the share of the basket collateral backing the to-be-redeemed tokens of a liquidated
+    /// vault that corresponds to `tokens`
+    fn take_liquidated_basket_collateral(
+        vault_id: &DefaultVaultId<T>,
+        tokens: &Amount<T>,
+        to_be_redeemed_tokens: &Amount<T>,
+    ) -> Result<Vec<Amount<T>>, DispatchError> {
+        let basket: Vec<_> = LiquidatedBasketCollateral::<T>::iter_prefix(vault_id).collect();
+        let mut taken = Vec::with_capacity(basket.len());
+        for (currency_id, amount) in basket {
+            let locked = Amount::new(amount, currency_id);
+            let to_take = Self::calculate_collateral(&locked, tokens, to_be_redeemed_tokens)?;
+            let remaining = locked.checked_sub(&to_take)?;
+            if remaining.is_zero() {
+                LiquidatedBasketCollateral::<T>::remove(vault_id, currency_id);
+            } else {
+                LiquidatedBasketCollateral::<T>::insert(vault_id, currency_id, remaining.amount());
+            }
+            if !to_take.is_zero() {
+                taken.push(to_take);
+            }
+        }
+        Ok(taken)
+    }
+
+    /// Returns the basket collateral backing the redeemed tokens of a liquidated vault to its
+    /// basket, from which the vault can withdraw it
+    fn restore_liquidated_basket_collateral(
+        vault_id: &DefaultVaultId<T>,
+        tokens: &Amount<T>,
+        to_be_redeemed_tokens: &Amount<T>,
+    ) -> DispatchResult {
+        for amount in Self::take_liquidated_basket_collateral(vault_id, tokens, to_be_redeemed_tokens)? {
+            BasketCollateral::<T>::try_mutate(vault_id, amount.currency(), |locked| -> DispatchResult {
+                *locked = locked.checked_add(&amount.amount()).ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Pays the basket collateral backing the cancelled to-be-redeemed tokens of a liquidated vault
+    /// to the redeemer if it is reimbursed, or otherwise to the liquidation vault
+    ///
+    /// # Arguments
+    /// * `vault_id` - the liquidated vault
+    /// * `tokens` - the tokens of the cancelled request
+    /// * `to_be_redeemed_tokens` - the to-be-redeemed tokens of the vault before the cancellation
+    /// * `reimbursed_redeemer` - the redeemer, if it is reimbursed in collateral
+    pub fn slash_liquidated_basket_collateral(
+        vault_id: &DefaultVaultId<T>,
+        tokens: &Amount<T>,
+        to_be_redeemed_tokens: &Amount<T>,
+        reimbursed_redeemer: Option<&T::AccountId>,
+    ) -> DispatchResult {
+        for amount in Self::take_liquidated_basket_collateral(vault_id, tokens, to_be_redeemed_tokens)? {
+            amount.unlock_on(&vault_id.account_id)?;
+            match reimbursed_redeemer {
+                Some(redeemer_id) => amount.transfer(&vault_id.account_id, redeemer_id)?,
+                None => Self::deposit_basket_to_liquidation_vault(vault_id, &amount)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Pays the share of the basket collateral in the liquidation vault for the redeemed tokens
+    fn redeem_basket_collateral_liquidation(
+        currency_pair: &DefaultVaultCurrencyPair<T>,
+        redeemer_id: &T::AccountId,
+        amount_wrapped: &Amount<T>,
+        to_be_backed_tokens: &Amount<T>,
+    ) -> DispatchResult {
+        let liquidation_account_id = Self::liquidation_vault_account_id();
+        let basket: Vec<_> = LiquidationBasketCollateral::<T>::iter_prefix(currency_pair).collect();
+        for (currency_id, amount) in basket {
+            let locked = Amount::new(amount, currency_id);
+            let to_transfer = Self::calculate_collateral(&locked, amount_wrapped, to_be_backed_tokens)?;
+            if to_transfer.is_zero() {
+                continue;
+            }
+
+            to_transfer.unlock_on(&liquidation_account_id)?;
+            to_transfer.transfer(&liquidation_account_id, redeemer_id)?;
+            LiquidationBasketCollateral::<T>::insert(
+                currency_pair,
+                currency_id,
+                locked.checked_sub(&to_transfer)?.amount(),
+            );
+        }
+        Ok(())
+    }
+
     /// Liquidates a vault, transferring all of its token balances to the
     /// `LiquidationVault`, as well as the collateral.
     ///
@@ -1482,6 +2628,8 @@ impl<T: Config> Pallet<T> {
         let vault_orig = vault.data.clone();
 
         let to_slash = vault.liquidate()?;
+        // a liquidated vault can no longer exit, it has to be recovered first
+        VaultExits::<T>::remove(vault_id);
 
         Self::deposit_event(Event::<T>::LiquidateVault {
             vault_id: vault_id.clone(),
@@ -1568,7 +2716,7 @@ impl<T: Config> Pallet<T> {
     pub fn will_be_below_premium_threshold(vault_id: &DefaultVaultId<T>) -> Result<bool, DispatchError> {
         let vault = Self::get_rich_vault_from_id(&vault_id)?;
         let threshold = Self::premium_redeem_threshold(&vault_id.currencies).ok_or(Error::<T>::ThresholdNotSet)?;
-        let collateral = Self::get_backing_collateral(vault_id)?;
+        let collateral = Self::get_effective_collateral(vault_id)?;
 
         Self::is_collateral_below_threshold(&collateral, &vault.to_be_backed_tokens()?, threshold)
     }
@@ -1579,7 +2727,7 @@ impl<T: Config> Pallet<T> {
         liquidation_threshold: UnsignedFixedPoint<T>,
     ) -> Result<bool, DispatchError> {
         Self::is_collateral_below_threshold(
-            &Self::get_backing_collateral(&vault.id)?,
+            &Self::get_effective_collateral(&vault.id)?,
             &Amount::new(vault.issued_tokens, vault.id.wrapped_currency()),
             liquidation_threshold,
         )
@@ -1805,7 +2953,7 @@ impl<T: Config> Pallet<T> {
         only_issued: bool,
     ) -> Result<UnsignedFixedPoint<T>, DispatchError> {
         let vault = Self::get_active_rich_vault_from_id(&vault_id)?;
-        let collateral = vault.get_total_collateral()?;
+        let collateral = vault.get_effective_collateral()?;
         Self::get_collateralization_from_vault_and_collateral(vault_id, &collateral, only_issued)
     }
 
@@ -1955,8 +3103,8 @@ impl<T: Config> Pallet<T> {
     ) -> Result<bool, DispatchError> {
         let vault = Self::get_rich_vault_from_id(&vault_id)?;
 
-        // the current locked backing collateral by the vault
-        let collateral = Self::get_backing_collateral(vault_id)?;
+        // the current locked backing and basket collateral by the vault
+        let collateral = Self::get_effective_collateral(vault_id)?;
 
         Self::is_collateral_below_threshold(&collateral, &vault.issued_tokens(), threshold)
     }
@@ -1,5 +1,5 @@
 use crate as vault_registry;
-use crate::{Config, Error};
+use crate::{types::BtcAddress, Config, Error};
 use currency::CurrencyConversion;
 use frame_support::{
     parameter_types,
@@ -37,6 +37,7 @@ frame_support::construct_runtime!(
         VaultStaking: staking::{Pallet, Storage, Event<T>},
 
         // Operational
+        BTCRelay: btc_relay::{Pallet, Call, Config<T>, Storage, Event<T>},
         Security: security::{Pallet, Call, Storage, Event<T>},
         VaultRegistry: vault_registry::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
         Oracle: oracle::{Pallet, Call, Config<T>, Storage, Event<T>},
@@ -267,15 +268,29 @@ impl traits::NominationApi<VaultId<AccountId, CurrencyId>, currency::Amount<Test
     fn ensure_opted_in_to_nomination(_vault_id: &VaultId<AccountId, CurrencyId>) -> Result<(), DispatchError> {
         Ok(())
     }
+    fn clear_nomination_opt_in(_vault_id: &VaultId<AccountId, CurrencyId>) {}
     #[cfg(any(feature = "runtime-benchmarks", test))]
     fn opt_in_to_nomination(_vault_id: &VaultId<AccountId, CurrencyId>) {}
 }
 
+pub struct MockRequestPayments;
+
+#[cfg_attr(test, mockable)]
+impl traits::RequestPaymentApi<VaultId<AccountId, CurrencyId>, H256, BtcAddress, Balance> for MockRequestPayments {
+    fn get_payable_request(
+        _request_id: &H256,
+        _vault_id: &VaultId<AccountId, CurrencyId>,
+    ) -> Option<(BtcAddress, Balance)> {
+        None
+    }
+}
+
 impl Config for Test {
     type PalletId = VaultPalletId;
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
+    type RequestPayments = MockRequestPayments;
 }
 
 impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
@@ -286,6 +301,19 @@ where
     type Extrinsic = Extrinsic;
 }
 
+parameter_types! {
+    pub const ParachainBlocksPerBitcoinBlock: BlockNumber = 100;
+    pub const RelayerRewardPot: AccountId = 1000;
+}
+
+impl btc_relay::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type ParachainBlocksPerBitcoinBlock = ParachainBlocksPerBitcoinBlock;
+    type WeightInfo = ();
+    type Currency = orml_tokens::CurrencyAdapter<Test, GetNativeCurrencyId>;
+    type RelayerRewardPot = RelayerRewardPot;
+}
+
 impl security::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
@@ -333,6 +361,7 @@ impl ExtBuilder {
         vault_registry::GenesisConfig::<Test> {
             minimum_collateral_vault: vec![(DEFAULT_COLLATERAL_CURRENCY, 0), (WORST_CASE_COLLATERAL_CURRENCY, 0)],
             punishment_delay: 0,
+            exit_period: 10,
             system_collateral_ceiling: vec![
                 (DEFAULT_CURRENCY_PAIR, 1_000_000_000_000),
                 (WORST_CASE_CURRENCY_PAIR, 1_000_000_000_000),
@@ -59,8 +59,11 @@ impl<T: Config> PoolManager<T> {
 
     // NOTE: temporarily public for reward migration
     pub(crate) fn update_reward_stake(vault_id: &DefaultVaultId<T>) -> Result<(), DispatchError> {
-        let vault = Pallet::<T>::get_vault_from_id(vault_id)?;
-        let new_reward_stake = if !vault.accepts_new_issues() {
+        // nominators can still withdraw from the stale pools of a vault that exited
+        let accepts_new_issues = Vaults::<T>::get(vault_id)
+            .map(|vault| vault.accepts_new_issues())
+            .unwrap_or_default();
+        let new_reward_stake = if !accepts_new_issues {
             // if the vault is not accepting new issues it's not getting rewards
             Amount::zero(vault_id.collateral_currency())
         } else {
@@ -1,8 +1,15 @@
 use crate::{
+    ext,
     mock::*,
-    types::{BalanceOf, UpdatableVault},
+    types::{BalanceOf, BtcAddress, UpdatableVault},
     BtcPublicKey, CurrencySource, DefaultVaultId, DispatchError, Vault,
 };
+use bitcoin::{
+    merkle::PartialTransactionProof,
+    types::{
+        FullTransactionProof, H256Le, Transaction, TransactionBuilder, TransactionInputBuilder, TransactionOutput,
+    },
+};
 use codec::Decode;
 use currency::Amount;
 use frame_support::{assert_err, assert_noop, assert_ok};
@@ -10,12 +17,13 @@ use mocktopus::mocking::*;
 use pretty_assertions::assert_eq;
 use security::Pallet as Security;
 use sp_arithmetic::{traits::One, FixedPointNumber, FixedU128};
-use sp_core::U256;
+use sp_core::{H160, H256, U256};
 use sp_runtime::{
     offchain::{testing::TestTransactionPoolExt, TransactionPoolExt},
     ArithmeticError,
 };
 use sp_std::convert::TryInto;
+use traits::RequestPaymentApi;
 
 type Event = crate::Event<Test>;
 
@@ -1443,3 +1451,918 @@ fn test_offchain_worker_unsigned_transaction_submission() {
         );
     })
 }
+
+fn unchecked_transaction() -> FullTransactionProof {
+    let proof = PartialTransactionProof {
+        transaction: Default::default(),
+        tx_encoded_len: u32::MAX,
+        merkle_proof: Default::default(),
+    };
+    FullTransactionProof {
+        user_tx_proof: proof.clone(),
+        coinbase_proof: proof,
+    }
+}
+
+fn spend_from_deposit_address(issue_id: H256, outputs: Vec<TransactionOutput>) -> Transaction {
+    let deposit_public_key = BtcPublicKey::dummy().new_deposit_public_key(issue_id).unwrap();
+    let mut builder = TransactionBuilder::new();
+    builder.add_input(
+        TransactionInputBuilder::new()
+            .with_p2wpkh(&deposit_public_key, vec![1; 72])
+            .build(),
+    );
+    for output in outputs {
+        builder.add_output(output);
+    }
+    builder.build()
+}
+
+fn report_vault_theft(vault_id: &DefaultVaultId<Test>, transaction: Transaction) -> Result<(), DispatchError> {
+    ext::btc_relay::verify_transaction_inclusion::<Test>
+        .mock_safe(move |_| MockResult::Return(Ok(transaction.clone())));
+    VaultRegistry::report_vault_theft(RuntimeOrigin::signed(5), vault_id.clone(), unchecked_transaction())
+        .map(|_| ())
+        .map_err(|err| err.error)
+}
+
+#[test]
+fn report_vault_theft_liquidates_vault() {
+    run_test(|| {
+        let id = create_sample_vault_and_issue_tokens(50);
+        VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+
+        let transaction = spend_from_deposit_address(
+            H256::zero(),
+            vec![TransactionOutput::payment(
+                100,
+                &BtcAddress::P2PKH(H160::repeat_byte(9)),
+            )],
+        );
+        let tx_id = transaction.tx_id();
+        assert_ok!(report_vault_theft(&id, transaction.clone()));
+
+        assert_emitted!(Event::VaultTheft {
+            vault_id: id.clone(),
+            tx_id
+        });
+        assert_eq!(VaultRegistry::is_vault_liquidated(&id), Ok(true));
+
+        // the same transaction can not be reported again, e.g. after the vault is recovered
+        assert_noop!(report_vault_theft(&id, transaction), TestError::TheftAlreadyReported);
+    });
+}
+
+#[test]
+fn report_vault_theft_fails_for_valid_vault_transactions() {
+    run_test(|| {
+        let id = create_sample_vault_and_issue_tokens(50);
+        let deposit_address = VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+        let other_address = BtcAddress::P2PKH(H160::repeat_byte(9));
+
+        // the vault never received funds on this address
+        let transaction = spend_from_deposit_address(
+            H256::repeat_byte(1),
+            vec![TransactionOutput::payment(100, &other_address)],
+        );
+        assert_noop!(report_vault_theft(&id, transaction), TestError::TransactionNotFromVault);
+
+        // moving funds between the vault's own addresses
+        let transaction =
+            spend_from_deposit_address(H256::zero(), vec![TransactionOutput::payment(100, &deposit_address)]);
+        assert_noop!(report_vault_theft(&id, transaction), TestError::ValidVaultTransaction);
+
+        // paying a request of the vault
+        let request_id = H256::repeat_byte(2);
+        let transaction = spend_from_deposit_address(
+            H256::zero(),
+            vec![
+                TransactionOutput::payment(100, &other_address),
+                TransactionOutput::op_return(0, request_id.as_bytes()),
+            ],
+        );
+        MockRequestPayments::get_payable_request.mock_safe(move |request, _| {
+            MockResult::Return(Some((other_address, 100)).filter(|_| request == &request_id))
+        });
+        assert_noop!(report_vault_theft(&id, transaction), TestError::ValidVaultTransaction);
+        assert_eq!(VaultRegistry::is_vault_liquidated(&id), Ok(false));
+    });
+}
+
+#[test]
+fn report_vault_theft_succeeds_for_invalid_request_payments() {
+    let recipient = BtcAddress::P2PKH(H160::repeat_byte(9));
+    let request_id = H256::repeat_byte(2);
+    let report_request_payment = |amount, address: BtcAddress, paid_request_id: H256| {
+        run_test(|| {
+            let id = create_sample_vault_and_issue_tokens(50);
+            VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+            MockRequestPayments::get_payable_request.mock_safe(move |request, _| {
+                MockResult::Return(Some((recipient, 100)).filter(|_| request == &request_id))
+            });
+            let transaction = spend_from_deposit_address(
+                H256::zero(),
+                vec![
+                    TransactionOutput::payment(amount, &address),
+                    TransactionOutput::op_return(0, paid_request_id.as_bytes()),
+                ],
+            );
+            assert_ok!(report_vault_theft(&id, transaction));
+            assert_eq!(VaultRegistry::is_vault_liquidated(&id), Ok(true));
+        })
+    };
+
+    // paying less than the requested amount
+    report_request_payment(99, recipient, request_id);
+    // paying the requested amount to another address
+    report_request_payment(100, BtcAddress::P2PKH(H160::repeat_byte(8)), request_id);
+    // reusing the id of a request that is no longer payable, e.g. a completed redeem
+    report_request_payment(100, recipient, H256::repeat_byte(3));
+}
+
+fn request_public_key_rotation(account_id: u64, public_key: BtcPublicKey) -> Result<(), DispatchError> {
+    VaultRegistry::request_public_key_rotation(RuntimeOrigin::signed(account_id), public_key)
+        .map(|_| ())
+        .map_err(|err| err.error)
+}
+
+fn rotate_public_key(account_id: u64, migration_tx: Option<Transaction>) -> Result<(), DispatchError> {
+    let migration_proof = migration_tx.map(|transaction| {
+        ext::btc_relay::verify_transaction_inclusion::<Test>
+            .mock_safe(move |_| MockResult::Return(Ok(transaction.clone())));
+        unchecked_transaction()
+    });
+    VaultRegistry::rotate_public_key(RuntimeOrigin::signed(account_id), migration_proof)
+        .map(|_| ())
+        .map_err(|err| err.error)
+}
+
+#[test]
+fn rotate_public_key_only_applies_to_new_deposit_addresses() {
+    run_test(|| {
+        let new_public_key = BtcPublicKey([2; 33]);
+        assert_noop!(
+            request_public_key_rotation(DEFAULT_ID.account_id, new_public_key.clone()),
+            TestError::NoBitcoinPublicKey
+        );
+
+        let id = create_sample_vault();
+        let old_address = VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+        assert_noop!(
+            request_public_key_rotation(id.account_id, BtcPublicKey::dummy()),
+            TestError::PublicKeyUnchanged
+        );
+        assert_noop!(rotate_public_key(id.account_id, None), TestError::NoPendingPublicKey);
+
+        assert_ok!(request_public_key_rotation(id.account_id, new_public_key.clone()));
+        assert_emitted!(Event::RequestPublicKeyRotation {
+            account_id: id.account_id,
+            public_key: new_public_key.clone(),
+        });
+        // the registered key is only replaced once the rotation is finalized
+        assert_eq!(
+            VaultRegistry::get_bitcoin_public_key(&id.account_id),
+            Ok(BtcPublicKey::dummy())
+        );
+
+        assert_ok!(rotate_public_key(id.account_id, None));
+        assert_emitted!(Event::RotatePublicKey {
+            account_id: id.account_id,
+            public_key: new_public_key.clone(),
+            migration_tx: None
+        });
+        assert_eq!(
+            VaultRegistry::get_bitcoin_public_key(&id.account_id),
+            Ok(new_public_key)
+        );
+        assert_noop!(rotate_public_key(id.account_id, None), TestError::NoPendingPublicKey);
+
+        // the address of the open request stays reserved for the vault
+        assert_eq!(crate::ReservedAddresses::<Test>::get(old_address), Some(id.clone()));
+        let new_address = VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+        assert_ne!(new_address, old_address);
+    });
+}
+
+#[test]
+fn rotate_public_key_with_migration_proof() {
+    run_test(|| {
+        let id = create_sample_vault();
+        VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+        let new_public_key = BtcPublicKey([2; 33]);
+        let new_address = BtcAddress::P2WPKHv0(new_public_key.to_hash());
+
+        assert_ok!(VaultRegistry::set_require_key_migration_proof(
+            RuntimeOrigin::root(),
+            true
+        ));
+        assert_ok!(request_public_key_rotation(id.account_id, new_public_key.clone()));
+        assert_noop!(
+            rotate_public_key(id.account_id, None),
+            TestError::MissingKeyMigrationProof
+        );
+
+        // the BTC must come from the vault
+        let transaction = spend_from_deposit_address(
+            H256::repeat_byte(1),
+            vec![TransactionOutput::payment(100, &new_address)],
+        );
+        assert_noop!(
+            rotate_public_key(id.account_id, Some(transaction)),
+            TestError::InvalidKeyMigrationProof
+        );
+
+        // and must not be sent to other addresses
+        let transaction = spend_from_deposit_address(
+            H256::zero(),
+            vec![
+                TransactionOutput::payment(100, &new_address),
+                TransactionOutput::payment(100, &BtcAddress::P2PKH(H160::repeat_byte(9))),
+            ],
+        );
+        assert_noop!(
+            rotate_public_key(id.account_id, Some(transaction)),
+            TestError::InvalidKeyMigrationProof
+        );
+
+        let transaction = spend_from_deposit_address(
+            H256::zero(),
+            vec![
+                TransactionOutput::payment(100, &new_address),
+                TransactionOutput::op_return(0, &[1; 32]),
+            ],
+        );
+        let tx_id = transaction.tx_id();
+        assert_ok!(rotate_public_key(id.account_id, Some(transaction)));
+        assert_emitted!(Event::RotatePublicKey {
+            account_id: id.account_id,
+            public_key: new_public_key,
+            migration_tx: Some(tx_id)
+        });
+    });
+}
+
+#[test]
+fn report_vault_theft_fails_for_key_migration() {
+    run_test(|| {
+        let id = create_sample_vault_and_issue_tokens(50);
+        VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+        let new_public_key = BtcPublicKey([2; 33]);
+        let transaction = spend_from_deposit_address(
+            H256::zero(),
+            vec![TransactionOutput::payment(
+                100,
+                &BtcAddress::P2WPKHv0(new_public_key.to_hash()),
+            )],
+        );
+
+        assert_ok!(request_public_key_rotation(id.account_id, new_public_key));
+        assert_ok!(rotate_public_key(id.account_id, Some(transaction.clone())));
+        // the migration stays valid once the key is rotated again
+        assert_ok!(request_public_key_rotation(id.account_id, BtcPublicKey([3; 33])));
+        assert_ok!(rotate_public_key(id.account_id, None));
+        assert_noop!(report_vault_theft(&id, transaction), TestError::ValidVaultTransaction);
+        assert_eq!(VaultRegistry::is_vault_liquidated(&id), Ok(false));
+    });
+}
+
+#[test]
+fn report_vault_theft_fails_for_key_migration_before_rotation() {
+    run_test(|| {
+        let id = create_sample_vault_and_issue_tokens(50);
+        VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+        let new_public_key = BtcPublicKey([2; 33]);
+        let transaction = spend_from_deposit_address(
+            H256::zero(),
+            vec![TransactionOutput::payment(
+                100,
+                &BtcAddress::P2PKH(new_public_key.to_hash()),
+            )],
+        );
+
+        // once the key is announced, moving the BTC to it is valid before the rotation is finalized
+        assert_ok!(request_public_key_rotation(id.account_id, new_public_key));
+        assert_noop!(
+            report_vault_theft(&id, transaction.clone()),
+            TestError::ValidVaultTransaction
+        );
+        assert_eq!(VaultRegistry::is_vault_liquidated(&id), Ok(false));
+
+        assert_ok!(rotate_public_key(id.account_id, Some(transaction)));
+    });
+}
+
+mod vault_exit_tests {
+    use super::*;
+
+    fn request_vault_exit(id: &DefaultVaultId<Test>) -> Result<(), DispatchError> {
+        VaultRegistry::request_vault_exit(RuntimeOrigin::signed(id.account_id.clone()), id.currencies.clone())
+            .map(|_| ())
+            .map_err(|err| err.error)
+    }
+
+    fn execute_vault_exit(id: &DefaultVaultId<Test>) -> Result<(), DispatchError> {
+        VaultRegistry::execute_vault_exit(RuntimeOrigin::signed(id.account_id.clone()), id.currencies.clone())
+            .map(|_| ())
+            .map_err(|err| err.error)
+    }
+
+    fn liquidate_expired_vault_exit(id: &DefaultVaultId<Test>) -> Result<(), DispatchError> {
+        VaultRegistry::liquidate_expired_vault_exit(RuntimeOrigin::signed(5), id.clone())
+            .map(|_| ())
+            .map_err(|err| err.error)
+    }
+
+    #[test]
+    fn request_vault_exit_stops_new_issues() {
+        run_test(|| {
+            let id = create_sample_vault();
+            assert_ok!(request_vault_exit(&id));
+
+            assert_emitted!(Event::RequestVaultExit {
+                vault_id: id.clone(),
+                deadline: 11
+            });
+            assert!(!VaultRegistry::get_vault_from_id(&id).unwrap().accepts_new_issues());
+            assert_noop!(request_vault_exit(&id), TestError::VaultExitAlreadyRequested);
+            assert_noop!(
+                VaultRegistry::accept_new_issues(
+                    RuntimeOrigin::signed(id.account_id.clone()),
+                    id.currencies.clone(),
+                    true
+                ),
+                TestError::VaultExiting
+            );
+            assert_noop!(VaultRegistry::ensure_not_exiting(&id), TestError::VaultExiting);
+        });
+    }
+
+    #[test]
+    fn execute_vault_exit_releases_collateral_and_removes_vault() {
+        run_test(|| {
+            let id = create_sample_vault_and_issue_tokens(50);
+            let deposit_address = VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+            assert_noop!(execute_vault_exit(&id), TestError::VaultExitNotRequested);
+            assert_ok!(request_vault_exit(&id));
+
+            // tokens must be redeemed or replaced first
+            assert_noop!(execute_vault_exit(&id), TestError::VaultExitPending);
+            assert_ok!(VaultRegistry::try_increase_to_be_redeemed_tokens(&id, &wrapped(50)));
+            assert_noop!(execute_vault_exit(&id), TestError::VaultExitPending);
+            assert_ok!(VaultRegistry::redeem_tokens(&id, &wrapped(50), &amount(0), &0));
+
+            assert_ok!(execute_vault_exit(&id));
+            assert_emitted!(Event::ExecuteVaultExit {
+                vault_id: id.clone(),
+                collateral: DEFAULT_COLLATERAL
+            });
+            assert_err!(VaultRegistry::get_vault_from_id(&id), TestError::VaultNotFound);
+            assert!(!VaultRegistry::is_vault_exiting(&id));
+            assert_eq!(
+                ext::currency::get_reserved_balance::<Test>(id.collateral_currency(), &id.account_id),
+                amount(0)
+            );
+            assert_eq!(
+                VaultRegistry::get_total_user_vault_collateral(&id.currencies).unwrap(),
+                amount(0)
+            );
+            // the deposit addresses are released
+            assert_eq!(crate::ReservedAddresses::<Test>::get(deposit_address), None);
+            assert_eq!(crate::VaultDepositAddresses::<Test>::iter_prefix(&id).count(), 0);
+        });
+    }
+
+    #[test]
+    fn liquidate_expired_vault_exit_succeeds_after_deadline() {
+        run_test(|| {
+            let id = create_sample_vault_and_issue_tokens(50);
+            assert_noop!(liquidate_expired_vault_exit(&id), TestError::VaultExitNotRequested);
+            assert_ok!(request_vault_exit(&id));
+
+            Security::<Test>::set_active_block_number(11);
+            assert_noop!(liquidate_expired_vault_exit(&id), TestError::VaultExitNotExpired);
+
+            Security::<Test>::set_active_block_number(12);
+            assert_ok!(liquidate_expired_vault_exit(&id));
+            assert_emitted!(Event::ExpireVaultExit { vault_id: id.clone() });
+            assert_eq!(VaultRegistry::is_vault_liquidated(&id), Ok(true));
+            assert!(!VaultRegistry::is_vault_exiting(&id));
+        });
+    }
+
+    #[test]
+    fn liquidate_expired_vault_exit_fails_without_outstanding_tokens() {
+        run_test(|| {
+            let id = create_sample_vault();
+            assert_ok!(request_vault_exit(&id));
+
+            Security::<Test>::set_active_block_number(12);
+            assert_noop!(liquidate_expired_vault_exit(&id), TestError::VaultExitExecutable);
+            assert_ok!(execute_vault_exit(&id));
+        });
+    }
+}
+
+mod basket_collateral_tests {
+    use super::*;
+
+    const BASKET_CURRENCY: CurrencyId = Token(KSM);
+
+    fn basket(amount: u128) -> Amount<Test> {
+        Amount::new(amount, BASKET_CURRENCY)
+    }
+
+    fn set_haircut(percent: u128) {
+        assert_ok!(VaultRegistry::set_basket_collateral_haircut(
+            RuntimeOrigin::root(),
+            BASKET_CURRENCY,
+            Some(UnsignedFixedPoint::from_rational(percent, 100))
+        ));
+    }
+
+    fn create_basket_vault(collateral: u128, basket_collateral: u128) -> DefaultVaultId<Test> {
+        create_vault_with_collateral(&DEFAULT_ID, collateral);
+        <oracle::Pallet<Test>>::_set_exchange_rate(BASKET_CURRENCY, UnsignedFixedPoint::one()).unwrap();
+        set_haircut(0);
+        basket(basket_collateral).mint_to(&DEFAULT_ID.account_id).unwrap();
+        assert_ok!(VaultRegistry::_deposit_basket_collateral(
+            &DEFAULT_ID,
+            &basket(basket_collateral)
+        ));
+        DEFAULT_ID
+    }
+
+    #[test]
+    fn deposit_basket_collateral_counts_at_haircut_value() {
+        run_test(|| {
+            let id = create_sample_vault();
+            <oracle::Pallet<Test>>::_set_exchange_rate(BASKET_CURRENCY, UnsignedFixedPoint::one()).unwrap();
+            basket(1000).mint_to(&id.account_id).unwrap();
+
+            assert_noop!(
+                VaultRegistry::_deposit_basket_collateral(&id, &basket(1000)),
+                TestError::NotBasketCollateral
+            );
+            set_haircut(50);
+            assert_noop!(
+                VaultRegistry::_deposit_basket_collateral(&id, &amount(10)),
+                TestError::NotBasketCollateral
+            );
+            assert_noop!(
+                VaultRegistry::set_basket_collateral_haircut(
+                    RuntimeOrigin::root(),
+                    BASKET_CURRENCY,
+                    Some(UnsignedFixedPoint::one())
+                ),
+                TestError::InvalidHaircut
+            );
+
+            assert_ok!(VaultRegistry::deposit_basket_collateral(
+                RuntimeOrigin::signed(id.account_id),
+                id.currencies.clone(),
+                BASKET_CURRENCY,
+                1000
+            ));
+            assert_emitted!(Event::DepositBasketCollateral {
+                vault_id: id.clone(),
+                currency_id: BASKET_CURRENCY,
+                amount: 1000
+            });
+            assert_eq!(
+                ext::currency::get_reserved_balance::<Test>(BASKET_CURRENCY, &id.account_id),
+                basket(1000)
+            );
+            assert_eq!(
+                VaultRegistry::get_effective_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL + 500))
+            );
+
+            // collateral in currencies that are no longer accepted does not count
+            assert_ok!(VaultRegistry::set_basket_collateral_haircut(
+                RuntimeOrigin::root(),
+                BASKET_CURRENCY,
+                None
+            ));
+            assert_eq!(
+                VaultRegistry::get_effective_collateral(&id),
+                Ok(amount(DEFAULT_COLLATERAL))
+            );
+        });
+    }
+
+    #[test]
+    fn basket_collateral_backs_issued_tokens() {
+        run_test(|| {
+            let id = create_basket_vault(100, 100);
+
+            // 200 collateral at a secure threshold of 200%
+            assert_eq!(VaultRegistry::get_issuable_tokens_from_vault(&id), Ok(wrapped(100)));
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(100)));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(100)));
+            assert_eq!(VaultRegistry::is_vault_below_secure_threshold(&id), Ok(false));
+
+            assert_noop!(
+                VaultRegistry::_withdraw_basket_collateral(&id, &basket(101)),
+                TestError::InsufficientBasketCollateral
+            );
+            assert_noop!(
+                VaultRegistry::_withdraw_basket_collateral(&id, &basket(1)),
+                TestError::InsufficientCollateral
+            );
+            assert_eq!(
+                VaultRegistry::is_allowed_to_withdraw_collateral(&id, Some(amount(100))),
+                Ok(false)
+            );
+
+            // the basket collateral loses value
+            set_haircut(10);
+            assert_eq!(VaultRegistry::is_vault_below_secure_threshold(&id), Ok(true));
+        });
+    }
+
+    #[test]
+    fn withdraw_basket_collateral_succeeds() {
+        run_test(|| {
+            let id = create_basket_vault(100, 100);
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(50)));
+
+            assert_ok!(VaultRegistry::withdraw_basket_collateral(
+                RuntimeOrigin::signed(id.account_id),
+                id.currencies.clone(),
+                BASKET_CURRENCY,
+                100
+            ));
+            assert_emitted!(Event::WithdrawBasketCollateral {
+                vault_id: id.clone(),
+                currency_id: BASKET_CURRENCY,
+                amount: 100
+            });
+            assert_eq!(VaultRegistry::get_basket_collateral(&id, BASKET_CURRENCY), basket(0));
+            assert_eq!(
+                ext::currency::get_free_balance::<Test>(BASKET_CURRENCY, &id.account_id),
+                basket(100)
+            );
+        });
+    }
+
+    #[test]
+    fn liquidation_seizes_basket_collateral_proportionally() {
+        run_test(|| {
+            let id = create_basket_vault(100, 100);
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(50)));
+
+            assert_ok!(VaultRegistry::liquidate_vault(&id));
+
+            // 50 tokens at a liquidation threshold of 110% use 55 of the 200 collateral
+            let liquidation_vault = VaultRegistry::get_liquidation_vault(&DEFAULT_CURRENCY_PAIR);
+            assert_eq!(liquidation_vault.collateral, 27);
+            assert_eq!(
+                crate::LiquidationBasketCollateral::<Test>::get(DEFAULT_CURRENCY_PAIR, BASKET_CURRENCY),
+                27
+            );
+            assert_eq!(VaultRegistry::get_basket_collateral(&id, BASKET_CURRENCY), basket(73));
+            assert_emitted!(Event::LiquidateBasketCollateral {
+                vault_id: id.clone(),
+                currency_id: BASKET_CURRENCY,
+                amount: 27
+            });
+
+            // redeemers of the liquidation vault receive their share of the basket collateral
+            let user_id = 5;
+            assert_ok!(VaultRegistry::redeem_tokens_liquidation(
+                DEFAULT_COLLATERAL_CURRENCY,
+                &user_id,
+                &wrapped(10)
+            ));
+            assert_eq!(
+                ext::currency::get_free_balance::<Test>(BASKET_CURRENCY, &user_id),
+                basket(5)
+            );
+            assert_eq!(
+                crate::LiquidationBasketCollateral::<Test>::get(DEFAULT_CURRENCY_PAIR, BASKET_CURRENCY),
+                22
+            );
+        });
+    }
+
+    #[test]
+    fn liquidated_basket_collateral_backs_to_be_redeemed_tokens() {
+        run_test(|| {
+            let id = create_basket_vault(100, 100);
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::try_increase_to_be_redeemed_tokens(&id, &wrapped(20)));
+
+            assert_ok!(VaultRegistry::liquidate_vault(&id));
+
+            // of the 27 seized basket collateral, 11 back the 20 to-be-redeemed tokens
+            assert_eq!(
+                crate::LiquidationBasketCollateral::<Test>::get(DEFAULT_CURRENCY_PAIR, BASKET_CURRENCY),
+                16
+            );
+            assert_eq!(crate::LiquidatedBasketCollateral::<Test>::get(&id, BASKET_CURRENCY), 11);
+            assert_eq!(VaultRegistry::get_basket_collateral(&id, BASKET_CURRENCY), basket(73));
+
+            // the rest is not needed anymore
+            assert_ok!(VaultRegistry::_withdraw_basket_collateral(&id, &basket(73)));
+
+            // the share of executed redeems is returned to the vault
+            assert_ok!(VaultRegistry::redeem_tokens(&id, &wrapped(5), &amount(0), &5));
+            assert_eq!(VaultRegistry::get_basket_collateral(&id, BASKET_CURRENCY), basket(2));
+
+            // a reimbursed redeemer receives the share of its request
+            let redeemer_id = 5;
+            assert_ok!(VaultRegistry::slash_liquidated_basket_collateral(
+                &id,
+                &wrapped(5),
+                &wrapped(15),
+                Some(&redeemer_id)
+            ));
+            assert_eq!(
+                ext::currency::get_free_balance::<Test>(BASKET_CURRENCY, &redeemer_id),
+                basket(3)
+            );
+
+            // otherwise the share goes to the liquidation vault
+            assert_ok!(VaultRegistry::slash_liquidated_basket_collateral(
+                &id,
+                &wrapped(10),
+                &wrapped(10),
+                None
+            ));
+            assert_eq!(
+                crate::LiquidationBasketCollateral::<Test>::get(DEFAULT_CURRENCY_PAIR, BASKET_CURRENCY),
+                22
+            );
+            assert!(!crate::LiquidatedBasketCollateral::<Test>::contains_key(
+                &id,
+                BASKET_CURRENCY
+            ));
+        });
+    }
+
+    #[test]
+    fn unpriced_basket_collateral_does_not_count() {
+        run_test(|| {
+            const UNPRICED_CURRENCY: CurrencyId = Token(KINT);
+            let id = create_basket_vault(100, 100);
+            assert_ok!(VaultRegistry::set_basket_collateral_haircut(
+                RuntimeOrigin::root(),
+                UNPRICED_CURRENCY,
+                Some(UnsignedFixedPoint::from_rational(0, 100))
+            ));
+            let unpriced = Amount::<Test>::new(1000, UNPRICED_CURRENCY);
+            unpriced.mint_to(&id.account_id).unwrap();
+            assert_ok!(VaultRegistry::_deposit_basket_collateral(&id, &unpriced));
+
+            assert_eq!(VaultRegistry::get_basket_collateral_value(&id), Ok(amount(100)));
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(50)));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(50)));
+            assert_eq!(
+                VaultRegistry::is_vault_below_liquidation_threshold(
+                    &VaultRegistry::get_vault_from_id(&id).unwrap(),
+                    UnsignedFixedPoint::checked_from_rational(110, 100).unwrap()
+                ),
+                Ok(false)
+            );
+            assert_ok!(VaultRegistry::liquidate_vault(&id));
+        });
+    }
+}
+
+mod swap_collateral_currency_tests {
+    use super::*;
+
+    const NEW_CURRENCY: CurrencyId = Token(KSM);
+
+    fn new_vault_id(id: &DefaultVaultId<Test>) -> DefaultVaultId<Test> {
+        DefaultVaultId::<Test>::new(id.account_id, NEW_CURRENCY, id.wrapped_currency())
+    }
+
+    fn configure_new_currency(id: &DefaultVaultId<Test>) {
+        let currency_pair = new_vault_id(id).currencies;
+        VaultRegistry::_set_secure_collateral_threshold(
+            currency_pair.clone(),
+            UnsignedFixedPoint::checked_from_rational(200, 100).unwrap(),
+        );
+        VaultRegistry::_set_premium_redeem_threshold(
+            currency_pair.clone(),
+            UnsignedFixedPoint::checked_from_rational(120, 100).unwrap(),
+        );
+        VaultRegistry::_set_liquidation_collateral_threshold(
+            currency_pair.clone(),
+            UnsignedFixedPoint::checked_from_rational(110, 100).unwrap(),
+        );
+        VaultRegistry::_set_minimum_collateral_vault(NEW_CURRENCY, 0);
+        VaultRegistry::_set_system_collateral_ceiling(currency_pair, 1_000_000_000_000);
+        <oracle::Pallet<Test>>::_set_exchange_rate(NEW_CURRENCY, UnsignedFixedPoint::one()).unwrap();
+    }
+
+    fn swap_collateral_currency(id: &DefaultVaultId<Test>, collateral: u128) -> Result<(), DispatchError> {
+        VaultRegistry::swap_collateral_currency(
+            RuntimeOrigin::signed(id.account_id),
+            id.currencies.clone(),
+            NEW_CURRENCY,
+            collateral,
+        )
+        .map(|_| ())
+        .map_err(|err| err.error)
+    }
+
+    #[test]
+    fn swap_collateral_currency_carries_over_issued_tokens() {
+        run_test(|| {
+            let id = create_sample_vault();
+            let new_id = new_vault_id(&id);
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(100)));
+            assert_ok!(VaultRegistry::issue_tokens(&id, &wrapped(100)));
+            configure_new_currency(&id);
+            Amount::<Test>::new(1000, NEW_CURRENCY).mint_to(&id.account_id).unwrap();
+
+            // 100 tokens require 200 collateral at the secure threshold
+            assert_noop!(swap_collateral_currency(&id, 199), TestError::InsufficientCollateral);
+            assert_ok!(swap_collateral_currency(&id, 200));
+
+            assert_emitted!(Event::SwapCollateralCurrency {
+                old_vault_id: id.clone(),
+                new_vault_id: new_id.clone(),
+                released_collateral: DEFAULT_COLLATERAL,
+                locked_collateral: 200
+            });
+            assert_err!(VaultRegistry::get_vault_from_id(&id), TestError::VaultNotFound);
+            let vault = VaultRegistry::get_vault_from_id(&new_id).unwrap();
+            assert_eq!(vault.issued_tokens, 100);
+            assert_eq!(
+                VaultRegistry::get_backing_collateral(&new_id),
+                Ok(Amount::new(200, NEW_CURRENCY))
+            );
+            assert_eq!(
+                ext::currency::get_reserved_balance::<Test>(DEFAULT_COLLATERAL_CURRENCY, &id.account_id),
+                amount(0)
+            );
+            assert_eq!(
+                ext::currency::get_reserved_balance::<Test>(NEW_CURRENCY, &id.account_id),
+                Amount::new(200, NEW_CURRENCY)
+            );
+            assert_eq!(VaultRegistry::is_vault_below_secure_threshold(&new_id), Ok(false));
+        });
+    }
+
+    #[test]
+    fn swap_collateral_currency_carries_over_deposit_addresses_and_theft_reports() {
+        run_test(|| {
+            let id = create_sample_vault_and_issue_tokens(50);
+            let new_id = new_vault_id(&id);
+            let deposit_address = VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+            let reported_tx_id = H256Le::from_bytes_le(&[1; 32]);
+            crate::TheftReports::<Test>::insert(&id, reported_tx_id, ());
+            configure_new_currency(&id);
+            Amount::<Test>::new(1000, NEW_CURRENCY).mint_to(&id.account_id).unwrap();
+            assert_ok!(swap_collateral_currency(&id, 1000));
+
+            assert!(!VaultRegistry::is_vault_address(&id, &deposit_address));
+            assert!(VaultRegistry::is_vault_address(&new_id, &deposit_address));
+            assert!(crate::TheftReports::<Test>::contains_key(&new_id, reported_tx_id));
+            assert!(!crate::TheftReports::<Test>::contains_key(&id, reported_tx_id));
+
+            // BTC stolen from the old deposit address is reported against the new vault id
+            let transaction = spend_from_deposit_address(
+                H256::zero(),
+                vec![TransactionOutput::payment(
+                    100,
+                    &BtcAddress::P2PKH(H160::repeat_byte(9)),
+                )],
+            );
+            assert_ok!(report_vault_theft(&new_id, transaction));
+            assert_eq!(VaultRegistry::is_vault_liquidated(&new_id), Ok(true));
+        });
+    }
+
+    #[test]
+    fn swap_collateral_currency_keeps_payments_of_cancelled_requests_valid() {
+        run_test(|| {
+            let id = create_sample_vault_and_issue_tokens(50);
+            let new_id = new_vault_id(&id);
+            VaultRegistry::register_deposit_address(&id, H256::zero()).unwrap();
+            let recipient = BtcAddress::P2PKH(H160::repeat_byte(9));
+            let request_id = H256::repeat_byte(2);
+            // the cancelled request still refers to the old vault id
+            let old_id = id.clone();
+            MockRequestPayments::get_payable_request.mock_safe(move |request, vault_id| {
+                MockResult::Return(Some((recipient, 100)).filter(|_| request == &request_id && vault_id == &old_id))
+            });
+            configure_new_currency(&id);
+            Amount::<Test>::new(1000, NEW_CURRENCY).mint_to(&id.account_id).unwrap();
+            assert_ok!(swap_collateral_currency(&id, 1000));
+            assert!(crate::PreviousVaultIds::<Test>::contains_key(&new_id, &id));
+
+            let transaction = spend_from_deposit_address(
+                H256::zero(),
+                vec![
+                    TransactionOutput::payment(100, &recipient),
+                    TransactionOutput::op_return(0, request_id.as_bytes()),
+                ],
+            );
+            assert_noop!(
+                report_vault_theft(&new_id, transaction),
+                TestError::ValidVaultTransaction
+            );
+            assert_eq!(VaultRegistry::is_vault_liquidated(&new_id), Ok(false));
+        });
+    }
+
+    #[test]
+    fn swap_collateral_currency_fails_with_open_requests() {
+        run_test(|| {
+            let id = create_sample_vault();
+            assert_noop!(swap_collateral_currency(&id, 1000), TestError::ThresholdNotSet);
+            configure_new_currency(&id);
+            Amount::<Test>::new(1000, NEW_CURRENCY).mint_to(&id.account_id).unwrap();
+
+            assert_noop!(
+                VaultRegistry::swap_collateral_currency(
+                    RuntimeOrigin::signed(id.account_id),
+                    id.currencies.clone(),
+                    DEFAULT_COLLATERAL_CURRENCY,
+                    1000
+                ),
+                TestError::InvalidCurrency
+            );
+
+            assert_ok!(VaultRegistry::try_increase_to_be_issued_tokens(&id, &wrapped(10)));
+            assert_noop!(swap_collateral_currency(&id, 1000), TestError::VaultHasOpenRequests);
+        });
+    }
+}
+
+mod migration_tests {
+    use super::*;
+    use crate::types::{v6, Version};
+    use frame_support::{traits::Hooks, weights::Weight};
+    use primitives::issue::IssueRequest;
+
+    fn insert_issue_request(issue_id: H256, vault: &DefaultVaultId<Test>) -> BtcAddress {
+        let btc_address = BtcAddress::random();
+        v6::IssueRequests::<Test>::insert(
+            issue_id,
+            IssueRequest {
+                vault: vault.clone(),
+                opentime: 1,
+                period: 10,
+                griefing_collateral: 5,
+                griefing_currency: DEFAULT_COLLATERAL_CURRENCY,
+                amount: 100,
+                fee: 1,
+                requester: 1,
+                btc_address,
+                btc_public_key: Default::default(),
+                btc_height: 1,
+                status: Default::default(),
+            },
+        );
+        btc_address
+    }
+
+    #[test]
+    fn migrate_v6_to_v7_reserves_deposit_addresses() {
+        run_test(|| {
+            crate::StorageVersion::<Test>::put(Version::V6);
+            let btc_address = insert_issue_request(H256::repeat_byte(1), &DEFAULT_ID);
+            let other_btc_address = insert_issue_request(H256::repeat_byte(2), &OTHER_ID);
+
+            VaultRegistry::on_idle(1, Weight::MAX);
+
+            assert_eq!(VaultRegistry::storage_version(), Version::V7);
+            assert!(VaultRegistry::is_vault_address(&DEFAULT_ID, &btc_address));
+            assert!(VaultRegistry::is_vault_address(&OTHER_ID, &other_btc_address));
+            assert_eq!(crate::DepositAddressMigrationCursor::<Test>::get(), None);
+        });
+    }
+
+    #[test]
+    fn migrate_v6_to_v7_resumes_after_cursor() {
+        run_test(|| {
+            crate::StorageVersion::<Test>::put(Version::V6);
+            let mut addresses = vec![
+                (
+                    H256::repeat_byte(1),
+                    insert_issue_request(H256::repeat_byte(1), &DEFAULT_ID),
+                ),
+                (
+                    H256::repeat_byte(2),
+                    insert_issue_request(H256::repeat_byte(2), &DEFAULT_ID),
+                ),
+            ];
+            // the requests are visited in the order of their storage keys
+            addresses.sort_by_key(|(issue_id, _)| v6::IssueRequests::<Test>::hashed_key_for(issue_id));
+            crate::DepositAddressMigrationCursor::<Test>::put(addresses[0].0);
+
+            VaultRegistry::on_idle(1, Weight::MAX);
+
+            assert_eq!(VaultRegistry::storage_version(), Version::V7);
+            assert!(!VaultRegistry::is_vault_address(&DEFAULT_ID, &addresses[0].1));
+            assert!(VaultRegistry::is_vault_address(&DEFAULT_ID, &addresses[1].1));
+        });
+    }
+}
@@ -21,7 +21,7 @@ use mocktopus::macros::mockable;
 pub use bitcoin::{Address as BtcAddress, PublicKey as BtcPublicKey};
 
 /// Storage version.
-#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+#[derive(Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
 pub enum Version {
     /// Initial version.
     V0,
@@ -37,6 +37,8 @@ pub enum Version {
     V5,
     /// Removed wallet
     V6,
+    /// Reserved the deposit addresses of all issue requests
+    V7,
 }
 
 #[derive(Debug, PartialEq)]
@@ -125,6 +127,65 @@ pub mod v1 {
     }
 }
 
+pub mod v6 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use primitives::issue::IssueRequest;
+
+    #[frame_support::storage_alias]
+    pub(crate) type IssueRequests<T: Config> = StorageMap<
+        Issue,
+        Blake2_128Concat,
+        H256,
+        IssueRequest<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, BalanceOf<T>, CurrencyId<T>>,
+        OptionQuery,
+    >;
+
+    /// Reserves the deposit addresses of the issue requests opened before addresses were reserved,
+    /// resuming after the last visited request. Only as many requests as fit in `max_weight` are
+    /// visited, the migration completes once all requests are visited.
+    pub fn migrate_v6_to_v7<T: Config>(max_weight: Weight) -> Weight {
+        let mut used_weight = T::DbWeight::get().reads(1);
+        if !matches!(crate::StorageVersion::<T>::get(), Version::V6) {
+            return used_weight; // already upgraded; don't run migration
+        }
+        used_weight.saturating_accrue(T::DbWeight::get().reads(1));
+        let cursor = crate::DepositAddressMigrationCursor::<T>::get();
+        let mut requests = match cursor {
+            Some(issue_id) => IssueRequests::<T>::iter_from(IssueRequests::<T>::hashed_key_for(issue_id)),
+            None => IssueRequests::<T>::iter(),
+        };
+
+        // the vault may have received BTC on the address of any issue request, whatever its status
+        let request_weight = T::DbWeight::get().reads_writes(1, 2);
+        let mut last_issue_id = None;
+        while used_weight
+            .saturating_add(request_weight)
+            .saturating_add(T::DbWeight::get().writes(2))
+            .all_lte(max_weight)
+        {
+            match requests.next() {
+                Some((issue_id, request)) => {
+                    Pallet::<T>::reserve_address(&request.vault, request.btc_address);
+                    used_weight.saturating_accrue(request_weight);
+                    last_issue_id = Some(issue_id);
+                }
+                None => {
+                    log::info!("Reserved the deposit addresses of all issue requests");
+                    crate::DepositAddressMigrationCursor::<T>::kill();
+                    crate::StorageVersion::<T>::put(Version::V7);
+                    return used_weight.saturating_add(T::DbWeight::get().writes(2));
+                }
+            }
+        }
+        if let Some(issue_id) = last_issue_id {
+            crate::DepositAddressMigrationCursor::<T>::put(issue_id);
+            used_weight.saturating_accrue(T::DbWeight::get().writes(1));
+        }
+        used_weight
+    }
+}
+
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
 pub enum VaultStatus {
     /// Vault is active - bool=true indicates that the vault accepts new issue requests
@@ -414,6 +475,11 @@ impl<T: Config> RichVault<T> {
         Pallet::<T>::get_backing_collateral(&self.id())
     }
 
+    /// The total collateral including the haircut value of the basket collateral
+    pub fn get_effective_collateral(&self) -> Result<Amount<T>, DispatchError> {
+        Pallet::<T>::get_effective_collateral(&self.id())
+    }
+
     pub fn get_secure_threshold(&self) -> Result<UnsignedFixedPoint<T>, DispatchError> {
         let global_threshold =
             Pallet::<T>::secure_collateral_threshold(&self.id().currencies).ok_or(Error::<T>::ThresholdNotSet)?;
@@ -426,14 +492,14 @@ impl<T: Config> RichVault<T> {
 
     pub fn get_free_collateral(&self) -> Result<Amount<T>, DispatchError> {
         let used_collateral = self.get_used_collateral(self.get_secure_threshold()?)?;
-        self.get_total_collateral()?.checked_sub(&used_collateral)
+        self.get_effective_collateral()?.checked_sub(&used_collateral)
     }
 
     pub fn get_used_collateral(&self, threshold: UnsignedFixedPoint<T>) -> Result<Amount<T>, DispatchError> {
         let issued_tokens = self.backed_tokens()?;
         let issued_tokens_in_collateral = issued_tokens.convert_to(self.data.id.currencies.collateral)?;
         let used_collateral = issued_tokens_in_collateral.checked_mul(&threshold)?;
-        self.get_total_collateral()?.min(&used_collateral)
+        self.get_effective_collateral()?.min(&used_collateral)
     }
 
     pub fn issuable_tokens(&self) -> Result<Amount<T>, DispatchError> {
@@ -586,10 +652,14 @@ impl<T: Config> RichVault<T> {
 
         // we liquidate at most LIQUIDATION_THRESHOLD * collateral
         // this value is the amount of collateral held for the issued + to_be_issued
-        let liquidated_collateral = self.get_used_collateral(
+        let used_collateral = self.get_used_collateral(
             Pallet::<T>::liquidation_collateral_threshold(&self.data.id.currencies)
                 .ok_or(Error::<T>::ThresholdNotSet)?,
         )?;
+        // the used collateral is seized proportionally from the backing and the basket collateral
+        let effective_collateral = self.get_effective_collateral()?;
+        let liquidated_collateral =
+            Pallet::<T>::calculate_collateral(&self.get_total_collateral()?, &used_collateral, &effective_collateral)?;
 
         // Clear `to_be_replaced` tokens, since the vault will have no more `issued` or `to_be_issued` tokens.
         let _ = Pallet::<T>::withdraw_replace_request(&self.data.id, &self.to_be_replaced_tokens())?;
@@ -616,6 +686,13 @@ impl<T: Config> RichVault<T> {
 
         // slash collateral used for issued + to_be_issued to the liquidation vault
         self.slash_to_liquidation_vault(&liquidated_collateral_excluding_to_be_redeemed)?;
+        Pallet::<T>::slash_basket_to_liquidation_vault(
+            &vault_id,
+            &used_collateral,
+            &effective_collateral,
+            &collateral_tokens.checked_sub(&self.to_be_redeemed_tokens())?,
+            &collateral_tokens,
+        )?;
 
         // Copy all tokens to the liquidation vault
         let mut liquidation_vault = Pallet::<T>::get_rich_liquidation_vault(&self.data.id.currencies);
@@ -615,6 +615,8 @@ impl btc_relay::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = weights::btc_relay::WeightInfo<Runtime>;
     type ParachainBlocksPerBitcoinBlock = ParachainBlocksPerBitcoinBlock;
+    type Currency = NativeCurrency;
+    type RelayerRewardPot = VaultAnnuityAccount;
 }
 
 parameter_types! {
@@ -1102,6 +1104,7 @@ impl vault_registry::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = weights::vault_registry::WeightInfo<Runtime>;
     type GetGriefingCollateralCurrencyId = GetNativeCurrencyId;
+    type RequestPayments = (Redeem, Replace);
 }
 
 impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
@@ -1147,11 +1150,12 @@ impl issue::Config for Runtime {
     type WeightInfo = weights::issue::WeightInfo<Runtime>;
 }
 
-pub use redeem::RedeemRequest;
+pub use redeem::{RedeemRequest, RedeemRequestGroupStatus};
 
 impl redeem::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = weights::redeem::WeightInfo<Runtime>;
+    type MaxRedeemSubRequests = ConstU32<10>;
 }
 
 pub use replace::ReplaceRequest;
@@ -1320,7 +1324,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
-    (evm::SetEvmChainId<Runtime>,),
+    (evm::SetEvmChainId<Runtime>, btc_relay::migration::v1::Migration<Runtime>),
 >;
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
@@ -1768,10 +1772,47 @@ impl_runtime_apis! {
     impl btc_relay_rpc_runtime_api::BtcRelayApi<
         Block,
         H256Le,
+        AccountId,
+        btc_relay::RelayerStats<Balance>,
+        btc_relay::BlockHeaderInfo,
+        btc_relay::ForkInfo,
+        btc_relay::FullTransactionProof,
     > for Runtime {
         fn verify_block_header_inclusion(block_hash: H256Le) -> Result<(), DispatchError> {
             BTCRelay::verify_block_header_inclusion(block_hash, None).map(|_| ())
         }
+
+        fn get_relayer_stats(relayer: AccountId) -> btc_relay::RelayerStats<Balance> {
+            BTCRelay::relayer_stats(relayer)
+        }
+
+        fn get_best_block() -> H256Le {
+            BTCRelay::get_best_block()
+        }
+
+        fn get_best_block_height() -> u32 {
+            BTCRelay::get_best_block_height()
+        }
+
+        fn get_block_header(block_hash: H256Le) -> Option<btc_relay::BlockHeaderInfo> {
+            BTCRelay::get_block_header_info(block_hash)
+        }
+
+        fn get_block_hash(block_height: u32) -> Option<H256Le> {
+            BTCRelay::get_main_chain_block_hash(block_height)
+        }
+
+        fn get_fork_info() -> Vec<btc_relay::ForkInfo> {
+            BTCRelay::get_fork_info()
+        }
+
+        fn get_stable_confirmations() -> u32 {
+            BTCRelay::bitcoin_confirmations()
+        }
+
+        fn is_tx_included(proof: btc_relay::FullTransactionProof) -> Result<(), DispatchError> {
+            BTCRelay::_verify_transaction_inclusion(proof, None).map(|_| ())
+        }
     }
 
     impl oracle_rpc_runtime_api::OracleApi<
@@ -1942,7 +1983,8 @@ impl_runtime_apis! {
         Balance,
         AccountId,
         H256,
-        RedeemRequest<AccountId, BlockNumber, Balance, CurrencyId>
+        RedeemRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+        RedeemRequestGroupStatus<Balance>
     > for Runtime {
         fn get_redeem_requests(account_id: AccountId) -> Vec<H256> {
             Redeem::get_redeem_requests_for_account(account_id)
@@ -1956,6 +1998,20 @@ impl_runtime_apis! {
             let result = Redeem::get_premium_redeem_vaults()?;
             Ok(result.iter().map(|v| (v.0.clone(), BalanceWrapper{amount:v.1.amount()})).collect())
         }
+
+        fn get_redeem_request_group_status(parent_id: H256) -> Result<RedeemRequestGroupStatus<Balance>, DispatchError> {
+            Redeem::get_redeem_request_group_status(parent_id)
+        }
+
+        fn get_redeem_request_parent(redeem_id: H256) -> Option<H256> {
+            Redeem::redeem_request_parent(redeem_id)
+        }
+
+        fn get_redeem_allocation(amount_wrapped: BalanceWrapper<Balance>) -> Result<Vec<(VaultId, BalanceWrapper<Balance>)>, DispatchError> {
+            let amount_wrapped = Amount::new(amount_wrapped.amount, GetWrappedCurrencyId::get());
+            let result = Redeem::get_redeem_allocation(&amount_wrapped)?;
+            Ok(result.into_iter().map(|(vault_id, amount)| (vault_id, BalanceWrapper{amount})).collect())
+        }
     }
 
     impl replace_rpc_runtime_api::ReplaceApi<
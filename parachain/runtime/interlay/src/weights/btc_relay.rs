@@ -60,18 +60,20 @@ impl<T: frame_system::Config> btc_relay::WeightInfo for WeightInfo<T> {
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BlockHeaders (r:0 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn initialize	() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `403`
 		//  Estimated: `3489`
 		// Minimum execution time: 71_783_000 picoseconds.
 		Weight::from_parts(74_009_000, 3489)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
-	/// Storage: BTCRelay ChainCounter (r:1 w:0)
-	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -85,18 +87,20 @@ impl<T: frame_system::Config> btc_relay::WeightInfo for WeightInfo<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
 	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn store_block_header	() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `874`
-		//  Estimated: `6340`
+		//  Estimated: `33090`
 		// Minimum execution time: 88_808_000 picoseconds.
-		Weight::from_parts(90_482_000, 6340)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
+		Weight::from_parts(90_482_000, 33090)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: BTCRelay ChainCounter (r:1 w:1)
 	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:6 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -110,23 +114,27 @@ impl<T: frame_system::Config> btc_relay::WeightInfo for WeightInfo<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
 	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[1, 6]`.
 	fn store_block_header_new_fork_sorted	(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `806 + f * (101 ±0)`
-		//  Estimated: `6340 + f * (2507 ±0)`
+		//  Estimated: `33090 + f * (2507 ±0)`
 		// Minimum execution time: 101_073_000 picoseconds.
-		Weight::from_parts(95_176_412, 6340)
+		Weight::from_parts(95_176_412, 33090)
 			// Standard Error: 452_083
 			.saturating_add(Weight::from_parts(11_888_980, 0).saturating_mul(f.into()))
-			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads(19_u64))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(f.into())))
-			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 			.saturating_add(Weight::from_parts(0, 2507).saturating_mul(f.into()))
 	}
 	/// Storage: BTCRelay ChainCounter (r:1 w:1)
 	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:1)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:2 w:1)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -140,24 +148,28 @@ impl<T: frame_system::Config> btc_relay::WeightInfo for WeightInfo<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
 	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[1, 6]`.
 	fn store_block_header_new_fork_unsorted	(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `842 + f * (99 ±0)`
-		//  Estimated: `6340 + f * (2499 ±0)`
+		//  Estimated: `33090 + f * (2499 ±0)`
 		// Minimum execution time: 97_675_000 picoseconds.
-		Weight::from_parts(89_104_041, 6340)
+		Weight::from_parts(89_104_041, 33090)
 			// Standard Error: 196_333
 			.saturating_add(Weight::from_parts(14_328_939, 0).saturating_mul(f.into()))
-			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads(20_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(f.into())))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(f.into())))
 			.saturating_add(Weight::from_parts(0, 2499).saturating_mul(f.into()))
 	}
-	/// Storage: BTCRelay ChainCounter (r:1 w:0)
-	/// Proof: BTCRelay ChainCounter (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	/// Storage: BTCRelay BlockHeaders (r:20 w:18)
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:30 w:18)
 	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
 	/// Storage: BTCRelay ChainsIndex (r:3 w:2)
 	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
@@ -175,18 +187,250 @@ impl<T: frame_system::Config> btc_relay::WeightInfo for WeightInfo<T> {
 	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
 	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
 	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// The range of component `f` is `[3, 6]`.
 	fn store_block_header_reorganize_chains	(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `4741 + f * (240 ±0)`
-		//  Estimated: `54490 + f * (1340 ±45)`
+		//  Estimated: `81240 + f * (1340 ±45)`
 		// Minimum execution time: 506_604_000 picoseconds.
-		Weight::from_parts(465_095_071, 54490)
+		Weight::from_parts(465_095_071, 81240)
 			// Standard Error: 430_820
 			.saturating_add(Weight::from_parts(18_597_675, 0).saturating_mul(f.into()))
-			.saturating_add(T::DbWeight::get().reads(42_u64))
+			.saturating_add(T::DbWeight::get().reads(53_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(f.into())))
 			.saturating_add(T::DbWeight::get().writes(46_u64))
 			.saturating_add(Weight::from_parts(0, 1340).saturating_mul(f.into()))
 	}
+	/// Storage: BTCRelay ChainsCount (r:1 w:0)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:100)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableDifficultyCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:100 w:100)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:0 w:1)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:0 w:1)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderMigrationHeight (r:1 w:0)
+	/// Proof: BTCRelay HeaderMigrationHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn store_block_headers	(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1953 + n * (117 ±0)`
+		//  Estimated: `11898 + n * (29429 ±0)`
+		// Minimum execution time: 66_102_000 picoseconds.
+		Weight::from_parts(71_804_000, 11898)
+			// Standard Error: 41_209
+			.saturating_add(Weight::from_parts(32_877_259, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 29429).saturating_mul(n.into()))
+	}
+	/// Storage: BTCRelay ChainsHashes (r:1 w:1)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:0 w:1)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockRelayers (r:1 w:1)
+	/// Proof: BTCRelay BlockRelayers (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn prune_block_header	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `517`
+		//  Estimated: `6170`
+		// Minimum execution time: 40_612_000 picoseconds.
+		Weight::from_parts(41_385_000, 6170)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: BTCRelay ChainsIndex (r:6 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay Chains (r:6 w:6)
+	/// Proof: BTCRelay Chains (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsCount (r:1 w:1)
+	/// Proof: BTCRelay ChainsCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// The range of component `f` is `[1, 6]`.
+	fn prune_fork	(f: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262 + f * (101 ±0)`
+		//  Estimated: `3497 + f * (5010 ±0)`
+		// Minimum execution time: 21_574_000 picoseconds.
+		Weight::from_parts(17_883_402, 3497)
+			// Standard Error: 31_870
+			.saturating_add(Weight::from_parts(6_415_219, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(f.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 5010).saturating_mul(f.into()))
+	}
+	/// Storage: BTCRelay HeaderRetention (r:0 w:1)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_header_retention	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_310_000 picoseconds.
+		Weight::from_parts(5_592_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: BTCRelay RelayerReward (r:0 w:1)
+	/// Proof: BTCRelay RelayerReward (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: BTCRelay OrphanedHeaderSlash (r:0 w:1)
+	/// Proof: BTCRelay OrphanedHeaderSlash (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn set_relayer_incentives	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_104_000 picoseconds.
+		Weight::from_parts(6_398_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderRetention (r:1 w:0)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn bond_relayer	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `239`
+		//  Estimated: `3580`
+		// Minimum execution time: 31_522_000 picoseconds.
+		Weight::from_parts(32_190_000, 3580)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: BTCRelay RelayerStatistics (r:1 w:1)
+	/// Proof: BTCRelay RelayerStatistics (max_values: None, max_size: Some(112), added: 2587, mode: MaxEncodedLen)
+	/// Storage: BTCRelay HeaderRetention (r:1 w:0)
+	/// Proof: BTCRelay HeaderRetention (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn unbond_relayer	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3580`
+		// Minimum execution time: 34_871_000 picoseconds.
+		Weight::from_parts(35_630_000, 3580)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: BTCRelay LatestCheckpoint (r:1 w:1)
+	/// Proof: BTCRelay LatestCheckpoint (max_values: Some(1), max_size: Some(36), added: 531, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:1 w:1)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:1)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:2 w:1)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: BTCRelay DisableDifficultyCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableDifficultyCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsIndex (r:1 w:1)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:0 w:1)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay Checkpoints (r:0 w:1)
+	/// Proof: BTCRelay Checkpoints (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn add_checkpoint	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1120`
+		//  Estimated: `6340`
+		// Minimum execution time: 48_315_000 picoseconds.
+		Weight::from_parts(49_204_000, 6340)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: BTCRelay StalenessThreshold (r:1 w:0)
+	/// Proof: BTCRelay StalenessThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlock (r:1 w:0)
+	/// Proof: BTCRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Security IsRelayStale (r:1 w:1)
+	/// Proof: Security IsRelayStale (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockUpdatedAt (r:1 w:0)
+	/// Proof: BTCRelay BestBlockUpdatedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn on_initialize	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `1489`
+		// Minimum execution time: 14_782_000 picoseconds.
+		Weight::from_parts(15_310_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Security IsRelayStale (r:1 w:1)
+	/// Proof: Security IsRelayStale (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockHeight (r:1 w:0)
+	/// Proof: BTCRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay StalenessThreshold (r:1 w:1)
+	/// Proof: BTCRelay StalenessThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BestBlockUpdatedAt (r:0 w:1)
+	/// Proof: BTCRelay BestBlockUpdatedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_staleness_threshold	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `214`
+		//  Estimated: `1489`
+		// Minimum execution time: 13_045_000 picoseconds.
+		Weight::from_parts(13_532_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: BTCRelay ChainsIndex (r:7 w:0)
+	/// Proof: BTCRelay ChainsIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: BTCRelay LatestCheckpoint (r:1 w:0)
+	/// Proof: BTCRelay LatestCheckpoint (max_values: Some(1), max_size: Some(36), added: 531, mode: MaxEncodedLen)
+	/// Storage: BTCRelay ChainsHashes (r:6 w:0)
+	/// Proof: BTCRelay ChainsHashes (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:12 w:0)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// The range of component `f` is `[1, 6]`.
+	fn find_prunable_fork	(f: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187 + f * (412 ±0)`
+		//  Estimated: `6004 + f * (7897 ±0)`
+		// Minimum execution time: 24_318_000 picoseconds.
+		Weight::from_parts(14_902_117, 6004)
+			// Standard Error: 42_583
+			.saturating_add(Weight::from_parts(11_287_406, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 7897).saturating_mul(f.into()))
+	}
+	/// Storage: BTCRelay SignetChallenge (r:1 w:0)
+	/// Proof: BTCRelay SignetChallenge (max_values: Some(1), max_size: Some(10002), added: 10497, mode: MaxEncodedLen)
+	/// The range of component `h` is `[2, 10]`.
+	/// The range of component `o` is `[2, 3]`.
+	/// The range of component `b` is `[541, 2048]`.
+	fn verify_signet_solution	(h: u32, o: u32, b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1089`
+		//  Estimated: `11487`
+		// Minimum execution time: 98_214_000 picoseconds.
+		Weight::from_parts(91_538_000, 11487)
+			// Standard Error: 39_471
+			.saturating_add(Weight::from_parts(2_384_017, 0).saturating_mul(h.into()))
+			// Standard Error: 213_854
+			.saturating_add(Weight::from_parts(3_754_260, 0).saturating_mul(o.into()))
+			// Standard Error: 218
+			.saturating_add(Weight::from_parts(4_871, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
 }
\ No newline at end of file
@@ -42,6 +42,8 @@ impl<T: frame_system::Config> security::WeightInfo for WeightInfo<T> {
 
 	/// Storage: Security IsDeactivated (r:1 w:0)
 	/// Proof: Security IsDeactivated (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: Security IsRelayStale (r:1 w:0)
+	/// Proof: Security IsRelayStale (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
 	/// Storage: Security ActiveBlockCount (r:1 w:1)
 	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn on_initialize	() -> Weight {
@@ -50,7 +52,7 @@ impl<T: frame_system::Config> security::WeightInfo for WeightInfo<T> {
 		//  Estimated: `1489`
 		// Minimum execution time: 17_345_000 picoseconds.
 		Weight::from_parts(18_226_000, 1489)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	/// Storage: Security IsDeactivated (r:0 w:1)
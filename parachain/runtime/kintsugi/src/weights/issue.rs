@@ -76,6 +76,10 @@ impl<T: frame_system::Config> issue::WeightInfo for WeightInfo<T> {
 	/// Proof: Issue IssuePeriod (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Issue IssueRequests (r:0 w:1)
 	/// Proof: Issue IssueRequests (max_values: None, max_size: Some(272), added: 2747, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ReservedAddresses (r:0 w:1)
+	/// Proof: VaultRegistry ReservedAddresses (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry VaultDepositAddresses (r:0 w:1)
+	/// Proof: VaultRegistry VaultDepositAddresses (max_values: None, max_size: Some(137), added: 2612, mode: MaxEncodedLen)
 	fn request_issue	() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2718`
@@ -83,7 +87,7 @@ impl<T: frame_system::Config> issue::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 558_298_000 picoseconds.
 		Weight::from_parts(565_393_000, 6028)
 			.saturating_add(T::DbWeight::get().reads(18_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
 	/// Storage: Issue IssueRequests (r:1 w:1)
 	/// Proof: Issue IssueRequests (max_values: None, max_size: Some(272), added: 2747, mode: MaxEncodedLen)
@@ -393,4 +393,31 @@ impl<T: frame_system::Config> redeem::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Storage: `Tokens::Accounts` (r:2 w:1)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(115), added: 2590, mode: `MaxEncodedLen`)
+	/// Storage: `VaultRegistry::Vaults` (r:1 w:1)
+	/// Proof: `VaultRegistry::Vaults` (`max_values`: None, `max_size`: Some(260), added: 2735, mode: `MaxEncodedLen`)
+	/// Storage: `Security::Nonce` (r:1 w:1)
+	/// Proof: `Security::Nonce` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `Redeem::RedeemRequests` (r:0 w:10)
+	/// Proof: `Redeem::RedeemRequests` (`max_values`: None, `max_size`: Some(245), added: 2720, mode: `MaxEncodedLen`)
+	/// Storage: `Redeem::RedeemRequestParents` (r:0 w:10)
+	/// Proof: `Redeem::RedeemRequestParents` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Redeem::RedeemRequestGroups` (r:0 w:1)
+	/// Proof: `Redeem::RedeemRequestGroups` (`max_values`: None, `max_size`: Some(337), added: 2812, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 10]`.
+	fn request_redeem_multi	(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3219 + n * (275 ±0)`
+		//  Estimated: `6260 + n * (2735 ±0)`
+		// Minimum execution time: 248_000_000 picoseconds.
+		Weight::from_parts(31_000_000, 6260)
+			// Standard Error: 61_425
+			.saturating_add(Weight::from_parts(232_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((26_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2735).saturating_mul(n.into()))
+	}
 }
\ No newline at end of file
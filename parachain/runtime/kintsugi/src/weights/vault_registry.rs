@@ -416,4 +416,255 @@ impl<T: frame_system::Config> vault_registry::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableInclusionCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:1 w:0)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry TheftReports (r:1 w:1)
+	/// Proof: VaultRegistry TheftReports (max_values: None, max_size: Some(99), added: 2574, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ReservedAddresses (r:1 w:0)
+	/// Proof: VaultRegistry ReservedAddresses (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry Vaults (r:1 w:1)
+	/// Proof: VaultRegistry Vaults (max_values: None, max_size: Some(260), added: 2735, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry KeyMigrationTransactions (r:1 w:0)
+	/// Proof: VaultRegistry KeyMigrationTransactions (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry VaultBitcoinPublicKey (r:1 w:0)
+	/// Proof: VaultRegistry VaultBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry PendingBitcoinPublicKey (r:1 w:0)
+	/// Proof: VaultRegistry PendingBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry PreviousVaultIds (r:1 w:0)
+	/// Proof: VaultRegistry PreviousVaultIds (max_values: None, max_size: Some(194), added: 2669, mode: MaxEncodedLen)
+	fn report_vault_theft	(h: u32, i: u32, o: u32, b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6981 + o * (1 ±0)`
+		//  Estimated: `140474`
+		// Minimum execution time: 1_301_420_000 picoseconds.
+		Weight::from_parts(1_288_136_000, 140474)
+			// Standard Error: 149_738
+			.saturating_add(Weight::from_parts(2_508_576, 0).saturating_mul(h.into()))
+			// Standard Error: 140_116
+			.saturating_add(Weight::from_parts(3_114_208, 0).saturating_mul(i.into()))
+			// Standard Error: 812_045
+			.saturating_add(Weight::from_parts(4_017_662, 0).saturating_mul(o.into()))
+			// Standard Error: 824
+			.saturating_add(Weight::from_parts(5_210, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(68_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(31_u64))
+	}
+	/// Storage: VaultRegistry VaultExits (r:1 w:1)
+	/// Proof: VaultRegistry VaultExits (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry Vaults (r:1 w:1)
+	/// Proof: VaultRegistry Vaults (max_values: None, max_size: Some(260), added: 2735, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ExitPeriod (r:1 w:0)
+	/// Proof: VaultRegistry ExitPeriod (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn request_vault_exit	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3345`
+		//  Estimated: `37714`
+		// Minimum execution time: 296_420_000 picoseconds.
+		Weight::from_parts(301_873_000, 37714)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: VaultRegistry VaultExits (r:1 w:1)
+	/// Proof: VaultRegistry VaultExits (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry Vaults (r:1 w:1)
+	/// Proof: VaultRegistry Vaults (max_values: None, max_size: Some(260), added: 2735, mode: MaxEncodedLen)
+	/// Storage: Nomination Vaults (r:1 w:1)
+	/// Proof: Nomination Vaults (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry VaultDepositAddresses (r:2 w:1)
+	/// Proof: VaultRegistry VaultDepositAddresses (max_values: None, max_size: Some(137), added: 2612, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ReservedAddresses (r:0 w:1)
+	/// Proof: VaultRegistry ReservedAddresses (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	fn execute_vault_exit	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5012`
+		//  Estimated: `83540`
+		// Minimum execution time: 702_315_000 picoseconds.
+		Weight::from_parts(711_904_000, 83540)
+			.saturating_add(T::DbWeight::get().reads(43_u64))
+			.saturating_add(T::DbWeight::get().writes(31_u64))
+	}
+	/// Storage: VaultRegistry VaultExits (r:1 w:1)
+	/// Proof: VaultRegistry VaultExits (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
+	/// Storage: Security ActiveBlockCount (r:1 w:0)
+	/// Proof: Security ActiveBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry Vaults (r:1 w:1)
+	/// Proof: VaultRegistry Vaults (max_values: None, max_size: Some(260), added: 2735, mode: MaxEncodedLen)
+	fn liquidate_expired_vault_exit	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6702`
+		//  Estimated: `140474`
+		// Minimum execution time: 1_245_112_000 picoseconds.
+		Weight::from_parts(1_260_437_000, 140474)
+			.saturating_add(T::DbWeight::get().reads(59_u64))
+			.saturating_add(T::DbWeight::get().writes(31_u64))
+	}
+	/// Storage: VaultRegistry ExitPeriod (r:0 w:1)
+	/// Proof: VaultRegistry ExitPeriod (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_exit_period	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `711`
+		//  Estimated: `0`
+		// Minimum execution time: 12_312_000 picoseconds.
+		Weight::from_parts(12_573_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: VaultRegistry VaultBitcoinPublicKey (r:0 w:1)
+	/// Proof: VaultRegistry VaultBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry RequireKeyMigrationProof (r:1 w:0)
+	/// Proof: VaultRegistry RequireKeyMigrationProof (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry PendingBitcoinPublicKey (r:1 w:1)
+	/// Proof: VaultRegistry PendingBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	fn rotate_public_key	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `621`
+		//  Estimated: `3546`
+		// Minimum execution time: 30_412_000 picoseconds.
+		Weight::from_parts(31_085_000, 3546)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: VaultRegistry VaultBitcoinPublicKey (r:0 w:1)
+	/// Proof: VaultRegistry VaultBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: BTCRelay DisableInclusionCheck (r:1 w:0)
+	/// Proof: BTCRelay DisableInclusionCheck (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: BTCRelay BlockHeaders (r:1 w:0)
+	/// Proof: BTCRelay BlockHeaders (max_values: None, max_size: Some(200), added: 2675, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ReservedAddresses (r:1 w:0)
+	/// Proof: VaultRegistry ReservedAddresses (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry KeyMigrationTransactions (r:0 w:1)
+	/// Proof: VaultRegistry KeyMigrationTransactions (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry PendingBitcoinPublicKey (r:1 w:1)
+	/// Proof: VaultRegistry PendingBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	fn rotate_public_key_with_proof	(h: u32, i: u32, o: u32, b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2134 + i * (1 ±0)`
+		//  Estimated: `8710`
+		// Minimum execution time: 121_305_000 picoseconds.
+		Weight::from_parts(118_264_000, 8710)
+			// Standard Error: 48_217
+			.saturating_add(Weight::from_parts(2_473_512, 0).saturating_mul(h.into()))
+			// Standard Error: 45_102
+			.saturating_add(Weight::from_parts(3_021_840, 0).saturating_mul(i.into()))
+			// Standard Error: 261_364
+			.saturating_add(Weight::from_parts(3_906_117, 0).saturating_mul(o.into()))
+			// Standard Error: 265
+			.saturating_add(Weight::from_parts(5_094, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: VaultRegistry RequireKeyMigrationProof (r:0 w:1)
+	/// Proof: VaultRegistry RequireKeyMigrationProof (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn set_require_key_migration_proof	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `711`
+		//  Estimated: `0`
+		// Minimum execution time: 12_104_000 picoseconds.
+		Weight::from_parts(12_395_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: VaultRegistry BasketCollateralHaircut (r:1 w:0)
+	/// Proof: VaultRegistry BasketCollateralHaircut (max_values: None, max_size: Some(43), added: 2518, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry Vaults (r:1 w:0)
+	/// Proof: VaultRegistry Vaults (max_values: None, max_size: Some(260), added: 2735, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry BasketCollateral (r:1 w:1)
+	/// Proof: VaultRegistry BasketCollateral (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	fn deposit_basket_collateral	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1602`
+		//  Estimated: `11431`
+		// Minimum execution time: 64_903_000 picoseconds.
+		Weight::from_parts(65_718_000, 11431)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: VaultRegistry BasketCollateral (r:2 w:1)
+	/// Proof: VaultRegistry BasketCollateral (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry Vaults (r:1 w:0)
+	/// Proof: VaultRegistry Vaults (max_values: None, max_size: Some(260), added: 2735, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry BasketCollateralHaircut (r:1 w:0)
+	/// Proof: VaultRegistry BasketCollateralHaircut (max_values: None, max_size: Some(43), added: 2518, mode: MaxEncodedLen)
+	/// Storage: Oracle Aggregate (r:2 w:0)
+	/// Proof: Oracle Aggregate (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: VaultStaking TotalCurrentStake (r:1 w:0)
+	/// Proof: VaultStaking TotalCurrentStake (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry SecureCollateralThreshold (r:1 w:0)
+	/// Proof: VaultRegistry SecureCollateralThreshold (max_values: None, max_size: Some(54), added: 2529, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(115), added: 2590, mode: MaxEncodedLen)
+	fn withdraw_basket_collateral	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3137`
+		//  Estimated: `25116`
+		// Minimum execution time: 132_687_000 picoseconds.
+		Weight::from_parts(134_552_000, 25116)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: VaultRegistry BasketCollateralHaircut (r:0 w:1)
+	/// Proof: VaultRegistry BasketCollateralHaircut (max_values: None, max_size: Some(43), added: 2518, mode: MaxEncodedLen)
+	fn set_basket_collateral_haircut	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `711`
+		//  Estimated: `0`
+		// Minimum execution time: 12_581_000 picoseconds.
+		Weight::from_parts(12_832_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: VaultRegistry SecureCollateralThreshold (r:2 w:0)
+	/// Proof: VaultRegistry SecureCollateralThreshold (max_values: None, max_size: Some(54), added: 2529, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry PremiumRedeemThreshold (r:1 w:0)
+	/// Proof: VaultRegistry PremiumRedeemThreshold (max_values: None, max_size: Some(54), added: 2529, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry LiquidationCollateralThreshold (r:1 w:0)
+	/// Proof: VaultRegistry LiquidationCollateralThreshold (max_values: None, max_size: Some(54), added: 2529, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry MinimumCollateralVault (r:1 w:0)
+	/// Proof: VaultRegistry MinimumCollateralVault (max_values: None, max_size: Some(43), added: 2518, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry SystemCollateralCeiling (r:2 w:0)
+	/// Proof: VaultRegistry SystemCollateralCeiling (max_values: None, max_size: Some(54), added: 2529, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry Vaults (r:2 w:2)
+	/// Proof: VaultRegistry Vaults (max_values: None, max_size: Some(260), added: 2735, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry VaultExits (r:1 w:0)
+	/// Proof: VaultRegistry VaultExits (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry BasketCollateral (r:2 w:1)
+	/// Proof: VaultRegistry BasketCollateral (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: Nomination Vaults (r:1 w:1)
+	/// Proof: Nomination Vaults (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry VaultDepositAddresses (r:2 w:2)
+	/// Proof: VaultRegistry VaultDepositAddresses (max_values: None, max_size: Some(137), added: 2612, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry ReservedAddresses (r:0 w:1)
+	/// Proof: VaultRegistry ReservedAddresses (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry TheftReports (r:2 w:2)
+	/// Proof: VaultRegistry TheftReports (max_values: None, max_size: Some(99), added: 2574, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry PreviousVaultIds (r:1 w:2)
+	/// Proof: VaultRegistry PreviousVaultIds (max_values: None, max_size: Some(194), added: 2669, mode: MaxEncodedLen)
+	fn swap_collateral_currency	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5730`
+		//  Estimated: `98362`
+		// Minimum execution time: 948_216_000 picoseconds.
+		Weight::from_parts(961_408_000, 98362)
+			.saturating_add(T::DbWeight::get().reads(57_u64))
+			.saturating_add(T::DbWeight::get().writes(43_u64))
+	}
+	/// Storage: VaultRegistry VaultBitcoinPublicKey (r:1 w:0)
+	/// Proof: VaultRegistry VaultBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: VaultRegistry PendingBitcoinPublicKey (r:0 w:1)
+	/// Proof: VaultRegistry PendingBitcoinPublicKey (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	fn request_public_key_rotation	() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `621`
+		//  Estimated: `3546`
+		// Minimum execution time: 24_874_000 picoseconds.
+		Weight::from_parts(25_391_000, 3546)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
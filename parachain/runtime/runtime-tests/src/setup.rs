@@ -110,6 +110,10 @@ impl ExtBuilder {
             parachain_confirmations: CONFIRMATIONS,
             disable_difficulty_check: true,
             disable_inclusion_check: false,
+            pow_network: Default::default(),
+            signet_challenge: vec![],
+            header_retention: Default::default(),
+            checkpoints: Default::default(),
         }
         .assimilate_storage(&mut storage)
         .unwrap();
@@ -124,6 +128,7 @@ impl ExtBuilder {
                 (LendToken(1), 0),
             ],
             punishment_delay: 8,
+            exit_period: 8,
             system_collateral_ceiling: iter_currency_pairs().map(|pair| (pair, FUND_LIMIT_CEILING)).collect(),
             secure_collateral_threshold: iter_currency_pairs()
                 .map(|pair| (pair, FixedU128::checked_from_rational(150, 100).unwrap()))
@@ -1289,11 +1289,13 @@ impl TransactionGenerator {
         let transaction = transaction_builder.build();
 
         let prev_hash = BTCRelayPallet::get_best_block();
+        // blocks must be later than the median time past of their predecessors
+        let mut timestamp = BTCRelayPallet::get_median_time_past(prev_hash).unwrap() + 1000;
         let block = BlockBuilder::new()
             .with_previous_hash(prev_hash)
             .with_version(4)
             .with_coinbase(&self.coinbase_destination, 50, 3)
-            .with_timestamp(1588814835)
+            .with_timestamp(timestamp)
             .add_transaction(transaction.clone())
             .mine(U256::from(2).pow(254.into()))
             .unwrap();
@@ -1309,7 +1311,6 @@ impl TransactionGenerator {
 
         // Mine six new blocks to get over required confirmations
         let mut prev_block_hash = block.header.hash;
-        let mut timestamp = 1588814835;
         for _ in 0..extra_confirmations {
             height += 1;
             timestamp += 1000;
@@ -223,6 +223,10 @@ pub fn interlay_genesis(
             parachain_confirmations: bitcoin_confirmations.saturating_mul(interlay_runtime::BITCOIN_BLOCK_SPACING),
             disable_difficulty_check,
             disable_inclusion_check: false,
+            pow_network: Default::default(),
+            signet_challenge: vec![],
+            header_retention: Default::default(),
+            checkpoints: Default::default(),
         },
         issue: interlay_runtime::IssueConfig {
             issue_period: interlay_runtime::DAYS,
@@ -240,6 +244,7 @@ pub fn interlay_genesis(
         vault_registry: interlay_runtime::VaultRegistryConfig {
             minimum_collateral_vault: vec![(Token(DOT), 30 * DOT.one())],
             punishment_delay: interlay_runtime::DAYS,
+            exit_period: interlay_runtime::DAYS * 7,
             system_collateral_ceiling: vec![(default_pair_interlay(Token(DOT)), 2_450_000 * DOT.one())],
             secure_collateral_threshold: vec![(
                 default_pair_interlay(Token(DOT)),
@@ -231,6 +231,10 @@ pub fn kintsugi_genesis(
             parachain_confirmations: bitcoin_confirmations.saturating_mul(kintsugi_runtime::BITCOIN_BLOCK_SPACING),
             disable_difficulty_check,
             disable_inclusion_check: false,
+            pow_network: Default::default(),
+            signet_challenge: vec![],
+            header_retention: Default::default(),
+            checkpoints: Default::default(),
         },
         issue: kintsugi_runtime::IssueConfig {
             issue_period: kintsugi_runtime::DAYS * 2,
@@ -248,6 +252,7 @@ pub fn kintsugi_genesis(
         vault_registry: kintsugi_runtime::VaultRegistryConfig {
             minimum_collateral_vault: vec![(Token(KINT), 55 * KINT.one()), (Token(KSM), 3 * KSM.one())],
             punishment_delay: kintsugi_runtime::DAYS,
+            exit_period: kintsugi_runtime::DAYS * 7,
             system_collateral_ceiling: vec![
                 (default_pair_kintsugi(Token(KINT)), 26_200 * KINT.one()),
                 (default_pair_kintsugi(Token(KSM)), 60_000 * KSM.one()),
@@ -92,8 +92,15 @@ pub trait RuntimeApiCollection:
     + substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
     + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
     + cumulus_primitives_core::CollectCollationInfo<Block>
-    + btc_relay_rpc_runtime_api::BtcRelayApi<Block, H256Le>
-    + oracle_rpc_runtime_api::OracleApi<Block, Balance, CurrencyId>
+    + btc_relay_rpc_runtime_api::BtcRelayApi<
+        Block,
+        H256Le,
+        AccountId,
+        btc_relay::RelayerStats<Balance>,
+        btc_relay::BlockHeaderInfo,
+        btc_relay::ForkInfo,
+        btc_relay::FullTransactionProof,
+    > + oracle_rpc_runtime_api::OracleApi<Block, Balance, CurrencyId>
     + vault_registry_rpc_runtime_api::VaultRegistryApi<
         Block,
         VaultId<AccountId, CurrencyId>,
@@ -114,6 +121,7 @@ pub trait RuntimeApiCollection:
         AccountId,
         H256,
         redeem::RedeemRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+        redeem::RedeemRequestGroupStatus<Balance>,
     > + replace_rpc_runtime_api::ReplaceApi<
         Block,
         AccountId,
@@ -142,8 +150,15 @@ where
         + substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
         + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
         + cumulus_primitives_core::CollectCollationInfo<Block>
-        + btc_relay_rpc_runtime_api::BtcRelayApi<Block, H256Le>
-        + oracle_rpc_runtime_api::OracleApi<Block, Balance, CurrencyId>
+        + btc_relay_rpc_runtime_api::BtcRelayApi<
+            Block,
+            H256Le,
+            AccountId,
+            btc_relay::RelayerStats<Balance>,
+            btc_relay::BlockHeaderInfo,
+            btc_relay::ForkInfo,
+            btc_relay::FullTransactionProof,
+        > + oracle_rpc_runtime_api::OracleApi<Block, Balance, CurrencyId>
         + vault_registry_rpc_runtime_api::VaultRegistryApi<
             Block,
             VaultId<AccountId, CurrencyId>,
@@ -164,6 +179,7 @@ where
             AccountId,
             H256,
             redeem::RedeemRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+            redeem::RedeemRequestGroupStatus<Balance>,
         > + replace_rpc_runtime_api::ReplaceApi<
             Block,
             AccountId,
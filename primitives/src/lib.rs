@@ -280,6 +280,32 @@ pub mod redeem {
         /// the status of this redeem request
         pub status: RedeemRequestStatus,
     }
+
+    /// The aggregate status of the sub-requests opened by a single multi-vault redeem.
+    #[derive(Serialize, Deserialize, Encode, Decode, Default, Clone, PartialEq, Eq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct RedeemRequestGroupStatus<Balance> {
+        /// the ids of the linked sub-requests
+        pub redeem_ids: Vec<H256>,
+        /// the number of sub-requests that are still pending
+        pub pending: u32,
+        /// the number of sub-requests that were executed
+        pub completed: u32,
+        /// the number of sub-requests that were cancelled (reimbursed or retried)
+        pub cancelled: u32,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// total amount of BTC for the vaults to send
+        pub amount_btc: Balance,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// amount of BTC sent by the vaults of the executed sub-requests
+        pub completed_amount_btc: Balance,
+    }
 }
 
 pub mod replace {
@@ -343,6 +369,84 @@ pub mod replace {
     }
 }
 
+pub mod btc_relay {
+    use super::*;
+
+    // Due to a known bug in serde we need to specify how u128 is (de)serialized.
+    // See https://github.com/paritytech/substrate/issues/4641
+    #[derive(Serialize, Deserialize, Encode, Decode, Default, Clone, PartialEq, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct RelayerStats<Balance> {
+        /// the number of submitted headers that extended the main chain
+        pub main_chain_headers: u32,
+        /// the number of submitted headers that were stored on a fork
+        pub fork_headers: u32,
+        /// the number of bonded headers that were pruned on an abandoned fork
+        pub orphaned_headers: u32,
+        /// the number of bonded headers that are still stored, which keep the bond locked
+        pub pending_headers: u32,
+        /// the Bitcoin height of the last submitted header
+        pub last_submission_height: u32,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// the total rewards paid for main chain headers
+        pub rewards: Balance,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// the currently reserved bond
+        pub bond: Balance,
+        #[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+        #[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+        #[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+        #[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+        /// the total amount slashed from the bond
+        pub slashed: Balance,
+    }
+
+    pub use bitcoin::types::FullTransactionProof;
+
+    #[derive(Serialize, Deserialize, Encode, Decode, Default, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct BlockHeaderInfo {
+        /// the hash of the block
+        pub hash: H256Le,
+        /// the height of the block in the bitcoin chain
+        pub block_height: u32,
+        /// the id of the relayed chain that the block belongs to
+        pub chain_id: u32,
+        /// true if the block is part of the main chain
+        pub on_main_chain: bool,
+        pub version: i32,
+        pub hash_prev_block: H256Le,
+        pub merkle_root: H256Le,
+        pub timestamp: u32,
+        /// the target in compact representation
+        pub bits: u32,
+        pub nonce: u32,
+        /// the median timestamp of the block and its stored predecessors
+        pub median_time_past: u32,
+    }
+
+    #[derive(Serialize, Deserialize, Encode, Decode, Default, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct ForkInfo {
+        /// the id of the relayed chain
+        pub chain_id: u32,
+        /// the height of the first block of the fork
+        pub start_height: u32,
+        /// the height of the tip of the fork
+        pub max_height: u32,
+        /// the hash of the tip of the fork
+        pub tip_hash: H256Le,
+        /// the number of blocks that the main chain is ahead of the fork
+        pub blocks_behind: u32,
+    }
+}
+
 pub mod oracle {
     use super::*;
 
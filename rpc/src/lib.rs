@@ -4,8 +4,11 @@
 //! capabilities that are specific to this project's runtime configuration.
 
 use primitives::{
-    issue::IssueRequest, redeem::RedeemRequest, replace::ReplaceRequest, AccountId, Balance, Block, BlockNumber,
-    CurrencyId, H256Le, Hash, Nonce, StablePoolId, VaultId,
+    btc_relay::{BlockHeaderInfo, ForkInfo, FullTransactionProof, RelayerStats},
+    issue::IssueRequest,
+    redeem::{RedeemRequest, RedeemRequestGroupStatus},
+    replace::ReplaceRequest,
+    AccountId, Balance, Block, BlockNumber, CurrencyId, H256Le, Hash, Nonce, StablePoolId, VaultId,
 };
 use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApiServer};
 pub use sc_rpc_api::DenyUnsafe;
@@ -73,7 +76,15 @@ where
     C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
-    C::Api: btc_relay_rpc::BtcRelayRuntimeApi<Block, H256Le>,
+    C::Api: btc_relay_rpc::BtcRelayRuntimeApi<
+        Block,
+        H256Le,
+        AccountId,
+        RelayerStats<Balance>,
+        BlockHeaderInfo,
+        ForkInfo,
+        FullTransactionProof,
+    >,
     C::Api: oracle_rpc::OracleRuntimeApi<Block, Balance, CurrencyId>,
     C::Api: vault_registry_rpc::VaultRegistryRuntimeApi<
         Block,
@@ -92,6 +103,7 @@ where
         AccountId,
         H256,
         RedeemRequest<AccountId, BlockNumber, Balance, CurrencyId>,
+        RedeemRequestGroupStatus<Balance>,
     >,
     C::Api: replace_rpc::ReplaceRuntimeApi<
         Block,